@@ -30,6 +30,9 @@ codegen_llvm_invalid_minimum_alignment_too_large =
 
 codegen_llvm_invalid_target_feature_prefix = target feature `{$feature}` must begin with a `+` or `-`"
 
+codegen_llvm_kcfi_typeid_width_unsupported = `-Zsanitizer-kcfi-typeid-width=64` is not supported by the LLVM backend
+    .note = LLVM KCFI type metadata identifiers are 32-bit
+
 codegen_llvm_load_bitcode = failed to load bitcode of module "{$name}"
 codegen_llvm_load_bitcode_with_llvm_err = failed to load bitcode of module "{$name}": {$llvm_err}
 
@@ -65,6 +68,8 @@ codegen_llvm_prepare_thin_lto_module_with_llvm_err = failed to prepare thin LTO
 codegen_llvm_run_passes = failed to run LLVM passes
 codegen_llvm_run_passes_with_llvm_err = failed to run LLVM passes: {$llvm_err}
 
+codegen_llvm_sanitizer_kcfi_arity_requires_llvm_21 = `-Zsanitizer-kcfi-arity` requires LLVM 21.0.0 or later
+
 codegen_llvm_sanitizer_memtag_requires_mte =
     `-Zsanitizer=memtag` requires `-Ctarget-feature=+mte`
 
@@ -1,15 +1,22 @@
 use crate::attributes;
-use libc::c_uint;
+use libc::{c_char, c_uint};
 use rustc_ast::expand::allocator::{
     alloc_error_handler_name, default_fn_name, global_fn_name, AllocatorKind, AllocatorTy,
     ALLOCATOR_METHODS, NO_ALLOC_SHIM_IS_UNSTABLE,
 };
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_hir as hir;
 use rustc_middle::bug;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_session::config::{DebugInfo, OomStrategy};
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::mangle_internal_symbol;
+use rustc_symbol_mangling::typeid::kcfi::{kcfi_typeid_for_fnsig, KcfiTypeId};
+use rustc_symbol_mangling::typeid::{declared_typeid_options, typeid_for_fnsig};
+use rustc_target::spec::abi::Abi;
 
 use crate::debuginfo;
-use crate::llvm::{self, Context, False, Module, True, Type};
+use crate::llvm::{self, Context, False, Module, True, Type, Value};
 use crate::ModuleLlvm;
 
 pub(crate) unsafe fn codegen(
@@ -30,51 +37,65 @@ pub(crate) unsafe fn codegen(
     let i8 = llvm::LLVMInt8TypeInContext(llcx);
     let i8p = llvm::LLVMPointerTypeInContext(llcx, 0);
 
+    let u8_ptr_ty = Ty::new_mut_ptr(tcx, tcx.types.u8);
+
     if kind == AllocatorKind::Default {
         for method in ALLOCATOR_METHODS {
             let mut args = Vec::with_capacity(method.inputs.len());
+            let mut arg_tys = Vec::with_capacity(method.inputs.len());
             for input in method.inputs.iter() {
                 match input.ty {
                     AllocatorTy::Layout => {
                         args.push(usize); // size
                         args.push(usize); // align
+                        arg_tys.extend([tcx.types.usize, tcx.types.usize]);
+                    }
+                    AllocatorTy::Ptr => {
+                        args.push(i8p);
+                        arg_tys.push(u8_ptr_ty);
+                    }
+                    AllocatorTy::Usize => {
+                        args.push(usize);
+                        arg_tys.push(tcx.types.usize);
                     }
-                    AllocatorTy::Ptr => args.push(i8p),
-                    AllocatorTy::Usize => args.push(usize),
 
                     AllocatorTy::ResultPtr | AllocatorTy::Unit => panic!("invalid allocator arg"),
                 }
             }
-            let output = match method.output {
-                AllocatorTy::ResultPtr => Some(i8p),
-                AllocatorTy::Unit => None,
+            let (output, output_ty) = match method.output {
+                AllocatorTy::ResultPtr => (Some(i8p), u8_ptr_ty),
+                AllocatorTy::Unit => (None, tcx.types.unit),
 
                 AllocatorTy::Layout | AllocatorTy::Usize | AllocatorTy::Ptr => {
                     panic!("invalid allocator output")
                 }
             };
 
-            let from_name = global_fn_name(method.name);
-            let to_name = default_fn_name(method.name);
+            let from_name = mangle_internal_symbol(tcx, &global_fn_name(method.name));
+            let to_name = mangle_internal_symbol(tcx, &default_fn_name(method.name));
 
-            create_wrapper_function(tcx, llcx, llmod, &from_name, &to_name, &args, output, false);
+            let llfn = create_wrapper_function(
+                tcx, llcx, llmod, &from_name, &to_name, &args, output, false,
+            );
+            add_type_metadata(tcx, llcx, llfn, usize, &arg_tys, output_ty);
         }
     }
 
     // rust alloc error handler
-    create_wrapper_function(
+    let llfn = create_wrapper_function(
         tcx,
         llcx,
         llmod,
-        "__rust_alloc_error_handler",
-        alloc_error_handler_name(alloc_error_handler_kind),
+        &mangle_internal_symbol(tcx, "__rust_alloc_error_handler"),
+        &mangle_internal_symbol(tcx, alloc_error_handler_name(alloc_error_handler_kind)),
         &[usize, usize], // size, align
         None,
         true,
     );
+    add_type_metadata(tcx, llcx, llfn, usize, &[tcx.types.usize, tcx.types.usize], tcx.types.never);
 
     // __rust_alloc_error_handler_should_panic
-    let name = OomStrategy::SYMBOL;
+    let name = mangle_internal_symbol(tcx, OomStrategy::SYMBOL);
     let ll_g = llvm::LLVMRustGetOrInsertGlobal(llmod, name.as_ptr().cast(), name.len(), i8);
     if tcx.sess.default_hidden_visibility() {
         llvm::LLVMRustSetVisibility(ll_g, llvm::Visibility::Hidden);
@@ -98,16 +119,16 @@ pub(crate) unsafe fn codegen(
     }
 }
 
-fn create_wrapper_function(
+fn create_wrapper_function<'ll>(
     tcx: TyCtxt<'_>,
-    llcx: &Context,
-    llmod: &Module,
+    llcx: &'ll Context,
+    llmod: &'ll Module,
     from_name: &str,
     to_name: &str,
-    args: &[&Type],
-    output: Option<&Type>,
+    args: &[&'ll Type],
+    output: Option<&'ll Type>,
     no_return: bool,
-) {
+) -> &'ll Value {
     unsafe {
         let ty = llvm::LLVMFunctionType(
             output.unwrap_or_else(|| llvm::LLVMVoidTypeInContext(llcx)),
@@ -172,5 +193,79 @@ fn create_wrapper_function(
             llvm::LLVMBuildRetVoid(llbuilder);
         }
         llvm::LLVMDisposeBuilder(llbuilder);
+        llfn
+    }
+}
+
+/// Attaches to the specified shim the type metadata identifiers and KCFI type metadata identifier
+/// of its declaration in the standard library (e.g., `fn __rust_alloc_error_handler(size: usize,
+/// align: usize) -> !` in `alloc`), so they agree with the ones computed by its callers (as the
+/// shims are defined in a different crate than the one they are declared and called from).
+fn add_type_metadata<'ll, 'tcx>(
+    tcx: TyCtxt<'tcx>,
+    llcx: &'ll Context,
+    llfn: &'ll Value,
+    usize: &'ll Type,
+    inputs: &[Ty<'tcx>],
+    output: Ty<'tcx>,
+) {
+    let sess = tcx.sess;
+    if !sess.is_sanitizer_cfi_enabled() && !sess.is_sanitizer_kcfi_enabled() {
+        return;
+    }
+
+    let fn_sig = ty::Binder::dummy(tcx.mk_fn_sig(
+        inputs.iter().copied(),
+        output,
+        false,
+        hir::Unsafety::Unsafe,
+        Abi::Rust,
+    ));
+    let (options, kcfi_options) = typeid_options(sess);
+
+    if sess.is_sanitizer_cfi_enabled() {
+        // The same type metadata identifiers `declare_fn` attaches to the declarations
+        let typeids: FxIndexSet<_> = declared_typeid_options(options)
+            .into_iter()
+            .map(|options| typeid_for_fnsig(tcx, fn_sig, options))
+            .collect();
+        for typeid in typeids {
+            unsafe {
+                let v = [
+                    llvm::LLVMConstInt(usize, 0, False),
+                    llvm::LLVMMDStringInContext(
+                        llcx,
+                        typeid.as_ptr() as *const c_char,
+                        typeid.len() as c_uint,
+                    ),
+                ];
+                llvm::LLVMRustGlobalAddMetadata(
+                    llfn,
+                    llvm::MD_type as c_uint,
+                    llvm::LLVMValueAsMetadata(llvm::LLVMMDNodeInContext(
+                        llcx,
+                        v.as_ptr(),
+                        v.len() as c_uint,
+                    )),
+                );
+            }
+        }
+    }
+
+    if sess.is_sanitizer_kcfi_enabled() {
+        let kcfi_typeid = match kcfi_typeid_for_fnsig(tcx, fn_sig, kcfi_options) {
+            KcfiTypeId::U32(kcfi_typeid) => unsafe {
+                llvm::LLVMConstInt(llvm::LLVMInt32TypeInContext(llcx), kcfi_typeid.into(), False)
+            },
+            // Rejected by `LlvmCodegenBackend::init`
+            KcfiTypeId::U64(_) => bug!("64-bit KCFI type metadata identifiers are unsupported"),
+        };
+        unsafe {
+            llvm::LLVMGlobalSetMetadata(
+                llfn,
+                llvm::MD_kcfi_type as c_uint,
+                llvm::LLVMMDNodeInContext2(llcx, &llvm::LLVMValueAsMetadata(kcfi_typeid), 1),
+            );
+        }
     }
 }
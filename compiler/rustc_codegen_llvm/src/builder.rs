@@ -22,10 +22,9 @@
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::config::OptLevel;
 use rustc_span::Span;
-use rustc_symbol_mangling::typeid::{
-    kcfi_typeid_for_fnabi, kcfi_typeid_for_instance, typeid_for_fnabi, typeid_for_instance,
-    TypeIdOptions,
-};
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::typeid::kcfi::{kcfi_typeid_for_fnabi, kcfi_typeid_for_instance};
+use rustc_symbol_mangling::typeid::{typeid_for_fnabi, typeid_for_instance};
 use rustc_target::abi::{self, call::FnAbi, Align, Size, WrappingRange};
 use rustc_target::spec::{HasTargetSpec, SanitizerSet, Target};
 use smallvec::SmallVec;
@@ -1632,13 +1631,7 @@ fn cfi_type_test(
                 return;
             }
 
-            let mut options = TypeIdOptions::empty();
-            if self.tcx.sess.is_sanitizer_cfi_generalize_pointers_enabled() {
-                options.insert(TypeIdOptions::GENERALIZE_POINTERS);
-            }
-            if self.tcx.sess.is_sanitizer_cfi_normalize_integers_enabled() {
-                options.insert(TypeIdOptions::NORMALIZE_INTEGERS);
-            }
+            let (options, _) = typeid_options(self.tcx.sess);
 
             let typeid = if let Some(instance) = instance {
                 typeid_for_instance(self.tcx, instance, options)
@@ -1680,13 +1673,7 @@ fn kcfi_operand_bundle(
                 return None;
             }
 
-            let mut options = TypeIdOptions::empty();
-            if self.tcx.sess.is_sanitizer_cfi_generalize_pointers_enabled() {
-                options.insert(TypeIdOptions::GENERALIZE_POINTERS);
-            }
-            if self.tcx.sess.is_sanitizer_cfi_normalize_integers_enabled() {
-                options.insert(TypeIdOptions::NORMALIZE_INTEGERS);
-            }
+            let (_, options) = typeid_options(self.tcx.sess);
 
             let kcfi_typeid = if let Some(instance) = instance {
                 kcfi_typeid_for_instance(self.tcx, instance, options)
@@ -1694,7 +1681,7 @@ fn kcfi_operand_bundle(
                 kcfi_typeid_for_fnabi(self.tcx, fn_abi, options)
             };
 
-            Some(llvm::OperandBundleDef::new("kcfi", &[self.const_u32(kcfi_typeid)]))
+            Some(llvm::OperandBundleDef::new("kcfi", &[self.const_kcfi_typeid(kcfi_typeid)]))
         } else {
             None
         };
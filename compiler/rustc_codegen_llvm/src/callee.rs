@@ -43,6 +43,13 @@ pub fn get_fn<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>, instance: Instance<'tcx>) ->
     let fn_abi = cx.fn_abi_of_instance(instance, ty::List::empty());
 
     let llfn = if let Some(llfn) = cx.get_declared_value(sym) {
+        // Builtins (e.g., `memcmp`) may already have been declared without type metadata for
+        // intrinsics lowered to calls to them, or with the type metadata of other foreign items
+        // declaring them with other types, but must also have the type metadata of the foreign
+        // items they're reached through to be called through pointers to them.
+        if tcx.is_foreign_item(instance.def_id()) {
+            cx.set_fn_type_metadata(llfn, fn_abi, Some(instance));
+        }
         llfn
     } else {
         let instance_def_id = instance.def_id();
@@ -14,6 +14,7 @@
 use rustc_middle::mir::interpret::{ConstAllocation, GlobalAlloc, Scalar};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::cstore::{DllCallingConvention, DllImport, PeImportNameType};
+use rustc_symbol_mangling::typeid::kcfi::KcfiTypeId;
 use rustc_target::abi::{self, AddressSpace, HasDataLayout, Pointer};
 use rustc_target::spec::Target;
 
@@ -108,6 +109,15 @@ pub fn const_bytes(&self, bytes: &[u8]) -> &'ll Value {
         bytes_in_context(self.llcx, bytes)
     }
 
+    /// Returns a KCFI type metadata identifier as a 32-bit constant, the only width LLVM supports
+    /// (see `LlvmCodegenBackend::init`).
+    pub fn const_kcfi_typeid(&self, kcfi_typeid: KcfiTypeId) -> &'ll Value {
+        match kcfi_typeid {
+            KcfiTypeId::U32(kcfi_typeid) => self.const_u32(kcfi_typeid),
+            KcfiTypeId::U64(_) => bug!("64-bit KCFI type metadata identifiers are unsupported"),
+        }
+    }
+
     pub fn const_get_elt(&self, v: &'ll Value, idx: u64) -> &'ll Value {
         unsafe {
             let idx = c_uint::try_from(idx).expect("LLVMGetAggregateElement index overflow");
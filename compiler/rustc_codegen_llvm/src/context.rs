@@ -211,6 +211,14 @@ pub unsafe fn create_module<'ll>(
         llvm::LLVMRustAddModuleFlag(llmod, llvm::LLVMModFlagBehavior::Override, kcfi, 1);
     }
 
+    // Add "kcfi-arity" module flag if KCFI arity encoding is enabled, so the arity of functions is
+    // encoded in the register their preambles load their KCFI type metadata identifiers into, as
+    // with Clang `-fsanitize-kcfi-arity`. (See https://github.com/llvm/llvm-project/pull/121070.)
+    if sess.is_sanitizer_kcfi_arity_enabled() {
+        let kcfi_arity = c"kcfi-arity".as_ptr().cast();
+        llvm::LLVMRustAddModuleFlag(llmod, llvm::LLVMModFlagBehavior::Override, kcfi_arity, 1);
+    }
+
     // Control Flow Guard is currently only supported by the MSVC linker on Windows.
     if sess.target.is_like_msvc {
         match sess.opts.cg.control_flow_guard {
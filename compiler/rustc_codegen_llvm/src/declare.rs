@@ -18,13 +18,14 @@
 use crate::llvm::AttributePlace::Function;
 use crate::type_::Type;
 use crate::value::Value;
-use itertools::Itertools;
 use rustc_codegen_ssa::traits::TypeMembershipMethods;
 use rustc_data_structures::fx::FxIndexSet;
+use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::ty::{Instance, Ty};
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::typeid::kcfi::{kcfi_typeid_for_fnabi, kcfi_typeid_for_instance};
 use rustc_symbol_mangling::typeid::{
-    kcfi_typeid_for_fnabi, kcfi_typeid_for_instance, typeid_for_fnabi, typeid_for_instance,
-    TypeIdOptions,
+    declared_typeid_options, typeid_for_fnabi, typeid_for_instance,
 };
 use smallvec::SmallVec;
 
@@ -140,47 +141,54 @@ pub fn declare_fn(
             fn_abi.llvm_type(self),
         );
         fn_abi.apply_attrs_llfn(self, llfn);
+        self.set_fn_type_metadata(llfn, fn_abi, instance);
 
+        llfn
+    }
+
+    /// Sets the CFI and KCFI type metadata of a declared function, from its instance if any or
+    /// else from its FnAbi.
+    pub fn set_fn_type_metadata(
+        &self,
+        llfn: &'ll Value,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        instance: Option<Instance<'tcx>>,
+    ) {
         if self.tcx.sess.is_sanitizer_cfi_enabled() {
+            // Functions are given the type metadata identifiers with and without pointers
+            // generalized and integers normalized (and `impl Trait` types encoded via their bounds
+            // when enabled, while pointers to the functions have the types they hide), and the
+            // encoding options of the session apply to all of them.
+            let (options, _) = typeid_options(self.tcx.sess);
+            let mut typeids = FxIndexSet::default();
             if let Some(instance) = instance {
-                let mut typeids = FxIndexSet::default();
-                for options in [
-                    TypeIdOptions::GENERALIZE_POINTERS,
-                    TypeIdOptions::NORMALIZE_INTEGERS,
-                    TypeIdOptions::USE_CONCRETE_SELF,
-                ]
-                .into_iter()
-                .powerset()
-                .map(TypeIdOptions::from_iter)
-                {
-                    let typeid = typeid_for_instance(self.tcx, instance, options);
-                    if typeids.insert(typeid.clone()) {
-                        self.add_type_metadata(llfn, typeid);
+                // Functions that can only be called directly are never checked against their type
+                // metadata identifiers, so these aren't computed for them. Whether functions
+                // defined in other codegen units or crates may be is unknown here, so their
+                // declarations are given type metadata identifiers anyway.
+                let may_be_called_indirectly = self
+                    .codegen_unit
+                    .items()
+                    .get(&MonoItem::Fn(instance))
+                    .map_or(true, |data| data.may_be_called_indirectly);
+                if may_be_called_indirectly {
+                    for options in declared_typeid_options(options) {
+                        typeids.insert(typeid_for_instance(self.tcx, instance, options));
                     }
                 }
             } else {
-                for options in
-                    [TypeIdOptions::GENERALIZE_POINTERS, TypeIdOptions::NORMALIZE_INTEGERS]
-                        .into_iter()
-                        .powerset()
-                        .map(TypeIdOptions::from_iter)
-                {
-                    let typeid = typeid_for_fnabi(self.tcx, fn_abi, options);
-                    self.add_type_metadata(llfn, typeid);
+                for options in declared_typeid_options(options) {
+                    typeids.insert(typeid_for_fnabi(self.tcx, fn_abi, options));
                 }
             }
+            for typeid in typeids {
+                self.add_type_metadata(llfn, typeid);
+            }
         }
 
         if self.tcx.sess.is_sanitizer_kcfi_enabled() {
             // LLVM KCFI does not support multiple !kcfi_type attachments
-            let mut options = TypeIdOptions::empty();
-            if self.tcx.sess.is_sanitizer_cfi_generalize_pointers_enabled() {
-                options.insert(TypeIdOptions::GENERALIZE_POINTERS);
-            }
-            if self.tcx.sess.is_sanitizer_cfi_normalize_integers_enabled() {
-                options.insert(TypeIdOptions::NORMALIZE_INTEGERS);
-            }
-
+            let (_, options) = typeid_options(self.tcx.sess);
             if let Some(instance) = instance {
                 let kcfi_typeid = kcfi_typeid_for_instance(self.tcx, instance, options);
                 self.set_kcfi_type_metadata(llfn, kcfi_typeid);
@@ -189,8 +197,6 @@ pub fn declare_fn(
                 self.set_kcfi_type_metadata(llfn, kcfi_typeid);
             }
         }
-
-        llfn
     }
 
     /// Declare a global with an intention to define it.
@@ -240,6 +240,15 @@ pub struct UnknownCompression {
     pub algorithm: &'static str,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_llvm_kcfi_typeid_width_unsupported)]
+#[note]
+pub(crate) struct KcfiTypeIdWidthUnsupported;
+
+#[derive(Diagnostic)]
+#[diag(codegen_llvm_sanitizer_kcfi_arity_requires_llvm_21)]
+pub(crate) struct SanitizerKcfiArityRequiresLlvm21;
+
 #[derive(Diagnostic)]
 #[diag(codegen_llvm_mismatch_data_layout)]
 pub struct MismatchedDataLayout<'a> {
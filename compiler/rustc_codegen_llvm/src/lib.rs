@@ -39,7 +39,7 @@
 use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::util::Providers;
-use rustc_session::config::{OptLevel, OutputFilenames, PrintKind, PrintRequest};
+use rustc_session::config::{KcfiTypeIdWidth, OptLevel, OutputFilenames, PrintKind, PrintRequest};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
 
@@ -266,6 +266,24 @@ fn locale_resource(&self) -> &'static str {
 
     fn init(&self, sess: &Session) {
         llvm_util::init(sess); // Make sure llvm is inited
+
+        // LLVM only supports 32-bit KCFI type metadata identifiers (in operand bundles, type
+        // metadata, and the preambles of functions), which other backends may not be limited to
+        if sess.is_sanitizer_kcfi_enabled()
+            && sess.opts.unstable_opts.sanitizer_kcfi_typeid_width == KcfiTypeIdWidth::Bits64
+        {
+            sess.dcx().emit_err(errors::KcfiTypeIdWidthUnsupported);
+        }
+
+        // Earlier LLVMs ignore the "kcfi-arity" module flag (the other requirements of
+        // `-Zsanitizer-kcfi-arity` are checked with the session)
+        if sess.is_sanitizer_kcfi_arity_enabled()
+            && sess.is_sanitizer_kcfi_enabled()
+            && sess.target.arch == "x86_64"
+            && llvm_util::get_version() < (21, 0, 0)
+        {
+            sess.dcx().emit_err(errors::SanitizerKcfiArityRequiresLlvm21);
+        }
     }
 
     fn provide(&self, providers: &mut Providers) {
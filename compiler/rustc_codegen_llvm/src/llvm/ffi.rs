@@ -975,6 +975,15 @@ pub fn LLVMConstInBoundsGEP2<'a>(
     pub fn LLVMSetAlignment(Global: &Value, Bytes: c_uint);
     pub fn LLVMSetDLLStorageClass(V: &Value, C: DLLStorageClass);
 
+    // Operations on aliases
+    pub fn LLVMAddAlias2<'a>(
+        M: &'a Module,
+        ValueTy: &Type,
+        AddrSpace: c_uint,
+        Aliasee: &Value,
+        Name: *const c_char,
+    ) -> &'a Value;
+
     // Operations on global variables
     pub fn LLVMIsAGlobalVariable(GlobalVar: &Value) -> Option<&Value>;
     pub fn LLVMAddGlobal<'a>(M: &'a Module, Ty: &'a Type, Name: *const c_char) -> &'a Value;
@@ -1,10 +1,14 @@
+use crate::abi::FnAbiLlvmExt;
 use crate::attributes;
 use crate::base;
 use crate::context::CodegenCx;
 use crate::errors::SymbolAlreadyDefined;
 use crate::llvm;
+use crate::type_::Type;
 use crate::type_of::LayoutLlvmExt;
+use crate::value::Value;
 use rustc_codegen_ssa::traits::*;
+use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::bug;
@@ -12,6 +16,7 @@
 use rustc_middle::ty::layout::{FnAbiOf, LayoutOf};
 use rustc_middle::ty::{self, Instance, TypeVisitableExt};
 use rustc_session::config::CrateType;
+use rustc_target::abi::{AddressSpace, HasDataLayout};
 use rustc_target::spec::RelocModel;
 
 impl<'tcx> PreDefineMethods<'tcx> for CodegenCx<'_, 'tcx> {
@@ -47,6 +52,8 @@ fn predefine_static(
             }
         }
 
+        self.define_legacy_symbol_alias(instance, g, llty, AddressSpace::DATA);
+
         self.instances.borrow_mut().insert(instance, g);
     }
 
@@ -95,10 +102,48 @@ fn predefine_fn(
             }
         }
 
+        self.define_legacy_symbol_alias(
+            instance,
+            lldecl,
+            fn_abi.llvm_type(self),
+            self.data_layout().instruction_address_space,
+        );
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
 }
 
+impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
+    /// Defines the legacy symbol of `instance` as an alias of its definition `aliasee`, with the
+    /// same linkage and visibility, if `-Z symbol-mangling-legacy-aliases` asks for one. The alias
+    /// is marked as used by the compiler, so that LTO doesn't drop it.
+    fn define_legacy_symbol_alias(
+        &self,
+        instance: Instance<'tcx>,
+        aliasee: &'ll Value,
+        llty: &'ll Type,
+        address_space: AddressSpace,
+    ) {
+        let Some(alias) = rustc_symbol_mangling::legacy_symbol_alias(self.tcx, instance) else {
+            return;
+        };
+        debug!("define_legacy_symbol_alias: {:?} as {alias}", instance);
+
+        let name = SmallCStr::new(&alias);
+        let llalias = unsafe {
+            let llalias =
+                llvm::LLVMAddAlias2(self.llmod, llty, address_space.0, aliasee, name.as_ptr());
+            llvm::LLVMRustSetLinkage(llalias, llvm::LLVMRustGetLinkage(aliasee));
+            llvm::LLVMRustSetVisibility(llalias, llvm::LLVMRustGetVisibility(aliasee));
+            if self.should_assume_dso_local(llalias, false) {
+                llvm::LLVMRustSetDSOLocal(llalias, true);
+            }
+            llalias
+        };
+        self.add_compiler_used_global(llalias);
+    }
+}
+
 impl CodegenCx<'_, '_> {
     /// Whether a definition or declaration can be assumed to be local to a group of
     /// libraries that form a single DSO or executable.
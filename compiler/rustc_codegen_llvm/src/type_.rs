@@ -13,6 +13,7 @@
 use rustc_middle::bug;
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_middle::ty::{self, Ty};
+use rustc_symbol_mangling::typeid::kcfi::KcfiTypeId;
 use rustc_target::abi::call::{CastTarget, FnAbi, Reg};
 use rustc_target::abi::{AddressSpace, Align, Integer, Size};
 
@@ -329,8 +330,8 @@ fn typeid_metadata(&self, typeid: String) -> Option<&'ll Value> {
         })
     }
 
-    fn add_kcfi_type_metadata(&self, function: &'ll Value, kcfi_typeid: u32) {
-        let kcfi_type_metadata = self.const_u32(kcfi_typeid);
+    fn add_kcfi_type_metadata(&self, function: &'ll Value, kcfi_typeid: KcfiTypeId) {
+        let kcfi_type_metadata = self.const_kcfi_typeid(kcfi_typeid);
         unsafe {
             llvm::LLVMRustGlobalAddMetadata(
                 function,
@@ -344,8 +345,8 @@ fn add_kcfi_type_metadata(&self, function: &'ll Value, kcfi_typeid: u32) {
         }
     }
 
-    fn set_kcfi_type_metadata(&self, function: &'ll Value, kcfi_typeid: u32) {
-        let kcfi_type_metadata = self.const_u32(kcfi_typeid);
+    fn set_kcfi_type_metadata(&self, function: &'ll Value, kcfi_typeid: KcfiTypeId) {
+        let kcfi_type_metadata = self.const_kcfi_typeid(kcfi_typeid);
         unsafe {
             llvm::LLVMGlobalSetMetadata(
                 function,
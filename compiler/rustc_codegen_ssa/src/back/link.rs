@@ -1981,8 +1981,8 @@ fn add_linked_symbol_object(
         // so add an empty section.
         file.add_section(Vec::new(), ".text".into(), object::SectionKind::Text);
 
-        // We handle the name decoration of COFF targets in `symbol_export.rs`, so disable the
-        // default mangler in `object` crate.
+        // We handle the name decoration of COFF targets in `rustc_symbol_mangling::decoration`, so
+        // disable the default mangler in `object` crate.
         file.set_mangling(object::write::Mangling::None);
     }
 
@@ -16,6 +16,7 @@
 use rustc_middle::ty::{GenericArgKind, GenericArgsRef};
 use rustc_middle::util::Providers;
 use rustc_session::config::{CrateType, OomStrategy};
+use rustc_symbol_mangling::mangle_internal_symbol;
 use rustc_target::spec::{SanitizerSet, TlsModel};
 
 pub fn threshold(tcx: TyCtxt<'_>) -> SymbolExportLevel {
@@ -220,6 +221,7 @@ fn exported_symbols_provider_local(
             .iter()
             .map(|method| format!("__rust_{}", method.name))
             .chain(["__rust_alloc_error_handler".to_string(), OomStrategy::SYMBOL.to_string()])
+            .map(|symbol_name| mangle_internal_symbol(tcx, &symbol_name))
         {
             let exported_symbol = ExportedSymbol::NoDefId(SymbolName::new(tcx, &symbol_name));
 
@@ -540,37 +542,21 @@ pub fn symbol_name_for_instance_in_crate<'tcx>(
 
 /// This is the symbol name of the given instance as seen by the linker.
 ///
-/// On 32-bit Windows symbols are decorated according to their calling conventions.
+/// Symbols are decorated according to the target's rules, e.g., according to their calling
+/// conventions on 32-bit Windows (see `rustc_symbol_mangling::decoration`).
 pub fn linking_symbol_name_for_instance_in_crate<'tcx>(
     tcx: TyCtxt<'tcx>,
     symbol: ExportedSymbol<'tcx>,
     instantiating_crate: CrateNum,
 ) -> String {
-    use rustc_target::abi::call::Conv;
-
-    let mut undecorated = symbol_name_for_instance_in_crate(tcx, symbol, instantiating_crate);
+    let undecorated = symbol_name_for_instance_in_crate(tcx, symbol, instantiating_crate);
 
     // thread local will not be a function call,
-    // so it is safe to return before windows symbol decoration check.
+    // so it is safe to return before symbol decoration.
     if let Some(name) = maybe_emutls_symbol_name(tcx, symbol, &undecorated) {
         return name;
     }
 
-    let target = &tcx.sess.target;
-    if !target.is_like_windows {
-        // Mach-O has a global "_" suffix and `object` crate will handle it.
-        // ELF does not have any symbol decorations.
-        return undecorated;
-    }
-
-    let prefix = match &target.arch[..] {
-        "x86" => Some('_'),
-        "x86_64" => None,
-        "arm64ec" => Some('#'),
-        // Only x86/64 use symbol decorations.
-        _ => return undecorated,
-    };
-
     let instance = match symbol {
         ExportedSymbol::NonGeneric(def_id) | ExportedSymbol::Generic(def_id, _)
             if tcx.is_static(def_id) =>
@@ -588,33 +574,15 @@ pub fn linking_symbol_name_for_instance_in_crate<'tcx>(
         ExportedSymbol::ThreadLocalShim(..) => None,
     };
 
-    let (conv, args) = instance
-        .map(|i| {
+    // Only computed on targets decorating symbols with calling conventions
+    let fn_abi = || {
+        instance.map(|i| {
             tcx.fn_abi_of_instance(ty::ParamEnv::reveal_all().and((i, ty::List::empty())))
                 .unwrap_or_else(|_| bug!("fn_abi_of_instance({i:?}) failed"))
         })
-        .map(|fnabi| (fnabi.conv, &fnabi.args[..]))
-        .unwrap_or((Conv::Rust, &[]));
-
-    // Decorate symbols with prefixes, suffixes and total number of bytes of arguments.
-    // Reference: https://docs.microsoft.com/en-us/cpp/build/reference/decorated-names?view=msvc-170
-    let (prefix, suffix) = match conv {
-        Conv::X86Fastcall => ("@", "@"),
-        Conv::X86Stdcall => ("_", "@"),
-        Conv::X86VectorCall => ("", "@@"),
-        _ => {
-            if let Some(prefix) = prefix {
-                undecorated.insert(0, prefix);
-            }
-            return undecorated;
-        }
     };
 
-    let args_in_bytes: u64 = args
-        .iter()
-        .map(|abi| abi.layout.size.bytes().next_multiple_of(target.pointer_width as u64 / 8))
-        .sum();
-    format!("{prefix}{undecorated}{suffix}{args_in_bytes}")
+    rustc_symbol_mangling::decoration::decorate_symbol_name(&tcx.sess.target, undecorated, fn_abi)
 }
 
 pub fn exporting_symbol_name_for_instance_in_crate<'tcx>(
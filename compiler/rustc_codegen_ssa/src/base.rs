@@ -37,6 +37,7 @@
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_span::Symbol;
+use rustc_symbol_mangling::mangle_internal_symbol;
 use rustc_target::abi::{Align, FIRST_VARIANT};
 
 use std::cmp;
@@ -968,7 +969,10 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
                         // errors.
                         linked_symbols.extend(ALLOCATOR_METHODS.iter().map(|method| {
                             (
-                                format!("{prefix}{}", global_fn_name(method.name).as_str()),
+                                format!(
+                                    "{prefix}{}",
+                                    mangle_internal_symbol(tcx, &global_fn_name(method.name))
+                                ),
                                 SymbolExportKind::Text,
                             )
                         }));
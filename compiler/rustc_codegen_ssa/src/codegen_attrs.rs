@@ -146,6 +146,9 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
             sym::rustc_std_internal_symbol => {
                 codegen_fn_attrs.flags |= CodegenFnAttrFlags::RUSTC_STD_INTERNAL_SYMBOL
             }
+            sym::rustc_unhashed_symbol => {
+                codegen_fn_attrs.flags |= CodegenFnAttrFlags::UNHASHED_SYMBOL
+            }
             sym::used => {
                 let inner = attr.meta_item_list();
                 match inner.as_deref() {
@@ -10,6 +10,7 @@
 use rustc_middle::ty::layout::LayoutError;
 use rustc_middle::ty::Ty;
 use rustc_span::{Span, Symbol};
+use rustc_symbol_mangling::demangle::demangle_symbols;
 use rustc_type_ir::FloatTy;
 use std::borrow::Cow;
 use std::io::Error;
@@ -355,8 +356,28 @@ fn into_diag(self, dcx: &DiagCtxt, level: Level) -> Diag<'_, G> {
 
         let contains_undefined_ref = self.escaped_output.contains("undefined reference to");
 
+        // Trying to match the undefined symbol errors of OS linkers, to show the Rust paths of
+        // the Rust symbols they mention, as the mangled symbols are hard to read.
+        let contains_undefined_symbol = contains_undefined_ref
+            || ["undefined symbol", "unresolved external symbol", "Undefined symbols"]
+                .iter()
+                .any(|error| self.escaped_output.contains(error));
+        let rust_symbols = if contains_undefined_symbol {
+            demangle_symbols(&self.escaped_output)
+                .into_iter()
+                .map(|(symbol, demangled)| format!("\n`{symbol}`: `{demangled}`"))
+                .collect::<String>()
+        } else {
+            String::new()
+        };
+
         diag.note(format!("{:?}", self.command)).note(self.escaped_output);
 
+        if !rust_symbols.is_empty() {
+            diag.arg("symbols", rust_symbols);
+            diag.note(fluent::codegen_ssa_demangled_linker_output_symbols);
+        }
+
         // Trying to match an error from OS linkers
         // which by now we have no way to translate.
         if contains_undefined_ref {
@@ -20,6 +20,8 @@
 use rustc_monomorphize::is_call_from_compiler_builtins_to_upstream_monomorphization;
 use rustc_session::config::OptLevel;
 use rustc_span::{source_map::Spanned, sym, Span};
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::typeid::typeid_inconsistency;
 use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode, Reg};
 use rustc_target::abi::{self, HasDataLayout, WrappingRange};
 use rustc_target::spec::abi::Abi;
@@ -975,6 +977,17 @@ fn codegen_call_terminator(
             }
         };
 
+        // Direct calls are not type tested, so check that pointers to the callee would pass the
+        // type tests of indirect calls
+        if bx.tcx().sess.opts.unstable_opts.cfi_assert_consistency
+            && let Some(instance) = instance
+        {
+            let (options, _) = typeid_options(bx.tcx().sess);
+            if let Some(inconsistency) = typeid_inconsistency(bx.tcx(), instance, options) {
+                span_bug!(span, "{inconsistency}");
+            }
+        }
+
         let mut llargs = Vec::with_capacity(arg_count);
         let destination = target.as_ref().map(|&target| {
             (
@@ -5,6 +5,7 @@
 use crate::mir::place::PlaceRef;
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_middle::ty::{self, Ty};
+use rustc_symbol_mangling::typeid::kcfi::KcfiTypeId;
 use rustc_target::abi::call::{ArgAbi, CastTarget, FnAbi, Reg};
 use rustc_target::abi::{AddressSpace, Integer};
 
@@ -165,8 +166,8 @@ fn set_type_metadata(&self, _function: Self::Function, _typeid: String) {}
     fn typeid_metadata(&self, _typeid: String) -> Option<Self::Value> {
         None
     }
-    fn add_kcfi_type_metadata(&self, _function: Self::Function, _typeid: u32) {}
-    fn set_kcfi_type_metadata(&self, _function: Self::Function, _typeid: u32) {}
+    fn add_kcfi_type_metadata(&self, _function: Self::Function, _typeid: KcfiTypeId) {}
+    fn set_kcfi_type_metadata(&self, _function: Self::Function, _typeid: KcfiTypeId) {}
 }
 
 pub trait ArgAbiMethods<'tcx>: HasCodegen<'tcx> {
@@ -1,3 +1,11 @@
+driver_impl_cfi_crate_not_found = cannot find extern crate `{$name}`
+    .help = pass it with `--extern force:{$name}=<path>`
+
+driver_impl_cfi_type_id_generic = cannot compute the type metadata identifier of generic function `{$path}`
+    .note = only functions that do not require monomorphization are supported
+
+driver_impl_cfi_type_id_not_found = cannot find function `{$path}` in this crate
+
 driver_impl_ice = the compiler unexpectedly panicked. this is a bug.
 driver_impl_ice_bug_report = we would appreciate a bug report: {$bug_report_url}
 driver_impl_ice_bug_report_internal_feature = using internal features is not supported and expected to cause internal compiler errors when used incorrectly
@@ -24,3 +32,21 @@ driver_impl_rlink_rustc_version_mismatch = .rlink file was produced by rustc ver
 driver_impl_rlink_unable_to_read = failed to read rlink file: `{$err}`
 
 driver_impl_rlink_wrong_file_type = The input does not look like a .rlink file
+
+driver_impl_typeid_snapshot_malformed = malformed line in type metadata identifier snapshot `{$path}`: `{$line}`
+    .note = lines must be of the form `<path>: <type metadata identifier>`
+
+driver_impl_typeid_snapshot_unreadable = cannot read type metadata identifier snapshot `{$path}`: {$err}
+
+driver_impl_typeid_stability_added = type metadata identifier of `{$path}` is not in the snapshot
+    .help = if this is intended, add the following to `{$snapshot}`:
+        {$lines}
+
+driver_impl_typeid_stability_changed = type metadata identifier of `{$path}` differs from the snapshot
+    .note = expected `{$expected}`, found `{$found}`
+    .difference = {$component} is `{$expected_component}` in the snapshot, but `{$found_component}` now
+    .help = encoding changes break linking with code compiled by other compiler versions; if this change is intended, update `{$snapshot}`
+
+driver_impl_typeid_stability_removed = type metadata identifier of `{$path}` in the snapshot was not found in this crate
+    .help = if this is intended, remove the following from `{$snapshot}`:
+        {$lines}
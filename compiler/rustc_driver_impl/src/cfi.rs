@@ -0,0 +1,376 @@
+//! Support for the control flow integrity (CFI) type metadata identifier print requests and debugging
+//! options (i.e., `--print cfi-type-id=<path>`, `-Zunpretty=cfi-typeids`,
+//! `-Zcfi-diff-typeids=<path>,<path>`, `-Zcfi-diff-crate-typeids=<crate>,<crate>`, and
+//! `-Zverify-typeid-stability=<path>`).
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_errors::ErrorGuaranteed;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::CrateNum;
+use rustc_middle::middle::exported_symbols::ExportedSymbol;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_session::config::{ExternLocation, OutFileName, PrintKind};
+use rustc_session::Session;
+use rustc_span::sym;
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::typeid::alias_set::same_alias_set;
+use rustc_symbol_mangling::typeid::demangle::{demangle_typeid, typeid_difference};
+use rustc_symbol_mangling::typeid::kcfi::{kcfi_typeid_for_instance, KcfiTypeId};
+use rustc_symbol_mangling::typeid::wasm::func_type_for_instance;
+use rustc_symbol_mangling::typeid::{typeid_for_instance, TypeIdOptions};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::{fs, iter};
+
+use crate::session_diagnostics::{
+    CfiCrateNotFound, CfiTypeIdGeneric, CfiTypeIdNotFound, TypeIdSnapshotMalformed,
+    TypeIdSnapshotUnreadable, TypeIdStabilityAdded, TypeIdStabilityChanged, TypeIdStabilityRemoved,
+};
+
+/// The options the type metadata identifiers are computed with for `-Zverify-typeid-stability`,
+/// in the order they are listed for each function in the snapshot.
+const STABILITY_OPTIONS: [TypeIdOptions; 4] = [
+    TypeIdOptions::empty(),
+    TypeIdOptions::NORMALIZE_INTEGERS,
+    TypeIdOptions::GENERALIZE_POINTERS,
+    TypeIdOptions::NORMALIZE_INTEGERS.union(TypeIdOptions::GENERALIZE_POINTERS),
+];
+
+/// Returns the options the type metadata identifiers are printed with, that is, the ones the
+/// functions are instrumented with, without compression with `-Zcfi-uncompressed-typeids`.
+fn printed_typeid_options(sess: &Session) -> TypeIdOptions {
+    let (mut options, _) = typeid_options(sess);
+    if sess.opts.unstable_opts.cfi_uncompressed_typeids {
+        options.insert(TypeIdOptions::UNCOMPRESSED);
+    }
+    options
+}
+
+/// Returns the instance of the non-generic function in the local crate with the specified path.
+fn resolve_fn<'tcx>(tcx: TyCtxt<'tcx>, path: &str) -> Result<Instance<'tcx>, ErrorGuaranteed> {
+    let local_path = path.strip_prefix("crate::").unwrap_or(path);
+    let Some(def_id) = tcx.hir_crate_items(()).definitions().find(|&def_id| {
+        matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            && with_no_trimmed_paths!(tcx.def_path_str(def_id)) == local_path
+    }) else {
+        return Err(tcx.dcx().emit_err(CfiTypeIdNotFound { path }));
+    };
+    if tcx.generics_of(def_id).requires_monomorphization(tcx) {
+        return Err(tcx.dcx().emit_err(CfiTypeIdGeneric { path }));
+    }
+    Ok(Instance::mono(tcx, def_id.to_def_id()))
+}
+
+/// Prints the type metadata identifier and KCFI type metadata identifier (and, for WebAssembly
+/// targets, the WebAssembly function type) of the functions requested with
+/// `--print cfi-type-id=<path>`, using the same options the functions are instrumented with.
+pub(crate) fn print_cfi_typeids(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
+    let sess = tcx.sess;
+    let (_, kcfi_options) = typeid_options(sess);
+    let options = printed_typeid_options(sess);
+
+    let mut result = Ok(());
+    for req in sess.opts.prints.iter().filter(|req| req.kind == PrintKind::CfiTypeId) {
+        let path = req.arg.as_deref().expect("cfi-type-id print request without a path");
+        let instance = match resolve_fn(tcx, path) {
+            Ok(instance) => instance,
+            Err(guar) => {
+                result = Err(guar);
+                continue;
+            }
+        };
+
+        let typeid = typeid_for_instance(tcx, instance, options);
+        let kcfi_typeid = match kcfi_typeid_for_instance(tcx, instance, kcfi_options) {
+            KcfiTypeId::U32(kcfi_typeid) => format!("{kcfi_typeid:#010x}"),
+            KcfiTypeId::U64(kcfi_typeid) => format!("{kcfi_typeid:#018x}"),
+        };
+        let signature = demangle_typeid(&typeid).unwrap_or_default();
+        let mut s =
+            format!("{path}: {signature}\n  typeid: {typeid}\n  kcfi-typeid: {kcfi_typeid}\n");
+        // WebAssembly engines check indirect calls against the WebAssembly function types instead
+        if matches!(&*sess.target.arch, "wasm32" | "wasm64") {
+            s.push_str(&format!("  wasm-type: {}\n", func_type_for_instance(tcx, instance)));
+        }
+        req.out.overwrite(&s, sess);
+    }
+    result
+}
+
+/// Returns the type metadata identifier of every non-generic function in the local crate, in source
+/// order, for `-Zunpretty=cfi-typeids`, as lines of the form
+/// `<path>: <transformed signature> => <type metadata identifier> [<encoding suffixes>]`, using
+/// the same options the functions are instrumented with.
+///
+/// The transformed signature is the signature the type metadata identifier encodes (i.e., after
+/// pointers are generalized, integers are normalized, repr(transparent) types are flattened, etc.).
+pub(crate) fn pretty_print_cfi_typeids(tcx: TyCtxt<'_>) -> String {
+    let options = printed_typeid_options(tcx.sess);
+
+    let mut def_ids: Vec<_> = tcx
+        .hir_crate_items(())
+        .definitions()
+        .filter(|&def_id| {
+            matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                && !tcx.generics_of(def_id).requires_monomorphization(tcx)
+                // Intrinsics without fallback bodies have no type metadata identifiers
+                && tcx.intrinsic(def_id).map_or(true, |intrinsic| {
+                    !intrinsic.must_be_overridden && tcx.has_attr(def_id, sym::rustc_intrinsic)
+                })
+        })
+        .collect();
+    def_ids.sort_by_key(|&def_id| tcx.def_span(def_id));
+
+    let mut s = String::new();
+    for def_id in def_ids {
+        let path = with_no_trimmed_paths!(tcx.def_path_str(def_id));
+        let typeid = typeid_for_instance(tcx, Instance::mono(tcx, def_id.to_def_id()), options);
+        let mut parts = typeid.split('.');
+        let encoding = parts.next().unwrap_or_default();
+        let suffixes: Vec<_> = parts.collect();
+        // User-defined encodings may not be renderable
+        let signature = demangle_typeid(encoding).unwrap_or_else(|| "<unrenderable>".to_string());
+        s.push_str(&format!("{path}: {signature} => {encoding}"));
+        if !suffixes.is_empty() {
+            s.push_str(&format!(" [{}]", suffixes.join(", ")));
+        }
+        s.push('\n');
+    }
+    s
+}
+
+/// Prints the type metadata identifiers of the two functions (or type metadata identifiers)
+/// specified with `-Zcfi-diff-typeids=<path>,<path>` and explains where they first differ.
+pub(crate) fn diff_cfi_typeids(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
+    let sess = tcx.sess;
+    let options = printed_typeid_options(sess);
+    let Some((lhs, rhs)) = &sess.opts.unstable_opts.cfi_diff_typeids else {
+        return Ok(());
+    };
+
+    // Arguments that are already type metadata identifiers (e.g., the ones from a C or C++
+    // compilation unit) are compared as is.
+    let typeid = |path: &str| {
+        if path.starts_with("_ZTS") {
+            Ok(path.to_string())
+        } else {
+            resolve_fn(tcx, path).map(|instance| typeid_for_instance(tcx, instance, options))
+        }
+    };
+    let (lhs_typeid, rhs_typeid) = (typeid(lhs), typeid(rhs));
+    let (lhs_typeid, rhs_typeid) = (lhs_typeid?, rhs_typeid?);
+
+    let mut s = String::new();
+    for (path, typeid) in [(lhs, &lhs_typeid), (rhs, &rhs_typeid)] {
+        let signature = demangle_typeid(typeid).unwrap_or_default();
+        s.push_str(&format!("{path}: {signature}\n  typeid: {typeid}\n"));
+    }
+    match typeid_difference(&lhs_typeid, &rhs_typeid) {
+        None => s.push_str("the type metadata identifiers are identical\n"),
+        Some(difference) => {
+            s.push_str(&format!("first difference: {}\n", difference.component));
+            s.push_str(&format!(
+                "  {lhs}: `{}` (encoded as `{}`)\n",
+                difference.lhs, difference.lhs_encoding
+            ));
+            s.push_str(&format!(
+                "  {rhs}: `{}` (encoded as `{}`)\n",
+                difference.rhs, difference.rhs_encoding
+            ));
+            if let Some(note) = difference.note {
+                s.push_str(&format!("  note: {note}\n"));
+            }
+            // Type metadata identifiers of other compilation units may be computed without the
+            // options of this one applied
+            if same_alias_set(&lhs_typeid, &rhs_typeid, options) {
+                s.push_str("they are in the same alias set with the options of this compilation\n");
+            }
+        }
+    }
+    OutFileName::Stdout.overwrite(&s, sess);
+    Ok(())
+}
+
+/// Returns the extern crate passed with `--extern <name>=<path>`.
+fn resolve_extern_crate(tcx: TyCtxt<'_>, name: &str) -> Result<CrateNum, ErrorGuaranteed> {
+    // Crates are identified by the (canonicalized) paths they were loaded from, as the versions of
+    // a crate being compared have the same crate name.
+    let paths: Vec<PathBuf> = match tcx.sess.opts.externs.get(name).map(|entry| &entry.location) {
+        Some(ExternLocation::ExactPaths(paths)) => {
+            paths.iter().map(|path| path.canonicalized().clone()).collect()
+        }
+        _ => Vec::new(),
+    };
+    tcx.crates(())
+        .iter()
+        .copied()
+        .find(|&cnum| tcx.used_crate_source(cnum).paths().any(|path| paths.contains(path)))
+        .ok_or_else(|| tcx.dcx().emit_err(CfiCrateNotFound { name }))
+}
+
+/// Returns the type metadata identifiers of the exported non-generic functions of the specified
+/// extern crate, by their paths within the crate.
+fn exported_fn_typeids(
+    tcx: TyCtxt<'_>,
+    cnum: CrateNum,
+    options: TypeIdOptions,
+) -> BTreeMap<String, String> {
+    tcx.exported_symbols(cnum)
+        .iter()
+        .filter_map(|&(symbol, _)| match symbol {
+            ExportedSymbol::NonGeneric(def_id)
+                if matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) =>
+            {
+                let path = tcx.def_path(def_id).to_string_no_crate_verbose();
+                let path = path.trim_start_matches("::").to_string();
+                Some((path, typeid_for_instance(tcx, Instance::mono(tcx, def_id), options)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Prints the type metadata identifiers of the exported non-generic functions that were changed,
+/// added, or removed between the two versions of a crate specified with
+/// `-Zcfi-diff-crate-typeids=<crate>,<crate>` (i.e., the names of the extern crates they are
+/// passed as, such as `--extern force:old=<path> --extern force:new=<path>`), matched by their
+/// paths within the crate, and explains where the changed ones first differ.
+///
+/// The type metadata identifiers are computed from the types of the functions in the metadata of
+/// the crates, with the options of the current session, so they are the ones code calling the
+/// functions through pointers is instrumented with.
+pub(crate) fn diff_cfi_crate_typeids(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
+    let sess = tcx.sess;
+    let options = printed_typeid_options(sess);
+    let Some((old, new)) = &sess.opts.unstable_opts.cfi_diff_crate_typeids else {
+        return Ok(());
+    };
+    let (old_cnum, new_cnum) = (resolve_extern_crate(tcx, old), resolve_extern_crate(tcx, new));
+    let (old_cnum, new_cnum) = (old_cnum?, new_cnum?);
+    let old_typeids = exported_fn_typeids(tcx, old_cnum, options);
+    let new_typeids = exported_fn_typeids(tcx, new_cnum, options);
+
+    let (mut changed, mut added, mut removed) = (0, 0, 0);
+    let mut s = String::new();
+    for (path, old_typeid) in &old_typeids {
+        let Some(new_typeid) = new_typeids.get(path) else {
+            removed += 1;
+            s.push_str(&format!("removed: {path}\n  {old}: {old_typeid}\n"));
+            continue;
+        };
+        let Some(difference) = typeid_difference(old_typeid, new_typeid) else {
+            continue;
+        };
+        changed += 1;
+        s.push_str(&format!("changed: {path}\n  {old}: {old_typeid}\n  {new}: {new_typeid}\n"));
+        s.push_str(&format!(
+            "  first difference: {}: `{}` => `{}`\n",
+            difference.component, difference.lhs, difference.rhs
+        ));
+        if let Some(note) = difference.note {
+            s.push_str(&format!("  note: {note}\n"));
+        }
+    }
+    for (path, new_typeid) in &new_typeids {
+        if !old_typeids.contains_key(path) {
+            added += 1;
+            s.push_str(&format!("added: {path}\n  {new}: {new_typeid}\n"));
+        }
+    }
+    s.push_str(&format!("{changed} changed, {added} added, {removed} removed\n"));
+    OutFileName::Stdout.overwrite(&s, sess);
+    Ok(())
+}
+
+/// Compares the type metadata identifiers of the non-generic functions in the local crate against
+/// the snapshot specified with `-Zverify-typeid-stability=<path>`, and reports any difference.
+///
+/// Changes to how types are encoded break linking with code compiled by other compiler versions,
+/// so the snapshot is expected to only be updated deliberately.
+pub(crate) fn verify_typeid_stability(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
+    let dcx = tcx.dcx();
+    let Some(snapshot) = &tcx.sess.opts.unstable_opts.verify_typeid_stability else {
+        return Ok(());
+    };
+    let contents = fs::read_to_string(snapshot)
+        .map_err(|err| dcx.emit_err(TypeIdSnapshotUnreadable { path: snapshot, err }))?;
+
+    // Lines are of the form `<path>: <typeid>`, with blank lines and lines starting with `#`
+    // ignored.
+    let mut expected: FxIndexMap<&str, Vec<&str>> = FxIndexMap::default();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((path, typeid)) = line.rsplit_once(": ") else {
+            return Err(dcx.emit_err(TypeIdSnapshotMalformed { path: snapshot, line }));
+        };
+        expected.entry(path).or_default().push(typeid);
+    }
+
+    let mut found = BTreeMap::new();
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            || tcx.generics_of(def_id).requires_monomorphization(tcx)
+        {
+            continue;
+        }
+        let instance = Instance::mono(tcx, def_id.to_def_id());
+        let typeids: Vec<_> = STABILITY_OPTIONS
+            .iter()
+            .map(|&options| typeid_for_instance(tcx, instance, options))
+            .collect();
+        found.insert(with_no_trimmed_paths!(tcx.def_path_str(def_id)), (def_id, typeids));
+    }
+
+    let lines = |path: &str, typeids: &[&str]| {
+        typeids.iter().map(|typeid| format!("{path}: {typeid}")).collect::<Vec<_>>().join("\n")
+    };
+    let mut result = Ok(());
+    for (path, (def_id, typeids)) in &found {
+        let span = tcx.def_span(*def_id);
+        let expected_typeids = expected.get(path.as_str()).map_or(&[][..], |typeids| typeids);
+        for (expected_typeid, typeid) in iter::zip(expected_typeids, typeids) {
+            let Some(difference) = typeid_difference(expected_typeid, typeid) else {
+                continue;
+            };
+            result = Err(dcx.emit_err(TypeIdStabilityChanged {
+                span,
+                path,
+                expected: expected_typeid,
+                found: typeid,
+                component: difference.component,
+                expected_component: difference.lhs,
+                found_component: difference.rhs,
+                snapshot,
+            }));
+        }
+        if typeids.len() > expected_typeids.len() {
+            let added: Vec<_> =
+                typeids[expected_typeids.len()..].iter().map(String::as_str).collect();
+            result = Err(dcx.emit_err(TypeIdStabilityAdded {
+                span,
+                path,
+                lines: lines(path, &added),
+                snapshot,
+            }));
+        } else if expected_typeids.len() > typeids.len() {
+            result = Err(dcx.emit_err(TypeIdStabilityRemoved {
+                path,
+                lines: lines(path, &expected_typeids[typeids.len()..]),
+                snapshot,
+            }));
+        }
+    }
+    for (path, expected_typeids) in &expected {
+        if !found.contains_key(*path) {
+            result = Err(dcx.emit_err(TypeIdStabilityRemoved {
+                path,
+                lines: lines(path, expected_typeids),
+                snapshot,
+            }));
+        }
+    }
+    result
+}
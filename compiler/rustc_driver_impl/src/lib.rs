@@ -35,7 +35,7 @@
 use rustc_metadata::creader::MetadataLoader;
 use rustc_metadata::locator;
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
-use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType};
+use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType, PrintKind};
 use rustc_session::getopts::{self, Matches};
 use rustc_session::lint::{Lint, LintId};
 use rustc_session::output::collect_crate_types;
@@ -82,6 +82,7 @@
 use {do_not_use_print as print, do_not_use_print as println};
 
 pub mod args;
+mod cfi;
 pub mod pretty;
 #[macro_use]
 mod print;
@@ -134,6 +135,7 @@ pub(super) fn install() {}
     rustc_query_system::DEFAULT_LOCALE_RESOURCE,
     rustc_resolve::DEFAULT_LOCALE_RESOURCE,
     rustc_session::DEFAULT_LOCALE_RESOURCE,
+    rustc_symbol_mangling::DEFAULT_LOCALE_RESOURCE,
     rustc_trait_selection::DEFAULT_LOCALE_RESOURCE,
     rustc_ty_utils::DEFAULT_LOCALE_RESOURCE,
     // tidy-alphabetical-end
@@ -444,6 +446,26 @@ fn run_compiler(
                 return early_exit();
             }
 
+            if sess.opts.prints.iter().any(|req| req.kind == PrintKind::CfiTypeId) {
+                queries.global_ctxt()?.enter(|tcx| cfi::print_cfi_typeids(tcx))?;
+                return early_exit();
+            }
+
+            if sess.opts.unstable_opts.cfi_diff_crate_typeids.is_some() {
+                queries.global_ctxt()?.enter(|tcx| cfi::diff_cfi_crate_typeids(tcx))?;
+                return early_exit();
+            }
+
+            if sess.opts.unstable_opts.cfi_diff_typeids.is_some() {
+                queries.global_ctxt()?.enter(|tcx| cfi::diff_cfi_typeids(tcx))?;
+                return early_exit();
+            }
+
+            if sess.opts.unstable_opts.verify_typeid_stability.is_some() {
+                queries.global_ctxt()?.enter(|tcx| cfi::verify_typeid_stability(tcx))?;
+                return early_exit();
+            }
+
             let linker = queries.codegen_and_build_linker()?;
 
             // This must run after monomorphization so that all generic types
@@ -458,6 +480,18 @@ fn run_compiler(
                 sess.code_stats.print_vtable_sizes(crate_name);
             }
 
+            if sess.opts.unstable_opts.cfi_compression_stats {
+                let crate_name = queries.global_ctxt()?.enter(|tcx| tcx.crate_name(LOCAL_CRATE));
+
+                sess.code_stats.print_cfi_compression_stats(crate_name);
+            }
+
+            if sess.opts.unstable_opts.cfi_fragment_stats {
+                let crate_name = queries.global_ctxt()?.enter(|tcx| tcx.crate_name(LOCAL_CRATE));
+
+                sess.code_stats.print_cfi_fragment_stats(crate_name);
+            }
+
             Ok(Some(linker))
         })?;
 
@@ -703,9 +737,10 @@ fn print_crate_info(
     #[allow(unused_imports)]
     use {do_not_use_safe_print as safe_print, do_not_use_safe_print as safe_println};
 
-    // NativeStaticLibs and LinkArgs are special - printed during linking
+    // NativeStaticLibs and LinkArgs are special - printed during linking,
+    // and CfiTypeId is printed after analysis
     // (empty iterator returns true)
-    if sess.opts.prints.iter().all(|p| p.kind == NativeStaticLibs || p.kind == LinkArgs) {
+    if sess.opts.prints.iter().all(|p| matches!(p.kind, NativeStaticLibs | LinkArgs | CfiTypeId)) {
         return Compilation::Continue;
     }
 
@@ -820,6 +855,12 @@ fn print_crate_info(
             // Any output here interferes with Cargo's parsing of other printed output
             NativeStaticLibs => {}
             LinkArgs => {}
+            CfiGrammar => {
+                let grammar = rustc_symbol_mangling::typeid::grammar::grammar_json();
+                println_info!("{}", serde_json::to_string_pretty(&grammar).unwrap());
+            }
+            // Needs the function path to be resolved, so it is printed after analysis
+            CfiTypeId => {}
             SplitDebuginfo => {
                 use rustc_target::spec::SplitDebuginfo::{Off, Packed, Unpacked};
 
@@ -333,6 +333,10 @@ pub fn print<'tcx>(sess: &Session, ppm: PpMode, ex: PrintExtra<'tcx>) {
             write_smir_pretty(ex.tcx(), &mut out).unwrap();
             String::from_utf8(out).unwrap()
         }
+        CfiTypeIds => {
+            debug!("pretty printing CFI type metadata identifiers");
+            crate::cfi::pretty_print_cfi_typeids(ex.tcx())
+        }
         ThirTree => {
             let tcx = ex.tcx();
             let mut out = String::new();
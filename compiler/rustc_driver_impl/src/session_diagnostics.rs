@@ -1,4 +1,6 @@
 use rustc_macros::{Diagnostic, Subdiagnostic};
+use rustc_span::Span;
+use std::path::Path;
 
 #[derive(Diagnostic)]
 #[diag(driver_impl_rlink_unable_to_read)]
@@ -32,6 +34,77 @@ pub(crate) struct RLinkRustcVersionMismatch<'a> {
 #[diag(driver_impl_rlink_no_a_file)]
 pub(crate) struct RlinkNotAFile;
 
+#[derive(Diagnostic)]
+#[diag(driver_impl_cfi_crate_not_found)]
+pub(crate) struct CfiCrateNotFound<'a> {
+    pub name: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_cfi_type_id_not_found)]
+pub(crate) struct CfiTypeIdNotFound<'a> {
+    pub path: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_cfi_type_id_generic)]
+#[note]
+pub(crate) struct CfiTypeIdGeneric<'a> {
+    pub path: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_typeid_snapshot_unreadable)]
+pub(crate) struct TypeIdSnapshotUnreadable<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_typeid_snapshot_malformed)]
+#[note]
+pub(crate) struct TypeIdSnapshotMalformed<'a> {
+    pub path: &'a Path,
+    pub line: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_typeid_stability_changed)]
+#[note]
+#[note(driver_impl_difference)]
+#[help]
+pub(crate) struct TypeIdStabilityChanged<'a> {
+    #[primary_span]
+    pub span: Span,
+    pub path: &'a str,
+    pub expected: &'a str,
+    pub found: &'a str,
+    pub component: String,
+    pub expected_component: String,
+    pub found_component: String,
+    pub snapshot: &'a Path,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_typeid_stability_added)]
+#[help]
+pub(crate) struct TypeIdStabilityAdded<'a> {
+    #[primary_span]
+    pub span: Span,
+    pub path: &'a str,
+    pub lines: String,
+    pub snapshot: &'a Path,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_typeid_stability_removed)]
+#[help]
+pub(crate) struct TypeIdStabilityRemoved<'a> {
+    pub path: &'a str,
+    pub lines: String,
+    pub snapshot: &'a Path,
+}
+
 #[derive(Diagnostic)]
 #[diag(driver_impl_ice)]
 pub(crate) struct Ice;
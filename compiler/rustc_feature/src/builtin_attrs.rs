@@ -528,11 +528,22 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::No, experimental!(do_not_recommend)
     ),
 
-    // `#[cfi_encoding = ""]`
+    // `#[cfi_encoding = ""]`, `#[cfi_encoding(namespace = "", name = "")]`, or
+    // `#[cfi_encoding(transform = "void")]`
     gated!(
-        cfi_encoding, Normal, template!(NameValueStr: "encoding"), ErrorPreceding,
+        cfi_encoding, Normal,
+        template!(
+            List: r#"/*opt*/ namespace = "...", name = "...", /*opt*/ template_args = "...""#,
+            NameValueStr: "encoding"
+        ),
+        ErrorPreceding,
         EncodeCrossCrate::Yes, experimental!(cfi_encoding)
     ),
+    // `#![cfi_encodings(path::to::Type = "encoding", ...)]`
+    gated!(
+        cfi_encodings, CrateLevel, template!(List: r#"path::to::Type = "encoding", ..."#),
+        DuplicatesOk, EncodeCrossCrate::Yes, cfi_encoding, experimental!(cfi_encodings)
+    ),
 
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
@@ -673,6 +684,11 @@ pub struct BuiltinAttribute {
         rustc_std_internal_symbol, Normal, template!(Word), WarnFollowing,
         EncodeCrossCrate::No, INTERNAL_UNSTABLE
     ),
+    rustc_attr!(
+        rustc_unhashed_symbol, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, "#[rustc_unhashed_symbol] keeps the symbol of a function unhashed \
+        with `-C symbol-mangling-version=hashed`"
+    ),
 
     // ==========================================================================
     // Internal attributes, Macro related:
@@ -1019,7 +1035,7 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::No
     ),
     rustc_attr!(
-        TEST, rustc_symbol_name, Normal, template!(Word),
+        TEST, rustc_symbol_name, Normal, template!(Word, List: "all"),
         WarnFollowing, EncodeCrossCrate::No
     ),
     rustc_attr!(
@@ -1030,6 +1046,10 @@ pub struct BuiltinAttribute {
         TEST, rustc_def_path, Normal, template!(Word),
         WarnFollowing, EncodeCrossCrate::No
     ),
+    rustc_attr!(
+        TEST, rustc_ty_name_encoding, Normal, template!(Word),
+        WarnFollowing, EncodeCrossCrate::No
+    ),
     rustc_attr!(
         TEST, rustc_mir, Normal, template!(List: "arg1, arg2, ..."),
         DuplicatesOk, EncodeCrossCrate::Yes
@@ -387,7 +387,7 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, cfg_ub_checks, "CURRENT_RUSTC_VERSION", Some(123499)),
     /// Allow conditional compilation depending on rust version
     (unstable, cfg_version, "1.45.0", Some(64796)),
-    /// Allows to use the `#[cfi_encoding = ""]` attribute.
+    /// Allows to use the `#[cfi_encoding = ""]` and `#[cfi_encoding(name = "")]` attributes.
     (unstable, cfi_encoding, "1.71.0", Some(89653)),
     /// Allows `for<...>` on closures and coroutines.
     (unstable, closure_lifetime_binder, "1.64.0", Some(97362)),
@@ -111,7 +111,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_fs_util::{link_or_copy, try_canonicalize, LinkOrCopy};
 use rustc_session::config::CrateType;
-use rustc_session::output::{collect_crate_types, find_crate_name};
+use rustc_session::output::{collect_crate_types, find_crate_name, stable_crate_id_metadata};
 use rustc_session::{Session, StableCrateId};
 
 use std::fs as std_fs;
@@ -609,7 +609,7 @@ fn crate_path(sess: &Session) -> PathBuf {
     let stable_crate_id = StableCrateId::new(
         crate_name,
         crate_types.contains(&CrateType::Executable),
-        sess.opts.cg.metadata.clone(),
+        stable_crate_id_metadata(sess),
         sess.cfg_version,
     );
 
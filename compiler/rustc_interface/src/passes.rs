@@ -935,6 +935,7 @@ pub fn start_codegen<'tcx>(
     // build-fail tests in check mode first and expects it to not give an error in that case.
     if tcx.sess.opts.output_types.should_codegen() {
         rustc_symbol_mangling::test::report_symbol_names(tcx);
+        rustc_monomorphize::check_cfi_alias_sets(tcx);
     }
 
     info!("Post-codegen\n{:?}", tcx.debug_stats());
@@ -18,7 +18,7 @@
 use rustc_serialize::opaque::FileEncodeResult;
 use rustc_session::config::{self, CrateType, OutputFilenames, OutputType};
 use rustc_session::cstore::Untracked;
-use rustc_session::output::{collect_crate_types, find_crate_name};
+use rustc_session::output::{collect_crate_types, find_crate_name, stable_crate_id_metadata};
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use std::any::Any;
@@ -132,7 +132,7 @@ pub fn global_ctxt(&'tcx self) -> Result<QueryResult<'_, &'tcx GlobalCtxt<'tcx>>
             let stable_crate_id = StableCrateId::new(
                 crate_name,
                 crate_types.contains(&CrateType::Executable),
-                sess.opts.cg.metadata.clone(),
+                stable_crate_id_metadata(sess),
                 sess.cfg_version,
             );
             let outputs = util::build_output_filenames(&pre_configured_attrs, sess);
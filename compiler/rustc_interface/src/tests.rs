@@ -4,11 +4,12 @@
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
 use rustc_session::config::{
     build_configuration, build_session_options, rustc_optgroups, BranchProtection, CFGuard, Cfg,
-    CollapseMacroDebuginfo, CoverageOptions, DebugInfo, DumpMonoStatsFormat, ErrorOutputType,
-    ExternEntry, ExternLocation, Externs, FunctionReturn, InliningThreshold, Input,
-    InstrumentCoverage, InstrumentXRay, LinkSelfContained, LinkerPluginLto, LocationDetail, LtoCli,
-    NextSolverConfig, OomStrategy, Options, OutFileName, OutputType, OutputTypes, PAuthKey, PacRet,
-    Passes, Polonius, ProcMacroExecutionStrategy, Strip, SwitchWithOptPath, SymbolManglingVersion,
+    CfiTraitObjectProjections, CfiTypeIdScheme, CollapseMacroDebuginfo, CoverageOptions, DebugInfo,
+    DumpMonoStatsFormat, ErrorOutputType, ExternEntry, ExternLocation, Externs, FunctionReturn,
+    InliningThreshold, Input, InstrumentCoverage, InstrumentXRay, KcfiTypeIdWidth,
+    LinkSelfContained, LinkerPluginLto, LocationDetail, LtoCli, NextSolverConfig, OomStrategy,
+    Options, OutFileName, OutputType, OutputTypes, PAuthKey, PacRet, Passes, Polonius,
+    ProcMacroExecutionStrategy, Strip, SwitchWithOptPath, SymbolHashPolicy, SymbolManglingVersion,
     WasiExecModel,
 };
 use rustc_session::lint::Level;
@@ -675,6 +676,17 @@ macro_rules! untracked {
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // tidy-alphabetical-start
     untracked!(assert_incr_state, Some(String::from("loaded")));
+    untracked!(cfi_alias_set_report, true);
+    untracked!(cfi_alias_set_threshold, 10);
+    untracked!(cfi_clang_compat, Some(17));
+    untracked!(cfi_compression_stats, true);
+    untracked!(cfi_diff_crate_typeids, Some((String::from("old"), String::from("new"))));
+    untracked!(cfi_diff_typeids, Some((String::from("foo"), String::from("bar"))));
+    untracked!(cfi_dump_typeids, Some(PathBuf::from("typeids.json")));
+    untracked!(cfi_export_typeids, Some(PathBuf::from("typeids.txt")));
+    untracked!(cfi_fragment_stats, true);
+    untracked!(cfi_trace_transform, Some(String::from("foo")));
+    untracked!(cfi_uncompressed_typeids, true);
     untracked!(deduplicate_diagnostics, false);
     untracked!(dump_dep_graph, true);
     untracked!(dump_mir, Some(String::from("abc")));
@@ -686,6 +698,7 @@ macro_rules! untracked {
     untracked!(dump_mono_stats_format, DumpMonoStatsFormat::Json);
     untracked!(dylib_lto, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(emit_symbol_map, Some(PathBuf::from("abc")));
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
@@ -708,6 +721,8 @@ macro_rules! untracked {
     untracked!(print_codegen_stats, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
+    untracked!(print_mono_items_matching, Some(String::from("abc::*")));
+    untracked!(print_symbol_explanations, true);
     untracked!(print_type_sizes, true);
     untracked!(proc_macro_backtrace, true);
     untracked!(proc_macro_execution_strategy, ProcMacroExecutionStrategy::CrossThread);
@@ -730,6 +745,7 @@ macro_rules! untracked {
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
+    untracked!(verify_typeid_stability, Some(PathBuf::from("abc")));
     untracked!(write_long_types_to_disk, false);
     // tidy-alphabetical-end
 
@@ -757,10 +773,16 @@ macro_rules! tracked {
             pac_ret: Some(PacRet { leaf: true, key: PAuthKey::B })
         })
     );
+    tracked!(cfi_assert_consistency, true);
+    tracked!(cfi_cross_language, true);
+    tracked!(cfi_eager_typeids, true);
+    tracked!(cfi_encoding_profile, Some(PathBuf::from("profile.json")));
+    tracked!(cfi_foreign_exceptions, true);
     tracked!(codegen_backend, Some("abc".to_string()));
     tracked!(collapse_macro_debuginfo, CollapseMacroDebuginfo::Yes);
     tracked!(coverage_options, CoverageOptions { branch: true });
     tracked!(crate_attr, vec!["abc".to_string()]);
+    tracked!(crate_id_from_content, Some("1.0.0".to_string()));
     tracked!(cross_crate_inline_threshold, InliningThreshold::Always);
     tracked!(debug_info_for_profiling, true);
     tracked!(debug_macros, true);
@@ -790,6 +812,7 @@ macro_rules! tracked {
     tracked!(llvm_module_flag, vec![("bar".to_string(), 123, "max".to_string())]);
     tracked!(llvm_plugins, vec![String::from("plugin_name")]);
     tracked!(location_detail, LocationDetail { file: true, line: false, column: false });
+    tracked!(mangle_internal_symbols, true);
     tracked!(maximal_hir_to_mir_coverage, true);
     tracked!(merge_functions, Some(MergeFunctions::Disabled));
     tracked!(mir_emit_retag, true);
@@ -825,10 +848,30 @@ macro_rules! tracked {
     tracked!(relro_level, Some(RelroLevel::Full));
     tracked!(remap_cwd_prefix, Some(PathBuf::from("abc")));
     tracked!(sanitizer, SanitizerSet::ADDRESS);
+    tracked!(sanitizer_cfi_box_as_ptr, Some(true));
+    tracked!(sanitizer_cfi_c_integer_types, Some(true));
     tracked!(sanitizer_cfi_canonical_jump_tables, None);
+    tracked!(sanitizer_cfi_cxx_references, Some(true));
+    tracked!(sanitizer_cfi_encode_captures, Some(true));
+    tracked!(sanitizer_cfi_encode_fn_sigs, Some(true));
+    tracked!(sanitizer_cfi_erase_const, Some(true));
+    tracked!(sanitizer_cfi_erase_phantom_data, Some(true));
+    tracked!(sanitizer_cfi_erase_ref_mut, Some(true));
+    tracked!(sanitizer_cfi_erase_return_types, Some(true));
+    tracked!(sanitizer_cfi_generalize_arrays, Some(true));
     tracked!(sanitizer_cfi_generalize_pointers, Some(true));
+    tracked!(sanitizer_cfi_impl_trait_bounds, Some(true));
+    tracked!(sanitizer_cfi_member_function_pointers, Some(true));
     tracked!(sanitizer_cfi_normalize_integers, Some(true));
+    tracked!(sanitizer_cfi_normalize_ptr_sized_integers, Some(true));
+    tracked!(sanitizer_cfi_refs_as_ptrs, Some(true));
+    tracked!(sanitizer_cfi_trait_object_projections, CfiTraitObjectProjections::Principal);
+    tracked!(sanitizer_cfi_typeid_length_cap, Some(256));
+    tracked!(sanitizer_cfi_typeid_scheme, CfiTypeIdScheme::Msvc);
     tracked!(sanitizer_dataflow_abilist, vec![String::from("/rustc/abc")]);
+    tracked!(sanitizer_kcfi_arity, Some(true));
+    tracked!(sanitizer_kcfi_seed, Some(0x1234abcd));
+    tracked!(sanitizer_kcfi_typeid_width, KcfiTypeIdWidth::Bits64);
     tracked!(sanitizer_memory_track_origins, 2);
     tracked!(sanitizer_recover, SanitizerSet::ADDRESS);
     tracked!(saturating_float_casts, Some(true));
@@ -838,6 +881,12 @@ macro_rules! tracked {
     tracked!(split_lto_unit, Some(true));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
     tracked!(stack_protector, StackProtector::All);
+    tracked!(symbol_length_cap, Some(256));
+    tracked!(symbol_mangling_hash_bits, Some(128));
+    tracked!(symbol_mangling_hash_policy, Some(SymbolHashPolicy::Private));
+    tracked!(symbol_mangling_legacy_aliases, true);
+    tracked!(symbol_mangling_suffix, Some(String::from("abc")));
+    tracked!(symbol_mangling_wide_legacy_hash, true);
     tracked!(teach, true);
     tracked!(thinlto, Some(true));
     tracked!(tiny_const_eval_limit, true);
@@ -161,6 +161,21 @@ lint_builtin_unused_doc_comment = unused doc comment
 lint_builtin_while_true = denote infinite loops with `loop {"{"} ... {"}"}`
     .suggestion = use `loop`
 
+lint_cfi_encoding_collisions = CFI encoding `{$encoding}` of `{$ty}` collides with the encoding of `{$other}`
+    .label = `{$ty}` is encoded as `{$encoding}`
+    .other_note = `{$other}` is also encoded as `{$encoding}`
+    .note = types with the same encoding are indistinguishable to CFI, so function pointers using either type may be used to call functions using the other
+    .help = if this is intentional, add `#[allow(cfi_encoding_collisions)]` to `{$ty}`
+
+lint_cfi_incompatible_ffi_types = `extern` function uses type `{$ty}`, which can never match a C type under cross-language CFI
+    .label = not compatible with cross-language CFI
+    .note = indirect calls between Rust and C through a pointer to this function will always be reported as CFI violations
+    .help = consider using a `#[repr(C)]` type or a raw pointer instead
+    .c_char_suggestion = consider using a raw pointer to a C string instead
+    .nullable_pointer_suggestion = consider using a nullable raw pointer instead
+    .raw_pointer_suggestion = consider using a raw pointer instead
+    .repr_c_suggestion = consider adding `#[repr(C)]` to `{$ty}`
+
 lint_check_name_unknown_tool = unknown lint tool: `{$tool_name}`
 
 lint_command_line_source = `forbid` lint level was set on command line
@@ -0,0 +1,148 @@
+use crate::lints::CfiEncodingCollisionsDiag;
+use crate::{LateContext, LateLintPass};
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::{declare_lint, declare_lint_pass};
+use rustc_span::{sym, Span};
+use rustc_symbol_mangling::typeid::{primitive_ty_encodings, type_def_encoding, TypeDefEncoding};
+
+declare_lint! {
+    /// The `cfi_encoding_collisions` lint detects user-provided control flow integrity (CFI)
+    /// encodings (i.e., `#[cfi_encoding]` attribute values) that are identical to the encoding of
+    /// another type in the crate graph.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(cfi_encoding)]
+    ///
+    /// #[repr(C)]
+    /// pub struct Foo(i32);
+    ///
+    /// #[cfi_encoding = "3Foo"]
+    /// pub struct Bar(i64);
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Types with the same encoding are indistinguishable in type metadata identifiers, so the
+    /// function types that use them are in the same alias set and indirect calls through a pointer
+    /// to one are allowed to target the other. This is sometimes intended (e.g., to make a Rust type
+    /// match a C type), but may also be a typo. If the aliasing is intentional, allow this lint on
+    /// the type with the user-provided encoding.
+    pub CFI_ENCODING_COLLISIONS,
+    Warn,
+    "detects user-provided CFI encodings that collide with the encoding of another type"
+}
+
+declare_lint_pass!(CfiEncodingCollisions => [CFI_ENCODING_COLLISIONS]);
+
+/// A type with an encoding in type metadata identifiers.
+#[derive(Clone, Copy, PartialEq)]
+enum EncodedTy<'tcx> {
+    Primitive(Ty<'tcx>),
+    Def(DefId),
+}
+
+impl<'tcx> LateLintPass<'tcx> for CfiEncodingCollisions {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let tcx = cx.tcx;
+        let user_encodings: Vec<_> = tcx
+            .hir_crate_items(())
+            .definitions()
+            .filter_map(|def_id| match type_def_encoding(tcx, def_id.to_def_id()) {
+                Some(TypeDefEncoding::UserDefined(encoding)) => Some((def_id, encoding)),
+                _ => None,
+            })
+            .collect();
+        if user_encodings.is_empty() {
+            return;
+        }
+
+        // The encodings of the primitive types, the local types, and the types of the crates in the
+        // crate graph, in this order.
+        let mut encodings: FxIndexMap<String, Vec<EncodedTy<'tcx>>> = Default::default();
+        for (ty, encoding) in primitive_ty_encodings(tcx) {
+            encodings.entry(encoding).or_default().push(EncodedTy::Primitive(ty));
+        }
+        let local_defs = tcx.hir_crate_items(()).definitions().map(|def_id| def_id.to_def_id());
+        let extern_defs = tcx.crates(()).iter().flat_map(|&cnum| extern_type_defs(tcx, cnum));
+        for def_id in local_defs.chain(extern_defs) {
+            let def_encodings = match type_def_encoding(tcx, def_id) {
+                Some(TypeDefEncoding::UserDefined(encoding)) => vec![encoding],
+                Some(TypeDefEncoding::Automatic(encodings)) => encodings,
+                None => continue,
+            };
+            for encoding in def_encodings {
+                encodings.entry(encoding).or_default().push(EncodedTy::Def(def_id));
+            }
+        }
+
+        for (def_id, encoding) in user_encodings {
+            let Some(&other) = encodings[&encoding]
+                .iter()
+                .find(|&&other| other != EncodedTy::Def(def_id.to_def_id()))
+            else {
+                continue;
+            };
+            let (other, other_span) = match other {
+                EncodedTy::Primitive(ty) => (ty.to_string(), None),
+                EncodedTy::Def(other_def_id) => {
+                    (tcx.def_path_str(other_def_id), Some(tcx.def_span(other_def_id)))
+                }
+            };
+            let span = cfi_encoding_span(tcx, def_id.to_def_id());
+            tcx.emit_node_span_lint(
+                CFI_ENCODING_COLLISIONS,
+                tcx.local_def_id_to_hir_id(def_id),
+                span,
+                CfiEncodingCollisionsDiag {
+                    ty: tcx.def_path_str(def_id),
+                    encoding,
+                    other,
+                    label: span,
+                    other_span,
+                },
+            );
+        }
+    }
+}
+
+/// Returns the span of the `cfi_encoding` attribute of the specified type.
+fn cfi_encoding_span(tcx: TyCtxt<'_>, def_id: DefId) -> Span {
+    tcx.get_attr(def_id, sym::cfi_encoding).map_or_else(|| tcx.def_span(def_id), |attr| attr.span)
+}
+
+/// Returns the user-defined types and extern types of the specified crate that are reachable
+/// through its public modules.
+fn extern_type_defs(tcx: TyCtxt<'_>, cnum: CrateNum) -> FxIndexSet<DefId> {
+    let mut defs = FxIndexSet::default();
+    let mut visited = FxHashSet::default();
+    let mut modules = vec![cnum.as_def_id()];
+    while let Some(module) = modules.pop() {
+        for child in tcx.module_children(module) {
+            let Res::Def(def_kind, def_id) = child.res else {
+                continue;
+            };
+            if def_id.krate != cnum {
+                continue;
+            }
+            match def_kind {
+                DefKind::Mod => {
+                    if visited.insert(def_id) {
+                        modules.push(def_id);
+                    }
+                }
+                DefKind::Struct | DefKind::Enum | DefKind::Union | DefKind::ForeignTy => {
+                    defs.insert(def_id);
+                }
+                _ => {}
+            }
+        }
+    }
+    defs
+}
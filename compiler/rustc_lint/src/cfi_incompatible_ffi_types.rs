@@ -0,0 +1,201 @@
+use crate::lints::{CfiIncompatibleFfiTypesDiag, CfiIncompatibleFfiTypesSugg};
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::ty::{self, Ty, TypeVisitableExt};
+use rustc_session::{declare_lint, declare_lint_pass};
+use rustc_span::sym;
+use rustc_symbol_mangling::typeid::{
+    cross_language_incompatible_ty, CrossLanguageIncompatibility, TypeIdOptions,
+};
+use rustc_target::spec::abi::Abi;
+use std::iter;
+
+declare_lint! {
+    /// The `cfi_incompatible_ffi_types` lint detects parameter and return types of functions with
+    /// the "C" calling convention that can never match a C type under cross-language control flow
+    /// integrity (CFI) (i.e., with `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`, and
+    /// `-Zsanitizer-cfi-normalize-integers`).
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (requires -Zsanitizer=cfi and -Zsanitizer-cfi-normalize-integers)
+    /// pub extern "C" fn foo(s: String) {}
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: `extern` function uses type `String`, which can never match a C type under cross-language CFI
+    ///  --> lint_example.rs:1:26
+    ///   |
+    /// 1 | pub extern "C" fn foo(s: String) {}
+    ///   |                          ^^^^^^ not compatible with cross-language CFI
+    ///   |
+    ///   = note: indirect calls between Rust and C through a pointer to this function will always be reported as CFI violations
+    ///   = note: `#[warn(cfi_incompatible_ffi_types)]` on by default
+    /// help: consider using a raw pointer to a C string instead
+    ///   |
+    /// 1 | pub extern "C" fn foo(s: *const core::ffi::c_char) {}
+    ///   |                          ~~~~~~~~~~~~~~~~~~~~~~~~
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Types without a C counterpart (such as references, trait objects, and user-defined types
+    /// that are not `#[repr(C)]`) are encoded as Rust types in type metadata identifiers, so the
+    /// type metadata identifier of a function that uses them can never match the one of any C
+    /// function type, and indirect calls to it from C (or to a C function through it from Rust)
+    /// will always trap.
+    pub CFI_INCOMPATIBLE_FFI_TYPES,
+    Warn,
+    "detects `extern \"C\"` function signatures that can never match a C function type under \
+     cross-language CFI"
+}
+
+declare_lint_pass!(CfiIncompatibleFfiTypes => [CFI_INCOMPATIBLE_FFI_TYPES]);
+
+impl<'tcx> LateLintPass<'tcx> for CfiIncompatibleFfiTypes {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        if let hir::ItemKind::Fn(sig, ..) = item.kind
+            && matches!(sig.header.abi, Abi::C { .. })
+        {
+            check_fn(cx, item.owner_id.def_id, sig.decl, false);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'tcx>) {
+        if let hir::ImplItemKind::Fn(sig, _) = item.kind
+            && matches!(sig.header.abi, Abi::C { .. })
+        {
+            check_fn(cx, item.owner_id.def_id, sig.decl, false);
+        }
+    }
+
+    fn check_foreign_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ForeignItem<'tcx>) {
+        if let hir::ForeignItemKind::Fn(decl, ..) = item.kind
+            && matches!(cx.tcx.hir().get_foreign_abi(item.hir_id()), Abi::C { .. })
+        {
+            check_fn(cx, item.owner_id.def_id, decl, true);
+        }
+    }
+}
+
+/// Checks the parameter and return types of a function with the "C" calling convention.
+fn check_fn<'tcx>(
+    cx: &LateContext<'tcx>,
+    def_id: LocalDefId,
+    decl: &'tcx hir::FnDecl<'tcx>,
+    is_foreign: bool,
+) {
+    let tcx = cx.tcx;
+    let sess = tcx.sess;
+    // Cross-language CFI and KCFI support requires integers to be normalized, otherwise integer
+    // types don't match C types either.
+    if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled())
+        || !sess.is_sanitizer_cfi_normalize_integers_enabled()
+        || tcx.generics_of(def_id).requires_monomorphization(tcx)
+    {
+        return;
+    }
+
+    let mut options = TypeIdOptions::GENERALIZE_REPR_C | TypeIdOptions::NORMALIZE_INTEGERS;
+    if sess.is_sanitizer_cfi_generalize_pointers_enabled() {
+        options.insert(TypeIdOptions::GENERALIZE_POINTERS);
+    }
+    // Boxes and references are encoded as the raw pointers they are passed as when enabled
+    if sess.is_sanitizer_cfi_box_as_ptr_enabled() {
+        options.insert(TypeIdOptions::BOX_AS_PTR);
+    }
+    if sess.is_sanitizer_cfi_refs_as_ptrs_enabled() {
+        options.insert(TypeIdOptions::REFS_AS_PTRS);
+    }
+
+    let sig = tcx.fn_sig(def_id).instantiate_identity();
+    let sig = tcx.instantiate_bound_regions_with_erased(sig);
+    if sig.references_error() {
+        return;
+    }
+
+    let inputs = iter::zip(sig.inputs().iter().copied(), decl.inputs)
+        .map(|(ty, hir_ty)| (ty, hir_ty, true));
+    let output = match decl.output {
+        hir::FnRetTy::Return(ret_hir) => Some((sig.output(), ret_hir, false)),
+        hir::FnRetTy::DefaultReturn(_) => None,
+    };
+    for (ty, hir_ty, is_input) in inputs.chain(output) {
+        let Some((rust_ty, cause)) = cross_language_incompatible_ty(tcx, ty, options) else {
+            continue;
+        };
+        let sugg = match (cause, rust_ty.kind(), hir_ty.kind) {
+            (
+                CrossLanguageIncompatibility::Reference,
+                ty::Ref(_, _, mutbl),
+                hir::TyKind::Ref(_, mut_ty),
+            ) => CfiIncompatibleFfiTypesSugg::RawPointer {
+                span: hir_ty.span.until(mut_ty.ty.span),
+                ptr: mutbl.ptr_str(),
+                // References passed to a declared function coerce to raw pointers, so its
+                // callers don't need to be changed.
+                applicability: if is_foreign && is_input {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                },
+            },
+            (CrossLanguageIncompatibility::String, ..) if !hir_ty.span.from_expansion() => {
+                CfiIncompatibleFfiTypesSugg::CChar {
+                    span: hir_ty.span,
+                    ptr: match ty.kind() {
+                        ty::Ref(_, _, mutbl) | ty::RawPtr(_, mutbl) => mutbl.ptr_str(),
+                        _ => "const",
+                    },
+                }
+            }
+            (CrossLanguageIncompatibility::NullablePointer, ..)
+                if !hir_ty.span.from_expansion()
+                    && let Some((pointee, mutbl)) = nullable_pointee(cx, ty) =>
+            {
+                CfiIncompatibleFfiTypesSugg::NullablePointer {
+                    span: hir_ty.span,
+                    ptr: mutbl.ptr_str(),
+                    pointee,
+                }
+            }
+            (CrossLanguageIncompatibility::NotReprC(did), ..)
+                if !tcx.def_span(did).from_expansion() =>
+            {
+                CfiIncompatibleFfiTypesSugg::ReprC {
+                    span: tcx.def_span(did).shrink_to_lo(),
+                    ty: rust_ty,
+                }
+            }
+            _ => CfiIncompatibleFfiTypesSugg::Help,
+        };
+        cx.emit_span_lint(
+            CFI_INCOMPATIBLE_FFI_TYPES,
+            hir_ty.span,
+            CfiIncompatibleFfiTypesDiag { ty: rust_ty, label: hir_ty.span, sugg },
+        );
+    }
+}
+
+/// Returns the pointee type and mutability of the raw pointer equivalent to the specified `Option`
+/// of a reference, `NonNull`, or raw pointer, if it is one.
+fn nullable_pointee<'tcx>(
+    cx: &LateContext<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<(Ty<'tcx>, ty::Mutability)> {
+    let ty::Adt(adt_def, args) = ty.kind() else { return None };
+    if !cx.tcx.is_diagnostic_item(sym::Option, adt_def.did()) {
+        return None;
+    }
+    match args.type_at(0).kind() {
+        ty::Ref(_, pointee, mutbl) | ty::RawPtr(pointee, mutbl) => Some((*pointee, *mutbl)),
+        ty::Adt(adt_def, args) if cx.tcx.is_diagnostic_item(sym::NonNull, adt_def.did()) => {
+            Some((args.type_at(0), ty::Mutability::Mut))
+        }
+        _ => None,
+    }
+}
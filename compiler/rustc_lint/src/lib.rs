@@ -50,6 +50,8 @@
 mod array_into_iter;
 mod async_fn_in_trait;
 pub mod builtin;
+mod cfi_encoding_collisions;
+mod cfi_incompatible_ffi_types;
 mod context;
 mod deref_into_dyn_supertrait;
 mod drop_forget_useless;
@@ -94,6 +96,8 @@
 use array_into_iter::ArrayIntoIter;
 use async_fn_in_trait::AsyncFnInTrait;
 use builtin::*;
+use cfi_encoding_collisions::*;
+use cfi_incompatible_ffi_types::*;
 use deref_into_dyn_supertrait::*;
 use drop_forget_useless::*;
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
@@ -189,6 +193,8 @@ fn lint_mod(tcx: TyCtxt<'_>, module_def_id: LocalModDefId) {
             HardwiredLints: HardwiredLints,
             ImproperCTypesDeclarations: ImproperCTypesDeclarations,
             ImproperCTypesDefinitions: ImproperCTypesDefinitions,
+            CfiIncompatibleFfiTypes: CfiIncompatibleFfiTypes,
+            CfiEncodingCollisions: CfiEncodingCollisions,
             InvalidFromUtf8: InvalidFromUtf8,
             VariantSizeDifferences: VariantSizeDifferences,
             BoxPointers: BoxPointers,
@@ -1936,6 +1936,81 @@ fn msg(&self) -> DiagMessage {
     }
 }
 
+// cfi_encoding_collisions.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_cfi_encoding_collisions)]
+#[note]
+#[help]
+pub struct CfiEncodingCollisionsDiag {
+    pub ty: String,
+    pub encoding: String,
+    pub other: String,
+    #[label]
+    pub label: Span,
+    #[note(lint_other_note)]
+    pub other_span: Option<Span>,
+}
+
+// cfi_incompatible_ffi_types.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_cfi_incompatible_ffi_types)]
+#[note]
+pub struct CfiIncompatibleFfiTypesDiag<'a> {
+    pub ty: Ty<'a>,
+    #[label]
+    pub label: Span,
+    #[subdiagnostic]
+    pub sugg: CfiIncompatibleFfiTypesSugg<'a>,
+}
+
+#[derive(Subdiagnostic)]
+pub enum CfiIncompatibleFfiTypesSugg<'a> {
+    #[suggestion(lint_raw_pointer_suggestion, code = "*{ptr} ", style = "verbose")]
+    RawPointer {
+        #[primary_span]
+        span: Span,
+        ptr: &'static str,
+        #[applicability]
+        applicability: Applicability,
+    },
+    #[suggestion(
+        lint_c_char_suggestion,
+        code = "*{ptr} core::ffi::c_char",
+        applicability = "maybe-incorrect",
+        style = "verbose"
+    )]
+    CChar {
+        #[primary_span]
+        span: Span,
+        ptr: &'static str,
+    },
+    #[suggestion(
+        lint_nullable_pointer_suggestion,
+        code = "*{ptr} {pointee}",
+        applicability = "maybe-incorrect",
+        style = "verbose"
+    )]
+    NullablePointer {
+        #[primary_span]
+        span: Span,
+        ptr: &'static str,
+        pointee: Ty<'a>,
+    },
+    #[suggestion(
+        lint_repr_c_suggestion,
+        code = "#[repr(C)]\n",
+        applicability = "machine-applicable",
+        style = "verbose"
+    )]
+    ReprC {
+        #[primary_span]
+        span: Span,
+        ty: Ty<'a>,
+    },
+    #[help(lint_help)]
+    Help,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(lint_unit_bindings)]
 pub struct UnitBindingsDiag {
@@ -57,6 +57,7 @@
         INVALID_TYPE_PARAM_DEFAULT,
         IRREFUTABLE_LET_PATTERNS,
         LARGE_ASSIGNMENTS,
+        LARGE_CFI_ALIAS_SETS,
         LATE_BOUND_LIFETIME_ARGUMENTS,
         LEGACY_DERIVE_HELPERS,
         LONG_RUNNING_CONST_EVAL,
@@ -3209,6 +3210,49 @@
     "detects large moves or copies",
 }
 
+declare_lint! {
+    /// The `large_cfi_alias_sets` lint detects when the number of functions that share a
+    /// control flow integrity (CFI) type metadata identifier exceeds the threshold set with
+    /// `-Zcfi-alias-set-threshold` (default: 1000).
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (requires -Zsanitizer=cfi or -Zsanitizer=kcfi)
+    /// #![warn(large_cfi_alias_sets)]
+    ///
+    /// pub fn foo(x: i32) -> i32 { x }
+    /// pub fn bar(x: i32) -> i32 { x }
+    /// ```
+    ///
+    /// This will produce (with `-Zcfi-alias-set-threshold=1`):
+    ///
+    /// ```text
+    /// warning: 2 functions share the CFI type metadata identifier `_ZTSFu3i32S_E`
+    ///  --> lint_example.rs:4:1
+    ///   |
+    /// 4 | pub fn bar(x: i32) -> i32 { x }
+    ///   | ^^^^^^^^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: an indirect call through a `fn(i32) -> i32` pointer may be redirected to any of them (e.g., `bar`, `foo`)
+    ///   = help: the threshold can be changed with `-Zcfi-alias-set-threshold` (currently 1)
+    /// note: the lint level is defined here
+    ///  --> lint_example.rs:1:9
+    ///   |
+    /// 1 | #![warn(large_cfi_alias_sets)]
+    ///   |         ^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// An indirect call is only checked to target a function with the same type metadata
+    /// identifier as the function pointer it is made through, so a type metadata identifier
+    /// shared by many functions provides little protection. Consider using more specific types
+    /// (e.g., newtypes) for the functions that are called indirectly.
+    pub LARGE_CFI_ALIAS_SETS,
+    Allow,
+    "detects large sets of functions that share a CFI type metadata identifier",
+}
+
 declare_lint! {
     /// The `deprecated_cfg_attr_crate_type_name` lint detects uses of the
     /// `#![cfg_attr(..., crate_type = "...")]` and
@@ -752,6 +752,20 @@ pub(crate) fn list_crate_metadata(
                     writeln!(out, "triple {}", root.header.triple.triple())?;
                     writeln!(out, "edition {}", root.edition)?;
                     writeln!(out, "symbol_mangling_version {:?}", root.symbol_mangling_version)?;
+                    writeln!(out, "symbol_mangling_hash_bits {}", root.symbol_mangling_hash_bits)?;
+                    writeln!(
+                        out,
+                        "symbol_mangling_hash_policy {:?}",
+                        root.symbol_mangling_hash_policy
+                    )?;
+                    writeln!(out, "symbol_length_cap {:?}", root.symbol_length_cap)?;
+                    writeln!(out, "symbol_mangling_suffix {:?}", root.symbol_mangling_suffix)?;
+                    writeln!(out, "mangle_internal_symbols {}", root.mangle_internal_symbols)?;
+                    writeln!(
+                        out,
+                        "symbol_mangling_wide_legacy_hash {}",
+                        root.symbol_mangling_wide_legacy_hash
+                    )?;
                     writeln!(
                         out,
                         "required_panic_strategy {:?} panic_in_drop_strategy {:?}",
@@ -309,6 +309,12 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     extern_crate => { cdata.extern_crate.map(|c| &*tcx.arena.alloc(c)) }
     is_no_builtins => { cdata.root.no_builtins }
     symbol_mangling_version => { cdata.root.symbol_mangling_version }
+    symbol_mangling_hash_bits => { cdata.root.symbol_mangling_hash_bits }
+    symbol_mangling_hash_policy => { cdata.root.symbol_mangling_hash_policy }
+    symbol_length_cap => { cdata.root.symbol_length_cap }
+    symbol_mangling_suffix => { cdata.root.symbol_mangling_suffix }
+    mangle_internal_symbols => { cdata.root.mangle_internal_symbols }
+    symbol_mangling_wide_legacy_hash => { cdata.root.symbol_mangling_wide_legacy_hash }
     reachable_non_generics => {
         let reachable_non_generics = tcx
             .exported_symbols(cdata.cnum)
@@ -717,6 +717,22 @@ macro_rules! stat {
                 panic_runtime: attr::contains_name(attrs, sym::panic_runtime),
                 profiler_runtime: attr::contains_name(attrs, sym::profiler_runtime),
                 symbol_mangling_version: tcx.sess.opts.get_symbol_mangling_version(),
+                symbol_mangling_hash_bits: tcx.sess.opts.get_symbol_mangling_hash_bits(),
+                symbol_mangling_hash_policy: tcx.sess.opts.get_symbol_mangling_hash_policy(),
+                symbol_length_cap: tcx.sess.opts.unstable_opts.symbol_length_cap,
+                symbol_mangling_suffix: tcx
+                    .sess
+                    .opts
+                    .unstable_opts
+                    .symbol_mangling_suffix
+                    .as_deref()
+                    .map(Symbol::intern),
+                mangle_internal_symbols: tcx.sess.opts.unstable_opts.mangle_internal_symbols,
+                symbol_mangling_wide_legacy_hash: tcx
+                    .sess
+                    .opts
+                    .unstable_opts
+                    .symbol_mangling_wide_legacy_hash,
 
                 crate_deps,
                 dylib_dependency_formats,
@@ -27,7 +27,7 @@
 use rustc_middle::ty::{DeducedParamAttrs, ParameterizedOverTcx, TyCtxt};
 use rustc_middle::util::Providers;
 use rustc_serialize::opaque::FileEncoder;
-use rustc_session::config::SymbolManglingVersion;
+use rustc_session::config::{SymbolHashPolicy, SymbolManglingVersion};
 use rustc_session::cstore::{CrateDepKind, ForeignModule, LinkagePreference, NativeLib};
 use rustc_span::edition::Edition;
 use rustc_span::hygiene::{ExpnIndex, MacroKind};
@@ -292,6 +292,12 @@ pub(crate) struct CrateRoot {
     panic_runtime: bool,
     profiler_runtime: bool,
     symbol_mangling_version: SymbolManglingVersion,
+    symbol_mangling_hash_bits: u32,
+    symbol_mangling_hash_policy: SymbolHashPolicy,
+    symbol_length_cap: Option<usize>,
+    symbol_mangling_suffix: Option<Symbol>,
+    mangle_internal_symbols: bool,
+    symbol_mangling_wide_legacy_hash: bool,
 }
 
 /// On-disk representation of `DefId`.
@@ -5,7 +5,7 @@
 
 use crate::mir;
 use crate::query::TyCtxtAt;
-use crate::ty::{Ty, TyCtxt};
+use crate::ty::{self, Ty, TyCtxt};
 use rustc_span::def_id::LocalDefId;
 use rustc_span::DUMMY_SP;
 
@@ -83,4 +83,13 @@ fn clone(&self) -> Self { *self }
     /// You do not want to call this yourself, instead use the cached version
     /// via `mir_built`
     hook build_mir(key: LocalDefId) -> mir::Body<'tcx>;
+
+    /// Returns a replacement for the specified CFI type metadata identifier computed with the
+    /// specified options (i.e., `TypeIdOptions` bits), if any. The instance is `None` for the type
+    /// metadata identifiers of call sites, which only have the function type.
+    ///
+    /// The default provider returns `None`. Drivers can override it (e.g., with
+    /// `rustc_interface::Config::override_queries`) to experiment with alternative CFI policies
+    /// without patching the compiler.
+    hook override_cfi_typeid(instance: Option<ty::Instance<'tcx>>, options: u32, typeid: &str) -> Option<String>;
 }
@@ -101,6 +101,9 @@ impl CodegenFnAttrFlags: u32 {
         const ALLOCATOR_ZEROED          = 1 << 18;
         /// `#[no_builtins]`: indicates that disable implicit builtin knowledge of functions for the function.
         const NO_BUILTINS               = 1 << 19;
+        /// `#[rustc_unhashed_symbol]`: indicates that the symbol of the function is mangled
+        /// with the v0 scheme instead of being hashed with `-C symbol-mangling-version=hashed`.
+        const UNHASHED_SYMBOL           = 1 << 20;
     }
 }
 rustc_data_structures::external_bitflags_debug! { CodegenFnAttrFlags }
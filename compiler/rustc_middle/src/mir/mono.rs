@@ -1,5 +1,7 @@
 use crate::dep_graph::{DepNode, WorkProduct, WorkProductId};
-use crate::ty::{GenericArgs, Instance, InstanceDef, SymbolName, TyCtxt};
+use crate::ty::{
+    Const, ExistentialPredicate, GenericArgs, Instance, InstanceDef, Region, SymbolName, Ty, TyCtxt,
+};
 use rustc_attr::InlineAttr;
 use rustc_data_structures::base_n;
 use rustc_data_structures::fingerprint::Fingerprint;
@@ -262,6 +264,12 @@ pub struct MonoItemData {
 
     /// A cached copy of the result of `MonoItem::size_estimate`.
     pub size_estimate: usize,
+
+    /// Whether this is a function that may be called indirectly (i.e., whose address may be taken
+    /// in the local crate or in other crates), so that CFI type metadata identifiers are only
+    /// computed and emitted for the functions whose indirect calls are checked. It is kept per
+    /// item so that codegen units only depend on whether their own functions are address-taken.
+    pub may_be_called_indirectly: bool,
 }
 
 /// Specifies the linkage type for a `MonoItem`.
@@ -289,6 +297,89 @@ pub enum Visibility {
     Protected,
 }
 
+/// A set of functions that share a CFI type metadata identifier (i.e., that an indirect call
+/// through a pointer of that type may be redirected to).
+#[derive(Clone, Debug, HashStable)]
+pub struct CfiAliasSet<'tcx> {
+    /// The type metadata identifier shared by the functions.
+    pub typeid: String,
+    /// The functions, sorted by symbol name.
+    pub members: Vec<Instance<'tcx>>,
+}
+
+/// The CFI type metadata identifiers a codegen unit defines (i.e., that the functions it defines
+/// are given) and references (i.e., that the indirect calls of its functions are checked against),
+/// for (Thin)LTO summaries.
+#[derive(Clone, Debug, Default, HashStable)]
+pub struct CfiTypeIdSummary {
+    /// The type metadata identifiers defined, sorted and deduplicated.
+    pub defined: Vec<String>,
+    /// The type metadata identifiers referenced, sorted and deduplicated.
+    pub referenced: Vec<String>,
+}
+
+/// An override of the CFI encoding of a user-defined type or extern type provided with
+/// `-Zcfi-encoding-profile` or `#![cfi_encodings]` (see `rustc_symbol_mangling::typeid`).
+#[derive(Clone, Debug, PartialEq, Eq, HashStable)]
+pub enum CfiEncodingOverride {
+    /// An encoding as is, as with `#[cfi_encoding = "<encoding>"]`.
+    Raw(String),
+    /// A C++ class, as with `#[cfi_encoding(namespace = "<path>", name = "<name>", template_args =
+    /// "<none|generic>")]`.
+    Class { namespace: Vec<String>, name: String, template_args: bool },
+    /// Encoded as `void` (i.e., transformed into the unit type, as `core::ffi::c_void`).
+    Void,
+    /// Encoded automatically, ignoring the `cfi_encoding` attribute of the type.
+    Automatic,
+}
+
+/// A type qualifier of a type component of a CFI type metadata identifier (see `CfiDictKey`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CfiTyQ {
+    None,
+    Const,
+    Mut,
+}
+
+/// A component of a CFI type metadata identifier that is a substitution candidate (see
+/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression>).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CfiDictKey<'tcx> {
+    Ty(Ty<'tcx>, CfiTyQ),
+    Region(Region<'tcx>),
+    Const(Const<'tcx>),
+    Predicate(ExistentialPredicate<'tcx>),
+    /// The qualified name (e.g., `a::b`) of an enclosing namespace or class of a structured
+    /// user-defined encoding.
+    Prefix(String),
+}
+
+/// The encoding of a type in CFI type metadata identifiers, computed once per session with an
+/// empty substitution dictionary by the `cfi_type_fragment` query and spliced into the type
+/// metadata identifiers it is a component of (see `rustc_symbol_mangling::typeid`).
+#[derive(Debug)]
+pub struct CfiFragment<'tcx> {
+    /// The encoding, split at its substitutions.
+    pub parts: Vec<CfiFragmentPart>,
+    /// The components the encoding adds substitution dictionary entries for, in order.
+    pub entries: Vec<CfiDictKey<'tcx>>,
+    /// The components the encoding looks up without adding entries for them. The encoding can only
+    /// be spliced into substitution dictionaries without entries for these and for `entries`, as it
+    /// would be compressed differently otherwise.
+    pub lookups: Vec<CfiDictKey<'tcx>>,
+}
+
+/// A part of the encoding of a `CfiFragment`.
+#[derive(Debug)]
+pub enum CfiFragmentPart {
+    /// Encoded as is.
+    Encoding(String),
+    /// A substitution, with the sequence number of the entry it refers to among the entries of the
+    /// fragment, as the sequence numbers of substitutions are relative to the number of entries of
+    /// the substitution dictionary the fragment is spliced into.
+    Substitution(usize),
+}
+
 impl<'tcx> CodegenUnit<'tcx> {
     #[inline]
     pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
@@ -243,6 +243,7 @@ impl EraseType for $ty {
     Option<rustc_span::def_id::DefId>,
     Option<rustc_span::def_id::LocalDefId>,
     Option<rustc_span::Span>,
+    Option<rustc_span::Symbol>,
     Option<rustc_target::abi::FieldIdx>,
     Option<rustc_target::spec::PanicStrategy>,
     Option<usize>,
@@ -306,6 +307,7 @@ impl EraseType for $ty {
     rustc_session::config::CrateType,
     rustc_session::config::EntryFnType,
     rustc_session::config::OptLevel,
+    rustc_session::config::SymbolHashPolicy,
     rustc_session::config::SymbolManglingVersion,
     rustc_session::cstore::CrateDepKind,
     rustc_session::cstore::ExternCrate,
@@ -510,6 +510,14 @@ fn default_span(&self, _tcx: TyCtxt<'_>) -> Span {
     }
 }
 
+impl<'tcx> Key for (Ty<'tcx>, u32) {
+    type Cache<V> = DefaultCache<Self, V>;
+
+    fn default_span(&self, _tcx: TyCtxt<'_>) -> Span {
+        DUMMY_SP
+    }
+}
+
 impl<'tcx> Key for (ty::Predicate<'tcx>, traits::WellFormedLoc) {
     type Cache<V> = DefaultCache<Self, V>;
 
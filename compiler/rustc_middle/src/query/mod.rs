@@ -24,7 +24,9 @@
     EvalToValTreeResult,
 };
 use crate::mir::interpret::{LitToConstError, LitToConstInput};
-use crate::mir::mono::CodegenUnit;
+use crate::mir::mono::{
+    CfiAliasSet, CfiEncodingOverride, CfiFragment, CfiTypeIdSummary, CodegenUnit,
+};
 use crate::query::erase::{erase, restore, Erase};
 use crate::query::plumbing::{
     query_ensure, query_ensure_error_guaranteed, query_get_at, CyclePlaceholder, DynamicQuery,
@@ -74,7 +76,9 @@
 use rustc_index::IndexVec;
 use rustc_query_system::ich::StableHashingContext;
 use rustc_query_system::query::{try_get_cached, QueryCache, QueryMode, QueryState};
-use rustc_session::config::{EntryFnType, OptLevel, OutputFilenames, SymbolManglingVersion};
+use rustc_session::config::{
+    EntryFnType, OptLevel, OutputFilenames, SymbolHashPolicy, SymbolManglingVersion,
+};
 use rustc_session::cstore::{CrateDepKind, CrateSource};
 use rustc_session::cstore::{ExternCrate, ForeignModule, LinkagePreference, NativeLib};
 use rustc_session::lint::LintExpectationId;
@@ -1493,6 +1497,36 @@
         desc { "getting a crate's symbol mangling version" }
         separate_provide_extern
     }
+    query symbol_mangling_hash_bits(_: CrateNum) -> u32 {
+        fatal_cycle
+        desc { "getting the number of bits of a crate's hashed symbols" }
+        separate_provide_extern
+    }
+    query symbol_mangling_hash_policy(_: CrateNum) -> SymbolHashPolicy {
+        fatal_cycle
+        desc { "getting which of a crate's symbols are hashed" }
+        separate_provide_extern
+    }
+    query symbol_length_cap(_: CrateNum) -> Option<usize> {
+        fatal_cycle
+        desc { "getting the maximum length of a crate's symbols" }
+        separate_provide_extern
+    }
+    query symbol_mangling_suffix(_: CrateNum) -> Option<Symbol> {
+        fatal_cycle
+        desc { "getting the suffix of a crate's symbols" }
+        separate_provide_extern
+    }
+    query symbol_mangling_wide_legacy_hash(_: CrateNum) -> bool {
+        fatal_cycle
+        desc { "checking if a crate's legacy symbols have 128-bit hashes" }
+        separate_provide_extern
+    }
+    query mangle_internal_symbols(_: CrateNum) -> bool {
+        fatal_cycle
+        desc { "checking if a crate mangles the symbols internal to the standard library" }
+        separate_provide_extern
+    }
 
     query extern_crate(def_id: DefId) -> Option<&'tcx ExternCrate> {
         eval_always
@@ -1906,6 +1940,48 @@
         desc { "collect_and_partition_mono_items" }
     }
 
+    /// Groups the functions codegened in the local crate by the CFI type metadata identifier (or
+    /// KCFI type metadata identifier, if KCFI is enabled) indirect calls to them are checked
+    /// against, largest sets first.
+    query cfi_alias_sets(_: ()) -> &'tcx Vec<CfiAliasSet<'tcx>> {
+        arena_cache
+        desc { "grouping functions by CFI type metadata identifier" }
+    }
+
+    /// Summarizes the CFI type metadata identifiers the given codegen unit defines and references
+    /// (i.e., its functions are given and its indirect calls are checked against, respectively), so
+    /// that backends can emit them in (Thin)LTO summaries without encoding types again.
+    query cfi_typeid_summary(cgu_name: Symbol) -> &'tcx CfiTypeIdSummary {
+        arena_cache
+        desc { "summarizing the CFI type metadata identifiers of codegen unit `{cgu_name}`" }
+    }
+
+    /// Reads the CFI encoding overrides of `-Zcfi-encoding-profile`, by the user-defined type or
+    /// extern type they apply to. The profile is read again in every session, as its contents
+    /// aren't tracked.
+    query cfi_encoding_profile(_: ()) -> &'tcx DefIdMap<CfiEncodingOverride> {
+        arena_cache
+        eval_always
+        desc { "reading the CFI encoding profile" }
+    }
+
+    /// Collects the CFI encoding overrides of the `#![cfi_encodings]` attributes of the crates in
+    /// the crate graph, by the user-defined type or extern type they apply to.
+    query cfi_encoding_registry(_: ()) -> &'tcx DefIdMap<CfiEncodingOverride> {
+        arena_cache
+        desc { "collecting the CFI encodings registered with `#![cfi_encodings]`" }
+    }
+
+    /// Computes the encoding of the given type in CFI type metadata identifiers with the given
+    /// encoding options (see `rustc_symbol_mangling::typeid::TypeIdOptions`) once per session, so
+    /// that it is spliced into every type metadata identifier it is a component of. Returns `None`
+    /// if the encoding can't be spliced (e.g., if it has errors).
+    query cfi_type_fragment(key: (Ty<'tcx>, u32)) -> &'tcx Option<CfiFragment<'tcx>> {
+        arena_cache
+        no_hash
+        desc { "encoding `{}` for CFI type metadata identifiers", key.0 }
+    }
+
     query is_codegened_item(def_id: DefId) -> bool {
         desc { |tcx| "determining whether `{}` needs codegen", tcx.def_path_str(def_id) }
     }
@@ -1,9 +1,22 @@
+monomorphize_cfi_typeid_hash_collision = the hashes of the type metadata identifiers of `{$item1}` and `{$item2}` collide in capped type metadata identifier `{$typeid}`
+    .label = the hash of the type metadata identifier of this item collides
+    .help = change the length of capped type metadata identifiers with `-Z sanitizer-cfi-typeid-length-cap`
+
 monomorphize_consider_type_length_limit =
     consider adding a `#![type_length_limit="{$type_length}"]` attribute to your crate
 
+monomorphize_couldnt_dump_cfi_typeids =
+    unexpected error occurred while dumping CFI type metadata identifiers: {$error}
+
 monomorphize_couldnt_dump_mono_stats =
     unexpected error occurred while dumping monomorphization stats: {$error}
 
+monomorphize_couldnt_emit_symbol_map =
+    unexpected error occurred while emitting the symbol map: {$error}
+
+monomorphize_couldnt_export_cfi_typeids =
+    unexpected error occurred while exporting CFI type metadata identifiers: {$error}
+
 monomorphize_encountered_error_while_instantiating =
     the above error was encountered while instantiating `{$formatted_item}`
 
@@ -12,6 +25,11 @@ monomorphize_large_assignments =
     .label = value moved from here
     .note = The current maximum size is {$limit}, but it can be customized with the move_size_limit attribute: `#![move_size_limit = "..."]`
 
+monomorphize_large_cfi_alias_set =
+    {$size} functions share the CFI type metadata identifier `{$typeid}`
+    .note = an indirect call through a `{$signature}` pointer may be redirected to any of them (e.g., {$representatives})
+    .help = the threshold can be changed with `-Zcfi-alias-set-threshold` (currently {$threshold})
+
 monomorphize_no_optimized_mir =
     missing optimized MIR for an item in the crate `{$crate_name}`
     .note = missing optimized MIR for this item (was the crate `{$crate_name}` compiled with `--emit=metadata`?)
@@ -24,6 +42,26 @@ monomorphize_start_not_found = using `fn main` requires the standard library
     .help = use `#![no_main]` to bypass the Rust generated entrypoint and declare a platform specific entrypoint yourself, usually with `#[no_mangle]`
 
 monomorphize_symbol_already_defined = symbol `{$symbol}` is already defined
+    .label = previous definition of `{$symbol}` here
+    .note = the symbol of `{$previous_item}` is {$previous_origin ->
+        [export_name] set with `#[export_name]`
+        [no_mangle] its name, because of `#[no_mangle]`
+        [foreign] the name of a foreign item
+        [internal] the name of an item internal to the standard library
+        [generated] generated by the compiler
+        *[other] mangled with the `{$previous_origin}` scheme
+    }, and the symbol of `{$item}` is {$origin ->
+        [export_name] set with `#[export_name]`
+        [no_mangle] its name, because of `#[no_mangle]`
+        [foreign] the name of a foreign item
+        [internal] the name of an item internal to the standard library
+        [generated] generated by the compiler
+        *[other] mangled with the `{$origin}` scheme
+    }
+
+monomorphize_symbol_hash_collision = the {$bits}-bit hashes of `{$item1}` and `{$item2}` collide in symbol `{$symbol}`
+    .label = the hash of this item collides
+    .help = increase the number of bits of the hashes with `-Z symbol-mangling-hash-bits`
 
 monomorphize_type_length_limit = reached the type-length limit while instantiating `{$shrunk}`
 
@@ -251,6 +251,10 @@ pub struct UsageMap<'tcx> {
 
     // Maps every mono item to the mono items that use it.
     user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+
+    // The functions whose addresses are taken (i.e., that are reified into function pointers, are
+    // in vtables, or are pointed to by constants and statics), which may be called indirectly.
+    address_taken: FxHashSet<MonoItem<'tcx>>,
 }
 
 type MonoItems<'tcx> = Vec<Spanned<MonoItem<'tcx>>>;
@@ -286,7 +290,11 @@ enum CollectionMode {
 
 impl<'tcx> UsageMap<'tcx> {
     fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default() }
+        UsageMap {
+            used_map: FxHashMap::default(),
+            user_map: FxHashMap::default(),
+            address_taken: FxHashSet::default(),
+        }
     }
 
     fn record_used<'a>(
@@ -304,6 +312,15 @@ fn record_used<'a>(
         assert!(self.used_map.insert(user_item, used_items).is_none());
     }
 
+    fn record_address_taken(&mut self, address_taken: &[MonoItem<'tcx>]) {
+        self.address_taken.extend(address_taken.iter().copied());
+    }
+
+    /// Returns whether the address of `item` is taken by any of the collected items.
+    pub fn is_address_taken(&self, item: MonoItem<'tcx>) -> bool {
+        self.address_taken.contains(&item)
+    }
+
     pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
         self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
     }
@@ -357,6 +374,7 @@ fn collect_items_rec<'tcx>(
 
     let mut used_items = MonoItems::new();
     let mut mentioned_items = MonoItems::new();
+    let mut address_taken = Vec::new();
     let recursion_depth_reset;
 
     // Post-monomorphization errors MVP
@@ -407,9 +425,11 @@ fn collect_items_rec<'tcx>(
                 }
 
                 if let Ok(alloc) = tcx.eval_static_initializer(def_id) {
+                    let len = used_items.len();
                     for &prov in alloc.inner().provenance().ptrs().values() {
                         collect_alloc(tcx, prov.alloc_id(), &mut used_items);
                     }
+                    push_address_taken_fns(&used_items[len..], &mut address_taken);
                 }
 
                 if tcx.needs_thread_local_shim(def_id) {
@@ -447,6 +467,7 @@ fn collect_items_rec<'tcx>(
                     instance,
                     &mut used_items,
                     &mut mentioned_items,
+                    &mut address_taken,
                     mode,
                 )
             });
@@ -470,7 +491,9 @@ fn collect_items_rec<'tcx>(
                         hir::InlineAsmOperand::SymFn { anon_const } => {
                             let fn_ty =
                                 tcx.typeck_body(anon_const.body).node_type(anon_const.hir_id);
+                            let len = used_items.len();
                             visit_fn_use(tcx, fn_ty, false, *op_sp, &mut used_items);
+                            push_address_taken_fns(&used_items[len..], &mut address_taken);
                         }
                         hir::InlineAsmOperand::SymStatic { path: _, def_id } => {
                             let instance = Instance::mono(tcx, *def_id);
@@ -514,7 +537,9 @@ fn collect_items_rec<'tcx>(
     // This is part of the output of collection and hence only relevant for "used" items.
     // ("Mentioned" items are only considered internally during collection.)
     if mode == CollectionMode::UsedItems {
-        state.usage_map.lock_mut().record_used(starting_item.node, &used_items);
+        let mut usage_map = state.usage_map.lock_mut();
+        usage_map.record_used(starting_item.node, &used_items);
+        usage_map.record_address_taken(&address_taken);
     }
 
     if mode == CollectionMode::MentionedItems {
@@ -662,6 +687,8 @@ struct MirUsedCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     body: &'a mir::Body<'tcx>,
     used_items: &'a mut MonoItems<'tcx>,
+    /// The used functions whose addresses are taken, rather than only called directly.
+    address_taken: &'a mut Vec<MonoItem<'tcx>>,
     /// See the comment in `collect_items_of_instance` for the purpose of this set.
     /// Note that this contains *not-monomorphized* items!
     used_mentioned_items: &'a mut FxHashSet<MentionedItem<'tcx>>,
@@ -860,6 +887,7 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                 if (target_ty.is_trait() && !source_ty.is_trait())
                     || (target_ty.is_dyn_star() && !source_ty.is_dyn_star())
                 {
+                    let len = self.used_items.len();
                     create_mono_items_for_vtable_methods(
                         self.tcx,
                         target_ty,
@@ -867,6 +895,7 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                         span,
                         self.used_items,
                     );
+                    push_address_taken_fns(&self.used_items[len..], self.address_taken);
                 }
             }
             mir::Rvalue::Cast(
@@ -878,7 +907,9 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                 // *Before* monomorphizing, record that we already handled this mention.
                 self.used_mentioned_items.insert(MentionedItem::Fn(fn_ty));
                 let fn_ty = self.monomorphize(fn_ty);
+                let len = self.used_items.len();
                 visit_fn_use(self.tcx, fn_ty, false, span, self.used_items);
+                push_address_taken_fns(&self.used_items[len..], self.address_taken);
             }
             mir::Rvalue::Cast(
                 mir::CastKind::PointerCoercion(PointerCoercion::ClosureFnPointer(_)),
@@ -893,7 +924,9 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                     let instance =
                         Instance::resolve_closure(self.tcx, def_id, args, ty::ClosureKind::FnOnce);
                     if should_codegen_locally(self.tcx, instance) {
-                        self.used_items.push(create_fn_mono_item(self.tcx, instance, span));
+                        let mono_item = create_fn_mono_item(self.tcx, instance, span);
+                        self.address_taken.push(mono_item.node);
+                        self.used_items.push(mono_item);
                     }
                 } else {
                     bug!()
@@ -919,7 +952,9 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
     fn visit_constant(&mut self, constant: &mir::ConstOperand<'tcx>, location: Location) {
         // No `super_constant` as we don't care about `visit_ty`/`visit_ty_const`.
         let Some(val) = self.eval_constant(constant) else { return };
+        let len = self.used_items.len();
         collect_const_value(self.tcx, val, self.used_items);
+        push_address_taken_fns(&self.used_items[len..], self.address_taken);
     }
 
     fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Location) {
@@ -958,7 +993,9 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                             // *Before* monomorphizing, record that we already handled this mention.
                             self.used_mentioned_items.insert(MentionedItem::Fn(fn_ty));
                             let fn_ty = self.monomorphize(fn_ty);
+                            let len = self.used_items.len();
                             visit_fn_use(self.tcx, fn_ty, false, source, self.used_items);
+                            push_address_taken_fns(&self.used_items[len..], self.address_taken);
                         }
                         mir::InlineAsmOperand::SymStatic { def_id } => {
                             let instance = Instance::mono(self.tcx, def_id);
@@ -1272,6 +1309,17 @@ fn create_fn_mono_item<'tcx>(
     respan(source, MonoItem::Fn(instance.polymorphize(tcx)))
 }
 
+/// Pushes the functions among `used_items` to `address_taken`, for the items used by taking their
+/// addresses.
+fn push_address_taken_fns<'tcx>(
+    used_items: &[Spanned<MonoItem<'tcx>>],
+    address_taken: &mut Vec<MonoItem<'tcx>>,
+) {
+    address_taken.extend(
+        used_items.iter().map(|item| item.node).filter(|item| matches!(item, MonoItem::Fn(_))),
+    );
+}
+
 /// Creates a `MonoItem` for each method that is referenced by the vtable for
 /// the given trait/impl pair.
 fn create_mono_items_for_vtable_methods<'tcx>(
@@ -1382,12 +1430,13 @@ fn build_skip_move_check_fns(tcx: TyCtxt<'_>) -> Vec<DefId> {
 /// Scans the MIR in order to find function calls, closures, and drop-glue.
 ///
 /// Anything that's found is added to `output`. Furthermore the "mentioned items" of the MIR are returned.
-#[instrument(skip(tcx, used_items, mentioned_items), level = "debug")]
+#[instrument(skip(tcx, used_items, mentioned_items, address_taken), level = "debug")]
 fn collect_items_of_instance<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     used_items: &mut MonoItems<'tcx>,
     mentioned_items: &mut MonoItems<'tcx>,
+    address_taken: &mut Vec<MonoItem<'tcx>>,
     mode: CollectionMode,
 ) {
     let body = tcx.instance_mir(instance.def);
@@ -1406,6 +1455,7 @@ fn collect_items_of_instance<'tcx>(
         tcx,
         body,
         used_items,
+        address_taken,
         used_mentioned_items: &mut used_mentioned_items,
         instance,
         move_size_spans: vec![],
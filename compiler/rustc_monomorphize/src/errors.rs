@@ -72,12 +72,70 @@ pub struct LargeAssignmentsLint {
     pub limit: u64,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(monomorphize_large_cfi_alias_set)]
+#[note]
+#[help]
+pub struct LargeCfiAliasSet {
+    pub size: usize,
+    pub typeid: String,
+    pub signature: String,
+    pub representatives: String,
+    pub threshold: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_symbol_already_defined)]
+#[note]
 pub struct SymbolAlreadyDefined {
     #[primary_span]
     pub span: Option<Span>,
+    #[label]
+    pub previous_span: Option<Span>,
     pub symbol: String,
+    pub item: String,
+    pub origin: &'static str,
+    pub previous_item: String,
+    pub previous_origin: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_symbol_hash_collision)]
+#[help]
+pub struct SymbolHashCollision {
+    #[primary_span]
+    pub span: Option<Span>,
+    #[label]
+    pub previous_span: Option<Span>,
+    pub symbol: String,
+    pub item1: String,
+    pub item2: String,
+    pub bits: u32,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_cfi_typeid_hash_collision)]
+#[help]
+pub struct CfiTypeIdHashCollision {
+    #[primary_span]
+    pub span: Option<Span>,
+    #[label]
+    pub previous_span: Option<Span>,
+    pub typeid: String,
+    pub item1: String,
+    pub item2: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_dump_cfi_typeids)]
+pub struct CouldntDumpCfiTypeIds {
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_export_cfi_typeids)]
+pub struct CouldntExportCfiTypeIds {
+    pub error: String,
 }
 
 #[derive(Diagnostic)]
@@ -86,6 +144,12 @@ pub struct CouldntDumpMonoStats {
     pub error: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_emit_symbol_map)]
+pub struct CouldntEmitSymbolMap {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_encountered_error_while_instantiating)]
 pub struct EncounteredErrorWhileInstantiating {
@@ -23,8 +23,10 @@
 mod partitioning;
 mod polymorphize;
 mod util;
+mod version_script;
 
 use collector::should_codegen_locally;
+pub use partitioning::check_cfi_alias_sets;
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
@@ -98,27 +98,47 @@
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_data_structures::sync;
+use rustc_errors::pluralize;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::{DefId, DefIdSet, LOCAL_CRATE};
+use rustc_hir::def_id::{DefId, DefIdSet, CRATE_DEF_ID, LOCAL_CRATE};
 use rustc_hir::definitions::DefPathDataName;
+use rustc_hir::CRATE_HIR_ID;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::exported_symbols::{SymbolExportInfo, SymbolExportLevel};
+use rustc_middle::mir;
 use rustc_middle::mir::mono::{
-    CodegenUnit, CodegenUnitNameBuilder, InstantiationMode, Linkage, MonoItem, MonoItemData,
-    Visibility,
+    CfiAliasSet, CfiTypeIdSummary, CodegenUnit, CodegenUnitNameBuilder, InstantiationMode, Linkage,
+    MonoItem, MonoItemData, Visibility,
 };
 use rustc_middle::query::Providers;
 use rustc_middle::ty::print::{characteristic_def_id_of_type, with_no_trimmed_paths};
-use rustc_middle::ty::{self, visit::TypeVisitableExt, InstanceDef, TyCtxt};
-use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use rustc_middle::ty::{self, visit::TypeVisitableExt, Instance, InstanceDef, TyCtxt};
+use rustc_session::config::{DumpMonoStatsFormat, KcfiTypeIdWidth, SwitchWithOptPath};
+use rustc_session::lint::builtin::LARGE_CFI_ALIAS_SETS;
+use rustc_session::lint::Level;
 use rustc_session::CodegenUnits;
 use rustc_span::symbol::Symbol;
+use rustc_symbol_mangling::api::typeid_options;
+use rustc_symbol_mangling::path_pattern::def_path_matches;
+use rustc_symbol_mangling::typeid::demangle::demangle_typeid;
+use rustc_symbol_mangling::typeid::kcfi::{kcfi_typeid_for_instance, KcfiTypeId};
+use rustc_symbol_mangling::typeid::{
+    declared_typeid_options, typeid_for_fnabi, typeid_for_instance, TypeIdOptions,
+};
+use rustc_symbol_mangling::{explain_v0_symbol_name, uncapped_symbol_name, SymbolOrigin};
+use rustc_target::spec::abi::Abi;
+use rustc_target::spec::SanitizerSet;
 
 use crate::collector::UsageMap;
 use crate::collector::{self, MonoItemCollectionStrategy};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined, UnknownCguCollectionMode};
+use crate::errors::{
+    CfiTypeIdHashCollision, CouldntDumpCfiTypeIds, CouldntDumpMonoStats, CouldntEmitSymbolMap,
+    CouldntExportCfiTypeIds, LargeCfiAliasSet, SymbolAlreadyDefined, SymbolHashCollision,
+    UnknownCguCollectionMode,
+};
+use crate::version_script::SymbolVersions;
 
 struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -250,9 +270,18 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
             internalization_candidates.insert(mono_item);
         }
         let size_estimate = mono_item.size_estimate(cx.tcx);
-
-        cgu.items_mut()
-            .insert(mono_item, MonoItemData { inlined: false, linkage, visibility, size_estimate });
+        let may_be_called_indirectly = may_be_called_indirectly(cx, mono_item);
+
+        cgu.items_mut().insert(
+            mono_item,
+            MonoItemData {
+                inlined: false,
+                linkage,
+                visibility,
+                size_estimate,
+                may_be_called_indirectly,
+            },
+        );
 
         // Get all inlined items that are reachable from `mono_item` without
         // going via another root item. This includes drop-glue, functions from
@@ -271,6 +300,7 @@ fn place_mono_items<'tcx, I>(cx: &PartitioningCx<'_, 'tcx>, mono_items: I) -> Pl
                 linkage: Linkage::Internal,
                 visibility: Visibility::Default,
                 size_estimate: inlined_item.size_estimate(cx.tcx),
+                may_be_called_indirectly: may_be_called_indirectly(cx, inlined_item),
             });
         }
     }
@@ -736,6 +766,57 @@ fn mono_item_linkage_and_visibility<'tcx>(
     (Linkage::External, vis)
 }
 
+/// Returns whether the given mono item is a function that may be called indirectly. Functions that
+/// can only be called directly are never checked against their CFI type metadata identifiers, so
+/// these aren't computed for them unless `-Zcfi-eager-typeids` is passed.
+fn may_be_called_indirectly<'tcx>(
+    cx: &PartitioningCx<'_, 'tcx>,
+    mono_item: MonoItem<'tcx>,
+) -> bool {
+    let tcx = cx.tcx;
+    let MonoItem::Fn(instance) = mono_item else {
+        return false;
+    };
+    if !tcx.sess.is_sanitizer_cfi_enabled() || tcx.sess.opts.unstable_opts.cfi_eager_typeids {
+        return true;
+    }
+
+    // Shims are mostly generated to be called indirectly (e.g., reify and vtable shims, and drop
+    // glue in vtables)
+    let InstanceDef::Item(def_id) = instance.def else {
+        return true;
+    };
+
+    // The entry function is called through a pointer by the runtime, and exported functions (or
+    // generic functions shared with other crates) may have their addresses taken in other crates
+    // or in other languages
+    if tcx.entry_fn(()).is_some_and(|(entry_def_id, _)| entry_def_id == def_id) {
+        return true;
+    }
+    if tcx.def_kind(def_id).has_codegen_attrs()
+        && tcx.codegen_fn_attrs(def_id).contains_extern_indicator()
+    {
+        return true;
+    }
+    let is_generic = instance.args.non_erasable_generics(tcx, def_id).next().is_some();
+    if (is_generic && tcx.sess.opts.share_generics())
+        || (!is_generic && tcx.is_reachable_non_generic(def_id))
+    {
+        return true;
+    }
+
+    // Methods of traits may be in the vtables created in other crates (e.g., for types returned
+    // as `impl Trait`)
+    if tcx.trait_of_item(def_id).is_some()
+        || tcx.impl_of_method(def_id).is_some_and(|impl_id| tcx.trait_id_of_impl(impl_id).is_some())
+    {
+        return true;
+    }
+
+    // The functions whose addresses are taken in the local crate were recorded by the collector
+    cx.usage_map.is_address_taken(mono_item)
+}
+
 type CguNameCache = FxHashMap<(DefId, bool), Symbol>;
 
 fn static_visibility<'tcx>(
@@ -1070,18 +1151,110 @@ fn assert_symbols_are_distinct<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, mono_items: I)
 
     for &[(mono_item1, ref sym1), (mono_item2, ref sym2)] in symbols.array_windows() {
         if sym1 == sym2 {
-            let span1 = mono_item1.local_span(tcx);
-            let span2 = mono_item2.local_span(tcx);
+            // Deterministically select one of the items for error reporting, and point at the
+            // other one as the previous definition
+            let (item, previous_item) =
+                match (mono_item1.local_span(tcx), mono_item2.local_span(tcx)) {
+                    (Some(span1), Some(span2)) if span1.lo().0 > span2.lo().0 => {
+                        (mono_item1, mono_item2)
+                    }
+                    (Some(_), None) => (mono_item1, mono_item2),
+                    _ => (mono_item2, mono_item1),
+                };
+            let span = item.local_span(tcx);
+            let previous_span = previous_item.local_span(tcx);
+            let origin = symbol_origin(tcx, item);
+            let previous_origin = symbol_origin(tcx, previous_item);
+
+            // Distinct items can only share a hashed symbol if their hashes collide. Falling back
+            // to another mangling for one of them is not an option, as downstream crates would
+            // still compute the hashed symbol, so the user has to pick a longer hash instead.
+            if origin == SymbolOrigin::Hashed && previous_origin == SymbolOrigin::Hashed {
+                tcx.dcx().emit_fatal(SymbolHashCollision {
+                    span,
+                    previous_span,
+                    symbol: sym1.to_string(),
+                    item1: mono_item1.to_string(),
+                    item2: mono_item2.to_string(),
+                    bits: tcx.sess.opts.get_symbol_mangling_hash_bits(),
+                });
+            }
+            tcx.dcx().emit_fatal(SymbolAlreadyDefined {
+                span,
+                previous_span,
+                symbol: sym1.to_string(),
+                item: symbol_item_name(tcx, item),
+                origin: origin.as_str(),
+                previous_item: symbol_item_name(tcx, previous_item),
+                previous_origin: previous_origin.as_str(),
+            });
+        }
+    }
+}
 
-            // Deterministically select one of the spans for error reporting
-            let span = match (span1, span2) {
-                (Some(span1), Some(span2)) => {
-                    Some(if span1.lo().0 > span2.lo().0 { span1 } else { span2 })
-                }
-                (span1, span2) => span1.or(span2),
-            };
+/// Returns how the symbol of `mono_item` was named (see `rustc_symbol_mangling::symbol_origin`).
+fn symbol_origin<'tcx>(tcx: TyCtxt<'tcx>, mono_item: &MonoItem<'tcx>) -> SymbolOrigin {
+    match *mono_item {
+        MonoItem::Fn(instance) => rustc_symbol_mangling::symbol_origin(tcx, instance),
+        MonoItem::Static(def_id) => {
+            rustc_symbol_mangling::symbol_origin(tcx, Instance::mono(tcx, def_id))
+        }
+        MonoItem::GlobalAsm(..) => SymbolOrigin::Generated,
+    }
+}
 
-            tcx.dcx().emit_fatal(SymbolAlreadyDefined { span, symbol: sym1.to_string() });
+/// Returns the name of `mono_item` for diagnostics about its symbol.
+fn symbol_item_name<'tcx>(tcx: TyCtxt<'tcx>, mono_item: &MonoItem<'tcx>) -> String {
+    match *mono_item {
+        MonoItem::Fn(instance) => instance.to_string(),
+        MonoItem::Static(def_id) => tcx.def_path_str(def_id),
+        MonoItem::GlobalAsm(..) => "global_asm!".to_string(),
+    }
+}
+
+/// Checks that no two functions with distinct type metadata identifiers are given the same type
+/// metadata identifier capped with `-Z sanitizer-cfi-typeid-length-cap` (i.e., that the hashes of
+/// their tails don't collide).
+#[inline(never)] // give this a place in the profiler
+fn assert_capped_typeids_are_distinct<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, mono_items: I)
+where
+    I: Iterator<Item = &'a MonoItem<'tcx>>,
+    'tcx: 'a,
+{
+    let _prof_timer = tcx.prof.generic_activity("assert_capped_typeids_are_distinct");
+
+    let (options, kcfi_options) = typeid_options(tcx.sess);
+    let all_options = if tcx.sess.is_sanitizer_cfi_enabled() {
+        declared_typeid_options(options)
+    } else {
+        vec![kcfi_options]
+    };
+
+    let mut capped_typeids: FxHashMap<String, (String, MonoItem<'tcx>)> = Default::default();
+    for mono_item in mono_items {
+        let MonoItem::Fn(instance) = *mono_item else { continue };
+        for &options in &all_options {
+            let typeid = typeid_for_instance(tcx, instance, options);
+            let full_typeid = typeid_for_instance(tcx, instance, options | TypeIdOptions::UNCAPPED);
+            if typeid == full_typeid {
+                continue;
+            }
+            match capped_typeids.entry(typeid) {
+                Entry::Occupied(entry) if entry.get().0 != full_typeid => {
+                    let previous_item = entry.get().1;
+                    tcx.dcx().emit_fatal(CfiTypeIdHashCollision {
+                        span: mono_item.local_span(tcx),
+                        previous_span: previous_item.local_span(tcx),
+                        typeid: entry.key().clone(),
+                        item1: previous_item.to_string(),
+                        item2: mono_item.to_string(),
+                    });
+                }
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert((full_typeid, *mono_item));
+                }
+            }
         }
     }
 }
@@ -1128,6 +1301,14 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         )
     });
 
+    if tcx.sess.opts.unstable_opts.sanitizer_cfi_typeid_length_cap.is_some()
+        && (tcx.sess.is_sanitizer_cfi_enabled() || tcx.sess.is_sanitizer_kcfi_enabled())
+    {
+        tcx.sess.time("assert_distinct_capped_typeids", || {
+            assert_capped_typeids_are_distinct(tcx, items.iter())
+        });
+    }
+
     if tcx.prof.enabled() {
         // Record CGU size estimates for self-profiling.
         for cgu in codegen_units {
@@ -1157,6 +1338,27 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     }
 
+    // Output the CFI and KCFI type metadata identifiers per symbol
+    if let Some(ref path) = tcx.sess.opts.unstable_opts.cfi_dump_typeids {
+        if let Err(err) = dump_cfi_typeids(tcx, &items, path) {
+            tcx.dcx().emit_fatal(CouldntDumpCfiTypeIds { error: err.to_string() });
+        }
+    }
+
+    // Output the symbol map, for symbolication, size analysis, etc.
+    if let Some(ref path) = tcx.sess.opts.unstable_opts.emit_symbol_map {
+        if let Err(err) = emit_symbol_map(tcx, codegen_units, path) {
+            tcx.dcx().emit_fatal(CouldntEmitSymbolMap { error: err.to_string() });
+        }
+    }
+
+    // Output the CFI and KCFI type metadata identifiers of the exported extern "C" functions
+    if let Some(ref path) = tcx.sess.opts.unstable_opts.cfi_export_typeids {
+        if let Err(err) = export_cfi_typeids(tcx, &items, path) {
+            tcx.dcx().emit_fatal(CouldntExportCfiTypeIds { error: err.to_string() });
+        }
+    }
+
     if tcx.sess.opts.unstable_opts.print_mono_items.is_some() {
         let mut item_to_cgus: FxHashMap<_, Vec<_>> = Default::default();
 
@@ -1208,6 +1410,34 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     }
 
+    if let Some(ref pattern) = tcx.sess.opts.unstable_opts.print_mono_items_matching {
+        print_mono_items_matching(tcx, codegen_units, pattern);
+    }
+
+    if tcx.sess.opts.unstable_opts.print_symbol_explanations {
+        let mut explanations: Vec<_> = items
+            .iter()
+            .filter(|mono_item| {
+                !matches!(
+                    symbol_origin(tcx, mono_item),
+                    SymbolOrigin::ExportName | SymbolOrigin::NoMangle
+                )
+            })
+            .filter_map(|mono_item| match *mono_item {
+                MonoItem::Fn(instance) => Some(instance),
+                MonoItem::Static(def_id) => Some(Instance::mono(tcx, def_id)),
+                MonoItem::GlobalAsm(..) => None,
+            })
+            .map(|instance| explain_v0_symbol_name(tcx, instance))
+            .collect();
+
+        explanations.sort();
+
+        for explanation in explanations {
+            println!("{explanation}\n");
+        }
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
@@ -1289,9 +1519,490 @@ struct MonoItem {
     Ok(())
 }
 
+/// Outputs the CFI and KCFI type metadata identifiers of every function `MonoItem`, keyed by
+/// symbol name, and the options they were computed with, as JSON to the given file.
+fn dump_cfi_typeids<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sess = tcx.sess;
+    let (options, kcfi_options) = typeid_options(sess);
+
+    // With CFI, functions are given a type metadata identifier for every combination of these
+    // options when declared, so dump all of them.
+    let typeid_options = if sess.is_sanitizer_cfi_enabled() {
+        declared_typeid_options(options)
+    } else {
+        vec![options]
+    };
+
+    #[derive(serde::Serialize)]
+    struct Options {
+        generalize_pointers: bool,
+        normalize_integers: bool,
+        normalize_ptr_sized_integers: bool,
+        member_function_pointers: bool,
+        cxx_references: bool,
+        erase_const: bool,
+        erase_ref_mut: bool,
+        encode_captures: bool,
+        erase_phantom_data: bool,
+        generalize_arrays: bool,
+        erase_return_types: bool,
+        c_integer_types: bool,
+        box_as_ptr: bool,
+        refs_as_ptrs: bool,
+        impl_trait_bounds: bool,
+        encode_fn_sigs: bool,
+        kcfi_arity: bool,
+        kcfi_seed: u64,
+        kcfi_typeid_width: u32,
+    }
+
+    #[derive(serde::Serialize)]
+    struct CfiTypeId {
+        typeid: String,
+        signature: Option<String>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct CfiTypeIds {
+        symbol: String,
+        name: String,
+        typeids: Vec<CfiTypeId>,
+        kcfi_typeid: u64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct CfiTypeIdsDump {
+        options: Options,
+        items: Vec<CfiTypeIds>,
+    }
+
+    let mut cfi_typeids: Vec<_> = items
+        .iter()
+        .filter_map(|mono_item| match *mono_item {
+            MonoItem::Fn(instance) => Some(instance),
+            _ => None,
+        })
+        .map(|instance| {
+            let mut typeids: Vec<CfiTypeId> = Vec::new();
+            for &options in &typeid_options {
+                let typeid = typeid_for_instance(tcx, instance, options);
+                if !typeids.iter().any(|cfi_typeid| cfi_typeid.typeid == typeid) {
+                    let signature = demangle_typeid(&typeid);
+                    typeids.push(CfiTypeId { typeid, signature });
+                }
+            }
+            let kcfi_typeid = match kcfi_typeid_for_instance(tcx, instance, kcfi_options) {
+                KcfiTypeId::U32(kcfi_typeid) => kcfi_typeid.into(),
+                KcfiTypeId::U64(kcfi_typeid) => kcfi_typeid,
+            };
+            CfiTypeIds {
+                symbol: tcx.symbol_name(instance).name.to_string(),
+                name: with_no_trimmed_paths!(instance.to_string()),
+                typeids,
+                kcfi_typeid,
+            }
+        })
+        .collect();
+    // Sort by symbol name so dumps of different builds can be diffed
+    cfi_typeids.sort_unstable_by(|a, b| a.symbol.cmp(&b.symbol));
+
+    let dump = CfiTypeIdsDump {
+        options: Options {
+            generalize_pointers: options.contains(TypeIdOptions::GENERALIZE_POINTERS),
+            normalize_integers: options.contains(TypeIdOptions::NORMALIZE_INTEGERS),
+            normalize_ptr_sized_integers: options
+                .contains(TypeIdOptions::NORMALIZE_PTR_SIZED_INTEGERS),
+            member_function_pointers: options.contains(TypeIdOptions::MEMBER_FUNCTION_POINTERS),
+            cxx_references: options.contains(TypeIdOptions::CXX_REFERENCES),
+            erase_const: options.contains(TypeIdOptions::ERASE_CONST),
+            erase_ref_mut: options.contains(TypeIdOptions::ERASE_REF_MUT),
+            encode_captures: options.contains(TypeIdOptions::ENCODE_CAPTURES),
+            erase_phantom_data: options.contains(TypeIdOptions::ERASE_PHANTOM_DATA),
+            generalize_arrays: options.contains(TypeIdOptions::GENERALIZE_ARRAYS),
+            erase_return_types: options.contains(TypeIdOptions::ERASE_RETURN_TYPES),
+            c_integer_types: options.contains(TypeIdOptions::C_INTEGER_TYPES),
+            box_as_ptr: options.contains(TypeIdOptions::BOX_AS_PTR),
+            refs_as_ptrs: options.contains(TypeIdOptions::REFS_AS_PTRS),
+            impl_trait_bounds: options.contains(TypeIdOptions::IMPL_TRAIT_BOUNDS),
+            encode_fn_sigs: options.contains(TypeIdOptions::ENCODE_FN_SIGS),
+            kcfi_arity: sess.is_sanitizer_kcfi_arity_enabled(),
+            kcfi_seed: sess.sanitizer_kcfi_seed().unwrap_or(0),
+            kcfi_typeid_width: match sess.opts.unstable_opts.sanitizer_kcfi_typeid_width {
+                KcfiTypeIdWidth::Bits32 => 32,
+                KcfiTypeIdWidth::Bits64 => 64,
+            },
+        },
+        items: cfi_typeids,
+    };
+
+    if let Some(directory) = output_path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let file = BufWriter::new(File::create(output_path)?);
+    serde_json::to_writer_pretty(file, &dump)?;
+
+    Ok(())
+}
+
+/// Outputs the generalized CFI type metadata identifier and KCFI type metadata identifier of every
+/// exported (i.e., `#[no_mangle]` or `#[export_name]`) function with the "C" calling convention to
+/// the given file, so C builds can embed or assert them (e.g., in headers generated for the crate).
+///
+/// The file has a `#`-prefixed header and one line per function, sorted by symbol name, with its
+/// symbol, its type metadata identifier (computed with pointers generalized, as for C code compiled
+/// with `-fsanitize-cfi-icall-generalize-pointers`), and the KCFI type metadata identifier it is
+/// instrumented with (as a hexadecimal number), separated by tabs.
+fn export_cfi_typeids<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (options, kcfi_options) = typeid_options(tcx.sess);
+    let generalized_options = options | TypeIdOptions::GENERALIZE_POINTERS;
+
+    let mut lines: Vec<_> = items
+        .iter()
+        .filter_map(|mono_item| match *mono_item {
+            MonoItem::Fn(instance @ Instance { def: InstanceDef::Item(def_id), .. })
+                if def_id.is_local()
+                    && tcx.codegen_fn_attrs(def_id).contains_extern_indicator()
+                    && matches!(tcx.fn_sig(def_id).skip_binder().abi(), Abi::C { .. }) =>
+            {
+                Some(instance)
+            }
+            _ => None,
+        })
+        .map(|instance| {
+            let typeid = typeid_for_instance(tcx, instance, generalized_options);
+            let kcfi_typeid = match kcfi_typeid_for_instance(tcx, instance, kcfi_options) {
+                KcfiTypeId::U32(kcfi_typeid) => format!("{kcfi_typeid:#010x}"),
+                KcfiTypeId::U64(kcfi_typeid) => format!("{kcfi_typeid:#018x}"),
+            };
+            format!("{}\t{typeid}\t{kcfi_typeid}", tcx.symbol_name(instance).name)
+        })
+        .collect();
+    // Sort by symbol name so exports of different builds can be diffed
+    lines.sort_unstable();
+
+    if let Some(directory) = output_path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut file = BufWriter::new(File::create(output_path)?);
+    writeln!(
+        file,
+        "# CFI type metadata identifiers of the exported extern \"C\" functions of `{}`",
+        tcx.crate_name(LOCAL_CRATE)
+    )?;
+    writeln!(
+        file,
+        "# <symbol>\t<generalized type metadata identifier>\t<KCFI type metadata identifier>"
+    )?;
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Outputs the symbol of every `MonoItem` of every codegen unit to the given file, so that
+/// symbolication, size analysis, etc. do not have to demangle or guess at the items of symbols.
+///
+/// The file has a `#`-prefixed header and one line per symbol and codegen unit it is placed in,
+/// sorted by symbol, with the symbol, the full symbol (which only differs from the symbol if it was
+/// capped with `-Z symbol-length-cap`), the demangled full symbol, the def path of the item, the
+/// name of the codegen unit, the version the version scripts passed to the linker assign to the
+/// symbol, and the CFI or KCFI type metadata identifier of the function, separated by tabs (with
+/// `-` for no version or type metadata identifier).
+fn emit_symbol_map<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sess = tcx.sess;
+    let (options, kcfi_options) = typeid_options(sess);
+    let symbol_versions = SymbolVersions::from_link_args(sess)?;
+
+    let mut lines: Vec<_> = codegen_units
+        .iter()
+        .flat_map(|cgu| cgu.items().keys().map(move |mono_item| (cgu.name(), mono_item)))
+        .filter_map(|(cgu_name, mono_item)| {
+            let instance = match *mono_item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(def_id) => Instance::mono(tcx, def_id),
+                MonoItem::GlobalAsm(..) => return None,
+            };
+            let symbol = tcx.symbol_name(instance).name;
+            let full_symbol = uncapped_symbol_name(tcx, instance);
+            let demangled = rustc_demangle::demangle(&full_symbol);
+            let def_path = tcx.def_path_debug_str(instance.def_id());
+            let version = symbol_versions.version_of(symbol).unwrap_or("-");
+            // The type metadata identifier indirect calls to the function are checked with, if any
+            let typeid = match *mono_item {
+                MonoItem::Fn(instance) if sess.is_sanitizer_cfi_enabled() => {
+                    typeid_for_instance(tcx, instance, options)
+                }
+                MonoItem::Fn(instance) if sess.is_sanitizer_kcfi_enabled() => {
+                    match kcfi_typeid_for_instance(tcx, instance, kcfi_options) {
+                        KcfiTypeId::U32(kcfi_typeid) => format!("{kcfi_typeid:#010x}"),
+                        KcfiTypeId::U64(kcfi_typeid) => format!("{kcfi_typeid:#018x}"),
+                    }
+                }
+                _ => "-".to_string(),
+            };
+            Some(format!(
+                "{symbol}\t{full_symbol}\t{demangled}\t{def_path}\t{cgu_name}\t{version}\t{typeid}"
+            ))
+        })
+        .collect();
+    lines.sort_unstable();
+
+    if let Some(directory) = output_path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut file = BufWriter::new(File::create(output_path)?);
+    writeln!(file, "# Symbols of `{}`", tcx.crate_name(LOCAL_CRATE))?;
+    writeln!(
+        file,
+        "# <symbol>\t<full symbol>\t<demangled symbol>\t<def path>\t<codegen unit>\t<version>\t\
+         <type metadata identifier>"
+    )?;
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Prints the mono items whose paths match `pattern` (see `rustc_symbol_mangling::path_pattern`),
+/// one per line, with their symbols, the demangling of their symbols and the codegen units they are
+/// placed in, separated by tabs.
+fn print_mono_items_matching<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+    pattern: &str,
+) {
+    let mut item_to_cgus: FxIndexMap<_, Vec<_>> = Default::default();
+    for cgu in codegen_units {
+        for mono_item in cgu.items().keys() {
+            item_to_cgus.entry(*mono_item).or_default().push(cgu.name());
+        }
+    }
+
+    let mut lines: Vec<_> = item_to_cgus
+        .into_iter()
+        .filter_map(|(mono_item, mut cgu_names)| {
+            let instance = match mono_item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(def_id) => Instance::mono(tcx, def_id),
+                MonoItem::GlobalAsm(..) => return None,
+            };
+            if !def_path_matches(tcx, instance.def_id(), pattern) {
+                return None;
+            }
+            let item = with_no_trimmed_paths!(mono_item.to_string());
+            let symbol = tcx.symbol_name(instance).name;
+            let demangled = rustc_demangle::demangle(&uncapped_symbol_name(tcx, instance));
+            cgu_names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+            let cgu_names: Vec<_> = cgu_names.iter().map(|name| name.as_str()).collect();
+            Some(format!("{item}\t{symbol}\t{demangled}\t{}", cgu_names.join(" ")))
+        })
+        .collect();
+    lines.sort_unstable();
+
+    for line in lines {
+        println!("{line}");
+    }
+}
+
+fn cfi_alias_sets<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> Vec<CfiAliasSet<'tcx>> {
+    let (options, kcfi_options) = typeid_options(tcx.sess);
+    let options = if tcx.sess.is_sanitizer_kcfi_enabled() { kcfi_options } else { options };
+
+    // Items that are instantiated as local copies may be placed in more than one codegen unit.
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    let instances: FxIndexSet<Instance<'tcx>> = codegen_units
+        .iter()
+        .flat_map(|cgu| cgu.items().keys())
+        .filter_map(|mono_item| match *mono_item {
+            MonoItem::Fn(instance) => Some(instance),
+            _ => None,
+        })
+        .collect();
+
+    let mut alias_sets: FxIndexMap<String, Vec<Instance<'tcx>>> = Default::default();
+    for instance in instances {
+        alias_sets.entry(typeid_for_instance(tcx, instance, options)).or_default().push(instance);
+    }
+
+    let mut alias_sets: Vec<_> = alias_sets
+        .into_iter()
+        .map(|(typeid, mut members)| {
+            members.sort_by_cached_key(|&instance| tcx.symbol_name(instance).name);
+            CfiAliasSet { typeid, members }
+        })
+        .collect();
+    // Sort the largest sets first (and by type metadata identifier to make things deterministic)
+    alias_sets.sort_unstable_by(|a, b| {
+        b.members.len().cmp(&a.members.len()).then_with(|| a.typeid.cmp(&b.typeid))
+    });
+    alias_sets
+}
+
+fn cfi_typeid_summary<'tcx>(tcx: TyCtxt<'tcx>, cgu_name: Symbol) -> CfiTypeIdSummary {
+    let (options, _) = typeid_options(tcx.sess);
+    let declared_options = declared_typeid_options(options);
+    let param_env = ty::ParamEnv::reveal_all();
+
+    let mut defined = FxHashSet::default();
+    let mut referenced = FxHashSet::default();
+    for (mono_item, data) in tcx.codegen_unit(cgu_name).items() {
+        let MonoItem::Fn(instance) = *mono_item else { continue };
+        if data.may_be_called_indirectly {
+            for &options in &declared_options {
+                defined.insert(typeid_for_instance(tcx, instance, options));
+            }
+        }
+
+        // Indirect calls of functions that aren't instrumented aren't checked (see
+        // `rustc_codegen_llvm::builder`)
+        if tcx.def_kind(instance.def_id()).has_codegen_attrs()
+            && tcx.codegen_fn_attrs(instance.def_id()).no_sanitize.contains(SanitizerSet::CFI)
+        {
+            continue;
+        }
+
+        let body = tcx.instance_mir(instance.def);
+        let monomorphize = |ty| {
+            instance.instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                param_env,
+                ty::EarlyBinder::bind(ty),
+            )
+        };
+        for block in body.basic_blocks.iter() {
+            let typeid = match &block.terminator().kind {
+                // Calls through function pointers are checked against the type metadata
+                // identifiers of their types, and virtual calls against the ones of the methods
+                // called.
+                mir::TerminatorKind::Call { func, args, .. } => {
+                    match *monomorphize(func.ty(body, tcx)).kind() {
+                        ty::FnPtr(sig) => {
+                            let extra_args = tcx.mk_type_list_from_iter(
+                                args[sig.inputs().skip_binder().len()..]
+                                    .iter()
+                                    .map(|arg| monomorphize(arg.node.ty(body, tcx))),
+                            );
+                            tcx.fn_abi_of_fn_ptr(param_env.and((sig, extra_args)))
+                                .ok()
+                                .map(|fn_abi| typeid_for_fnabi(tcx, fn_abi, options))
+                        }
+                        ty::FnDef(def_id, args) => Instance::resolve(tcx, param_env, def_id, args)
+                            .ok()
+                            .flatten()
+                            .filter(|callee| matches!(callee.def, InstanceDef::Virtual(..)))
+                            .map(|callee| typeid_for_instance(tcx, callee, options)),
+                        _ => None,
+                    }
+                }
+                // Drops of trait objects are virtual calls of their drop glue
+                mir::TerminatorKind::Drop { place, .. } => {
+                    let ty = monomorphize(place.ty(body, tcx).ty);
+                    if let ty::Dynamic(..) = ty.kind() {
+                        let drop_fn = Instance::resolve_drop_in_place(tcx, ty);
+                        let virtual_drop = Instance {
+                            def: InstanceDef::Virtual(drop_fn.def_id(), 0),
+                            args: drop_fn.args,
+                        };
+                        Some(typeid_for_instance(tcx, virtual_drop, options))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+            referenced.extend(typeid);
+        }
+    }
+
+    let mut defined: Vec<_> = defined.into_iter().collect();
+    defined.sort_unstable();
+    let mut referenced: Vec<_> = referenced.into_iter().collect();
+    referenced.sort_unstable();
+    CfiTypeIdSummary { defined, referenced }
+}
+
+/// Prints the CFI alias sets of the local crate with `-Zcfi-alias-set-report`, and emits the
+/// `large_cfi_alias_sets` lint for the ones with more functions than `-Zcfi-alias-set-threshold`
+/// when CFI or KCFI is enabled.
+pub fn check_cfi_alias_sets(tcx: TyCtxt<'_>) {
+    let sess = tcx.sess;
+    let report = sess.opts.unstable_opts.cfi_alias_set_report;
+    let lint = (sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled())
+        && tcx.lint_level_at_node(LARGE_CFI_ALIAS_SETS, CRATE_HIR_ID).0 != Level::Allow;
+    if !report && !lint {
+        return;
+    }
+
+    let threshold = sess.opts.unstable_opts.cfi_alias_set_threshold;
+    for alias_set in tcx.cfi_alias_sets(()) {
+        let signature = demangle_typeid(&alias_set.typeid).unwrap_or_default();
+        let names: Vec<_> = alias_set
+            .members
+            .iter()
+            .map(|instance| with_no_trimmed_paths!(instance.to_string()))
+            .collect();
+
+        if report {
+            println!(
+                "print-cfi-alias-set typeid: `{}` ({signature}): {} function{}",
+                alias_set.typeid,
+                names.len(),
+                pluralize!(names.len())
+            );
+            for name in &names {
+                println!("print-cfi-alias-set     function: `{name}`");
+            }
+        }
+
+        if lint && alias_set.members.len() > threshold {
+            // Point at a function of the local crate if there is one, since the set may also
+            // contain functions of other crates instantiated in this one.
+            let span = alias_set
+                .members
+                .iter()
+                .map(|instance| instance.def_id())
+                .find(|def_id| def_id.is_local())
+                .map_or_else(|| tcx.def_span(CRATE_DEF_ID), |def_id| tcx.def_span(def_id));
+            let representatives =
+                names.iter().take(3).map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ");
+            tcx.emit_node_span_lint(
+                LARGE_CFI_ALIAS_SETS,
+                CRATE_HIR_ID,
+                span,
+                LargeCfiAliasSet {
+                    size: alias_set.members.len(),
+                    typeid: alias_set.typeid.clone(),
+                    signature,
+                    representatives,
+                    threshold,
+                },
+            );
+        }
+    }
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
+    providers.cfi_alias_sets = cfi_alias_sets;
+    providers.cfi_typeid_summary = cfi_typeid_summary;
+
     providers.is_codegened_item = |tcx, def_id| {
         let (all_mono_items, _) = tcx.collect_and_partition_mono_items(());
         all_mono_items.contains(&def_id)
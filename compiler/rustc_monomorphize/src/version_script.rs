@@ -0,0 +1,154 @@
+//! The symbol versions assigned by the GNU version scripts passed to the linker (e.g., with
+//! `-Clink-arg=-Wl,--version-script=libfoo.map`), so `-Zemit-symbol-map` can record the version of
+//! every versioned export.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use rustc_session::Session;
+use rustc_symbol_mangling::path_pattern::path_matches;
+
+/// The global symbols (or patterns of symbols) of the named version nodes of the version scripts,
+/// with the names of their version nodes.
+pub(crate) struct SymbolVersions {
+    globals: Vec<(String, String)>,
+}
+
+impl SymbolVersions {
+    /// Reads the version scripts passed with `-C link-arg(s)` or `-Z pre-link-arg(s)`, either
+    /// directly (`--version-script=<path>`) or through the compiler driver
+    /// (`-Wl,--version-script=<path>` or `-Wl,--version-script,<path>`).
+    pub(crate) fn from_link_args(sess: &Session) -> io::Result<SymbolVersions> {
+        let args = sess.opts.unstable_opts.pre_link_args.iter().chain(&sess.opts.cg.link_args);
+        let mut globals = Vec::new();
+        for path in version_script_paths(args.map(String::as_str)) {
+            globals.extend(parse_version_script(&fs::read_to_string(path)?));
+        }
+        Ok(SymbolVersions { globals })
+    }
+
+    /// Returns the name of the version node `symbol` is a global of, if any. As with GNU ld, a
+    /// symbol that is named in a version node takes the version of that node over any versions of
+    /// the nodes with patterns matching it.
+    pub(crate) fn version_of(&self, symbol: &str) -> Option<&str> {
+        let is_pattern = |global: &str| global.contains(['*', '?']);
+        self.globals
+            .iter()
+            .find(|(global, _)| !is_pattern(global) && global == symbol)
+            .or_else(|| {
+                self.globals
+                    .iter()
+                    .find(|(global, _)| is_pattern(global) && path_matches(global, symbol))
+            })
+            .map(|(_, version)| version.as_str())
+    }
+}
+
+/// Returns the paths of the version scripts passed in `args`.
+fn version_script_paths<'a>(args: impl Iterator<Item = &'a str>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut next_is_path = false;
+    for arg in args.flat_map(|arg| match arg.strip_prefix("-Wl,") {
+        Some(linker_args) => linker_args.split(',').collect(),
+        None => vec![arg],
+    }) {
+        if next_is_path {
+            paths.push(PathBuf::from(arg));
+            next_is_path = false;
+        } else if let Some(path) = arg.strip_prefix("--version-script=") {
+            paths.push(PathBuf::from(path));
+        } else {
+            next_is_path = arg == "--version-script";
+        }
+    }
+    paths
+}
+
+/// Returns the global symbols (or patterns of symbols) of the named version nodes of `script`,
+/// with the names of their version nodes. Anonymous version nodes don't version their symbols, and
+/// `extern "C++"` (or other language) blocks, which match demangled names, are skipped.
+fn parse_version_script(script: &str) -> Vec<(String, String)> {
+    let tokens = tokenize(script);
+    let mut globals = Vec::new();
+    let mut tokens = tokens.iter().map(String::as_str).peekable();
+    while let Some(token) = tokens.next() {
+        // A version node: `[<name>] { [global:] <symbol>; ... [local: <symbol>; ...] } [<dep>];`
+        let name = if token == "{" {
+            None
+        } else if tokens.next_if_eq(&"{").is_some() {
+            Some(token)
+        } else {
+            continue;
+        };
+        let mut is_global = true;
+        while let Some(token) = tokens.next() {
+            match token {
+                "}" => break,
+                "global" | "local" if tokens.next_if_eq(&":").is_some() => {
+                    is_global = token == "global";
+                }
+                "extern" => {
+                    // Skip the language and its block
+                    let mut depth = 0;
+                    for token in tokens.by_ref() {
+                        match token {
+                            "{" => depth += 1,
+                            "}" if depth == 1 => break,
+                            "}" => depth -= 1,
+                            _ => {}
+                        }
+                    }
+                }
+                ";" => {}
+                symbol => {
+                    if let (true, Some(name)) = (is_global, name) {
+                        globals.push((symbol.to_string(), name.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    globals
+}
+
+/// Splits `script` into its tokens: braces, colons, semicolons, and the words between them, without
+/// comments (`/* ... */` and `# ...`) and with the quotes of quoted words removed.
+fn tokenize(script: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = script.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = None;
+                for c in chars.by_ref() {
+                    if prev == Some('*') && c == '/' {
+                        break;
+                    }
+                    prev = Some(c);
+                }
+            }
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '{' | '}' | ':' | ';' => tokens.push(c.to_string()),
+            '"' => tokens.push(chars.by_ref().take_while(|&c| c != '"').collect()),
+            c if c.is_whitespace() => {}
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars
+                    .next_if(|&c| !c.is_whitespace() && !matches!(c, '{' | '}' | ':' | ';' | '"'))
+                {
+                    word.push(c);
+                }
+                tokens.push(word);
+            }
+        }
+    }
+    tokens
+}
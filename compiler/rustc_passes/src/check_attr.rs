@@ -153,7 +153,7 @@ fn check_attributes(
                     self.check_rustc_std_internal_symbol(attr, span, target)
                 }
                 sym::naked => self.check_naked(hir_id, attr, span, target),
-                sym::rustc_never_returns_null_ptr => {
+                sym::rustc_never_returns_null_ptr | sym::rustc_unhashed_symbol => {
                     self.check_applied_to_fn_or_method(hir_id, attr, span, target)
                 }
                 sym::rustc_legacy_const_generics => {
@@ -2085,7 +2085,7 @@ fn check_rustc_std_internal_symbol(
         target: Target,
     ) -> bool {
         match target {
-            Target::Fn | Target::Static => true,
+            Target::Fn | Target::Static | Target::ForeignFn | Target::ForeignStatic => true,
             _ => {
                 self.tcx
                     .dcx()
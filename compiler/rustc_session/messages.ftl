@@ -5,6 +5,22 @@ session_cannot_enable_crt_static_linux = sanitizer is incompatible with statical
 
 session_cannot_mix_and_match_sanitizers = `-Zsanitizer={$first}` is incompatible with `-Zsanitizer={$second}`
 
+session_cfi_clang_compat_requires_cfi = `-Zcfi-clang-compat` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_cfi_clang_compat_unsupported_option = `{$option}` is not supported by Clang {$version}
+    .note = it is supported since Clang {$since}, and `-Zcfi-clang-compat={$version}` was specified
+
+session_cfi_clang_compat_unsupported_option_in_any_version = `{$option}` is not supported by Clang
+    .note = `-Zcfi-clang-compat={$version}` was specified
+
+session_cfi_cross_language_conflicts_with = `{$flag}` conflicts with `{$option}`
+
+session_cfi_cross_language_requires_cfi = `{$flag}` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_cfi_cross_language_requires_linker_plugin_lto = `{$flag}` with `-Zsanitizer=cfi` requires `-Clinker-plugin-lto`
+
+session_cfi_foreign_exceptions_requires_panic_unwind = `-Zcfi-foreign-exceptions` requires `-Cpanic=unwind`
+
 session_cli_feature_diagnostic_help =
     add `-Zcrate-attr="feature({$feature})"` to the command-line options to enable
 
@@ -86,18 +102,61 @@ session_profile_sample_use_file_does_not_exist = file `{$path}` passed to `-C pr
 
 session_profile_use_file_does_not_exist = file `{$path}` passed to `-C profile-use` does not exist.
 
+session_sanitizer_cfi_box_as_ptr_requires_cfi = `-Zsanitizer-cfi-box-as-ptr` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_c_integer_types_requires_cfi = `-Zsanitizer-cfi-c-integer-types` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
 session_sanitizer_cfi_canonical_jump_tables_requires_cfi = `-Zsanitizer-cfi-canonical-jump-tables` requires `-Zsanitizer=cfi`
 
+session_sanitizer_cfi_cxx_references_requires_cfi = `-Zsanitizer-cfi-cxx-references` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_encode_captures_requires_cfi = `-Zsanitizer-cfi-encode-captures` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_encode_fn_sigs_requires_cfi = `-Zsanitizer-cfi-encode-fn-sigs` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_erase_const_requires_cfi = `-Zsanitizer-cfi-erase-const` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_erase_phantom_data_requires_cfi = `-Zsanitizer-cfi-erase-phantom-data` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_erase_ref_mut_requires_cfi = `-Zsanitizer-cfi-erase-ref-mut` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_erase_return_types_requires_cfi = `-Zsanitizer-cfi-erase-return-types` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_generalize_arrays_requires_cfi = `-Zsanitizer-cfi-generalize-arrays` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
 session_sanitizer_cfi_generalize_pointers_requires_cfi = `-Zsanitizer-cfi-generalize-pointers` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
 
+session_sanitizer_cfi_impl_trait_bounds_requires_cfi = `-Zsanitizer-cfi-impl-trait-bounds` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_member_function_pointers_requires_cfi = `-Zsanitizer-cfi-member-function-pointers` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
 session_sanitizer_cfi_normalize_integers_requires_cfi = `-Zsanitizer-cfi-normalize-integers` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
 
+session_sanitizer_cfi_normalize_ptr_sized_integers_requires_cfi = `-Zsanitizer-cfi-normalize-ptr-sized-integers` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_refs_as_ptrs_requires_cfi = `-Zsanitizer-cfi-refs-as-ptrs` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
 session_sanitizer_cfi_requires_lto = `-Zsanitizer=cfi` requires `-Clto` or `-Clinker-plugin-lto`
 
 session_sanitizer_cfi_requires_single_codegen_unit = `-Zsanitizer=cfi` with `-Clto` requires `-Ccodegen-units=1`
 
+session_sanitizer_cfi_trait_object_projections_requires_cfi = `-Zsanitizer-cfi-trait-object-projections` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_typeid_length_cap_requires_cfi = `-Zsanitizer-cfi-typeid-length-cap` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_cfi_typeid_scheme_requires_cfi = `-Zsanitizer-cfi-typeid-scheme` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+
+session_sanitizer_kcfi_arity_requires_kcfi = `-Zsanitizer-kcfi-arity` requires `-Zsanitizer=kcfi`
+
+session_sanitizer_kcfi_arity_requires_x86_64 = `-Zsanitizer-kcfi-arity` is only supported on x86-64 targets
+
 session_sanitizer_kcfi_requires_panic_abort = `-Z sanitizer=kcfi` requires `-C panic=abort`
 
+session_sanitizer_kcfi_seed_incompatible_with_clang = a KCFI seed makes KCFI type metadata identifiers incompatible with the ones of Clang-built code, which `{$option}` is for
+    .note = the seed is given by `-Zsanitizer-kcfi-seed` or by the target, and Clang has no equivalent
+
+session_sanitizer_kcfi_seed_requires_kcfi = `-Zsanitizer-kcfi-seed` requires `-Zsanitizer=kcfi`
+
 session_sanitizer_not_supported = {$us} sanitizer is not supported for this target
 
 session_sanitizers_not_supported = {$us} sanitizers are not supported for this target
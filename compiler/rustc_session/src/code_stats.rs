@@ -89,10 +89,37 @@ pub struct VTableSizeInfo {
     pub upcasting_cost_percent: f64,
 }
 
+/// The compression statistics of a CFI type metadata identifier (see `-Zcfi-compression-stats`).
+#[derive(Clone, Copy, Debug)]
+pub struct CfiTypeIdSizeInfo {
+    /// The number of substitutions.
+    pub substitutions: usize,
+    /// The length of the type metadata identifier without substitutions.
+    pub uncompressed_len: usize,
+}
+
+/// The statistics of the encodings of types computed once and spliced into the CFI type metadata
+/// identifiers they are components of (see `-Zcfi-fragment-stats`).
+#[derive(Clone, Copy, Debug, Default)]
+struct CfiFragmentStats {
+    /// The number of encodings computed.
+    fragments: usize,
+    /// The total length of the encodings computed.
+    bytes: usize,
+    /// The total number of substitution dictionary keys of the encodings computed.
+    dictionary_keys: usize,
+    /// The number of encodings spliced.
+    hits: usize,
+    /// The number of encodings that couldn't be spliced.
+    conflicts: usize,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    cfi_typeid_sizes: Lock<FxHashMap<String, CfiTypeIdSizeInfo>>,
+    cfi_fragments: Lock<CfiFragmentStats>,
 }
 
 impl CodeStats {
@@ -134,6 +161,34 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    /// Records the compression statistics of a CFI type metadata identifier, once per type
+    /// metadata identifier.
+    pub fn record_cfi_typeid_size(&self, typeid: &str, info: CfiTypeIdSizeInfo) {
+        let mut cfi_typeid_sizes = self.cfi_typeid_sizes.lock();
+        if !cfi_typeid_sizes.contains_key(typeid) {
+            cfi_typeid_sizes.insert(typeid.to_string(), info);
+        }
+    }
+
+    /// Records the length and number of substitution dictionary keys of an encoding of a type
+    /// computed to be spliced into CFI type metadata identifiers.
+    pub fn record_cfi_fragment(&self, bytes: usize, dictionary_keys: usize) {
+        let mut stats = self.cfi_fragments.lock();
+        stats.fragments += 1;
+        stats.bytes += bytes;
+        stats.dictionary_keys += dictionary_keys;
+    }
+
+    /// Records whether an encoding of a type was spliced into a CFI type metadata identifier.
+    pub fn record_cfi_fragment_splice(&self, spliced: bool) {
+        let mut stats = self.cfi_fragments.lock();
+        if spliced {
+            stats.hits += 1;
+        } else {
+            stats.conflicts += 1;
+        }
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
@@ -266,4 +321,37 @@ pub fn print_vtable_sizes(&self, crate_name: Symbol) {
             );
         }
     }
+
+    pub fn print_cfi_compression_stats(&self, crate_name: Symbol) {
+        // We will soon sort, so the initial order does not matter.
+        #[allow(rustc::potential_query_instability)]
+        let mut infos =
+            std::mem::take(&mut *self.cfi_typeid_sizes.lock()).into_iter().collect::<Vec<_>>();
+        // Largest first (and by type metadata identifier to make things deterministic)
+        infos.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let bytes: usize = infos.iter().map(|(typeid, _)| typeid.len()).sum();
+        let uncompressed_bytes: usize = infos.iter().map(|(_, info)| info.uncompressed_len).sum();
+        let substitutions: usize = infos.iter().map(|(_, info)| info.substitutions).sum();
+        println!(
+            r#"print-cfi-compression-stats {{ "crate_name": "{crate_name}", "typeids": "{}", "bytes": "{bytes}", "uncompressed_bytes": "{uncompressed_bytes}", "substitutions": "{substitutions}" }}"#,
+            infos.len()
+        );
+        for (typeid, info) in infos.iter().take(10) {
+            println!(
+                r#"print-cfi-compression-stats {{ "crate_name": "{crate_name}", "typeid": "{typeid}", "bytes": "{}", "uncompressed_bytes": "{}", "substitutions": "{}" }}"#,
+                typeid.len(),
+                info.uncompressed_len,
+                info.substitutions
+            );
+        }
+    }
+
+    pub fn print_cfi_fragment_stats(&self, crate_name: Symbol) {
+        let CfiFragmentStats { fragments, bytes, dictionary_keys, hits, conflicts } =
+            *self.cfi_fragments.lock();
+        println!(
+            r#"print-cfi-fragment-stats {{ "crate_name": "{crate_name}", "fragments": "{fragments}", "bytes": "{bytes}", "dictionary_keys": "{dictionary_keys}", "hits": "{hits}", "conflicts": "{conflicts}" }}"#
+        );
+    }
 }
@@ -341,6 +341,18 @@ pub enum SymbolManglingVersion {
     Hashed,
 }
 
+/// Which symbols are hashed with `-C symbol-mangling-version=hashed`, the others are mangled with
+/// the v0 scheme. Items marked with `#[rustc_unhashed_symbol]` are never hashed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, HashStable_Generic)]
+#[derive(Encodable, Decodable)]
+pub enum SymbolHashPolicy {
+    /// Hash all symbols.
+    All,
+    /// Hash the symbols of all items except the public ones (i.e., those declared `pub` in
+    /// modules that are all `pub`), which keeps the public API of the crate demangleable.
+    Private,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Hash)]
 pub enum DebugInfo {
     None,
@@ -692,6 +704,9 @@ pub fn files(&self) -> Option<impl Iterator<Item = &CanonicalizedPath>> {
 pub struct PrintRequest {
     pub kind: PrintKind,
     pub out: OutFileName,
+    /// The argument of print requests that take one (e.g., the function path of
+    /// `--print cfi-type-id=<path>`).
+    pub arg: Option<String>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -715,6 +730,8 @@ pub enum PrintKind {
     LinkArgs,
     SplitDebuginfo,
     DeploymentTarget,
+    CfiGrammar,
+    CfiTypeId,
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -1107,6 +1124,14 @@ pub fn share_generics(&self) -> bool {
     pub fn get_symbol_mangling_version(&self) -> SymbolManglingVersion {
         self.cg.symbol_mangling_version.unwrap_or(SymbolManglingVersion::Legacy)
     }
+
+    pub fn get_symbol_mangling_hash_bits(&self) -> u32 {
+        self.unstable_opts.symbol_mangling_hash_bits.unwrap_or(64)
+    }
+
+    pub fn get_symbol_mangling_hash_policy(&self) -> SymbolHashPolicy {
+        self.unstable_opts.symbol_mangling_hash_policy.unwrap_or(SymbolHashPolicy::All)
+    }
 }
 
 impl UnstableOptions {
@@ -1767,11 +1792,19 @@ fn collect_print_requests(
 ) -> Vec<PrintRequest> {
     let mut prints = Vec::<PrintRequest>::new();
     if cg.target_cpu.as_ref().is_some_and(|s| s == "help") {
-        prints.push(PrintRequest { kind: PrintKind::TargetCPUs, out: OutFileName::Stdout });
+        prints.push(PrintRequest {
+            kind: PrintKind::TargetCPUs,
+            out: OutFileName::Stdout,
+            arg: None,
+        });
         cg.target_cpu = None;
     };
     if cg.target_feature == "help" {
-        prints.push(PrintRequest { kind: PrintKind::TargetFeatures, out: OutFileName::Stdout });
+        prints.push(PrintRequest {
+            kind: PrintKind::TargetFeatures,
+            out: OutFileName::Stdout,
+            arg: None,
+        });
         cg.target_feature = String::new();
     }
 
@@ -1780,6 +1813,8 @@ fn collect_print_requests(
         ("all-target-specs-json", PrintKind::AllTargetSpecs),
         ("calling-conventions", PrintKind::CallingConventions),
         ("cfg", PrintKind::Cfg),
+        ("cfi-grammar", PrintKind::CfiGrammar),
+        ("cfi-type-id", PrintKind::CfiTypeId),
         ("code-models", PrintKind::CodeModels),
         ("crate-name", PrintKind::CrateName),
         ("deployment-target", PrintKind::DeploymentTarget),
@@ -1806,6 +1841,24 @@ fn collect_print_requests(
     let mut printed_paths = FxHashSet::default();
 
     prints.extend(matches.opt_strs("print").into_iter().map(|req| {
+        // `cfi-type-id` takes the path of the function to print the type metadata identifier of
+        // instead of an output file.
+        if let Some(path) = req.strip_prefix("cfi-type-id=")
+            && !path.is_empty()
+        {
+            if !unstable_opts.unstable_options {
+                early_dcx.early_fatal(
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the cfi-type-id print option",
+                );
+            }
+            return PrintRequest {
+                kind: PrintKind::CfiTypeId,
+                out: OutFileName::Stdout,
+                arg: Some(path.to_string()),
+            };
+        }
+
         let (req, out) = split_out_file_name(&req);
 
         let kind = match PRINT_KINDS.iter().find(|&&(name, _)| name == req) {
@@ -1829,6 +1882,20 @@ fn collect_print_requests(
                     );
                 }
             }
+            Some((_, PrintKind::CfiGrammar)) => {
+                if unstable_opts.unstable_options {
+                    PrintKind::CfiGrammar
+                } else {
+                    early_dcx.early_fatal(
+                        "the `-Z unstable-options` flag must also be passed to \
+                         enable the cfi-grammar print option",
+                    );
+                }
+            }
+            Some((_, PrintKind::CfiTypeId)) => early_dcx.early_fatal(
+                "the cfi-type-id print option requires a function path \
+                 (e.g., `--print cfi-type-id=foo::bar`)",
+            ),
             Some(&(_, print_kind)) => print_kind,
             None => {
                 let prints =
@@ -1850,7 +1917,7 @@ fn collect_print_requests(
             }
         }
 
-        PrintRequest { kind, out }
+        PrintRequest { kind, out, arg: None }
     }));
 
     prints
@@ -2355,6 +2422,71 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         }
     }
 
+    if let Some(bits) = unstable_opts.symbol_mangling_hash_bits {
+        if cg.symbol_mangling_version != Some(SymbolManglingVersion::Hashed) {
+            early_dcx.early_fatal(
+                "`-Z symbol-mangling-hash-bits` requires `-C symbol-mangling-version=hashed`",
+            );
+        }
+        if !(8..=128).contains(&bits) {
+            early_dcx.early_fatal(format!(
+                "`-Z symbol-mangling-hash-bits` must be between 8 and 128, but it is {bits}"
+            ));
+        }
+    }
+
+    if unstable_opts.symbol_mangling_legacy_aliases
+        && cg.symbol_mangling_version != Some(SymbolManglingVersion::V0)
+    {
+        early_dcx.early_fatal(
+            "`-Z symbol-mangling-legacy-aliases` requires `-C symbol-mangling-version=v0`",
+        );
+    }
+
+    if unstable_opts.symbol_mangling_wide_legacy_hash
+        && !matches!(cg.symbol_mangling_version, None | Some(SymbolManglingVersion::Legacy))
+    {
+        early_dcx.early_fatal(
+            "`-Z symbol-mangling-wide-legacy-hash` requires `-C symbol-mangling-version=legacy`",
+        );
+    }
+
+    if let Some(cap) = unstable_opts.symbol_length_cap
+        && cap < 32
+    {
+        early_dcx
+            .early_fatal(format!("`-Z symbol-length-cap` must be at least 32, but it is {cap}"));
+    }
+
+    if let Some(cap) = unstable_opts.sanitizer_cfi_typeid_length_cap
+        && cap < 32
+    {
+        early_dcx.early_fatal(format!(
+            "`-Z sanitizer-cfi-typeid-length-cap` must be at least 32, but it is {cap}"
+        ));
+    }
+
+    if let Some(suffix) = &unstable_opts.symbol_mangling_suffix
+        && (suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'))
+    {
+        early_dcx.early_fatal(format!(
+            "`-Z symbol-mangling-suffix` must be made of ASCII alphanumerics and `_`, \
+            but it is `{suffix}`"
+        ));
+    }
+
+    if unstable_opts.crate_id_from_content.as_ref().is_some_and(|version| version.is_empty()) {
+        early_dcx.early_fatal("`-Z crate-id-from-content` requires a non-empty declared version");
+    }
+
+    if unstable_opts.symbol_mangling_hash_policy.is_some()
+        && cg.symbol_mangling_version != Some(SymbolManglingVersion::Hashed)
+    {
+        early_dcx.early_fatal(
+            "`-Z symbol-mangling-hash-policy` requires `-C symbol-mangling-version=hashed`",
+        );
+    }
+
     if cg.instrument_coverage != InstrumentCoverage::No {
         if cg.profile_generate.enabled() || cg.profile_use.is_some() {
             early_dcx.early_fatal(
@@ -2578,12 +2710,13 @@ fn parse_pretty(early_dcx: &EarlyDiagCtxt, unstable_opts: &UnstableOptions) -> O
         "mir" => Mir,
         "stable-mir" => StableMir,
         "mir-cfg" => MirCFG,
+        "cfi-typeids" => CfiTypeIds,
         name => early_dcx.early_fatal(format!(
             "argument to `unpretty` must be one of `normal`, `identified`, \
                             `expanded`, `expanded,identified`, `expanded,hygiene`, \
                             `ast-tree`, `ast-tree,expanded`, `hir`, `hir,identified`, \
-                            `hir,typed`, `hir-tree`, `thir-tree`, `thir-flat`, `mir`, `stable-mir`, or \
-                            `mir-cfg`; got {name}"
+                            `hir,typed`, `hir-tree`, `thir-tree`, `thir-flat`, `mir`, `stable-mir`, \
+                            `mir-cfg`, or `cfi-typeids`; got {name}"
         )),
     };
     debug!("got unpretty option: {first:?}");
@@ -2759,6 +2892,8 @@ pub enum PpMode {
     MirCFG,
     /// `-Zunpretty=stable-mir`
     StableMir,
+    /// `-Zunpretty=cfi-typeids`
+    CfiTypeIds,
 }
 
 impl PpMode {
@@ -2776,7 +2911,8 @@ pub fn needs_ast_map(&self) -> bool {
             | ThirFlat
             | Mir
             | MirCFG
-            | StableMir => true,
+            | StableMir
+            | CfiTypeIds => true,
         }
     }
     pub fn needs_hir(&self) -> bool {
@@ -2784,13 +2920,18 @@ pub fn needs_hir(&self) -> bool {
         match *self {
             Source(_) | AstTree | AstTreeExpanded => false,
 
-            Hir(_) | HirTree | ThirTree | ThirFlat | Mir | MirCFG | StableMir => true,
+            Hir(_) | HirTree | ThirTree | ThirFlat | Mir | MirCFG | StableMir | CfiTypeIds => {
+                true
+            }
         }
     }
 
     pub fn needs_analysis(&self) -> bool {
         use PpMode::*;
-        matches!(*self, Hir(PpHirMode::Typed) | Mir | StableMir | MirCFG | ThirTree | ThirFlat)
+        matches!(
+            *self,
+            Hir(PpHirMode::Typed) | Mir | StableMir | MirCFG | ThirTree | ThirFlat | CfiTypeIds
+        )
     }
 }
 
@@ -2820,12 +2961,13 @@ pub enum WasiExecModel {
 /// how the hash should be calculated when adding a new command-line argument.
 pub(crate) mod dep_tracking {
     use super::{
-        BranchProtection, CFGuard, CFProtection, CollapseMacroDebuginfo, CoverageOptions,
-        CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FunctionReturn,
-        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
-        Polonius, RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm,
-        SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
+        BranchProtection, CFGuard, CFProtection, CfiTraitObjectProjections, CfiTypeIdScheme,
+        CollapseMacroDebuginfo, CoverageOptions, CrateType, DebugInfo, DebugInfoCompression,
+        ErrorOutputType, FunctionReturn, InliningThreshold, InstrumentCoverage, InstrumentXRay,
+        KcfiTypeIdWidth, LinkerPluginLto, LocationDetail, LtoCli, NextSolverConfig, OomStrategy,
+        OptLevel, OutFileName, OutputType, OutputTypes, Polonius, RemapPathScopeComponents,
+        ResolveDocLinks, SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath,
+        SymbolHashPolicy, SymbolManglingVersion, WasiExecModel,
     };
     use crate::lint;
     use crate::utils::NativeLib;
@@ -2920,6 +3062,7 @@ fn hash(
         StackProtector,
         SwitchWithOptPath,
         SymbolManglingVersion,
+        SymbolHashPolicy,
         RemapPathScopeComponents,
         SourceFileHashAlgorithm,
         OutFileName,
@@ -2933,6 +3076,9 @@ fn hash(
         Polonius,
         InliningThreshold,
         FunctionReturn,
+        CfiTraitObjectProjections,
+        CfiTypeIdScheme,
+        KcfiTypeIdWidth,
     );
 
     impl<T1, T2> DepTrackingHash for (T1, T2)
@@ -3146,6 +3292,45 @@ fn default() -> Self {
     }
 }
 
+/// The different settings that the `-Zsanitizer-cfi-trait-object-projections` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub enum CfiTraitObjectProjections {
+    /// Encode the associated type projections of the principal trait of trait objects and of all
+    /// its supertraits.
+    #[default]
+    All,
+
+    /// Encode the associated type projections of the principal trait of trait objects only, so
+    /// adding associated types to its supertraits doesn't change the encodings.
+    Principal,
+
+    /// Encode trait objects without associated type projections.
+    None,
+}
+
+/// The different settings that the `-Zsanitizer-cfi-typeid-scheme` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub enum CfiTypeIdScheme {
+    /// Encode type metadata identifiers using the Itanium C++ ABI, as Clang does for most targets.
+    #[default]
+    Itanium,
+
+    /// Encode the type metadata identifiers of functions with the "C" calling convention using the
+    /// Microsoft C++ ABI, as Clang does for MSVC targets.
+    Msvc,
+}
+
+/// The different settings that the `-Zsanitizer-kcfi-typeid-width` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub enum KcfiTypeIdWidth {
+    /// Use the lower half of the xxHash64 of the type metadata identifier, as Clang does.
+    #[default]
+    Bits32,
+
+    /// Use the whole xxHash64 of the type metadata identifier.
+    Bits64,
+}
+
 /// The different settings that the `-Zfunction-return` flag can have.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
 pub enum FunctionReturn {
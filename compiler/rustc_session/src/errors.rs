@@ -125,6 +125,50 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag(session_cannot_enable_crt_static_linux)]
 pub(crate) struct CannotEnableCrtStaticLinux;
 
+#[derive(Diagnostic)]
+#[diag(session_cfi_clang_compat_requires_cfi)]
+pub(crate) struct CfiClangCompatRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_clang_compat_unsupported_option)]
+#[note]
+pub(crate) struct CfiClangCompatUnsupportedOption {
+    pub option: &'static str,
+    pub version: u32,
+    pub since: u32,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_clang_compat_unsupported_option_in_any_version)]
+#[note]
+pub(crate) struct CfiClangCompatUnsupportedOptionInAnyVersion {
+    pub option: &'static str,
+    pub version: u32,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_cross_language_conflicts_with)]
+pub(crate) struct CfiCrossLanguageConflictsWith {
+    pub flag: &'static str,
+    pub option: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_cross_language_requires_cfi)]
+pub(crate) struct CfiCrossLanguageRequiresCfi {
+    pub flag: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_cross_language_requires_linker_plugin_lto)]
+pub(crate) struct CfiCrossLanguageRequiresLinkerPluginLto {
+    pub flag: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_cfi_foreign_exceptions_requires_panic_unwind)]
+pub(crate) struct CfiForeignExceptionsRequiresPanicUnwind;
+
 #[derive(Diagnostic)]
 #[diag(session_sanitizer_cfi_requires_lto)]
 pub(crate) struct SanitizerCfiRequiresLto;
@@ -137,18 +181,105 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag(session_sanitizer_cfi_canonical_jump_tables_requires_cfi)]
 pub(crate) struct SanitizerCfiCanonicalJumpTablesRequiresCfi;
 
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_cxx_references_requires_cfi)]
+pub(crate) struct SanitizerCfiCxxReferencesRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_box_as_ptr_requires_cfi)]
+pub(crate) struct SanitizerCfiBoxAsPtrRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_c_integer_types_requires_cfi)]
+pub(crate) struct SanitizerCfiCIntegerTypesRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_encode_captures_requires_cfi)]
+pub(crate) struct SanitizerCfiEncodeCapturesRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_encode_fn_sigs_requires_cfi)]
+pub(crate) struct SanitizerCfiEncodeFnSigsRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_erase_const_requires_cfi)]
+pub(crate) struct SanitizerCfiEraseConstRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_erase_phantom_data_requires_cfi)]
+pub(crate) struct SanitizerCfiErasePhantomDataRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_erase_ref_mut_requires_cfi)]
+pub(crate) struct SanitizerCfiEraseRefMutRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_erase_return_types_requires_cfi)]
+pub(crate) struct SanitizerCfiEraseReturnTypesRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_generalize_arrays_requires_cfi)]
+pub(crate) struct SanitizerCfiGeneralizeArraysRequiresCfi;
+
 #[derive(Diagnostic)]
 #[diag(session_sanitizer_cfi_generalize_pointers_requires_cfi)]
 pub(crate) struct SanitizerCfiGeneralizePointersRequiresCfi;
 
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_impl_trait_bounds_requires_cfi)]
+pub(crate) struct SanitizerCfiImplTraitBoundsRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_member_function_pointers_requires_cfi)]
+pub(crate) struct SanitizerCfiMemberFunctionPointersRequiresCfi;
+
 #[derive(Diagnostic)]
 #[diag(session_sanitizer_cfi_normalize_integers_requires_cfi)]
 pub(crate) struct SanitizerCfiNormalizeIntegersRequiresCfi;
 
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_normalize_ptr_sized_integers_requires_cfi)]
+pub(crate) struct SanitizerCfiNormalizePtrSizedIntegersRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_refs_as_ptrs_requires_cfi)]
+pub(crate) struct SanitizerCfiRefsAsPtrsRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_trait_object_projections_requires_cfi)]
+pub(crate) struct SanitizerCfiTraitObjectProjectionsRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_typeid_length_cap_requires_cfi)]
+pub(crate) struct SanitizerCfiTypeIdLengthCapRequiresCfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_cfi_typeid_scheme_requires_cfi)]
+pub(crate) struct SanitizerCfiTypeIdSchemeRequiresCfi;
+
 #[derive(Diagnostic)]
 #[diag(session_sanitizer_kcfi_requires_panic_abort)]
 pub(crate) struct SanitizerKcfiRequiresPanicAbort;
 
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_kcfi_arity_requires_kcfi)]
+pub(crate) struct SanitizerKcfiArityRequiresKcfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_kcfi_arity_requires_x86_64)]
+pub(crate) struct SanitizerKcfiArityRequiresX86_64;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_kcfi_seed_requires_kcfi)]
+pub(crate) struct SanitizerKcfiSeedRequiresKcfi;
+
+#[derive(Diagnostic)]
+#[diag(session_sanitizer_kcfi_seed_incompatible_with_clang)]
+#[note]
+pub(crate) struct SanitizerKcfiSeedIncompatibleWithClang {
+    pub(crate) option: &'static str,
+}
+
 #[derive(Diagnostic)]
 #[diag(session_split_lto_unit_requires_lto)]
 pub(crate) struct SplitLtoUnitRequiresLto;
@@ -385,8 +385,16 @@ mod desc {
     pub const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
     pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `dataflow`, `hwaddress`, `kcfi`, `kernel-address`, `leak`, `memory`, `memtag`, `safestack`, `shadow-call-stack`, or `thread`";
     pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
+    pub const parse_sanitizer_kcfi_seed: &str = "a hexadecimal number (e.g., `0x1234abcd`)";
+    pub const parse_sanitizer_kcfi_typeid_width: &str = "either `32` or `64`";
+    pub const parse_sanitizer_cfi_trait_object_projections: &str =
+        "one of: `all`, `principal`, or `none`";
+    pub const parse_sanitizer_cfi_typeid_scheme: &str = "either `itanium` or `msvc`";
     pub const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
+    pub const parse_cfi_diff_crate_typeids: &str = "two comma-separated extern crate names";
+    pub const parse_cfi_diff_typeids: &str =
+        "two comma-separated function paths or type metadata identifiers";
     pub const parse_cfprotection: &str = "`none`|`no`|`n` (default), `branch`, `return`, or `full`|`yes`|`y` (equivalent to `branch` and `return`)";
     pub const parse_debuginfo: &str = "either an integer (0, 1, 2), `none`, `line-directives-only`, `line-tables-only`, `limited`, or `full`";
     pub const parse_debuginfo_compression: &str = "one of `none`, `zlib`, or `zstd`";
@@ -411,6 +419,7 @@ mod desc {
     pub const parse_merge_functions: &str = "one of: `disabled`, `trampolines`, or `aliases`";
     pub const parse_symbol_mangling_version: &str =
         "one of: `legacy`, `v0` (RFC 2603), or `hashed`";
+    pub const parse_symbol_hash_policy: &str = "either `all` or `private`";
     pub const parse_src_file_hash: &str = "either `md5` or `sha1`";
     pub const parse_relocation_model: &str =
         "one of supported relocation models (`rustc --print relocation-models`)";
@@ -752,6 +761,54 @@ pub(crate) fn parse_sanitizers(slot: &mut SanitizerSet, v: Option<&str>) -> bool
         }
     }
 
+    pub(crate) fn parse_sanitizer_kcfi_seed(slot: &mut Option<u64>, v: Option<&str>) -> bool {
+        match v {
+            Some(s) => {
+                let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+                *slot = u64::from_str_radix(s, 16).ok();
+                slot.is_some()
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn parse_sanitizer_kcfi_typeid_width(
+        slot: &mut KcfiTypeIdWidth,
+        v: Option<&str>,
+    ) -> bool {
+        match v {
+            Some("32") => *slot = KcfiTypeIdWidth::Bits32,
+            Some("64") => *slot = KcfiTypeIdWidth::Bits64,
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn parse_sanitizer_cfi_trait_object_projections(
+        slot: &mut CfiTraitObjectProjections,
+        v: Option<&str>,
+    ) -> bool {
+        match v {
+            Some("all") => *slot = CfiTraitObjectProjections::All,
+            Some("principal") => *slot = CfiTraitObjectProjections::Principal,
+            Some("none") => *slot = CfiTraitObjectProjections::None,
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn parse_sanitizer_cfi_typeid_scheme(
+        slot: &mut CfiTypeIdScheme,
+        v: Option<&str>,
+    ) -> bool {
+        match v {
+            Some("itanium") => *slot = CfiTypeIdScheme::Itanium,
+            Some("msvc") => *slot = CfiTypeIdScheme::Msvc,
+            _ => return false,
+        }
+        true
+    }
+
     pub(crate) fn parse_sanitizer_memory_track_origins(slot: &mut usize, v: Option<&str>) -> bool {
         match v {
             Some("2") | None => {
@@ -798,6 +855,26 @@ pub(crate) fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
         true
     }
 
+    pub(crate) fn parse_cfi_diff_crate_typeids(
+        slot: &mut Option<(String, String)>,
+        v: Option<&str>,
+    ) -> bool {
+        parse_cfi_diff_typeids(slot, v)
+    }
+
+    pub(crate) fn parse_cfi_diff_typeids(
+        slot: &mut Option<(String, String)>,
+        v: Option<&str>,
+    ) -> bool {
+        match v.and_then(|s| s.split_once(',')) {
+            Some((lhs, rhs)) if !lhs.is_empty() && !rhs.is_empty() && !rhs.contains(',') => {
+                *slot = Some((lhs.to_string(), rhs.to_string()));
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub(crate) fn parse_cfprotection(slot: &mut CFProtection, v: Option<&str>) -> bool {
         if v.is_some() {
             let mut bool_arg = None;
@@ -1217,6 +1294,18 @@ pub(crate) fn parse_symbol_mangling_version(
         true
     }
 
+    pub(crate) fn parse_symbol_hash_policy(
+        slot: &mut Option<SymbolHashPolicy>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("all") => Some(SymbolHashPolicy::All),
+            Some("private") => Some(SymbolHashPolicy::Private),
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_src_file_hash(
         slot: &mut Option<SourceFileHashAlgorithm>,
         v: Option<&str>,
@@ -1573,6 +1662,60 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "set options for branch target identification and pointer authentication on AArch64"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
+    cfi_alias_set_report: bool = (false, parse_bool, [UNTRACKED],
+        "print the sets of functions that share a CFI type metadata identifier, largest first \
+        (default: no)"),
+    cfi_alias_set_threshold: usize = (1000, parse_number, [UNTRACKED],
+        "the number of functions sharing a CFI type metadata identifier above which the \
+        `large_cfi_alias_sets` lint is emitted (default: 1000)"),
+    cfi_assert_consistency: bool = (false, parse_bool, [TRACKED],
+        "check that every function called directly that can also be called through a function \
+        pointer is defined with the CFI type metadata identifier of its function pointer type, \
+        and ICE with a report of both otherwise (default: no)"),
+    cfi_clang_compat: Option<u32> = (None, parse_opt_number, [UNTRACKED],
+        "reject the CFI and KCFI options whose type metadata identifiers the specified Clang major \
+        version can't compute (e.g., `-Zcfi-clang-compat=16`)"),
+    cfi_compression_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print statistics about the compression of the CFI type metadata identifiers computed \
+        (i.e., their lengths with and without substitutions, and the largest ones) (default: no)"),
+    cfi_cross_language: bool = (false, parse_bool, [TRACKED],
+        "enable all the CFI and KCFI options required for cross-language CFI and KCFI support with C \
+        and C++ code (i.e., `-Zsanitizer-cfi-normalize-integers`) (default: no)"),
+    cfi_diff_crate_typeids: Option<(String, String)> = (None, parse_cfi_diff_crate_typeids, [UNTRACKED],
+        "print the CFI type metadata identifiers of the exported non-generic functions that were \
+        changed, added, or removed between two versions of a crate, passed as the extern crates \
+        with the specified names (e.g., `-Zcfi-diff-crate-typeids=old,new`)"),
+    cfi_diff_typeids: Option<(String, String)> = (None, parse_cfi_diff_typeids, [UNTRACKED],
+        "print the CFI type metadata identifiers of two functions (or type metadata identifiers) \
+        and explain where they first differ (e.g., `-Zcfi-diff-typeids=foo,bar`)"),
+    cfi_dump_typeids: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON map of the symbol of every mono item to its CFI and KCFI type metadata \
+        identifiers to the specified file"),
+    cfi_eager_typeids: bool = (false, parse_bool, [TRACKED],
+        "give CFI type metadata identifiers to all functions, rather than only to the ones that \
+        may be called indirectly (default: no)"),
+    cfi_encoding_profile: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "read overrides of the CFI encodings of user-defined types and extern types (e.g., of \
+        other crates) from a JSON file mapping their paths to encodings"),
+    cfi_export_typeids: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the generalized CFI type metadata identifier and KCFI type metadata identifier of \
+        every exported `extern \"C\"` function to the specified file, one function per line"),
+    cfi_foreign_exceptions: bool = (false, parse_bool, [TRACKED],
+        "enable the CFI options required for propagating foreign (e.g., C++) exceptions across \
+        `extern \"C-unwind\"` functions checked with CFI on both sides of the language boundary \
+        (i.e., those of `-Zcfi-cross-language`), and check that unwinding is enabled (default: no)"),
+    cfi_fragment_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print statistics about the encodings of types computed once and spliced into the CFI type \
+        metadata identifiers they are components of (default: no)"),
+    cfi_trace_transform: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "log the type transformations applied when computing the CFI type metadata identifiers of \
+        the function with the specified path (logged at the `info` level, e.g., with \
+        `RUSTC_LOG=rustc_symbol_mangling::typeid=info`)"),
+    cfi_uncompressed_typeids: bool = (false, parse_bool, [UNTRACKED],
+        "print the CFI type metadata identifiers of `--print cfi-type-id`, \
+        `-Zunpretty=cfi-typeids`, `-Zcfi-diff-typeids`, and `-Zcfi-diff-crate-typeids` without \
+        compression (i.e., with every repeated component encoded in full instead of as a \
+        substitution) (default: no)"),
     check_cfg_all_expected: bool = (false, parse_bool, [UNTRACKED],
         "show all expected values in check-cfg diagnostics (default: no)"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1586,6 +1729,10 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "control details of coverage instrumentation"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
+    crate_id_from_content: Option<String> = (None, parse_opt_string, [TRACKED],
+        "derive the crate disambiguator from the crate root's source and the given declared \
+        version instead of `-C metadata`, so that identical sources get identical symbols \
+        (WARNING: distinct crates with the same name, version and root file will collide)"),
     cross_crate_inline_threshold: InliningThreshold = (InliningThreshold::Sometimes(100), parse_inlining_threshold, [TRACKED],
         "threshold to allow cross crate inlining of functions"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1640,6 +1787,11 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "generate Windows EHCont Guard tables"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    emit_symbol_map: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a map of the symbol of every mono item of the crate to its full symbol (if capped \
+        with `-Z symbol-length-cap`), demangled symbol, def path, codegen unit, version (if \
+        assigned by a version script passed to the linker) and CFI or KCFI type metadata \
+        identifier to the specified file"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
         "emit the bc module with thin LTO info (default: yes)"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
@@ -1738,6 +1890,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         (space separated)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
+    mangle_internal_symbols: bool = (false, parse_bool, [TRACKED],
+        "mangle the symbols internal to the standard library and the allocator shim (like \
+        `__rust_alloc`) with the disambiguator of `core`, when compiling `core` (default: no)"),
     maximal_hir_to_mir_coverage: bool = (false, parse_bool, [TRACKED],
         "save as much information as possible about the correspondence between MIR and HIR \
         as source scopes (default: no)"),
@@ -1835,6 +1990,12 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "print the result of the monomorphization collection pass. \
          Value `lazy` means to use normal collection; `eager` means to collect all items.
          Note that this overwrites the effect `-Clink-dead-code` has on collection!"),
+    print_mono_items_matching: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the mono items whose paths match the given glob pattern (e.g., `alloc::vec::*`), \
+        with their symbols and codegen units"),
+    print_symbol_explanations: bool = (false, parse_bool, [UNTRACKED],
+        "print the v0 symbol of each mangled item of the crate, with each path, generic argument \
+        and self type in it underlined and described (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     print_vtable_sizes: bool = (false, parse_bool, [UNTRACKED],
@@ -1872,14 +2033,67 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
 written to standard error output)"),
     sanitizer: SanitizerSet = (SanitizerSet::empty(), parse_sanitizers, [TRACKED],
         "use a sanitizer"),
+    sanitizer_cfi_box_as_ptr: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding `Box<T>` as `*mut T` in functions with the C calling convention \
+        (default: no)"),
+    sanitizer_cfi_c_integer_types: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding integer types as C integer types in functions with the C calling \
+        convention (default: no)"),
     sanitizer_cfi_canonical_jump_tables: Option<bool> = (Some(true), parse_opt_bool, [TRACKED],
         "enable canonical jump tables (default: yes)"),
+    sanitizer_cfi_cxx_references: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding references as C++ references (default: no)"),
+    sanitizer_cfi_encode_captures: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding the captures of coroutines and coroutine closures (default: no)"),
+    sanitizer_cfi_encode_fn_sigs: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding functions from their declared signatures instead of their ABI \
+        (default: no)"),
+    sanitizer_cfi_erase_const: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable erasing the const qualifier of pointee types (default: no)"),
+    sanitizer_cfi_erase_phantom_data: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable erasing the `PhantomData` generic arguments of types (default: no)"),
+    sanitizer_cfi_erase_ref_mut: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable erasing the mutability of reference types (default: no)"),
+    sanitizer_cfi_erase_return_types: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable erasing the return types of functions (default: no)"),
+    sanitizer_cfi_generalize_arrays: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable generalizing array types in functions with the C calling convention (default: no)"),
     sanitizer_cfi_generalize_pointers: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable generalizing pointer types (default: no)"),
+    sanitizer_cfi_impl_trait_bounds: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding `impl Trait` types in function signatures via their bounds (default: no)"),
+    sanitizer_cfi_member_function_pointers: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding trait methods called through trait objects as pointer to member \
+        function types (default: no)"),
     sanitizer_cfi_normalize_integers: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable normalizing integer types (default: no)"),
+    sanitizer_cfi_normalize_ptr_sized_integers: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable normalizing pointer-sized integer types only (default: no)"),
+    sanitizer_cfi_refs_as_ptrs: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding references as raw pointers in functions with the C calling convention \
+        (default: no)"),
+    sanitizer_cfi_trait_object_projections: CfiTraitObjectProjections =
+        (CfiTraitObjectProjections::default(), parse_sanitizer_cfi_trait_object_projections,
+        [TRACKED],
+        "associated type projections encoded in trait objects: `all` (default), `principal` \
+        (those of the principal trait only), or `none`"),
+    sanitizer_cfi_typeid_length_cap: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the maximum length (at least 32) of type metadata identifiers, whose tails are replaced \
+        with a hash of the full type metadata identifier when they are longer (default: no \
+        maximum length)"),
+    sanitizer_cfi_typeid_scheme: CfiTypeIdScheme = (CfiTypeIdScheme::default(),
+        parse_sanitizer_cfi_typeid_scheme, [TRACKED],
+        "mangling scheme of CFI type metadata identifiers: `itanium` (default) or `msvc`"),
     sanitizer_dataflow_abilist: Vec<String> = (Vec::new(), parse_comma_list, [TRACKED],
         "additional ABI list files that control how shadow parameters are passed (comma separated)"),
+    sanitizer_kcfi_arity: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "enable encoding the number of parameters of functions into their KCFI preambles (default: no)"),
+    sanitizer_kcfi_seed: Option<u64> = (None, parse_sanitizer_kcfi_seed, [TRACKED],
+        "seed KCFI type metadata identifiers are hashed with (default: the target's, or none)"),
+    sanitizer_kcfi_typeid_width: KcfiTypeIdWidth = (KcfiTypeIdWidth::default(),
+        parse_sanitizer_kcfi_typeid_width, [TRACKED],
+        "width of KCFI type metadata identifiers: `32` (default) or `64` (not supported by the \
+        LLVM backend)"),
     sanitizer_memory_track_origins: usize = (0, parse_sanitizer_memory_track_origins, [TRACKED],
         "enable origins tracking in MemorySanitizer"),
     sanitizer_recover: SanitizerSet = (SanitizerSet::empty(), parse_sanitizers, [TRACKED],
@@ -1940,6 +2154,26 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "prefer dynamic linking to static linking for staticlibs (default: no)"),
     strict_init_checks: bool = (false, parse_bool, [TRACKED],
         "control if mem::uninitialized and mem::zeroed panic on more UB"),
+    symbol_length_cap: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the maximum length (at least 32) of mangled symbols, whose tails are replaced with a \
+        hash of the full symbol when they are longer, see `-Z emit-symbol-map` (default: no \
+        maximum length)"),
+    symbol_mangling_hash_bits: Option<u32> = (None, parse_opt_number, [TRACKED],
+        "the number of bits (between 8 and 128) of the hashes of the symbols mangled with \
+        `-C symbol-mangling-version=hashed` (default: 64)"),
+    symbol_mangling_hash_policy: Option<SymbolHashPolicy> = (None, parse_symbol_hash_policy,
+        [TRACKED], "which symbols to hash with `-C symbol-mangling-version=hashed`: `all` \
+        (default), or `private` to keep the symbols of public items unhashed"),
+    symbol_mangling_legacy_aliases: bool = (false, parse_bool, [TRACKED],
+        "also emit every symbol mangled with `-C symbol-mangling-version=v0` under its legacy \
+        symbol, as an alias, for tools that only understand legacy symbols (default: no)"),
+    symbol_mangling_suffix: Option<String> = (None, parse_opt_string, [TRACKED],
+        "append `.` and the specified suffix (of ASCII alphanumerics and `_`) to every mangled \
+        symbol of the crate, to keep its symbols apart from those of other builds"),
+    symbol_mangling_wide_legacy_hash: bool = (false, parse_bool, [TRACKED],
+        "use 128-bit hashes instead of 64-bit ones in the symbols mangled with \
+        `-C symbol-mangling-version=legacy`, to make collisions unlikely in very large \
+        programs (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::teach` instead of this field")]
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
@@ -2033,6 +2267,9 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
     #[rustc_lint_opt_deny_field_access("use `Session::verify_llvm_ir` instead of this field")]
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
+    verify_typeid_stability: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "compare the CFI type metadata identifiers of the functions in the crate against the \
+        snapshot in the specified file and report any difference"),
     virtual_function_elimination: bool = (false, parse_bool, [TRACKED],
         "enables dead virtual function elimination optimization. \
         Requires `-Clto[=[fat,yes]]`"),
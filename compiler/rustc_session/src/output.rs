@@ -87,6 +87,33 @@ pub fn find_crate_name(sess: &Session, attrs: &[ast::Attribute]) -> Symbol {
     Symbol::intern("rust_out")
 }
 
+/// Returns the `-C metadata` arguments the [`StableCrateId`] of the local crate is computed from.
+///
+/// With `-Z crate-id-from-content`, these are replaced by the hash of the crate root's source and
+/// the declared version, so that machines passing different `-C metadata` still agree on the
+/// crate's symbols. Nothing else about the crate (its other source files, `--cfg`s, or
+/// dependencies) contributes to it, so two such crates that only differ there get the same ID.
+///
+/// [`StableCrateId`]: rustc_span::def_id::StableCrateId
+pub fn stable_crate_id_metadata(sess: &Session) -> Vec<String> {
+    let Some(version) = &sess.opts.unstable_opts.crate_id_from_content else {
+        return sess.opts.cg.metadata.clone();
+    };
+    // The crate root (including stdin) has been loaded by the parser at this point, and its hash
+    // only depends on its contents (and `-Z src-hash-algorithm`), not on where it is on disk. An
+    // empty hash would give every crate with the same name and version the same ID, so its absence
+    // is a bug.
+    let source_name = sess.io.input.source_name();
+    let Some(root) = sess.source_map().get_source_file(&source_name) else {
+        sess.dcx().bug(format!(
+            "`-Z crate-id-from-content` requires the crate root `{}` to be loaded",
+            source_name.prefer_local()
+        ));
+    };
+    let src_hash: String = root.src_hash.hash_bytes().iter().map(|b| format!("{b:02x}")).collect();
+    vec![format!("content:{src_hash}"), format!("version:{version}")]
+}
+
 pub fn validate_crate_name(sess: &Session, s: Symbol, sp: Option<Span>) {
     let mut err_count = 0;
     {
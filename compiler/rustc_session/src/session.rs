@@ -364,18 +364,93 @@ pub fn is_sanitizer_cfi_canonical_jump_tables_enabled(&self) -> bool {
         self.opts.unstable_opts.sanitizer_cfi_canonical_jump_tables == Some(true)
     }
 
+    pub fn is_sanitizer_cfi_box_as_ptr_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_box_as_ptr == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_c_integer_types_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_c_integer_types == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_cxx_references_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_cxx_references == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_encode_captures_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_encode_captures == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_encode_fn_sigs_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_encode_fn_sigs == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_erase_const_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_erase_const == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_erase_phantom_data_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_erase_phantom_data == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_erase_ref_mut_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_erase_ref_mut == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_erase_return_types_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_erase_return_types == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_generalize_arrays_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_generalize_arrays == Some(true)
+    }
+
     pub fn is_sanitizer_cfi_generalize_pointers_enabled(&self) -> bool {
         self.opts.unstable_opts.sanitizer_cfi_generalize_pointers == Some(true)
     }
 
+    pub fn is_sanitizer_cfi_impl_trait_bounds_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_impl_trait_bounds == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_member_function_pointers_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_member_function_pointers == Some(true)
+    }
+
     pub fn is_sanitizer_cfi_normalize_integers_enabled(&self) -> bool {
         self.opts.unstable_opts.sanitizer_cfi_normalize_integers == Some(true)
+            || self.is_cfi_cross_language_enabled()
+    }
+
+    pub fn is_sanitizer_cfi_normalize_ptr_sized_integers_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_normalize_ptr_sized_integers == Some(true)
+    }
+
+    pub fn is_sanitizer_cfi_refs_as_ptrs_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_cfi_refs_as_ptrs == Some(true)
+    }
+
+    pub fn is_cfi_cross_language_enabled(&self) -> bool {
+        self.opts.unstable_opts.cfi_cross_language || self.is_cfi_foreign_exceptions_enabled()
+    }
+
+    pub fn is_cfi_foreign_exceptions_enabled(&self) -> bool {
+        self.opts.unstable_opts.cfi_foreign_exceptions
     }
 
     pub fn is_sanitizer_kcfi_enabled(&self) -> bool {
         self.opts.unstable_opts.sanitizer.contains(SanitizerSet::KCFI)
     }
 
+    pub fn is_sanitizer_kcfi_arity_enabled(&self) -> bool {
+        self.opts.unstable_opts.sanitizer_kcfi_arity == Some(true)
+    }
+
+    /// Returns the seed KCFI type metadata identifiers are hashed with, if any. The
+    /// `-Zsanitizer-kcfi-seed` option takes precedence over the target's `kcfi-seed`.
+    pub fn sanitizer_kcfi_seed(&self) -> Option<u64> {
+        self.opts.unstable_opts.sanitizer_kcfi_seed.or(self.target.kcfi_seed)
+    }
+
     pub fn is_split_lto_unit_enabled(&self) -> bool {
         self.opts.unstable_opts.split_lto_unit == Some(true)
     }
@@ -1228,6 +1303,42 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         sess.dcx().emit_err(errors::SanitizerCfiRequiresSingleCodegenUnit);
     }
 
+    // KCFI arity encoding requires KCFI.
+    if sess.is_sanitizer_kcfi_arity_enabled() && !sess.is_sanitizer_kcfi_enabled() {
+        sess.dcx().emit_err(errors::SanitizerKcfiArityRequiresKcfi);
+    }
+
+    // KCFI arity encoding is only implemented for the preambles of x86-64 functions.
+    if sess.is_sanitizer_kcfi_arity_enabled() && sess.target.arch != "x86_64" {
+        sess.dcx().emit_err(errors::SanitizerKcfiArityRequiresX86_64);
+    }
+
+    // KCFI seeds require KCFI.
+    if sess.opts.unstable_opts.sanitizer_kcfi_seed.is_some() && !sess.is_sanitizer_kcfi_enabled() {
+        sess.dcx().emit_err(errors::SanitizerKcfiSeedRequiresKcfi);
+    }
+
+    // Clang has no KCFI seed, so a seed makes the KCFI type metadata identifiers incompatible with
+    // the ones of Clang-built code, which the cross-language options are for.
+    if sess.is_sanitizer_kcfi_enabled()
+        && sess.sanitizer_kcfi_seed().is_some_and(|seed| seed != 0)
+    {
+        let options = [
+            (sess.is_cfi_cross_language_enabled(), "-Zcfi-cross-language"),
+            (
+                sess.is_sanitizer_cfi_normalize_integers_enabled(),
+                "-Zsanitizer-cfi-normalize-integers",
+            ),
+            (
+                sess.is_sanitizer_cfi_generalize_pointers_enabled(),
+                "-Zsanitizer-cfi-generalize-pointers",
+            ),
+        ];
+        if let Some(&(_, option)) = options.iter().find(|(enabled, _)| *enabled) {
+            sess.dcx().emit_warn(errors::SanitizerKcfiSeedIncompatibleWithClang { option });
+        }
+    }
+
     // LLVM CFI is incompatible with LLVM KCFI.
     if sess.is_sanitizer_cfi_enabled() && sess.is_sanitizer_kcfi_enabled() {
         sess.dcx().emit_err(errors::CannotMixAndMatchSanitizers {
@@ -1243,6 +1354,106 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // LLVM CFI C++ reference encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_cxx_references_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiCxxReferencesRequiresCfi);
+        }
+    }
+
+    // LLVM CFI capture encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_encode_captures_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiEncodeCapturesRequiresCfi);
+        }
+    }
+
+    // LLVM CFI signature-based encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_encode_fn_sigs_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiEncodeFnSigsRequiresCfi);
+        }
+    }
+
+    // LLVM CFI Box encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_box_as_ptr_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiBoxAsPtrRequiresCfi);
+        }
+    }
+
+    // LLVM CFI C integer type encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_c_integer_types_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiCIntegerTypesRequiresCfi);
+        }
+    }
+
+    // LLVM CFI const erasure requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_erase_const_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiEraseConstRequiresCfi);
+        }
+    }
+
+    // LLVM CFI PhantomData erasure requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_erase_phantom_data_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiErasePhantomDataRequiresCfi);
+        }
+    }
+
+    // LLVM CFI reference mutability erasure requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_erase_ref_mut_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiEraseRefMutRequiresCfi);
+        }
+    }
+
+    // LLVM CFI return type erasure requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_erase_return_types_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiEraseReturnTypesRequiresCfi);
+        }
+    }
+
+    // LLVM CFI array generalization requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_generalize_arrays_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiGeneralizeArraysRequiresCfi);
+        }
+    }
+
+    // LLVM CFI reference encoding as raw pointers requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_refs_as_ptrs_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiRefsAsPtrsRequiresCfi);
+        }
+    }
+
+    // LLVM CFI trait object projection settings require CFI or KCFI.
+    if sess.opts.unstable_opts.sanitizer_cfi_trait_object_projections
+        != config::CfiTraitObjectProjections::All
+    {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiTraitObjectProjectionsRequiresCfi);
+        }
+    }
+
+    // LLVM CFI type metadata identifier length caps require CFI or KCFI.
+    if sess.opts.unstable_opts.sanitizer_cfi_typeid_length_cap.is_some() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiTypeIdLengthCapRequiresCfi);
+        }
+    }
+
+    // LLVM CFI type metadata identifier schemes require CFI or KCFI.
+    if sess.opts.unstable_opts.sanitizer_cfi_typeid_scheme != config::CfiTypeIdScheme::Itanium {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiTypeIdSchemeRequiresCfi);
+        }
+    }
+
     // LLVM CFI pointer generalization requires CFI or KCFI.
     if sess.is_sanitizer_cfi_generalize_pointers_enabled() {
         if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
@@ -1250,13 +1461,109 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // LLVM CFI `impl Trait` bound encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_impl_trait_bounds_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiImplTraitBoundsRequiresCfi);
+        }
+    }
+
+    // LLVM CFI member function pointer encoding requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_member_function_pointers_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiMemberFunctionPointersRequiresCfi);
+        }
+    }
+
     // LLVM CFI integer normalization requires CFI or KCFI.
-    if sess.is_sanitizer_cfi_normalize_integers_enabled() {
+    if sess.opts.unstable_opts.sanitizer_cfi_normalize_integers == Some(true) {
         if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
             sess.dcx().emit_err(errors::SanitizerCfiNormalizeIntegersRequiresCfi);
         }
     }
 
+    // LLVM CFI pointer-sized integer normalization requires CFI or KCFI.
+    if sess.is_sanitizer_cfi_normalize_ptr_sized_integers_enabled() {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::SanitizerCfiNormalizePtrSizedIntegersRequiresCfi);
+        }
+    }
+
+    // Cross-language LLVM CFI (also enabled by `-Zcfi-foreign-exceptions`) requires CFI or KCFI,
+    // all the options it bundles, and (for CFI) linker plugin LTO, as the C and C++ code must be
+    // part of the same LTO unit.
+    if sess.is_cfi_cross_language_enabled() {
+        let flag = if sess.opts.unstable_opts.cfi_cross_language {
+            "-Zcfi-cross-language"
+        } else {
+            "-Zcfi-foreign-exceptions"
+        };
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::CfiCrossLanguageRequiresCfi { flag });
+        }
+        if sess.opts.unstable_opts.sanitizer_cfi_normalize_integers == Some(false) {
+            sess.dcx().emit_err(errors::CfiCrossLanguageConflictsWith {
+                flag,
+                option: "-Zsanitizer-cfi-normalize-integers=no",
+            });
+        }
+        if sess.is_sanitizer_cfi_enabled() && !sess.opts.cg.linker_plugin_lto.enabled() {
+            sess.dcx().emit_err(errors::CfiCrossLanguageRequiresLinkerPluginLto { flag });
+        }
+    }
+
+    // Propagating foreign exceptions across functions checked with LLVM CFI requires unwinding
+    // (and so can't be done with KCFI, which requires panic=abort).
+    if sess.is_cfi_foreign_exceptions_enabled() && sess.panic_strategy() != PanicStrategy::Unwind {
+        sess.dcx().emit_err(errors::CfiForeignExceptionsRequiresPanicUnwind);
+    }
+
+    // The Clang version the type metadata identifiers must be compatible with (i.e., computable by)
+    // requires CFI or KCFI, and rejects the options Clang doesn't support in that version.
+    if let Some(version) = sess.opts.unstable_opts.cfi_clang_compat {
+        if !(sess.is_sanitizer_cfi_enabled() || sess.is_sanitizer_kcfi_enabled()) {
+            sess.dcx().emit_err(errors::CfiClangCompatRequiresCfi);
+        }
+        let kcfi_typeid_width = sess.opts.unstable_opts.sanitizer_kcfi_typeid_width;
+        // The options and the Clang versions they are supported since, if any
+        let options = [
+            (sess.is_sanitizer_kcfi_enabled(), "-Zsanitizer=kcfi", Some(16)),
+            (
+                sess.is_sanitizer_cfi_normalize_integers_enabled(),
+                "-Zsanitizer-cfi-normalize-integers",
+                Some(17),
+            ),
+            (sess.is_sanitizer_kcfi_arity_enabled(), "-Zsanitizer-kcfi-arity", Some(21)),
+            (sess.sanitizer_kcfi_seed().is_some(), "-Zsanitizer-kcfi-seed", None),
+            (
+                kcfi_typeid_width == config::KcfiTypeIdWidth::Bits64,
+                "-Zsanitizer-kcfi-typeid-width=64",
+                None,
+            ),
+        ];
+        for (enabled, option, since) in options {
+            if !enabled {
+                continue;
+            }
+            match since {
+                Some(since) if version >= since => {}
+                Some(since) => {
+                    sess.dcx().emit_err(errors::CfiClangCompatUnsupportedOption {
+                        option,
+                        version,
+                        since,
+                    });
+                }
+                None => {
+                    sess.dcx().emit_err(errors::CfiClangCompatUnsupportedOptionInAnyVersion {
+                        option,
+                        version,
+                    });
+                }
+            }
+        }
+    }
+
     // LTO unit splitting requires LTO.
     if sess.is_split_lto_unit_enabled()
         && !(sess.lto() == config::Lto::Fat
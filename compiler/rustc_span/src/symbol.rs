@@ -518,6 +518,7 @@
         cfg_version,
         cfi,
         cfi_encoding,
+        cfi_encodings,
         char,
         client,
         clippy,
@@ -1192,6 +1193,7 @@
         mul_assign,
         mul_with_overflow,
         multiple_supertrait_upcastable,
+        multivalue,
         must_not_suspend,
         must_use,
         mut_ref,
@@ -1620,6 +1622,8 @@
         rustc_test_marker,
         rustc_then_this_would_need,
         rustc_trivial_field_reads,
+        rustc_ty_name_encoding,
+        rustc_unhashed_symbol,
         rustc_unsafe_specialization_marker,
         rustc_variance,
         rustc_variance_of_opaques,
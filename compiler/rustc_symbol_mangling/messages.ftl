@@ -0,0 +1,48 @@
+symbol_mangling_cfi_encoding_attr = `cfi_encoding` attribute of the type here
+
+symbol_mangling_cfi_encodings_attr_conflict =
+    `{$ty_path}` is registered with a CFI encoding other than the one of its `cfi_encoding` attribute
+    .label = registered as `{$encoding}` here
+    .note = the `cfi_encoding` attribute of the type takes precedence
+
+symbol_mangling_cfi_sanitizer_note =
+    type metadata identifiers are required by `-Zsanitizer=cfi` and `-Zsanitizer=kcfi`
+
+symbol_mangling_conflicting_cfi_encodings =
+    conflicting CFI encodings for `{$ty_path}`
+    .note = registered as `{$encoding}` by `{$krate}`, but as `{$prev_encoding}` by `{$prev_krate}`, which is used
+
+symbol_mangling_default_cfi_encoding = use the default encoding as a starting point
+
+symbol_mangling_intrinsic_without_fallback_body =
+    cannot compute the type metadata identifier of intrinsic `{$name}`
+    .note = intrinsics without fallback bodies are never codegened as functions, so they can't be called indirectly
+
+symbol_mangling_invalid_cfi_encoding =
+    invalid `cfi_encoding` for `{$ty}`
+    .label = {$reason}
+
+symbol_mangling_invalid_cfi_encoding_profile =
+    invalid CFI encoding profile `{$path}`: {$reason}
+
+symbol_mangling_invalid_cfi_encoding_ty = `{$ty}` defined here
+
+symbol_mangling_invalid_cfi_encodings_entry =
+    invalid `cfi_encodings` entry
+    .label = {$reason}
+
+symbol_mangling_invalid_structured_cfi_encoding =
+    invalid `cfi_encoding` for `{$ty}`
+    .label = {$reason}
+
+symbol_mangling_unresolved_cfi_encoding_profile_path =
+    `{$ty_path}` in CFI encoding profile `{$path}` doesn't name a user-defined type or extern type
+    .note = the entry is ignored
+
+symbol_mangling_unresolved_cfi_encodings_path =
+    `{$ty_path}` in `cfi_encodings` doesn't name a user-defined type or extern type
+
+symbol_mangling_unsupported_const_param =
+    type `{$ty}` cannot be used as a const parameter with CFI enabled
+    .label = const parameter declared here
+    .note = `{$item}` is instantiated with a value of type `{$ty}`, which cannot be encoded in type metadata identifiers
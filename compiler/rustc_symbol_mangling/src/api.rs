@@ -0,0 +1,129 @@
+//! Symbol names and type metadata identifiers of arbitrary instances, for tools built on
+//! `rustc_driver` (e.g., Miri-like interpreters, kernel build integration, or ABI analyzers).
+//!
+//! These functions compute exactly what the compiler itself emits for an instance in the current
+//! session, so that such tools don't have to copy the mangling and encoding logic or reach into the
+//! other (unstable and frequently reorganized) modules of this crate.
+//!
+//! # Stability
+//!
+//! Like the rest of the compiler, this module is only available with `#![feature(rustc_private)]`
+//! and is not covered by the Rust stability guarantees. It is kept stable in the following sense,
+//! however:
+//!
+//! - The functions and re-exports in this module are not renamed or removed, and their signatures
+//!   only change when the compiler types they take (e.g., `TyCtxt` or `Instance`) do. Anything that
+//!   is to be removed is deprecated for at least one release first.
+//! - New behavior is added as new functions or new [`TypeIdOptions`] flags, not as new parameters
+//!   of existing functions.
+//! - The results are those of the compiler, and change when its encodings do: symbol names follow
+//!   the mangling options of the session (e.g., `-C symbol-mangling-version`), and type metadata
+//!   identifiers follow the encoding, whose changes are checked with
+//!   `-Zverify-typeid-stability`.
+
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_session::config::SymbolManglingVersion;
+use rustc_session::Session;
+
+pub use crate::typeid::kcfi::KcfiTypeId;
+pub use crate::typeid::provider::{ItaniumTypeIdProvider, TypeIdProvider};
+pub use crate::typeid::TypeIdOptions;
+
+/// Returns the symbol name of `instance`, as referred to from the local crate (i.e., the symbol it
+/// is defined with, whether it is defined in the local crate or an upstream one).
+pub fn symbol_name_for_instance<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> String {
+    tcx.symbol_name(instance).name.to_string()
+}
+
+/// Returns whether `symbol` is the symbol name of `instance` with `mangling_version` (legacy, v0 or
+/// hashed), as referred to from the local crate, whichever mangling version the crate responsible
+/// for it was compiled with.
+///
+/// The symbol must be exactly the one the compiler computes, including the length cap of
+/// `-Z symbol-length-cap`, but without any decoration by the target (e.g., the `_` prefix on
+/// 32-bit x86 Windows) or suffix added by LLVM (e.g., `.llvm.1234`).
+pub fn is_symbol_for_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    symbol: &str,
+    instance: Instance<'tcx>,
+    mangling_version: SymbolManglingVersion,
+) -> bool {
+    crate::is_symbol_for_instance(tcx, symbol, instance, mangling_version)
+}
+
+/// Returns the options the functions are instrumented with for CFI and KCFI in the session,
+/// respectively (e.g., `TypeIdOptions::NORMALIZE_INTEGERS` with
+/// `-Zsanitizer-cfi-normalize-integers`). They are currently the same, as no KCFI-specific option
+/// (e.g., `-Zsanitizer-kcfi-arity`) changes the type metadata identifiers.
+pub fn typeid_options(sess: &Session) -> (TypeIdOptions, TypeIdOptions) {
+    let mut options = TypeIdOptions::empty();
+    if sess.is_sanitizer_cfi_generalize_pointers_enabled() {
+        options.insert(TypeIdOptions::GENERALIZE_POINTERS);
+    }
+    if sess.is_sanitizer_cfi_normalize_integers_enabled() {
+        options.insert(TypeIdOptions::NORMALIZE_INTEGERS);
+    }
+    if sess.is_sanitizer_cfi_normalize_ptr_sized_integers_enabled() {
+        options.insert(TypeIdOptions::NORMALIZE_PTR_SIZED_INTEGERS);
+    }
+    if sess.is_sanitizer_cfi_member_function_pointers_enabled() {
+        options.insert(TypeIdOptions::MEMBER_FUNCTION_POINTERS);
+    }
+    if sess.is_sanitizer_cfi_cxx_references_enabled() {
+        options.insert(TypeIdOptions::CXX_REFERENCES);
+    }
+    if sess.is_sanitizer_cfi_erase_const_enabled() {
+        options.insert(TypeIdOptions::ERASE_CONST);
+    }
+    if sess.is_sanitizer_cfi_erase_ref_mut_enabled() {
+        options.insert(TypeIdOptions::ERASE_REF_MUT);
+    }
+    if sess.is_sanitizer_cfi_encode_captures_enabled() {
+        options.insert(TypeIdOptions::ENCODE_CAPTURES);
+    }
+    if sess.is_sanitizer_cfi_erase_phantom_data_enabled() {
+        options.insert(TypeIdOptions::ERASE_PHANTOM_DATA);
+    }
+    if sess.is_sanitizer_cfi_generalize_arrays_enabled() {
+        options.insert(TypeIdOptions::GENERALIZE_ARRAYS);
+    }
+    if sess.is_sanitizer_cfi_erase_return_types_enabled() {
+        options.insert(TypeIdOptions::ERASE_RETURN_TYPES);
+    }
+    if sess.is_sanitizer_cfi_c_integer_types_enabled() {
+        options.insert(TypeIdOptions::C_INTEGER_TYPES);
+    }
+    if sess.is_sanitizer_cfi_box_as_ptr_enabled() {
+        options.insert(TypeIdOptions::BOX_AS_PTR);
+    }
+    if sess.is_sanitizer_cfi_refs_as_ptrs_enabled() {
+        options.insert(TypeIdOptions::REFS_AS_PTRS);
+    }
+    if sess.is_sanitizer_cfi_impl_trait_bounds_enabled() {
+        options.insert(TypeIdOptions::IMPL_TRAIT_BOUNDS);
+    }
+    if sess.is_sanitizer_cfi_encode_fn_sigs_enabled() {
+        options.insert(TypeIdOptions::ENCODE_FN_SIGS);
+    }
+    (options, options)
+}
+
+/// Returns the CFI type metadata identifier of `instance` (e.g., `_ZTSFvu3i32E`) computed with
+/// `options`, including any override of the `override_cfi_typeid` hook.
+pub fn typeid_for_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    crate::typeid::typeid_for_instance(tcx, instance, options)
+}
+
+/// Returns the KCFI type metadata identifier of `instance` computed with `options`, with the seed
+/// and width of the session (see `-Zsanitizer-kcfi-seed` and `-Zsanitizer-kcfi-typeid-width`).
+pub fn kcfi_typeid_for_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    options: TypeIdOptions,
+) -> KcfiTypeId {
+    crate::typeid::kcfi::kcfi_typeid_for_instance(tcx, instance, options)
+}
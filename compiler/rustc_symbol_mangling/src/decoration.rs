@@ -0,0 +1,48 @@
+//! Target-specific decoration of the linker-visible names of symbols.
+//!
+//! Some targets don't refer to symbols by their (mangled) names alone, but decorate them with a
+//! prefix (e.g., `_` on 32-bit x86 Windows) or with the calling convention and the size of the
+//! arguments of functions (e.g., `_f@8` for `stdcall`). The rules are given by the
+//! `symbol-prefix` and `call-conv-symbol-decoration` fields of the target spec, so custom targets
+//! can express theirs without changes to the compiler.
+
+use rustc_middle::ty::Ty;
+use rustc_target::abi::call::{Conv, FnAbi};
+use rustc_target::spec::Target;
+
+/// Decorates the name of a symbol the way the linker of `target` sees it.
+///
+/// `fn_abi` returns the ABI of the function the symbol is the name of, or `None` if it is not the
+/// name of a function or the function follows the target's default decoration scheme (e.g., drop
+/// glue). It is only called on targets decorating symbols with calling conventions, as computing
+/// the ABI is not free.
+///
+/// Mach-O has a global `_` prefix, which the `object` crate and LLVM add themselves (so Mach-O
+/// targets can't have a `symbol-prefix`), and ELF does not have any symbol decorations.
+///
+/// Reference: https://docs.microsoft.com/en-us/cpp/build/reference/decorated-names?view=msvc-170
+pub fn decorate_symbol_name<'tcx>(
+    target: &Target,
+    mut undecorated: String,
+    fn_abi: impl FnOnce() -> Option<&'tcx FnAbi<'tcx, Ty<'tcx>>>,
+) -> String {
+    let fn_abi = if target.call_conv_symbol_decoration() { fn_abi() } else { None };
+    let (conv, args) =
+        fn_abi.map(|fn_abi| (fn_abi.conv, &fn_abi.args[..])).unwrap_or((Conv::Rust, &[]));
+
+    let (prefix, suffix) = match conv {
+        Conv::X86Fastcall => ("@", "@"),
+        Conv::X86Stdcall => ("_", "@"),
+        Conv::X86VectorCall => ("", "@@"),
+        _ => {
+            undecorated.insert_str(0, target.symbol_prefix());
+            return undecorated;
+        }
+    };
+
+    let args_in_bytes: u64 = args
+        .iter()
+        .map(|abi| abi.layout.size.bytes().next_multiple_of(target.pointer_width as u64 / 8))
+        .sum();
+    format!("{prefix}{undecorated}{suffix}{args_in_bytes}")
+}
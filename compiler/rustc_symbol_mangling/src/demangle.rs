@@ -0,0 +1,29 @@
+//! Demangling of the Rust symbols (mangled with the legacy or v0 schemes) found in text, such as
+//! the output of linkers, so diagnostics can show the Rust paths of the symbols alongside them.
+
+#[cfg(test)]
+mod tests;
+
+/// Returns every distinct Rust symbol found in `text`, in order of appearance, with its demangled
+/// path (without the crate disambiguators and hashes). The symbols may be prefixed with an extra
+/// `_`, as on Apple targets.
+pub fn demangle_symbols(text: &str) -> Vec<(&str, String)> {
+    let mut symbols: Vec<(&str, String)> = Vec::new();
+    let words = text
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.')))
+        .map(|word| word.trim_end_matches('.'));
+    for word in words {
+        let unprefixed = word.strip_prefix('_').unwrap_or(word);
+        let unprefixed = unprefixed.strip_prefix('_').unwrap_or(unprefixed);
+        if !(unprefixed.starts_with("ZN") || unprefixed.starts_with('R'))
+            || unprefixed.len() == word.len()
+            || symbols.iter().any(|&(symbol, _)| symbol == word)
+        {
+            continue;
+        }
+        if let Ok(demangled) = rustc_demangle::try_demangle(word) {
+            symbols.push((word, format!("{demangled:#}")));
+        }
+    }
+    symbols
+}
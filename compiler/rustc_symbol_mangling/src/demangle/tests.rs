@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn test_demangle_symbols_gnu_ld() {
+    let output = "/usr/bin/ld: foo.o: in function `foo::main':\n\
+        foo.rs:4: undefined reference to `_ZN3bar3baz17h0123456789abcdefE'\n\
+        foo.rs:5: undefined reference to `_RNvCs1234_3bar3qux'\n\
+        foo.rs:6: undefined reference to `_ZN3bar3baz17h0123456789abcdefE'\n\
+        foo.rs:7: undefined reference to `native_function'\n\
+        collect2: error: ld returned 1 exit status";
+    assert_eq!(
+        demangle_symbols(output),
+        [
+            ("_ZN3bar3baz17h0123456789abcdefE", "bar::baz".to_string()),
+            ("_RNvCs1234_3bar3qux", "bar::qux".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_demangle_symbols_apple_ld() {
+    let output = "Undefined symbols for architecture arm64:\n  \
+        \"__RINvCs1234_3bar3quxlEB2_\", referenced from:\n      \
+        __RNvCs5678_3foo4main in foo.foo.1234-cgu.0.rcgu.o\n\
+        ld: symbol(s) not found for architecture arm64";
+    assert_eq!(
+        demangle_symbols(output),
+        [
+            ("__RINvCs1234_3bar3quxlEB2_", "bar::qux::<i32>".to_string()),
+            ("__RNvCs5678_3foo4main", "foo::main".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_demangle_symbols_msvc() {
+    let output = "foo.foo.1234-cgu.0.rcgu.o : error LNK2019: unresolved external symbol \
+        _RNvCs1234_3bar3qux referenced in function _RNvCs5678_3foo4main.\n\
+        foo.exe : fatal error LNK1120: 1 unresolved externals";
+    assert_eq!(
+        demangle_symbols(output),
+        [
+            ("_RNvCs1234_3bar3qux", "bar::qux".to_string()),
+            ("_RNvCs5678_3foo4main", "foo::main".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_demangle_symbols_not_rust() {
+    assert!(demangle_symbols("undefined reference to `_ZN3foo3barEv' in Rust code").is_empty());
+    assert!(demangle_symbols("Rust _R ZN3foo3barE").is_empty());
+}
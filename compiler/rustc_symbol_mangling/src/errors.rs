@@ -1,7 +1,9 @@
 //! Errors emitted by symbol_mangling.
 
-use rustc_errors::{Diag, DiagCtxt, Diagnostic, EmissionGuarantee, Level};
-use rustc_span::Span;
+use rustc_errors::{codes::*, Diag, DiagCtxt, Diagnostic, EmissionGuarantee, Level};
+use rustc_macros::Diagnostic;
+use rustc_middle::ty::Ty;
+use rustc_span::{Span, Symbol};
 use std::fmt;
 
 pub struct TestOutput {
@@ -22,11 +24,33 @@ fn into_diag(self, dcx: &'_ DiagCtxt, level: Level) -> Diag<'_, G> {
     }
 }
 
+/// The outputs of `#[rustc_symbol_name(all)]`, i.e., the symbol name and its demanglings under
+/// each mangling version, reported as the notes of a single diagnostic.
+pub struct AllSymbolNames {
+    pub span: Span,
+    pub outputs: Vec<(&'static str, Kind, String)>,
+}
+
+// Like `TestOutput`, this diagnostic doesn't need translation.
+impl<G: EmissionGuarantee> Diagnostic<'_, G> for AllSymbolNames {
+    #[allow(rustc::untranslatable_diagnostic)]
+    fn into_diag(self, dcx: &'_ DiagCtxt, level: Level) -> Diag<'_, G> {
+        let AllSymbolNames { span, outputs } = self;
+
+        let mut diag = Diag::new(dcx, level, "symbol-names").with_span(span);
+        for (version, kind, content) in outputs {
+            diag.note(format!("{version}: {kind}({content})"));
+        }
+        diag
+    }
+}
+
 pub enum Kind {
     SymbolName,
     Demangling,
     DemanglingAlt,
     DefPath,
+    TyNameEncoding,
 }
 
 impl fmt::Display for Kind {
@@ -36,6 +60,122 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             Kind::Demangling => write!(f, "demangling"),
             Kind::DemanglingAlt => write!(f, "demangling-alt"),
             Kind::DefPath => write!(f, "def-path"),
+            Kind::TyNameEncoding => write!(f, "ty-name-encoding"),
         }
     }
 }
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_invalid_cfi_encoding, code = E0798)]
+pub struct InvalidCfiEncoding<'tcx> {
+    #[primary_span]
+    #[label]
+    #[suggestion(
+        symbol_mangling_default_cfi_encoding,
+        code = "#[cfi_encoding = \"{default}\"]",
+        applicability = "machine-applicable"
+    )]
+    pub span: Span,
+    #[label(symbol_mangling_invalid_cfi_encoding_ty)]
+    pub ty_span: Span,
+    pub ty: Ty<'tcx>,
+    pub reason: &'static str,
+    pub default: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_invalid_structured_cfi_encoding, code = E0799)]
+pub struct InvalidStructuredCfiEncoding<'tcx> {
+    #[primary_span]
+    #[label]
+    #[suggestion(
+        symbol_mangling_default_cfi_encoding,
+        code = "#[cfi_encoding = \"{default}\"]",
+        applicability = "machine-applicable"
+    )]
+    pub span: Span,
+    #[label(symbol_mangling_invalid_cfi_encoding_ty)]
+    pub ty_span: Span,
+    pub ty: Ty<'tcx>,
+    pub reason: String,
+    pub default: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_invalid_cfi_encodings_entry)]
+pub struct InvalidCfiEncodingsEntry {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    pub reason: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_unresolved_cfi_encodings_path)]
+pub struct UnresolvedCfiEncodingsPath {
+    #[primary_span]
+    pub span: Span,
+    pub ty_path: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_cfi_encodings_attr_conflict)]
+#[note]
+pub struct CfiEncodingsAttrConflict {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    #[label(symbol_mangling_cfi_encoding_attr)]
+    pub attr_span: Span,
+    pub ty_path: String,
+    pub encoding: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_conflicting_cfi_encodings)]
+#[note]
+pub struct ConflictingCfiEncodings {
+    #[primary_span]
+    pub span: Option<Span>,
+    pub ty_path: String,
+    pub encoding: String,
+    pub krate: Symbol,
+    pub prev_encoding: String,
+    pub prev_krate: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_invalid_cfi_encoding_profile)]
+pub struct InvalidCfiEncodingProfile {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_unresolved_cfi_encoding_profile_path)]
+#[note]
+pub struct UnresolvedCfiEncodingProfilePath {
+    pub ty_path: String,
+    pub path: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_unsupported_const_param, code = E0800)]
+#[note]
+#[note(symbol_mangling_cfi_sanitizer_note)]
+pub struct UnsupportedConstParam<'tcx> {
+    #[primary_span]
+    #[label]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+    pub item: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(symbol_mangling_intrinsic_without_fallback_body)]
+#[note]
+pub struct IntrinsicWithoutFallbackBody {
+    #[primary_span]
+    pub span: Span,
+    pub name: Symbol,
+}
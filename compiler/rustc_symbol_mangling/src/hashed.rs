@@ -1,14 +1,62 @@
 use crate::v0;
-use rustc_data_structures::stable_hasher::{Hash64, HashStable, StableHasher};
-use rustc_hir::def_id::CrateNum;
-use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_data_structures::base_n;
+use rustc_data_structures::stable_hasher::{Hash128, HashStable, StableHasher};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::ty::{Instance, InstanceDef, TyCtxt};
+use rustc_session::config::SymbolHashPolicy;
 
 use std::fmt::Write;
 
+/// Returns whether the symbol of `instance` is hashed under `policy`, rather than mangled with
+/// the v0 scheme. This only depends on the item and on the policy of the crate responsible for
+/// the mangling version, so that every crate agrees on the symbol.
+pub(super) fn should_hash<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    policy: SymbolHashPolicy,
+) -> bool {
+    // Shims, drop glue, etc. are always hashed, only the items themselves can opt out.
+    let InstanceDef::Item(def_id) = instance.def else {
+        return true;
+    };
+    if !matches!(
+        tcx.def_kind(def_id),
+        DefKind::Fn | DefKind::AssocFn | DefKind::Static { nested: false, .. }
+    ) {
+        return true;
+    }
+    if tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::UNHASHED_SYMBOL) {
+        return false;
+    }
+    match policy {
+        SymbolHashPolicy::All => true,
+        SymbolHashPolicy::Private => !is_public(tcx, def_id),
+    }
+}
+
+/// Returns whether `def_id` is declared `pub` and only nested in `pub` modules. Unlike the
+/// effective visibility, this is available for items of any crate.
+fn is_public(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if !tcx.visibility(def_id).is_public() {
+        return false;
+    }
+    let mut parent = tcx.opt_parent(def_id);
+    while let Some(module) = parent {
+        if tcx.def_kind(module) == DefKind::Mod && !tcx.visibility(module).is_public() {
+            return false;
+        }
+        parent = tcx.opt_parent(module);
+    }
+    true
+}
+
 pub(super) fn mangle<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     instantiating_crate: Option<CrateNum>,
+    hash_bits: u32,
     full_mangling_name: impl FnOnce() -> String,
 ) -> String {
     // The symbol of a generic function may be scattered in multiple downstream dylibs.
@@ -26,18 +74,23 @@ pub(super) fn mangle<'tcx>(
     let hash = tcx.with_stable_hashing_context(|mut hcx| {
         let mut hasher = StableHasher::new();
         full_mangling_name().hash_stable(&mut hcx, &mut hasher);
-        hasher.finish::<Hash64>().as_u64()
+        hasher.finish::<Hash128>().as_u128()
     });
 
-    push_hash64(hash, &mut symbol);
+    // The low 64 bits of the 128-bit hash are the 64-bit hash, so the default of 64 bits keeps
+    // the same symbols as before the hash length became configurable.
+    let hash = if hash_bits >= 128 { hash } else { hash & ((1 << hash_bits) - 1) };
+    push_hash(hash, &mut symbol);
 
     symbol
 }
 
 // The hash is encoded based on `base-62` and the final terminator `_` is removed because it does
 // not help prevent hash collisions
-fn push_hash64(hash: u64, output: &mut String) {
-    let hash = v0::encode_integer_62(hash);
-    let hash_len = hash.len();
-    let _ = write!(output, "{hash_len}H{}", &hash[..hash_len - 1]);
+fn push_hash(hash: u128, output: &mut String) {
+    let mut encoded = String::new();
+    if let Some(hash) = hash.checked_sub(1) {
+        base_n::push_str(hash, base_n::ALPHANUMERIC_ONLY, &mut encoded);
+    }
+    let _ = write!(output, "{}H{encoded}", encoded.len() + 1);
 }
@@ -1,4 +1,4 @@
-use rustc_data_structures::stable_hasher::{Hash64, HashStable, StableHasher};
+use rustc_data_structures::stable_hasher::{Hash128, HashStable, StableHasher};
 use rustc_hir::def_id::CrateNum;
 use rustc_hir::definitions::{DefPathData, DisambiguatedDefPathData};
 use rustc_middle::ty::print::{PrettyPrinter, Print, PrintError, Printer};
@@ -12,6 +12,7 @@ pub(super) fn mangle<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     instantiating_crate: Option<CrateNum>,
+    wide_hash: bool,
 ) -> String {
     let def_id = instance.def_id();
 
@@ -89,7 +90,7 @@ pub(super) fn mangle<'tcx>(
         _ => {}
     }
 
-    printer.path.finish(hash)
+    printer.path.finish(hash, wide_hash)
 }
 
 fn get_symbol_hash<'tcx>(
@@ -105,7 +106,7 @@ fn get_symbol_hash<'tcx>(
     item_type: Ty<'tcx>,
 
     instantiating_crate: Option<CrateNum>,
-) -> Hash64 {
+) -> Hash128 {
     let def_id = instance.def_id();
     let args = instance.args;
     debug!("get_symbol_hash(def_id={:?}, parameters={:?})", def_id, args);
@@ -147,8 +148,10 @@ fn get_symbol_hash<'tcx>(
             discriminant(&instance.def).hash_stable(hcx, &mut hasher);
         });
 
-        // 64 bits should be enough to avoid collisions.
-        hasher.finish::<Hash64>()
+        // 64 bits (its lower half) should be enough to avoid collisions, but
+        // `-Z symbol-mangling-wide-legacy-hash` uses all 128 bits for very
+        // large programs.
+        hasher.finish::<Hash128>()
     })
 }
 
@@ -186,10 +189,14 @@ fn finalize_pending_component(&mut self) {
         }
     }
 
-    fn finish(mut self, hash: Hash64) -> String {
+    fn finish(mut self, hash: Hash128, wide_hash: bool) -> String {
         self.finalize_pending_component();
         // E = end name-sequence
-        let _ = write!(self.result, "17h{hash:016x}E");
+        if wide_hash {
+            let _ = write!(self.result, "33h{hash:032x}E");
+        } else {
+            let _ = write!(self.result, "17h{:016x}E", hash.truncate());
+        }
         self.result
     }
 }
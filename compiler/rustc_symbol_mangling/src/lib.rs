@@ -99,23 +99,34 @@
 #[macro_use]
 extern crate tracing;
 
+use rustc_data_structures::stable_hasher::{Hash64, StableHasher};
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
+use rustc_hir::weak_lang_items::WEAK_LANG_ITEMS;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
 use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
-use rustc_middle::query::Providers;
 use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_middle::util::Providers;
 use rustc_session::config::SymbolManglingVersion;
+use rustc_span::Symbol;
+
+use std::hash::Hash;
 
 mod hashed;
 mod legacy;
 mod v0;
 
+pub mod api;
+pub mod decoration;
+pub mod demangle;
 pub mod errors;
+pub mod path_pattern;
 pub mod test;
 pub mod typeid;
 
+rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
+
 /// This function computes the symbol name for the given `instance` and the
 /// given instantiating crate. That is, if you know that instance X is
 /// instantiated in crate Y, this is the symbol name this instance would have.
@@ -124,35 +135,139 @@ pub fn symbol_name_for_instance_in_crate<'tcx>(
     instance: Instance<'tcx>,
     instantiating_crate: CrateNum,
 ) -> String {
-    compute_symbol_name(tcx, instance, || instantiating_crate)
+    compute_symbol_name(tcx, instance, || instantiating_crate, None, true)
+}
+
+/// Computes the symbol name for calling the given instance from the local
+/// crate, without capping its length with `-Z symbol-length-cap`. This is
+/// used to map capped symbols back to their full names.
+pub fn uncapped_symbol_name<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> String {
+    compute_symbol_name(tcx, instance, || instantiating_crate(tcx, instance), None, false)
 }
 
 pub fn provide(providers: &mut Providers) {
-    *providers = Providers { symbol_name: symbol_name_provider, ..*providers };
+    providers.symbol_name = symbol_name_provider;
+    providers.cfi_encoding_profile = typeid::profile::cfi_encoding_profile;
+    providers.cfi_encoding_registry = typeid::registry::cfi_encoding_registry;
+    providers.cfi_type_fragment = typeid::typeid_itanium_cxx_abi::cfi_type_fragment;
+    providers.hooks.override_cfi_typeid = |_, _, _, _| None;
 }
 
 // The `symbol_name` query provides the symbol name for calling a given
 // instance from the local crate. In particular, it will also look up the
 // correct symbol name of instances from upstream crates.
 fn symbol_name_provider<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> ty::SymbolName<'tcx> {
-    let symbol_name = compute_symbol_name(tcx, instance, || {
-        // This closure determines the instantiating crate for instances that
-        // need an instantiating-crate-suffix for their symbol name, in order
-        // to differentiate between local copies.
-        if is_generic(instance, tcx) {
-            // For generics we might find re-usable upstream instances. If there
-            // is one, we rely on the symbol being instantiated locally.
-            instance.upstream_monomorphization(tcx).unwrap_or(LOCAL_CRATE)
-        } else {
-            // For non-generic things that need to avoid naming conflicts, we
-            // always instantiate a copy in the local crate.
-            LOCAL_CRATE
-        }
-    });
+    let symbol_name =
+        compute_symbol_name(tcx, instance, || instantiating_crate(tcx, instance), None, true);
 
     ty::SymbolName::new(tcx, &symbol_name)
 }
 
+/// Computes the symbol name for calling the given instance from the local
+/// crate with the given mangling version, instead of the one of the crate
+/// responsible for it. This is used by `#[rustc_symbol_name(all)]`.
+pub(crate) fn symbol_name_with_mangling_version<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    mangling_version: SymbolManglingVersion,
+) -> String {
+    compute_symbol_name(
+        tcx,
+        instance,
+        || instantiating_crate(tcx, instance),
+        Some(mangling_version),
+        true,
+    )
+}
+
+/// Checks whether `symbol` is the symbol name for calling the given instance
+/// from the local crate with the given mangling version, i.e. whether it is what
+/// `symbol_name_with_mangling_version` computes, including the length cap of
+/// `-Z symbol-length-cap`.
+pub(crate) fn is_symbol_for_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    symbol: &str,
+    instance: Instance<'tcx>,
+    mangling_version: SymbolManglingVersion,
+) -> bool {
+    symbol == symbol_name_with_mangling_version(tcx, instance, mangling_version)
+}
+
+/// Explains the v0 mangling of `instance`, i.e. its symbol with `-C symbol-mangling-version=v0`
+/// (whatever its actual mangling version is), by following it with a line per path, generic
+/// argument and self type in it, which underlines that production and describes it. This is used
+/// by `-Z print-symbol-explanations`.
+pub fn explain_v0_symbol_name<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> String {
+    let instantiating_crate =
+        avoid_cross_crate_conflicts(tcx, instance).then(|| instantiating_crate(tcx, instance));
+    v0::explain(tcx, instance, instantiating_crate)
+}
+
+/// Returns the legacy symbol to emit as an alias of the symbol of `instance`, if the local crate was
+/// compiled with `-Z symbol-mangling-legacy-aliases` and that symbol isn't a legacy one already
+/// (as with `#[no_mangle]`). This keeps tools which only understand legacy symbols, like old
+/// profilers, working while migrating to `-C symbol-mangling-version=v0`.
+///
+/// Thread-local statics have no alias, as accessing them needs a TLS model of their own.
+pub fn legacy_symbol_alias<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Option<String> {
+    if !tcx.sess.opts.unstable_opts.symbol_mangling_legacy_aliases
+        || tcx.is_thread_local_static(instance.def_id())
+    {
+        return None;
+    }
+
+    let symbol = tcx.symbol_name(instance).name;
+    let alias = symbol_name_with_mangling_version(tcx, instance, SymbolManglingVersion::Legacy);
+    (alias != symbol).then_some(alias)
+}
+
+/// Determines the instantiating crate for instances that need an
+/// instantiating-crate-suffix for their symbol name, in order to
+/// differentiate between local copies.
+fn instantiating_crate<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> CrateNum {
+    if is_generic(instance, tcx) {
+        // For generics we might find re-usable upstream instances. If there
+        // is one, we rely on the symbol being instantiated locally.
+        instance.upstream_monomorphization(tcx).unwrap_or(LOCAL_CRATE)
+    } else {
+        // For non-generic things that need to avoid naming conflicts, we
+        // always instantiate a copy in the local crate.
+        LOCAL_CRATE
+    }
+}
+
+/// Returns the symbol of `item_name`, an item internal to the standard library or the allocator
+/// shim (like `__rust_alloc`, or any `#[rustc_std_internal_symbol]` item).
+///
+/// These are unmangled, unless the crate defining the `Sized` lang item (i.e. `core`) was compiled
+/// with `-Z mangle-internal-symbols`. They are then mangled as `__rustc::<item_name>` in a crate
+/// with the disambiguator of `core`, so that a `std` built by the user doesn't clash with the
+/// pre-built one, while all the crates sharing that `core` agree on the name.
+pub fn mangle_internal_symbol(tcx: TyCtxt<'_>, item_name: &str) -> String {
+    // `core` refers to the weak lang items by their unmangled names, which LLVM also knows
+    // `rust_eh_personality` by.
+    if WEAK_LANG_ITEMS
+        .iter()
+        .any(|item| item.link_name().is_some_and(|name| name.as_str() == item_name))
+    {
+        return item_name.to_owned();
+    }
+
+    let Some(core) = tcx.lang_items().sized_trait().map(|def_id| def_id.krate) else {
+        return item_name.to_owned();
+    };
+    let mangle = if core == LOCAL_CRATE {
+        tcx.sess.opts.unstable_opts.mangle_internal_symbols
+    } else {
+        tcx.mangle_internal_symbols(core)
+    };
+    if !mangle {
+        return item_name.to_owned();
+    }
+
+    v0::mangle_internal_symbol(tcx, tcx.stable_crate_id(core), item_name)
+}
+
 pub fn typeid_for_trait_ref<'tcx>(
     tcx: TyCtxt<'tcx>,
     trait_ref: ty::PolyExistentialTraitRef<'tcx>,
@@ -160,14 +275,107 @@ pub fn typeid_for_trait_ref<'tcx>(
     v0::mangle_typeid_for_trait_ref(tcx, trait_ref)
 }
 
+/// Returns whether the symbol of `instance` has to include its instantiating crate.
+fn avoid_cross_crate_conflicts<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+    // If we're dealing with an instance of a function that's inlined from
+    // another crate but we're marking it as globally shared to our
+    // compilation (aka we're not making an internal copy in each of our
+    // codegen units) then this symbol may become an exported (but hidden
+    // visibility) symbol. This means that multiple crates may do the same
+    // and we want to be sure to avoid any symbol conflicts here.
+    let is_globally_shared_function = matches!(
+        tcx.def_kind(instance.def_id()),
+        DefKind::Fn | DefKind::AssocFn | DefKind::Closure | DefKind::Ctor(..)
+    ) && matches!(
+        MonoItem::Fn(instance).instantiation_mode(tcx),
+        InstantiationMode::GloballyShared { may_conflict: true }
+    );
+
+    // If this is an instance of a generic function, we also hash in
+    // the ID of the instantiating crate. This avoids symbol conflicts
+    // in case the same instances is emitted in two crates of the same
+    // project.
+    is_generic(instance, tcx) || is_globally_shared_function
+}
+
+/// How the symbol of an instance is named, as returned by `symbol_origin`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolOrigin {
+    /// Generated by the compiler (e.g., for the proc macro declarations static).
+    Generated,
+    /// The name of a foreign item, or its `#[link_name]`.
+    Foreign,
+    /// The name of an item internal to the standard library (see `mangle_internal_symbol`).
+    Internal,
+    /// Set with `#[export_name]`.
+    ExportName,
+    /// The name of the item, because of `#[no_mangle]`.
+    NoMangle,
+    /// Mangled with the legacy scheme.
+    Legacy,
+    /// Mangled with the v0 scheme, including items kept unhashed by the hashed scheme (see
+    /// `-Z symbol-mangling-hash-policy`).
+    V0,
+    /// Hashed with the hashed scheme.
+    Hashed,
+}
+
+impl SymbolOrigin {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SymbolOrigin::Generated => "generated",
+            SymbolOrigin::Foreign => "foreign",
+            SymbolOrigin::Internal => "internal",
+            SymbolOrigin::ExportName => "export_name",
+            SymbolOrigin::NoMangle => "no_mangle",
+            SymbolOrigin::Legacy => "legacy",
+            SymbolOrigin::V0 => "v0",
+            SymbolOrigin::Hashed => "hashed",
+        }
+    }
+}
+
+/// Returns how the symbol for calling the given instance from the local crate is named, i.e., the
+/// attribute naming it or the scheme it is actually mangled with, which follows the crate
+/// responsible for it (as `symbol_name` does). This computes the symbol again, so it is meant for
+/// diagnostics.
+pub fn symbol_origin<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> SymbolOrigin {
+    compute_symbol_name_and_origin(tcx, instance, || instantiating_crate(tcx, instance), None, true)
+        .1
+}
+
 /// Computes the symbol name for the given instance. This function will call
 /// `compute_instantiating_crate` if it needs to factor the instantiating crate
-/// into the symbol name.
+/// into the symbol name. If `mangling_version` is `Some`, it is used instead
+/// of the mangling version of the crate responsible for the symbol. If
+/// `cap_length` is `true`, mangled symbols longer than the cap of that crate
+/// have their tail replaced with a hash (see `cap_symbol_length`).
 fn compute_symbol_name<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     compute_instantiating_crate: impl FnOnce() -> CrateNum,
+    mangling_version: Option<SymbolManglingVersion>,
+    cap_length: bool,
 ) -> String {
+    compute_symbol_name_and_origin(
+        tcx,
+        instance,
+        compute_instantiating_crate,
+        mangling_version,
+        cap_length,
+    )
+    .0
+}
+
+/// Computes the symbol name for the given instance as `compute_symbol_name` does, along with how
+/// it was named.
+fn compute_symbol_name_and_origin<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    compute_instantiating_crate: impl FnOnce() -> CrateNum,
+    mangling_version: Option<SymbolManglingVersion>,
+    cap_length: bool,
+) -> (String, SymbolOrigin) {
     let def_id = instance.def_id();
     let args = instance.args;
 
@@ -176,7 +384,8 @@ fn compute_symbol_name<'tcx>(
     if let Some(def_id) = def_id.as_local() {
         if tcx.proc_macro_decls_static(()) == Some(def_id) {
             let stable_crate_id = tcx.stable_crate_id(LOCAL_CRATE);
-            return tcx.sess.generate_proc_macro_decls_symbol(stable_crate_id);
+            let symbol = tcx.sess.generate_proc_macro_decls_symbol(stable_crate_id);
+            return (symbol, SymbolOrigin::Generated);
         }
     }
 
@@ -207,43 +416,30 @@ fn compute_symbol_name<'tcx>(
         && (!tcx.sess.target.is_like_wasm
             || !tcx.wasm_import_module_map(def_id.krate).contains_key(&def_id))
     {
-        if let Some(name) = attrs.link_name {
-            return name.to_string();
+        let name = attrs.link_name.unwrap_or_else(|| tcx.item_name(def_id));
+        if attrs.flags.contains(CodegenFnAttrFlags::RUSTC_STD_INTERNAL_SYMBOL) {
+            return (mangle_internal_symbol(tcx, name.as_str()), SymbolOrigin::Internal);
         }
-        return tcx.item_name(def_id).to_string();
+        return (name.to_string(), SymbolOrigin::Foreign);
+    }
+
+    if attrs.flags.contains(CodegenFnAttrFlags::RUSTC_STD_INTERNAL_SYMBOL) {
+        let name = attrs.export_name.unwrap_or_else(|| tcx.item_name(def_id));
+        return (mangle_internal_symbol(tcx, name.as_str()), SymbolOrigin::Internal);
     }
 
     if let Some(name) = attrs.export_name {
         // Use provided name
-        return name.to_string();
+        return (name.to_string(), SymbolOrigin::ExportName);
     }
 
     if attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) {
         // Don't mangle
-        return tcx.item_name(def_id).to_string();
+        return (tcx.item_name(def_id).to_string(), SymbolOrigin::NoMangle);
     }
 
-    // If we're dealing with an instance of a function that's inlined from
-    // another crate but we're marking it as globally shared to our
-    // compilation (aka we're not making an internal copy in each of our
-    // codegen units) then this symbol may become an exported (but hidden
-    // visibility) symbol. This means that multiple crates may do the same
-    // and we want to be sure to avoid any symbol conflicts here.
-    let is_globally_shared_function = matches!(
-        tcx.def_kind(instance.def_id()),
-        DefKind::Fn | DefKind::AssocFn | DefKind::Closure | DefKind::Ctor(..)
-    ) && matches!(
-        MonoItem::Fn(instance).instantiation_mode(tcx),
-        InstantiationMode::GloballyShared { may_conflict: true }
-    );
-
-    // If this is an instance of a generic function, we also hash in
-    // the ID of the instantiating crate. This avoids symbol conflicts
-    // in case the same instances is emitted in two crates of the same
-    // project.
-    let avoid_cross_crate_conflicts = is_generic(instance, tcx) || is_globally_shared_function;
-
-    let instantiating_crate = avoid_cross_crate_conflicts.then(compute_instantiating_crate);
+    let instantiating_crate =
+        avoid_cross_crate_conflicts(tcx, instance).then(compute_instantiating_crate);
 
     // Pick the crate responsible for the symbol mangling version, which has to:
     // 1. be stable for each instance, whether it's being defined or imported
@@ -254,26 +450,100 @@ fn compute_symbol_name<'tcx>(
     // either and have a stable choice of symbol mangling version
     // 2. we favor `instantiating_crate` where possible (i.e. when `Some`)
     let mangling_version_crate = instantiating_crate.unwrap_or(def_id.krate);
-    let mangling_version = if mangling_version_crate == LOCAL_CRATE {
-        tcx.sess.opts.get_symbol_mangling_version()
-    } else {
-        tcx.symbol_mangling_version(mangling_version_crate)
+    let mangling_version = mangling_version.unwrap_or_else(|| {
+        if mangling_version_crate == LOCAL_CRATE {
+            tcx.sess.opts.get_symbol_mangling_version()
+        } else {
+            tcx.symbol_mangling_version(mangling_version_crate)
+        }
+    });
+
+    let (mut symbol, origin) = match mangling_version {
+        SymbolManglingVersion::Legacy => {
+            // The hash width follows the crate picked for the mangling version above.
+            let wide_hash = if mangling_version_crate == LOCAL_CRATE {
+                tcx.sess.opts.unstable_opts.symbol_mangling_wide_legacy_hash
+            } else {
+                tcx.symbol_mangling_wide_legacy_hash(mangling_version_crate)
+            };
+            (legacy::mangle(tcx, instance, instantiating_crate, wide_hash), SymbolOrigin::Legacy)
+        }
+        SymbolManglingVersion::V0 => {
+            (v0::mangle(tcx, instance, instantiating_crate), SymbolOrigin::V0)
+        }
+        SymbolManglingVersion::Hashed => {
+            // The hash length and policy follow the crate picked for the mangling version above.
+            let (hash_bits, hash_policy) = if mangling_version_crate == LOCAL_CRATE {
+                (
+                    tcx.sess.opts.get_symbol_mangling_hash_bits(),
+                    tcx.sess.opts.get_symbol_mangling_hash_policy(),
+                )
+            } else {
+                (
+                    tcx.symbol_mangling_hash_bits(mangling_version_crate),
+                    tcx.symbol_mangling_hash_policy(mangling_version_crate),
+                )
+            };
+            if hashed::should_hash(tcx, instance, hash_policy) {
+                let symbol = hashed::mangle(tcx, instance, instantiating_crate, hash_bits, || {
+                    v0::mangle(tcx, instance, instantiating_crate)
+                });
+                (symbol, SymbolOrigin::Hashed)
+            } else {
+                (v0::mangle(tcx, instance, instantiating_crate), SymbolOrigin::V0)
+            }
+        }
     };
 
-    let symbol = match mangling_version {
-        SymbolManglingVersion::Legacy => legacy::mangle(tcx, instance, instantiating_crate),
-        SymbolManglingVersion::V0 => v0::mangle(tcx, instance, instantiating_crate),
-        SymbolManglingVersion::Hashed => hashed::mangle(tcx, instance, instantiating_crate, || {
-            v0::mangle(tcx, instance, instantiating_crate)
-        }),
+    // The suffix also follows the crate picked for the mangling version above. Demanglers keep
+    // `.`-prefixed suffixes, e.g. `foo::bar.suffix`.
+    let suffix = if mangling_version_crate == LOCAL_CRATE {
+        tcx.sess.opts.unstable_opts.symbol_mangling_suffix.as_deref().map(Symbol::intern)
+    } else {
+        tcx.symbol_mangling_suffix(mangling_version_crate)
     };
+    if let Some(suffix) = suffix {
+        symbol.push('.');
+        symbol.push_str(suffix.as_str());
+    }
 
     debug_assert!(
         rustc_demangle::try_demangle(&symbol).is_ok(),
         "compute_symbol_name: `{symbol}` cannot be demangled"
     );
 
-    symbol
+    // The length cap also follows the crate picked for the mangling version above, so that
+    // every crate agrees on the capped symbol.
+    let length_cap = if mangling_version_crate == LOCAL_CRATE {
+        tcx.sess.opts.unstable_opts.symbol_length_cap
+    } else {
+        tcx.symbol_length_cap(mangling_version_crate)
+    };
+    if cap_length
+        && let Some(cap) = length_cap
+        && symbol.len() > cap
+    {
+        return (cap_symbol_length(&symbol, cap), origin);
+    }
+
+    (symbol, origin)
+}
+
+/// Replaces the tail of `symbol` with `_h` followed by the hexadecimal 64-bit
+/// stable hash of the whole symbol, so that it is `cap` bytes long. The hash
+/// only depends on the symbol, so the capped symbol is the same in every crate.
+/// Capped symbols can no longer be demangled, `-Z emit-symbol-map` maps them
+/// back to the full symbols.
+fn cap_symbol_length(symbol: &str, cap: usize) -> String {
+    let mut hasher = StableHasher::new();
+    symbol.hash(&mut hasher);
+    let suffix = format!("_h{:016x}", hasher.finish::<Hash64>().as_u64());
+
+    let mut end = cap - suffix.len();
+    while !symbol.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{suffix}", &symbol[..end])
 }
 
 fn is_generic<'tcx>(instance: Instance<'tcx>, tcx: TyCtxt<'tcx>) -> bool {
@@ -0,0 +1,56 @@
+//! Glob patterns over the paths of items, as they appear in demangled symbols (e.g.,
+//! `alloc::vec::*` or `*::new`). This is used by `-Z print-mono-items-matching` to find the
+//! mangled symbols of items by their human-readable paths.
+
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::ty::print::{with_no_trimmed_paths, with_no_visible_paths};
+use rustc_middle::ty::TyCtxt;
+
+#[cfg(test)]
+mod tests;
+
+/// Returns the path of `def_id` as it appears in demangled symbols, i.e., starting with the name
+/// of its crate and without generic arguments (e.g., `foo::bar::Baz::new`).
+pub fn def_path_for_matching(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let path = with_no_visible_paths!(with_no_trimmed_paths!(tcx.def_path_str(def_id)));
+    if def_id.is_local() { format!("{}::{path}", tcx.crate_name(LOCAL_CRATE)) } else { path }
+}
+
+/// Returns whether the path of `def_id` (see [`def_path_for_matching`]) matches `pattern`.
+pub fn def_path_matches(tcx: TyCtxt<'_>, def_id: DefId, pattern: &str) -> bool {
+    path_matches(pattern, &def_path_for_matching(tcx, def_id))
+}
+
+/// Returns whether `path` matches the glob `pattern`, in which `*` matches any (possibly empty)
+/// sequence of characters, including `::`, and `?` matches any single character.
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+
+    let (mut p, mut s) = (0, 0);
+    // The position of the last `*` in the pattern and of the character in the path it was
+    // tried against, to backtrack to if the rest of the pattern doesn't match.
+    let mut backtrack = None;
+    while s < path.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, s));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == path[s] => {
+                p += 1;
+                s += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_s)) => {
+                    // Let the `*` match one more character.
+                    backtrack = Some((star, star_s + 1));
+                    p = star + 1;
+                    s = star_s + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
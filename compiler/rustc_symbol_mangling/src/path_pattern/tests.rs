@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn test_path_matches_literal() {
+    assert!(path_matches("foo::bar", "foo::bar"));
+    assert!(!path_matches("foo::bar", "foo::baz"));
+    assert!(!path_matches("foo::bar", "foo::bar::baz"));
+    assert!(!path_matches("foo::bar::baz", "foo::bar"));
+}
+
+#[test]
+fn test_path_matches_wildcards() {
+    assert!(path_matches("foo::*", "foo::bar"));
+    assert!(path_matches("foo::*", "foo::bar::baz"));
+    assert!(path_matches("*::new", "foo::Bar::new"));
+    assert!(!path_matches("*::new", "foo::Bar::new_in"));
+    assert!(path_matches("foo::*::baz", "foo::bar::qux::baz"));
+    assert!(path_matches("foo::ba?", "foo::baz"));
+    assert!(!path_matches("foo::ba?", "foo::ba"));
+    assert!(path_matches("*", ""));
+    assert!(path_matches("**a*", "bab"));
+}
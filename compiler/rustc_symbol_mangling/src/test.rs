@@ -1,17 +1,34 @@
 //! Walks the crate looking for items/impl-items/trait-items that have
-//! either a `rustc_symbol_name` or `rustc_def_path` attribute and
-//! generates an error giving, respectively, the symbol name or
-//! def-path. This is used for unit testing the code that generates
-//! paths etc in all kinds of annoying scenarios.
+//! a `rustc_symbol_name`, `rustc_def_path` or `rustc_ty_name_encoding`
+//! attribute and generates an error giving, respectively, the symbol name,
+//! def-path, or the encoding of the item name in type metadata identifiers.
+//! This is used for unit testing the code that generates paths etc in all
+//! kinds of annoying scenarios.
+//!
+//! With `#[rustc_symbol_name(all)]`, the symbol name is computed with every
+//! mangling version instead of the one the crate is compiled with, and all of
+//! them are reported in a single error.
 
-use crate::errors::{Kind, TestOutput};
+use crate::errors::{AllSymbolNames, Kind, TestOutput};
+use crate::typeid::encode_ty_name;
+use crate::{is_symbol_for_instance, symbol_name_with_mangling_version};
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::LocalDefId;
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{GenericArgs, Instance, TyCtxt};
+use rustc_session::config::SymbolManglingVersion;
 use rustc_span::symbol::{sym, Symbol};
 
 const SYMBOL_NAME: Symbol = sym::rustc_symbol_name;
 const DEF_PATH: Symbol = sym::rustc_def_path;
+const TY_NAME_ENCODING: Symbol = sym::rustc_ty_name_encoding;
+
+/// The mangling versions reported by `#[rustc_symbol_name(all)]`, in order.
+const MANGLING_VERSIONS: [(SymbolManglingVersion, &str); 3] = [
+    (SymbolManglingVersion::Legacy, "legacy"),
+    (SymbolManglingVersion::V0, "v0"),
+    (SymbolManglingVersion::Hashed, "hashed"),
+];
 
 pub fn report_symbol_names(tcx: TyCtxt<'_>) {
     // if the `rustc_attrs` feature is not enabled, then the
@@ -59,7 +76,36 @@ fn process_attrs(&mut self, def_id: LocalDefId) {
                 def_id,
                 tcx.erase_regions(GenericArgs::identity_for_item(tcx, def_id)),
             );
+            if attr
+                .meta_item_list()
+                .is_some_and(|list| list.iter().any(|nested| nested.has_name(sym::all)))
+            {
+                let mut outputs = vec![];
+                for (mangling_version, version) in MANGLING_VERSIONS {
+                    let mangled =
+                        symbol_name_with_mangling_version(tcx, instance, mangling_version);
+                    debug_assert!(is_symbol_for_instance(
+                        tcx,
+                        &mangled,
+                        instance,
+                        mangling_version
+                    ));
+                    outputs.push((version, Kind::SymbolName, mangled.clone()));
+                    if let Ok(demangling) = rustc_demangle::try_demangle(&mangled) {
+                        outputs.push((version, Kind::Demangling, format!("{demangling}")));
+                        outputs.push((version, Kind::DemanglingAlt, format!("{demangling:#}")));
+                    }
+                }
+                tcx.dcx().emit_err(AllSymbolNames { span: attr.span, outputs });
+                continue;
+            }
             let mangled = tcx.symbol_name(instance);
+            debug_assert!(is_symbol_for_instance(
+                tcx,
+                mangled.name,
+                instance,
+                tcx.sess.opts.get_symbol_mangling_version()
+            ));
             tcx.dcx().emit_err(TestOutput {
                 span: attr.span,
                 kind: Kind::SymbolName,
@@ -86,5 +132,18 @@ fn process_attrs(&mut self, def_id: LocalDefId) {
                 content: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
             });
         }
+
+        for attr in tcx.get_attrs(def_id, TY_NAME_ENCODING) {
+            // The def paths of these items aren't names that can be encoded.
+            if matches!(tcx.def_kind(def_id), DefKind::Use | DefKind::GlobalAsm | DefKind::Macro(_))
+            {
+                continue;
+            }
+            tcx.dcx().emit_err(TestOutput {
+                span: attr.span,
+                kind: Kind::TyNameEncoding,
+                content: encode_ty_name(tcx, def_id.to_def_id()),
+            });
+        }
     }
 }
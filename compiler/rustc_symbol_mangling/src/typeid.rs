@@ -4,10 +4,13 @@
 /// For more information about LLVM CFI and cross-language LLVM CFI support for the Rust compiler,
 /// see design document in the tracking issue #89653.
 use bitflags::bitflags;
-use rustc_middle::ty::{Instance, InstanceDef, ReifyReason, Ty, TyCtxt};
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::{self, Instance, PolyFnSig, Ty, TyCtxt, TypeVisitableExt};
+use rustc_span::def_id::DefId;
 use rustc_target::abi::call::FnAbi;
-use std::hash::Hasher;
-use twox_hash::XxHash64;
+use std::fmt;
+
+use crate::typeid::demangle::{demangle_typeid, typeid_difference};
 
 bitflags! {
     /// Options for typeid_for_fnabi.
@@ -32,10 +35,187 @@ pub struct TypeIdOptions: u32 {
         /// either typeid_for_instance or typeid_for_fnabi at call sites during code generation for
         /// type membership tests when methods are used as function pointers.)
         const USE_CONCRETE_SELF = 8;
+        /// Encodes the type ids of trait methods called through trait objects as pointer to member
+        /// function types, with the trait object as the class, for compatibility with Clang
+        /// `-fsanitize=cfi-mfcall` option for C++ code storing them as pointers to member
+        /// functions.
+        ///
+        /// (This applies to typeid_for_instance only, and to virtual instances only.)
+        const MEMBER_FUNCTION_POINTERS = 32;
+        /// Encodes references as C++ lvalue references (i.e., `&T` as `const T&` and `&mut T` as
+        /// `T&`) instead of vendor extended types, for compatibility with C++ code declaring the
+        /// parameters shared with Rust code as references, for cross-language LLVM CFI and KCFI
+        /// support.
+        const CXX_REFERENCES = 64;
+        /// Encodes the types of the captures of coroutines and coroutine closures (as a tuple
+        /// following their parent generic arguments), so that they only share type metadata
+        /// identifiers with the ones capturing the same types. (Closures are always encoded with
+        /// their captures.)
+        const ENCODE_CAPTURES = 128;
+        /// Encodes arrays as slices (i.e., `[T; N]` as `[T]`) in the parameter and return types of
+        /// functions with the "C" calling convention, as C prototypes can't express array lengths
+        /// in parameters (i.e., arrays decay to pointers).
+        const GENERALIZE_ARRAYS = 256;
+        /// Erases the const qualifier of pointees (i.e., encodes `*const T` as `*mut T`, and `&T`
+        /// as `&mut T` with `CXX_REFERENCES`), as Clang does for C code declaring the pointer
+        /// parameters shared with Rust code without `const`, for cross-language LLVM CFI and KCFI
+        /// support.
+        const ERASE_CONST = 512;
+        /// Encodes integer types as the C integer types Clang encodes the libc typedefs of the
+        /// same size and signedness as on the target (e.g., `usize` as `size_t`, that is, `m` on
+        /// x86_64 Linux) in the parameter and return types of functions with the "C" calling
+        /// convention, for cross-language LLVM CFI and KCFI support without integer normalization.
+        ///
+        /// (This has no effect on the integer types normalized by `NORMALIZE_INTEGERS`.)
+        const C_INTEGER_TYPES = 1024;
+        /// Encodes the `PhantomData<T>` generic arguments of user-defined types, trait objects,
+        /// and closures as the unit type, so that types that only differ in their marker
+        /// parameters (e.g., `Foo<PhantomData<*mut ()>>` and `Foo<PhantomData<u8>>`) share type
+        /// metadata identifiers.
+        const ERASE_PHANTOM_DATA = 2048;
+        /// Encodes the argument-position `impl Trait` types in the signatures of functions, and
+        /// the opaque types (e.g., return-position `impl Trait` types), via their bounds as the
+        /// trait objects of them (i.e., `impl Plugin` as `dyn Plugin`) instead of the types they
+        /// hide, so that separately built callers and callees (e.g., plugin hosts and plugins)
+        /// share type metadata identifiers.
+        ///
+        /// (This applies to typeid_for_instance only for argument-position `impl Trait` types,
+        /// as function pointer types can't have them.)
+        const IMPL_TRAIT_BOUNDS = 4096;
+        /// Encodes functions from their declared signatures (i.e., their `FnSig`) instead of their
+        /// ABI (i.e., their `FnAbi`), so that their type metadata identifiers don't depend on how
+        /// their parameters are passed (e.g., parameters of zero-sized types are encoded even
+        /// though they're ignored, and the receivers of trait methods as declared), which may
+        /// change between compiler versions.
+        const ENCODE_FN_SIGS = 8192;
+        /// Doesn't cap the length of type metadata identifiers with
+        /// `-Zsanitizer-cfi-typeid-length-cap`, so that capped ones can be checked for collisions.
+        const UNCAPPED = 16384;
+        /// Encodes the return types of functions as `void`, so that functions that only differ in
+        /// their return types share type metadata identifiers, for deployments that trade the
+        /// precision of the checks for interoperability (e.g., with C code declaring return types
+        /// loosely).
+        ///
+        /// (Function pointer types nested in the parameter types are still encoded with their
+        /// return types.)
+        const ERASE_RETURN_TYPES = 32768;
+        /// Erases the mutability of references (i.e., encodes `&mut T` as `&T`, that is, without
+        /// the `U3mut` vendor extended type qualifier, or `T&` as `const T&` with
+        /// `CXX_REFERENCES`), so that functions that only differ in the mutability of their
+        /// reference parameters (e.g., callbacks transmuted between them) share type metadata
+        /// identifiers.
+        ///
+        /// (With both `ERASE_CONST` and `CXX_REFERENCES`, references are already all encoded as
+        /// `T&`.)
+        const ERASE_REF_MUT = 65536;
+        /// Encodes `Box<T>` as `*mut T` (for sized `T` and the global allocator, as `Box<T>` is
+        /// then passed as a raw pointer) in the parameter and return types of functions with the
+        /// "C" calling convention, as C headers declare the ownership-transferring parameters as
+        /// plain pointers, for cross-language LLVM CFI and KCFI support.
+        const BOX_AS_PTR = 131072;
+        /// Encodes references to sized types as the raw pointers they are passed as (i.e., `&T` as
+        /// `*const T` and `&mut T` as `*mut T`) in the parameter and return types of functions
+        /// with the "C" calling convention, as bindings commonly declare the `const T*` parameters
+        /// of C functions as references, for cross-language LLVM CFI and KCFI support.
+        ///
+        /// (This takes precedence over `CXX_REFERENCES` and `ERASE_REF_MUT` in these functions.)
+        const REFS_AS_PTRS = 262144;
+        /// Normalizes the pointer-sized integer types only (i.e., `usize` and `isize` as the
+        /// fixed-width integer types of the same size, as `NORMALIZE_INTEGERS` does), so that Rust
+        /// and C agree on `uintptr_t` parameters without normalizing the other integer types.
+        /// Type metadata identifiers computed with it (and without `NORMALIZE_INTEGERS`) have the
+        /// `.ptrsized` suffix.
+        const NORMALIZE_PTR_SIZED_INTEGERS = 524288;
+        /// Doesn't compress type metadata identifiers (i.e., encodes every repeated component in
+        /// full instead of as a substitution of its first occurrence), so that their structure is
+        /// apparent when they are dumped or diffed (see `-Zcfi-uncompressed-typeids`).
+        ///
+        /// (This applies to the Itanium C++ ABI encoding only. Type metadata identifiers computed
+        /// with it never match the ones functions are instrumented with, or Clang's, so this is
+        /// for debugging only.)
+        const UNCOMPRESSED = 1048576;
     }
 }
 
-mod typeid_itanium_cxx_abi;
+pub mod alias_set;
+pub mod c_prototype;
+pub mod demangle;
+pub mod grammar;
+pub mod interpret;
+pub mod itanium;
+pub mod kcfi;
+pub(crate) mod profile;
+pub(crate) mod registry;
+pub mod provider;
+pub(crate) mod typeid_itanium_cxx_abi;
+mod typeid_msvc_abi;
+pub mod wasm;
+
+pub use typeid_itanium_cxx_abi::{CrossLanguageIncompatibility, TypeDefEncoding};
+
+/// Returns the first type in the specified parameter or return type of a function with the "C"
+/// calling convention that has no C counterpart (i.e., whose encoding can never match the encoding
+/// of a C type for cross-language LLVM CFI and KCFI support) and the cause of it having no C
+/// counterpart, if any.
+pub fn cross_language_incompatible_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    options: TypeIdOptions,
+) -> Option<(Ty<'tcx>, CrossLanguageIncompatibility)> {
+    typeid_itanium_cxx_abi::cross_language_incompatible_ty(tcx, ty, options)
+}
+
+/// Returns the encodings of the specified user-defined type or extern type definition in type
+/// metadata identifiers, if it is one and its `cfi_encoding` attribute (if any) is valid.
+pub fn type_def_encoding(tcx: TyCtxt<'_>, def_id: DefId) -> Option<TypeDefEncoding> {
+    typeid_itanium_cxx_abi::type_def_encoding(tcx, def_id)
+}
+
+/// Returns the encodings of the primitive types in type metadata identifiers.
+pub fn primitive_ty_encodings<'tcx>(tcx: TyCtxt<'tcx>) -> Vec<(Ty<'tcx>, String)> {
+    typeid_itanium_cxx_abi::primitive_ty_encodings(tcx)
+}
+
+/// Builds a type of bounded depth from the specified bytes and checks that its encodings are
+/// deterministic, valid, and equivalent to its uncompressed encodings, returning the type built.
+/// This is for testing (e.g., fuzzing) the encoder only.
+pub fn check_arbitrary_ty_encoding<'tcx>(tcx: TyCtxt<'tcx>, data: &[u8]) -> Ty<'tcx> {
+    typeid_itanium_cxx_abi::check_arbitrary_ty_encoding(tcx, data)
+}
+
+/// Returns the encoding of the specified type transformed with the specified options, as the types
+/// of the parameters and return types of functions are. This is for benchmarking the encoder only.
+pub fn transform_and_encode_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    typeid_itanium_cxx_abi::transform_and_encode_ty(tcx, ty, options)
+}
+
+/// Returns the encoding of the name (i.e., the path with its namespace tags, crate disambiguator,
+/// and path disambiguators) of the specified user-defined type or item in type metadata
+/// identifiers. This is used by `#[rustc_ty_name_encoding]`.
+pub(crate) fn encode_ty_name(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    typeid_itanium_cxx_abi::encode_ty_name(tcx, def_id)
+}
+
+/// Returns the options functions are given a CFI type metadata identifier with when declared, from
+/// the options of the session: every combination of pointers generalized, integers normalized, the
+/// concrete self type used, and `impl Trait` types encoded via their bounds (when enabled), with
+/// the other options of the session applied to all of them. Some of them may result in the same
+/// type metadata identifiers (e.g., `USE_CONCRETE_SELF` for functions that aren't methods).
+pub fn declared_typeid_options(options: TypeIdOptions) -> Vec<TypeIdOptions> {
+    let variants = TypeIdOptions::GENERALIZE_POINTERS | TypeIdOptions::NORMALIZE_INTEGERS;
+    let all =
+        variants | TypeIdOptions::USE_CONCRETE_SELF | (options & TypeIdOptions::IMPL_TRAIT_BOUNDS);
+    let always = options - variants - TypeIdOptions::IMPL_TRAIT_BOUNDS;
+    (0..=all.bits())
+        .filter_map(TypeIdOptions::from_bits)
+        .filter(|options| all.contains(*options))
+        .map(|options| options | always)
+        .collect()
+}
 
 /// Returns a type metadata identifier for the specified FnAbi.
 pub fn typeid_for_fnabi<'tcx>(
@@ -46,6 +226,18 @@ pub fn typeid_for_fnabi<'tcx>(
     typeid_itanium_cxx_abi::typeid_for_fnabi(tcx, fn_abi, options)
 }
 
+/// Returns a type metadata identifier for the specified PolyFnSig (e.g., of a function pointer
+/// type), without computing its FnAbi. It's the same as the one for its FnAbi when no parameter is
+/// ignored, or with `TypeIdOptions::ENCODE_FN_SIGS`. (Functions with the "C" calling convention
+/// are always encoded using the Itanium C++ ABI, whatever `-Zsanitizer-cfi-typeid-scheme`.)
+pub fn typeid_for_fnsig<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_sig: PolyFnSig<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    typeid_itanium_cxx_abi::typeid_for_fnsig(tcx, fn_sig, options)
+}
+
 /// Returns a type metadata identifier for the specified Instance.
 pub fn typeid_for_instance<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -55,46 +247,81 @@ pub fn typeid_for_instance<'tcx>(
     typeid_itanium_cxx_abi::typeid_for_instance(tcx, instance, options)
 }
 
-/// Returns a KCFI type metadata identifier for the specified FnAbi.
-pub fn kcfi_typeid_for_fnabi<'tcx>(
-    tcx: TyCtxt<'tcx>,
-    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
-    options: TypeIdOptions,
-) -> u32 {
-    // A KCFI type metadata identifier is a 32-bit constant produced by taking the lower half of the
-    // xxHash64 of the type metadata identifier. (See llvm/llvm-project@cff5bef.)
-    let mut hash: XxHash64 = Default::default();
-    hash.write(typeid_itanium_cxx_abi::typeid_for_fnabi(tcx, fn_abi, options).as_bytes());
-    hash.finish() as u32
+/// The type metadata identifiers of a function that differ from the one of the function pointer
+/// type of a pointer to it, so that indirect calls through the pointer would fail the type tests
+/// (see `-Zcfi-assert-consistency`).
+#[derive(Debug)]
+pub struct TypeIdInconsistency<'tcx> {
+    pub instance: Instance<'tcx>,
+    /// The type metadata identifiers the function is defined with (i.e., with and without its
+    /// concrete self type).
+    pub definition: Vec<String>,
+    /// The type metadata identifier of the function pointer type.
+    pub fn_ptr: String,
 }
 
-/// Returns a KCFI type metadata identifier for the specified Instance.
-pub fn kcfi_typeid_for_instance<'tcx>(
+impl fmt::Display for TypeIdInconsistency<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signature = |typeid: &str| demangle_typeid(typeid).unwrap_or_default();
+        writeln!(
+            f,
+            "the type metadata identifiers of `{}` don't match its function pointer type's",
+            self.instance
+        )?;
+        for typeid in &self.definition {
+            writeln!(f, "  definition: {typeid} ({})", signature(typeid))?;
+        }
+        write!(f, "  function pointer: {} ({})", self.fn_ptr, signature(&self.fn_ptr))?;
+        if let Some(difference) = typeid_difference(&self.definition[0], &self.fn_ptr) {
+            write!(
+                f,
+                "\n  first difference: {}: `{}` (encoded as `{}`) vs `{}` (encoded as `{}`)",
+                difference.component,
+                difference.lhs,
+                difference.lhs_encoding,
+                difference.rhs,
+                difference.rhs_encoding
+            )?;
+            if let Some(note) = difference.note {
+                write!(f, "\n  note: {note}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the type metadata identifiers of the specified function and of the function pointer type
+/// of a pointer to it if the function isn't defined with the latter, that is, if the function can
+/// be reified (i.e., it isn't an intrinsic, a shim, or a `#[track_caller]` function, which is
+/// reified as a shim) and indirect calls through pointers to it would fail the type tests.
+pub fn typeid_inconsistency<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
-    mut options: TypeIdOptions,
-) -> u32 {
-    // KCFI support for Rust shares most of its implementation with the CFI support, with some key
-    // differences:
-    //
-    // 1. KCFI performs type tests differently and are implemented as different LLVM passes than CFI
-    //    to not require LTO.
-    // 2. KCFI has the limitation that a function or method may have one type id assigned only.
-    //
-    // Because of the limitation listed above (2), the current KCFI implementation (not CFI) does
-    // reifying of types (i.e., adds shims/trampolines for indirect calls in these cases) for:
-    //
-    // * Supporting casting between function items, closures, and Fn trait objects.
-    // * Supporting methods being cast as function pointers.
-    //
-    // This was implemented for KCFI support in #123106 and #123052 (which introduced the
-    // ReifyReason). The tracking issue for KCFI support for Rust is #123479.
-    if matches!(instance.def, InstanceDef::ReifyShim(_, Some(ReifyReason::FnPtr))) {
-        options.insert(TypeIdOptions::USE_CONCRETE_SELF);
+    options: TypeIdOptions,
+) -> Option<TypeIdInconsistency<'tcx>> {
+    let ty::InstanceDef::Item(def_id) = instance.def else {
+        return None;
+    };
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        || tcx.intrinsic(def_id).is_some()
+        || instance.def.requires_caller_location(tcx)
+        || instance.args.has_param()
+    {
+        return None;
     }
-    // A KCFI type metadata identifier is a 32-bit constant produced by taking the lower half of the
-    // xxHash64 of the type metadata identifier. (See llvm/llvm-project@cff5bef.)
-    let mut hash: XxHash64 = Default::default();
-    hash.write(typeid_itanium_cxx_abi::typeid_for_instance(tcx, instance, options).as_bytes());
-    hash.finish() as u32
+
+    // The function pointer type of a pointer to the function, as callers computing the type
+    // metadata identifier of a call through the pointer see it
+    let fn_sig = interpret::callee_fn_sig(tcx, instance)?;
+    let fn_abi =
+        tcx.fn_abi_of_fn_ptr(ty::ParamEnv::reveal_all().and((fn_sig, ty::List::empty()))).ok()?;
+    let fn_ptr = typeid_for_fnabi(tcx, fn_abi, options);
+
+    // Functions are defined with both (see `declare_fn`)
+    let mut definition = vec![
+        typeid_for_instance(tcx, instance, options),
+        typeid_for_instance(tcx, instance, options | TypeIdOptions::USE_CONCRETE_SELF),
+    ];
+    definition.dedup();
+    (!definition.contains(&fn_ptr)).then(|| TypeIdInconsistency { instance, definition, fn_ptr })
 }
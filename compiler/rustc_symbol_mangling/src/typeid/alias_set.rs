@@ -0,0 +1,148 @@
+//! Comparison of type metadata identifiers for LLVM Control Flow Integrity (CFI) and Kernel Control
+//! Flow Integrity (KCFI) support.
+//!
+//! Determines whether two type metadata identifiers (e.g., one computed by the Rust compiler and
+//! one by Clang, or ones computed by different compiler versions) would be in the same alias set
+//! (i.e., whether indirect calls through a pointer to one of the functions would be allowed to call
+//! the other) once encoded with a given set of options, for the lints, `-Zcfi-diff-typeids`, and
+//! external verification tooling.
+
+use rustc_data_structures::stack::ensure_sufficient_stack;
+
+use crate::typeid::itanium::{FnType, Type, TypeId};
+use crate::typeid::TypeIdOptions;
+
+#[cfg(test)]
+mod tests;
+
+/// Returns whether the specified type metadata identifiers are in the same alias set once the
+/// options that can be applied to an encoding are applied to them, that is, generalizing pointers
+/// (`GENERALIZE_POINTERS`), erasing const qualifiers (`ERASE_CONST`), erasing the mutability of
+/// references (`ERASE_REF_MUT`), generalizing arrays (`GENERALIZE_ARRAYS`), and erasing return
+/// types (`ERASE_RETURN_TYPES`), with the encoding suffixes of the applied options added.
+///
+/// The other options need the types the encodings were computed from (e.g., integers are
+/// normalized from their sizes on the target), so the type metadata identifiers are expected to be
+/// computed with them already, and their encoding suffixes (and salts) are compared as is. Arrays
+/// are generalized as in the types of functions with the "C" calling convention, as the calling
+/// convention isn't encoded. Type metadata identifiers that aren't valid are only in the same
+/// alias set as themselves.
+pub fn same_alias_set(lhs: &str, rhs: &str, options: TypeIdOptions) -> bool {
+    if lhs == rhs {
+        return true;
+    }
+    let (Some(lhs), Some(rhs)) = (TypeId::parse(lhs), TypeId::parse(rhs)) else {
+        return false;
+    };
+    apply_options(lhs.expand_substitutions(), options)
+        == apply_options(rhs.expand_substitutions(), options)
+}
+
+/// Applies the options to the specified (expanded) type metadata identifier as the encoder does,
+/// adding the encoding suffixes of the applied options.
+fn apply_options(mut typeid: TypeId, options: TypeIdOptions) -> TypeId {
+    typeid.class = typeid.class.map(|class| transform(class, options));
+    if options.contains(TypeIdOptions::ERASE_RETURN_TYPES) {
+        typeid.fn_ty.ret = Type::Builtin("v");
+        typeid.returns = true;
+    } else {
+        typeid.fn_ty.ret = transform(typeid.fn_ty.ret, options);
+    }
+    typeid.fn_ty.params =
+        typeid.fn_ty.params.into_iter().map(|param| transform(param, options)).collect();
+    if options.contains(TypeIdOptions::GENERALIZE_POINTERS) {
+        typeid.generalized = true;
+    }
+    if options.contains(TypeIdOptions::GENERALIZE_ARRAYS) {
+        typeid.arrays = true;
+    }
+    typeid
+}
+
+/// Applies the options to the specified (expanded) type as `TransformTy` does to the type it was
+/// encoded from.
+fn transform(ty: Type, options: TypeIdOptions) -> Type {
+    let generalize = options.contains(TypeIdOptions::GENERALIZE_POINTERS);
+    let erase_const = options.contains(TypeIdOptions::ERASE_CONST);
+    let erase_ref_mut = options.contains(TypeIdOptions::ERASE_REF_MUT);
+    let void = || Type::Builtin("v");
+    let is_ref = |ty: &Type| matches!(ty, Type::Vendor { name, .. } if name == "ref");
+    let map = |ty: Box<Type>| Box::new(transform(*ty, options));
+    let map_all = |tys: Vec<Type>| -> Vec<Type> {
+        tys.into_iter().map(|ty| transform(ty, options)).collect()
+    };
+
+    // Types may be nested arbitrarily deeply (see `itanium::Parser::ty`)
+    ensure_sufficient_stack(|| match ty {
+        // Raw pointers and function pointers (i.e., `*const ()`, or `*mut ()` if const qualifiers
+        // are erased, as function pointers are generalized as `*const ()`)
+        Type::Ptr(pointee) if generalize => {
+            if matches!(*pointee, Type::Const(_) | Type::Fn(_)) && !erase_const {
+                Type::Ptr(Box::new(Type::Const(Box::new(void()))))
+            } else {
+                Type::Ptr(Box::new(void()))
+            }
+        }
+        Type::Ptr(pointee) => match *pointee {
+            Type::Const(ty) if erase_const => Type::Ptr(map(ty)),
+            pointee => Type::Ptr(Box::new(transform(pointee, options))),
+        },
+
+        // Mutable references (i.e., `&mut ()`, or `&()` if the mutability is erased)
+        Type::Mut(ty) if is_ref(&ty) => {
+            let ty = if generalize {
+                Type::Vendor { name: "ref".to_string(), args: Some(vec![void()]) }
+            } else {
+                transform(*ty, options)
+            };
+            if erase_ref_mut { ty } else { Type::Mut(Box::new(ty)) }
+        }
+        Type::Vendor { name, args: Some(_) } if generalize && name == "ref" => {
+            Type::Vendor { name, args: Some(vec![void()]) }
+        }
+
+        // C++ lvalue references (i.e., `const T&` for `&T` and `T&` for `&mut T`), whose
+        // mutability is erased as const qualifiers, unless these are also erased
+        Type::Ref(pointee) => {
+            let is_mut = !matches!(*pointee, Type::Const(_));
+            let is_mut = erase_const || (is_mut && !erase_ref_mut);
+            let pointee = match *pointee {
+                _ if generalize => void(),
+                Type::Const(ty) => transform(*ty, options),
+                pointee => transform(pointee, options),
+            };
+            if is_mut {
+                Type::Ref(Box::new(pointee))
+            } else {
+                Type::Ref(Box::new(Type::Const(Box::new(pointee))))
+            }
+        }
+
+        // Arrays (i.e., `[T]` for `[T; N]`)
+        Type::Array { ty, .. } if options.contains(TypeIdOptions::GENERALIZE_ARRAYS) => {
+            Type::Vendor { name: "slice".to_string(), args: Some(vec![transform(*ty, options)]) }
+        }
+
+        Type::Builtin(_) | Type::Substitution(_) | Type::Region(_) | Type::Name(_) => ty,
+        Type::Vendor { name, args } => Type::Vendor { name, args: args.map(map_all) },
+        Type::Projection { name, args, term } => {
+            Type::Projection { name, args: args.map(map_all), term: map(term) }
+        }
+        Type::Class { nested, prefix, names, template_args } => {
+            Type::Class { nested, prefix, names, template_args: template_args.map(map_all) }
+        }
+        Type::Pattern { ty, pat } => Type::Pattern { ty: map(ty), pat },
+        Type::Dyn { star, predicates, region } => {
+            Type::Dyn { star, predicates: map_all(predicates), region }
+        }
+        Type::Mut(ty) => Type::Mut(map(ty)),
+        Type::Const(ty) => Type::Const(map(ty)),
+        Type::Fn(fn_ty) => {
+            let FnType { ret, params, c_variadic } = *fn_ty;
+            let ret = transform(ret, options);
+            Type::Fn(Box::new(FnType { ret, params: map_all(params), c_variadic }))
+        }
+        Type::Array { len, ty } => Type::Array { len, ty: map(ty) },
+        Type::Literal { ty, value } => Type::Literal { ty: map(ty), value },
+    })
+}
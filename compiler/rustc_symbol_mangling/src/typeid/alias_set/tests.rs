@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn test_same_alias_set_identical() {
+    assert!(same_alias_set("_ZTSFu3i32S_E", "_ZTSFu3i32S_E", TypeIdOptions::empty()));
+    assert!(!same_alias_set("_ZTSFu3i32S_E", "_ZTSFu3u32S_E", TypeIdOptions::empty()));
+    // Type metadata identifiers that differ in their compression only
+    assert!(same_alias_set("_ZTSFvPKu3i32PS_E", "_ZTSFvPKu3i32Pu3i32E", TypeIdOptions::empty()));
+}
+
+#[test]
+fn test_same_alias_set_generalize_pointers() {
+    let options = TypeIdOptions::GENERALIZE_POINTERS;
+    assert!(!same_alias_set("_ZTSFvu3refIu3i32EE", "_ZTSFvu3refIu2u8EE", TypeIdOptions::empty()));
+    assert!(same_alias_set("_ZTSFvu3refIu3i32EE", "_ZTSFvu3refIu2u8EE", options));
+    assert!(same_alias_set(
+        "_ZTSFvu3refIu3i32EU3mutS0_PKS_PS_E",
+        "_ZTSFvu3refIvEU3mutS_PKvPvE.generalized",
+        options
+    ));
+    assert!(same_alias_set("_ZTSFvPFu3i32S_EE", "_ZTSFvPKvE.generalized", options));
+    assert!(!same_alias_set("_ZTSFvU3mutu3refIu3i32EE", "_ZTSFvu3refIvEE.generalized", options));
+    assert!(!same_alias_set("_ZTSFvPKu3i32E", "_ZTSFvPvE.generalized", options));
+}
+
+#[test]
+fn test_same_alias_set_erase_const() {
+    let options = TypeIdOptions::ERASE_CONST;
+    assert!(!same_alias_set("_ZTSFvPKu3i32E", "_ZTSFvPu3i32E", TypeIdOptions::empty()));
+    assert!(same_alias_set("_ZTSFvPKu3i32E", "_ZTSFvPu3i32E", options));
+    assert!(same_alias_set("_ZTSFvPKu3i32PS_E", "_ZTSFvPu3i32S0_E", options));
+    assert!(same_alias_set("_ZTSFvRKu3i32E", "_ZTSFvRu3i32E", options));
+    // References that aren't encoded as C++ references aren't const-qualified
+    assert!(!same_alias_set("_ZTSFvu3refIu3i32EE", "_ZTSFvU3mutu3refIu3i32EE", options));
+    assert!(same_alias_set(
+        "_ZTSFvPFu3i32S_EE",
+        "_ZTSFvPvE.generalized",
+        options | TypeIdOptions::GENERALIZE_POINTERS
+    ));
+}
+
+#[test]
+fn test_same_alias_set_erase_ref_mut() {
+    let options = TypeIdOptions::ERASE_REF_MUT;
+    assert!(same_alias_set("_ZTSFvU3mutu3refIu3i32EE", "_ZTSFvu3refIu3i32EE", options));
+    assert!(same_alias_set("_ZTSFvRu3i32E", "_ZTSFvRKu3i32E", options));
+    assert!(same_alias_set(
+        "_ZTSFvU3mutu3refIu3i32EE",
+        "_ZTSFvu3refIvEE.generalized",
+        options | TypeIdOptions::GENERALIZE_POINTERS
+    ));
+    // Raw pointers keep their mutability
+    assert!(!same_alias_set("_ZTSFvPKu3i32E", "_ZTSFvPu3i32E", options));
+}
+
+#[test]
+fn test_same_alias_set_generalize_arrays() {
+    let options = TypeIdOptions::GENERALIZE_ARRAYS;
+    assert!(!same_alias_set("_ZTSFvPA4u3i32E", "_ZTSFvPA8u3i32E", TypeIdOptions::empty()));
+    assert!(same_alias_set("_ZTSFvPA4u3i32E", "_ZTSFvPA8u3i32E", options));
+    assert!(same_alias_set("_ZTSFvPA4u3i32E", "_ZTSFvPu5sliceIu3i32EE.arrays", options));
+}
+
+#[test]
+fn test_same_alias_set_erase_return_types() {
+    let options = TypeIdOptions::ERASE_RETURN_TYPES;
+    assert!(same_alias_set("_ZTSFu3i32S_E", "_ZTSFu3u32u3i32E", options));
+    assert!(same_alias_set("_ZTSFu3i32S_E", "_ZTSFvu3i32E.returns", options));
+    assert!(!same_alias_set("_ZTSFu3i32S_E", "_ZTSFu3i32u3u32E", options));
+}
+
+#[test]
+fn test_same_alias_set_suffixes() {
+    let options = TypeIdOptions::all();
+    assert!(!same_alias_set("_ZTSFvu3i32E", "_ZTSFvu3i32E.normalized", options));
+    assert!(!same_alias_set("_ZTSFvu3i32E.ptrsized", "_ZTSFvu3i32E", options));
+    assert!(!same_alias_set("_ZTSFvvE.saltAndroid", "_ZTSFvvE", options));
+    assert!(!same_alias_set(
+        "_ZTSFvvE",
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFvvE",
+        options
+    ));
+}
+
+#[test]
+fn test_same_alias_set_invalid() {
+    assert!(same_alias_set("_ZTSFvu3i32", "_ZTSFvu3i32", TypeIdOptions::empty()));
+    assert!(!same_alias_set("_ZTSFvu3i32", "_ZTSFvu3i32E", TypeIdOptions::all()));
+}
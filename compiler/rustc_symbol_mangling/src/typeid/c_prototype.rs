@@ -0,0 +1,366 @@
+/// Type metadata identifiers for C function prototypes, computed as Clang computes them, for
+/// checking the compatibility of the type metadata identifiers of Rust `extern "C"` functions with
+/// the ones of their C counterparts (see the `tests/ui-fulldeps/cfi-clang-conformance.rs` test).
+///
+/// Only the C subset needed for function prototypes at FFI boundaries is supported (i.e., the
+/// arithmetic types, the fixed-width integer typedefs, `struct`, `union`, and `enum` types,
+/// pointers, one-dimensional array parameters, function pointers, and variadic functions), and
+/// the prototype must be a single function declarator (e.g., `int foo(const char *, ...)`).
+use rustc_target::spec::Target;
+
+use crate::typeid::itanium::{FnType, Type, TypeId};
+use crate::typeid::TypeIdOptions;
+
+#[cfg(test)]
+mod tests;
+
+/// The sizes and signedness of the C types that differ between targets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CDataModel {
+    /// Whether `char` is signed.
+    pub char_signed: bool,
+    /// The size of `int` in bits.
+    pub int_bits: u64,
+    /// The size of `long` in bits.
+    pub long_bits: u64,
+}
+
+impl CDataModel {
+    /// The LP64 data model (with a signed `char`), as on x86_64 Linux.
+    pub const LP64: CDataModel = CDataModel { char_signed: true, int_bits: 32, long_bits: 64 };
+
+    /// Returns the data model of the specified target, matching the `core::ffi` type aliases on
+    /// most targets.
+    pub fn for_target(target: &Target) -> CDataModel {
+        let char_signed = target.is_like_osx
+            || target.is_like_windows
+            || !matches!(
+                &*target.arch,
+                "aarch64"
+                    | "arm"
+                    | "csky"
+                    | "hexagon"
+                    | "msp430"
+                    | "powerpc"
+                    | "powerpc64"
+                    | "riscv32"
+                    | "riscv64"
+                    | "s390x"
+                    | "xtensa"
+            );
+        let long_bits = if target.pointer_width == 64 && !target.is_like_windows { 64 } else { 32 };
+        CDataModel { char_signed, int_bits: target.c_int_width.parse().unwrap_or(32), long_bits }
+    }
+}
+
+/// Tokenizes a C prototype into identifiers, numbers, punctuators, and the ellipsis.
+fn tokenize(prototype: &str) -> Option<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = prototype.trim_start();
+    while !rest.is_empty() {
+        let len = if rest.starts_with("...") {
+            3
+        } else if rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+            rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len())
+        } else if rest.starts_with(['*', '(', ')', ',', '[', ']']) {
+            1
+        } else {
+            return None;
+        };
+        let (token, next) = rest.split_at(len);
+        tokens.push(token);
+        rest = next.trim_start();
+    }
+    Some(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [&'a str],
+    data_model: CDataModel,
+    /// Whether integer types are normalized (i.e., `TypeIdOptions::NORMALIZE_INTEGERS`).
+    normalize_integers: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.first().copied()
+    }
+
+    fn bump(&mut self) {
+        self.tokens = &self.tokens[1..];
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.peek() == Some(token) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Option<()> {
+        self.eat(token).then_some(())
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        let token = self.peek()?;
+        let is_ident = token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+        is_ident.then(|| {
+            self.bump();
+            token
+        })
+    }
+
+    /// Parses the declaration specifiers of a declaration (i.e., its qualifiers and base type).
+    fn specifiers(&mut self) -> Option<Type> {
+        let mut is_const = false;
+        let mut tag = None;
+        let mut words = Vec::new();
+        loop {
+            match self.peek()? {
+                "const" => is_const = true,
+                "struct" | "union" | "enum" => {
+                    self.bump();
+                    tag = Some(self.ident()?);
+                    continue;
+                }
+                word @ ("void" | "_Bool" | "bool" | "char" | "short" | "int" | "long"
+                | "signed" | "unsigned" | "float" | "double" | "int8_t" | "int16_t"
+                | "int32_t" | "int64_t" | "uint8_t" | "uint16_t" | "uint32_t"
+                | "uint64_t") => words.push(word),
+                _ => break,
+            }
+            self.bump();
+        }
+        let ty = match tag {
+            Some(name) if words.is_empty() => Type::Name(name.to_string()),
+            Some(_) => return None,
+            None => self.base_type(words)?,
+        };
+        Some(if is_const { Type::Const(Box::new(ty)) } else { ty })
+    }
+
+    /// Returns the arithmetic type with the specified type specifiers (e.g., `unsigned long int`),
+    /// with integer types normalized if `normalize_integers` is set.
+    fn base_type(&self, mut words: Vec<&str>) -> Option<Type> {
+        let signed = words.contains(&"signed");
+        let unsigned = words.contains(&"unsigned");
+        if signed && unsigned {
+            return None;
+        }
+        let sign = signed || unsigned;
+        words.retain(|&word| word != "signed" && word != "unsigned");
+        words.sort_unstable();
+
+        let data_model = self.data_model;
+        let integer = |code: &'static str, signed: bool, bits: u64| {
+            if self.normalize_integers {
+                let name = format!("{}{bits}", if signed { 'i' } else { 'u' });
+                Type::Vendor { name, args: None }
+            } else {
+                Type::Builtin(code)
+            }
+        };
+        let int = |codes: [&'static str; 2], bits: u64| {
+            integer(codes[unsigned as usize], !unsigned, bits)
+        };
+        let ty = match words[..] {
+            ["void"] if !sign => Type::Builtin("v"),
+            ["_Bool"] | ["bool"] if !sign => integer("b", false, 8),
+            ["float"] if !sign => Type::Builtin("f"),
+            ["double"] if !sign => Type::Builtin("d"),
+            ["double", "long"] if !sign => Type::Builtin("e"),
+            ["char"] if !sign => integer("c", data_model.char_signed, 8),
+            ["char"] => int(["a", "h"], 8),
+            ["short"] | ["int", "short"] => int(["s", "t"], 16),
+            [] if sign => int(["i", "j"], data_model.int_bits),
+            ["int"] => int(["i", "j"], data_model.int_bits),
+            ["long"] | ["int", "long"] => int(["l", "m"], data_model.long_bits),
+            ["long", "long"] | ["int", "long", "long"] => int(["x", "y"], 64),
+            // The fixed-width integer typedefs, which are the smallest standard integer types of
+            // their sizes (e.g., `int64_t` is `long` on LP64 targets and `long long` otherwise)
+            [name] if !sign && name.ends_with("_t") => {
+                let signed = name.starts_with('i');
+                let bits = name.trim_start_matches('u').trim_start_matches("int");
+                let bits: u64 = bits.trim_end_matches("_t").parse().ok()?;
+                let codes = match bits {
+                    8 => ["a", "h"],
+                    16 => ["s", "t"],
+                    _ if bits == data_model.int_bits => ["i", "j"],
+                    _ if bits == data_model.long_bits => ["l", "m"],
+                    _ => ["x", "y"],
+                };
+                integer(codes[!signed as usize], signed, bits)
+            }
+            _ => return None,
+        };
+        Some(ty)
+    }
+
+    /// Parses the pointer declarators (e.g., `* const *`) applied to a type.
+    fn pointers(&mut self, mut ty: Type) -> Type {
+        while self.eat("*") {
+            ty = Type::Ptr(Box::new(ty));
+            if self.eat("const") {
+                ty = Type::Const(Box::new(ty));
+            }
+        }
+        ty
+    }
+
+    /// Parses a parameter list (i.e., `(<parameter-type1..parameter-typeN>[, ...])`), returning
+    /// the function type with the specified return type.
+    fn fn_ty(&mut self, ret: Type) -> Option<FnType> {
+        self.expect("(")?;
+        let mut params = Vec::new();
+        let mut c_variadic = false;
+        if self.tokens.get(..2) == Some(&["void", ")"]) {
+            self.bump();
+        } else if self.peek() != Some(")") {
+            loop {
+                if self.eat("...") {
+                    c_variadic = !params.is_empty();
+                    if !c_variadic {
+                        return None;
+                    }
+                    break;
+                }
+                params.push(self.param()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+        self.expect(")")?;
+        // Empty parameter lists are encoded with a void parameter specifier "v"
+        if params.is_empty() && !c_variadic {
+            params.push(Type::Builtin("v"));
+        }
+        Some(FnType { ret: unqualified(ret), params, c_variadic })
+    }
+
+    /// Parses a parameter declaration, adjusting its type as a parameter of a function type (i.e.,
+    /// without qualifiers, and with arrays decayed into pointers).
+    fn param(&mut self) -> Option<Type> {
+        let ty = self.specifiers()?;
+        let ty = self.pointers(ty);
+        let ty = if self.eat("(") {
+            // A function pointer (e.g., `int (*callback)(int)`)
+            self.expect("*")?;
+            while self.eat("const") {}
+            let _ = self.ident();
+            self.expect(")")?;
+            Type::Ptr(Box::new(Type::Fn(Box::new(self.fn_ty(ty)?))))
+        } else {
+            let _ = self.ident();
+            if self.eat("[") {
+                // Array parameters are pointers to their elements
+                let size = self.peek().filter(|token| token.bytes().all(|b| b.is_ascii_digit()));
+                if size.is_some() {
+                    self.bump();
+                }
+                self.expect("]")?;
+                Type::Ptr(Box::new(ty))
+            } else {
+                ty
+            }
+        };
+        let ty = unqualified(ty);
+        (ty != Type::Builtin("v")).then_some(ty)
+    }
+
+    /// Parses a function prototype (i.e., `<return-type> [<name>](<parameters>)`).
+    fn prototype(&mut self) -> Option<FnType> {
+        let ret = self.specifiers()?;
+        let ret = self.pointers(ret);
+        let _ = self.ident();
+        let fn_ty = self.fn_ty(ret)?;
+        self.tokens.is_empty().then_some(fn_ty)
+    }
+}
+
+/// Removes the top-level qualifiers of a type.
+fn unqualified(ty: Type) -> Type {
+    match ty {
+        Type::Const(ty) => *ty,
+        ty => ty,
+    }
+}
+
+/// Generalizes a pointer type of the parameter or return types of the outermost function type into
+/// a `void` pointer with the qualifiers of its pointee, as Clang does if
+/// `TypeIdOptions::GENERALIZE_POINTERS` is set.
+fn generalize(ty: Type) -> Type {
+    match ty {
+        Type::Ptr(pointee) => {
+            let void = Type::Builtin("v");
+            let pointee =
+                if matches!(*pointee, Type::Const(_)) { Type::Const(Box::new(void)) } else { void };
+            Type::Ptr(Box::new(pointee))
+        }
+        ty => ty,
+    }
+}
+
+/// Replaces every repeated component of a type by a substitution of the dictionary entry of its
+/// first occurrence, as Clang does, where every type but the builtin types is a substitution
+/// candidate, including the function types of function pointers, which the encoder doesn't add to
+/// the dictionary (see <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression>).
+fn compress(ty: Type, dict: &mut Vec<Type>) -> Type {
+    if let Type::Builtin(_) = ty {
+        return ty;
+    }
+    if let Some(index) = dict.iter().position(|other| *other == ty) {
+        return Type::Substitution(index);
+    }
+    let compressed = match ty.clone() {
+        Type::Const(ty) => Type::Const(Box::new(compress(*ty, dict))),
+        Type::Ptr(ty) => Type::Ptr(Box::new(compress(*ty, dict))),
+        Type::Fn(fn_ty) => Type::Fn(Box::new(compress_fn_ty(*fn_ty, dict))),
+        // The C types are only builtin types, normalized integers, names, or the above
+        ty => ty,
+    };
+    dict.push(ty);
+    compressed
+}
+
+/// Compresses the return and parameter types of a function type (see `compress`).
+fn compress_fn_ty(fn_ty: FnType, dict: &mut Vec<Type>) -> FnType {
+    let ret = compress(fn_ty.ret, dict);
+    let params = fn_ty.params.into_iter().map(|param| compress(param, dict)).collect();
+    FnType { ret, params, c_variadic: fn_ty.c_variadic }
+}
+
+/// Returns the type metadata identifier Clang computes for a function with the specified C
+/// prototype (e.g., `void foo(const int *, ...)`) for the specified data model, with
+/// `TypeIdOptions::NORMALIZE_INTEGERS` and `TypeIdOptions::GENERALIZE_POINTERS` corresponding to
+/// the Clang `-fsanitize-cfi-icall-experimental-normalize-integers` and
+/// `-fsanitize-cfi-icall-generalize-pointers` options, respectively. Returns `None` if the
+/// prototype isn't supported.
+pub fn typeid_for_c_prototype(
+    prototype: &str,
+    data_model: CDataModel,
+    options: TypeIdOptions,
+) -> Option<String> {
+    let tokens = tokenize(prototype)?;
+    let normalize_integers = options.contains(TypeIdOptions::NORMALIZE_INTEGERS);
+    let mut fn_ty = Parser { tokens: &tokens, data_model, normalize_integers }.prototype()?;
+    let generalized = options.contains(TypeIdOptions::GENERALIZE_POINTERS);
+    if generalized {
+        fn_ty.ret = generalize(fn_ty.ret);
+        fn_ty.params = fn_ty.params.into_iter().map(generalize).collect();
+    }
+
+    let typeid = TypeId {
+        class: None,
+        fn_ty: compress_fn_ty(fn_ty, &mut Vec::new()),
+        normalized: normalize_integers,
+        ptrsized: false,
+        generalized,
+        arrays: false,
+        returns: false,
+        salt: None,
+    };
+    Some(typeid.to_string())
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+// The type metadata identifiers are the ones Clang computes for the prototypes (e.g., with
+// `-fsanitize=cfi-icall -fsanitize-cfi-icall-experimental-normalize-integers`) for x86_64 Linux.
+
+#[track_caller]
+fn assert_typeid(prototype: &str, options: TypeIdOptions, expected: &str) {
+    assert_eq!(
+        typeid_for_c_prototype(prototype, CDataModel::LP64, options).as_deref(),
+        Some(expected),
+        "`{prototype}`"
+    );
+}
+
+#[test]
+fn test_builtin_types() {
+    let options = TypeIdOptions::empty();
+    assert_typeid("void foo(void)", options, "_ZTSFvvE");
+    assert_typeid("void foo()", options, "_ZTSFvvE");
+    assert_typeid("int foo(int, unsigned, long, unsigned long)", options, "_ZTSFiijlmE");
+    assert_typeid("void foo(char, signed char, unsigned char)", options, "_ZTSFvcahE");
+    assert_typeid(
+        "void foo(short int, unsigned short, long long, unsigned long long int)",
+        options,
+        "_ZTSFvstxyE",
+    );
+    assert_typeid("_Bool foo(float, double, long double)", options, "_ZTSFbfdeE");
+    assert_typeid("void foo(int8_t, uint16_t, int32_t, uint64_t)", options, "_ZTSFvatimE");
+}
+
+#[test]
+fn test_pointer_types() {
+    let options = TypeIdOptions::empty();
+    assert_typeid("void foo(const int *x, int *y, int *z)", options, "_ZTSFvPKiPiS1_E");
+    assert_typeid("void foo(int *const x, const int *const *y)", options, "_ZTSFvPiPKPKiE");
+    assert_typeid("char *foo(const char *, ...)", options, "_ZTSFPcPKczE");
+    assert_typeid("void foo(int x[], int y[4])", options, "_ZTSFvPiS_E");
+    assert_typeid("void foo(void *, const void *)", options, "_ZTSFvPvPKvE");
+}
+
+#[test]
+fn test_tag_types() {
+    let options = TypeIdOptions::empty();
+    assert_typeid("void foo(struct Point, struct Point *)", options, "_ZTSFv5PointPS_E");
+    assert_typeid(
+        "union Value foo(enum Kind, const union Value *)",
+        options,
+        "_ZTSF5Value4KindPKS_E",
+    );
+}
+
+#[test]
+fn test_function_pointer_types() {
+    let options = TypeIdOptions::empty();
+    assert_typeid("void foo(int (*callback)(int))", options, "_ZTSFvPFiiEE");
+    // The function type is a substitution candidate as well as the pointer to it
+    assert_typeid("void foo(void (*)(void), void (*)(void))", options, "_ZTSFvPFvvES0_E");
+    assert_typeid("int *foo(int *(*)(int *), int *)", options, "_ZTSFPiPFS_S_ES_E");
+}
+
+#[test]
+fn test_normalized_integers() {
+    let options = TypeIdOptions::NORMALIZE_INTEGERS;
+    assert_typeid("int foo(int)", options, "_ZTSFu3i32S_E.normalized");
+    assert_typeid("long foo(long long, int64_t)", options, "_ZTSFu3i64S_S_E.normalized");
+    assert_typeid(
+        "unsigned char foo(_Bool, char, signed char)",
+        options,
+        "_ZTSFu2u8S_u2i8S0_E.normalized",
+    );
+    assert_typeid("void foo(const unsigned short *, float)", options, "_ZTSFvPKu3u16fE.normalized");
+}
+
+#[test]
+fn test_generalized_pointers() {
+    let options = TypeIdOptions::GENERALIZE_POINTERS;
+    assert_typeid(
+        "void foo(const int *, int **, int (*)(int))",
+        options,
+        "_ZTSFvPKvPvS1_E.generalized",
+    );
+    assert_typeid("char *foo(const char *const *)", options, "_ZTSFPvPKvE.generalized");
+    let options = TypeIdOptions::NORMALIZE_INTEGERS | TypeIdOptions::GENERALIZE_POINTERS;
+    assert_typeid("int foo(int, int *)", options, "_ZTSFu3i32S_PvE.normalized.generalized");
+}
+
+#[test]
+fn test_data_models() {
+    let llp64 = CDataModel { char_signed: true, int_bits: 32, long_bits: 32 };
+    let typeid = |prototype| {
+        typeid_for_c_prototype(prototype, llp64, TypeIdOptions::NORMALIZE_INTEGERS).unwrap()
+    };
+    assert_eq!(typeid("long foo(int64_t)"), "_ZTSFu3i32u3i64E.normalized");
+    let unsigned_char = CDataModel { char_signed: false, ..CDataModel::LP64 };
+    assert_eq!(
+        typeid_for_c_prototype("char foo(void)", unsigned_char, TypeIdOptions::NORMALIZE_INTEGERS),
+        Some("_ZTSFu2u8vE.normalized".to_string())
+    );
+}
+
+#[test]
+fn test_invalid_prototypes() {
+    for prototype in [
+        "",
+        "int",
+        "int foo(",
+        "int foo(int",
+        "int foo(int) bar",
+        "int foo(...)",
+        "int foo(void, int)",
+        "int foo(signed unsigned)",
+        "int foo(struct)",
+        "int foo(long long long)",
+        "int foo(size_t)",
+        "int foo(int @)",
+    ] {
+        assert_eq!(
+            typeid_for_c_prototype(prototype, CDataModel::LP64, TypeIdOptions::empty()),
+            None,
+            "`{prototype}` was accepted"
+        );
+    }
+}
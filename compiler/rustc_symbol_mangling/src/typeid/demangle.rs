@@ -0,0 +1,525 @@
+//! Demangling of type metadata identifiers for LLVM Control Flow Integrity (CFI) and Kernel Control
+//! Flow Integrity (KCFI) support.
+//!
+//! Renders type metadata identifiers (i.e., the encoding strings, such as `_ZTSFvu3refIu3i32EE`)
+//! back into Rust-like function signatures (such as `fn(&i32)`) for diagnostics and debugging
+//! output, and finds where two type metadata identifiers diverge. The rendering is meant for humans
+//! and is lossy (e.g., crate disambiguators and regions are not rendered). Type metadata identifiers
+//! are parsed with the parser the encoder is checked against (see `itanium`).
+
+use std::iter;
+
+use rustc_data_structures::stack::ensure_sufficient_stack;
+
+use crate::typeid::itanium::{ClassPrefix, FnType, Type, TypeId};
+
+#[cfg(test)]
+mod tests;
+
+/// The Rust-like renderings of the builtin types (see
+/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-builtin>).
+const BUILTIN_TYPES: &[(&str, &str)] = &[
+    ("v", "()"),
+    ("b", "bool"),
+    ("c", "c_char"),
+    ("a", "c_schar"),
+    ("h", "c_uchar"),
+    ("s", "c_short"),
+    ("t", "c_ushort"),
+    ("i", "c_int"),
+    ("j", "c_uint"),
+    ("l", "c_long"),
+    ("m", "c_ulong"),
+    ("x", "c_longlong"),
+    ("y", "c_ulonglong"),
+    ("n", "i128"),
+    ("o", "u128"),
+    ("w", "wchar_t"),
+    ("f", "f32"),
+    ("d", "f64"),
+    ("e", "c_longdouble"),
+    ("g", "f128"),
+    ("Dh", "f16"),
+    ("Dd", "decimal64"),
+    ("De", "decimal128"),
+    ("Df", "decimal32"),
+    ("Di", "char32_t"),
+    ("Ds", "char16_t"),
+    ("Du", "char8_t"),
+    ("Dn", "nullptr_t"),
+];
+
+/// A return or parameter type of a function type.
+struct Component {
+    /// The encoding of the type (which may be a substitution).
+    encoding: String,
+    /// The Rust-like rendering of the type.
+    rendered: String,
+}
+
+/// The components of a type metadata identifier.
+struct TypeIdComponents {
+    /// The class of a pointer to member function type (see
+    /// `-Zsanitizer-cfi-member-function-pointers`).
+    class: Option<Component>,
+    ret: Component,
+    params: Vec<Component>,
+    suffixes: Vec<String>,
+}
+
+impl TypeIdComponents {
+    fn parse(typeid: &str) -> Option<Self> {
+        // The components are encoded as they are in the type metadata identifier, and rendered
+        // with their substitutions expanded
+        let typeid = TypeId::parse(typeid)?;
+        let expanded = typeid.clone().expand_substitutions();
+        let component = |ty: &Type, expanded: &Type| {
+            Some(Component { encoding: ty.to_string(), rendered: render(expanded)? })
+        };
+
+        let class = match (&typeid.class, &expanded.class) {
+            (Some(class), Some(expanded)) => Some(component(class, expanded)?),
+            _ => None,
+        };
+        let ret = component(&typeid.fn_ty.ret, &expanded.fn_ty.ret)?;
+        let mut params = if has_no_params(&typeid.fn_ty) {
+            Vec::new()
+        } else {
+            iter::zip(&typeid.fn_ty.params, &expanded.fn_ty.params)
+                .map(|(param, expanded)| component(param, expanded))
+                .collect::<Option<Vec<_>>>()?
+        };
+        if typeid.fn_ty.c_variadic {
+            params.push(Component { encoding: "z".to_string(), rendered: "...".to_string() });
+        }
+
+        // Encoding suffixes
+        let mut suffixes = [
+            ("normalized", typeid.normalized),
+            ("ptrsized", typeid.ptrsized),
+            ("generalized", typeid.generalized),
+            ("arrays", typeid.arrays),
+            ("returns", typeid.returns),
+        ]
+        .into_iter()
+        .filter_map(|(suffix, is_present)| is_present.then(|| suffix.to_string()))
+        .collect::<Vec<_>>();
+        if let Some(salt) = &typeid.salt {
+            suffixes.push(format!("salt {salt}"));
+        }
+
+        Some(TypeIdComponents { class, ret, params, suffixes })
+    }
+
+    fn render(&self) -> String {
+        let params = self.params.iter().map(|param| &param.rendered[..]);
+        let mut s = render_fn_type(&self.ret.rendered, params);
+        if let Some(class) = &self.class {
+            s = format!("<{}>::{s}", class.rendered);
+        }
+        if !self.suffixes.is_empty() {
+            s.push_str(&format!(" ({})", self.suffixes.join(", ")));
+        }
+        s
+    }
+}
+
+/// Returns the Rust-like function signature for the specified type metadata identifier, or `None`
+/// if it isn't a valid type metadata identifier.
+pub fn demangle_typeid(typeid: &str) -> Option<String> {
+    TypeIdComponents::parse(typeid).map(|components| components.render())
+}
+
+/// The first difference between two type metadata identifiers.
+#[derive(Debug, PartialEq)]
+pub struct TypeIdDifference {
+    /// The first differing component (e.g., `parameter #2`).
+    pub component: String,
+    /// The Rust-like rendering of the component of the first type metadata identifier.
+    pub lhs: String,
+    /// The encoding of the component of the first type metadata identifier.
+    pub lhs_encoding: String,
+    /// The Rust-like rendering of the component of the second type metadata identifier.
+    pub rhs: String,
+    /// The encoding of the component of the second type metadata identifier.
+    pub rhs_encoding: String,
+    /// A likely explanation for the difference, if any.
+    pub note: Option<&'static str>,
+}
+
+/// Returns the first difference between the specified type metadata identifiers, or `None` if they
+/// are identical.
+pub fn typeid_difference(lhs: &str, rhs: &str) -> Option<TypeIdDifference> {
+    if lhs == rhs {
+        return None;
+    }
+
+    let difference = |component: &str, lhs: &Component, rhs: &Component, note| TypeIdDifference {
+        component: component.to_string(),
+        lhs: lhs.rendered.clone(),
+        lhs_encoding: lhs.encoding.clone(),
+        rhs: rhs.rendered.clone(),
+        rhs_encoding: rhs.encoding.clone(),
+        note,
+    };
+    let whole = |note| {
+        let component = |typeid: &str| Component {
+            encoding: typeid.to_string(),
+            rendered: demangle_typeid(typeid).unwrap_or_else(|| typeid.to_string()),
+        };
+        difference("type metadata identifier", &component(lhs), &component(rhs), note)
+    };
+
+    let (Some(lhs), Some(rhs)) = (TypeIdComponents::parse(lhs), TypeIdComponents::parse(rhs))
+    else {
+        return Some(whole(None));
+    };
+
+    // Class of pointer to member function types
+    match (&lhs.class, &rhs.class) {
+        (Some(lhs), Some(rhs)) if lhs.rendered != rhs.rendered => {
+            return Some(difference("class", lhs, rhs, None));
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            return Some(whole(Some(
+                "only one of them is encoded with `-Zsanitizer-cfi-member-function-pointers`",
+            )));
+        }
+        _ => {}
+    }
+
+    // Return and parameter types
+    if lhs.ret.rendered != rhs.ret.rendered {
+        let note = note_for_types(&lhs.ret.rendered, &rhs.ret.rendered);
+        return Some(difference("return type", &lhs.ret, &rhs.ret, note));
+    }
+    for (i, (lhs, rhs)) in iter::zip(&lhs.params, &rhs.params).enumerate() {
+        if lhs.rendered != rhs.rendered {
+            let note = note_for_types(&lhs.rendered, &rhs.rendered);
+            return Some(difference(&format!("parameter #{}", i + 1), lhs, rhs, note));
+        }
+    }
+    if lhs.params.len() != rhs.params.len() {
+        let params = |params: &[Component]| Component {
+            encoding: params.iter().map(|param| &param.encoding[..]).collect(),
+            rendered: params.len().to_string(),
+        };
+        let note = Some("the functions have a different number of parameters");
+        return Some(difference(
+            "number of parameters",
+            &params(&lhs.params),
+            &params(&rhs.params),
+            note,
+        ));
+    }
+
+    // Encoding suffixes
+    if lhs.suffixes != rhs.suffixes {
+        let suffixes = |suffixes: &[String]| Component {
+            encoding: suffixes
+                .iter()
+                .map(|suffix| format!(".{}", suffix.replace(' ', "")))
+                .collect(),
+            rendered: suffixes.join(", "),
+        };
+        let differs = |suffix: &str| {
+            lhs.suffixes.iter().any(|s| s.starts_with(suffix))
+                != rhs.suffixes.iter().any(|s| s.starts_with(suffix))
+        };
+        let note = if differs("normalized") {
+            Some("only one of them is encoded with `-Zsanitizer-cfi-normalize-integers`")
+        } else if differs("ptrsized") {
+            Some("only one of them is encoded with `-Zsanitizer-cfi-normalize-ptr-sized-integers`")
+        } else if differs("generalized") {
+            Some("only one of them is encoded with `-Zsanitizer-cfi-generalize-pointers`")
+        } else if differs("arrays") {
+            Some("only one of them is encoded with `-Zsanitizer-cfi-generalize-arrays`")
+        } else if differs("returns") {
+            Some("only one of them is encoded with `-Zsanitizer-cfi-erase-return-types`")
+        } else if lhs.suffixes.iter().find(|s| s.starts_with("salt"))
+            != rhs.suffixes.iter().find(|s| s.starts_with("salt"))
+        {
+            Some("they are salted with different target `cfi-typeid-salt`s")
+        } else {
+            None
+        };
+        return Some(difference(
+            "encoding suffixes",
+            &suffixes(&lhs.suffixes),
+            &suffixes(&rhs.suffixes),
+            note,
+        ));
+    }
+
+    Some(whole(Some(
+        "the types are rendered the same but encoded differently (e.g., types with the same path \
+         from different crates)",
+    )))
+}
+
+/// Returns a likely explanation for the difference between the specified rendered types, if any.
+fn note_for_types(lhs: &str, rhs: &str) -> Option<&'static str> {
+    /// Returns the signedness and size in bits (if fixed) of an integer type.
+    fn int(ty: &str) -> Option<(bool, Option<u32>)> {
+        let (signed, bits) = if let Some(bits) = ty.strip_prefix('i') {
+            (true, bits)
+        } else {
+            (false, ty.strip_prefix('u')?)
+        };
+        match bits {
+            "size" => Some((signed, None)),
+            "8" | "16" | "32" | "64" | "128" => Some((signed, bits.parse().ok())),
+            _ => None,
+        }
+    }
+
+    match (int(lhs), int(rhs)) {
+        (Some((lhs_signed, Some(lhs_bits))), Some((rhs_signed, Some(rhs_bits))))
+            if lhs_bits == rhs_bits && lhs_signed != rhs_signed =>
+        {
+            return Some("the integer types have the same size but different signedness");
+        }
+        (Some((_, None)), Some(_)) | (Some(_), Some((_, None))) => {
+            return Some(
+                "`isize` and `usize` are only encoded as fixed-width integers with \
+                 `-Zsanitizer-cfi-normalize-integers`",
+            );
+        }
+        (Some(_), None) | (None, Some(_))
+            if ["bool", "char"].contains(&lhs) || ["bool", "char"].contains(&rhs) =>
+        {
+            return Some(
+                "`bool` and `char` are only encoded as integers with \
+                 `-Zsanitizer-cfi-normalize-integers`",
+            );
+        }
+        _ => {}
+    }
+
+    /// Returns whether a type is a reference or raw pointer, whether it is mutable, and its
+    /// pointee.
+    fn ptr(ty: &str) -> Option<(bool, bool, &str)> {
+        if let Some(pointee) = ty.strip_prefix("&mut ") {
+            Some((true, true, pointee))
+        } else if let Some(pointee) = ty.strip_prefix('&') {
+            Some((true, false, pointee))
+        } else if let Some(pointee) = ty.strip_prefix("*mut ") {
+            Some((false, true, pointee))
+        } else if let Some(pointee) = ty.strip_prefix("*const ") {
+            Some((false, false, pointee))
+        } else {
+            None
+        }
+    }
+
+    let is_ptr = |ty: &str| ptr(ty).is_some() || ty.starts_with("fn(");
+    match (ptr(lhs), ptr(rhs)) {
+        (Some((lhs_ref, lhs_mut, lhs_pointee)), Some((rhs_ref, rhs_mut, rhs_pointee)))
+            if lhs_pointee == rhs_pointee =>
+        {
+            if lhs_ref != rhs_ref {
+                Some("references and raw pointers are encoded differently")
+            } else if lhs_mut != rhs_mut && lhs_ref {
+                Some("the references differ in mutability (see `-Zsanitizer-cfi-erase-ref-mut`)")
+            } else if lhs_mut != rhs_mut {
+                Some("the pointers differ in mutability")
+            } else {
+                None
+            }
+        }
+        (Some((.., "()")), _) | (_, Some((.., "()"))) if is_ptr(lhs) && is_ptr(rhs) => {
+            Some("pointers are only generalized with `-Zsanitizer-cfi-generalize-pointers`")
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether a function type has no parameters (i.e., is encoded with a void parameter
+/// specifier "v").
+fn has_no_params(fn_ty: &FnType) -> bool {
+    !fn_ty.c_variadic && fn_ty.params == [Type::Builtin("v")]
+}
+
+/// Returns the Rust-like rendering of a type whose substitutions are expanded (see
+/// `TypeId::expand_substitutions`), or `None` if it isn't the encoding of a Rust type.
+fn render(ty: &Type) -> Option<String> {
+    // Types may be nested arbitrarily deeply (see `itanium::Parser::ty`)
+    ensure_sufficient_stack(|| {
+        let s = match ty {
+            Type::Builtin(code) => BUILTIN_TYPES.iter().find(|(s, _)| s == code)?.1.to_string(),
+            Type::Vendor { name, args } => match (&name[..], args) {
+                (
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                    | "u128" | "usize" | "char" | "str",
+                    None,
+                ) => name.clone(),
+                ("never", None) => "!".to_string(),
+                ("param", None) => "_".to_string(),
+                ("tuple", Some(tys)) => {
+                    let tys = tys.iter().map(render).collect::<Option<Vec<_>>>()?;
+                    if tys.len() == 1 {
+                        format!("({},)", tys[0])
+                    } else {
+                        format!("({})", tys.join(", "))
+                    }
+                }
+                ("slice", Some(tys)) => match &tys[..] {
+                    [ty] => format!("[{}]", render(ty)?),
+                    _ => return None,
+                },
+                ("ref", Some(tys)) => match &tys[..] {
+                    [ty] => format!("&{}", render(ty)?),
+                    _ => return None,
+                },
+                (_, args) => {
+                    let mut s = path(name).unwrap_or_else(|| name.clone());
+                    let args = render_args(args.as_deref().unwrap_or_default())?;
+                    if !args.is_empty() {
+                        s.push_str(&format!("<{}>", args.join(", ")));
+                    }
+                    s
+                }
+            },
+            Type::Pattern { ty, pat } => format!("{} is {pat}", render(ty)?),
+            Type::Dyn { star, predicates, .. } => render_dyn(*star, predicates)?,
+            // U3mutu3refI<element-type>E as vendor extended type qualifier and type
+            Type::Mut(ty) => format!("&mut {}", render(ty)?.strip_prefix('&')?),
+            Type::Ptr(pointee) => match &**pointee {
+                Type::Fn(fn_ty) => {
+                    let params = if has_no_params(fn_ty) { &[][..] } else { &fn_ty.params[..] };
+                    let mut params = params.iter().map(render).collect::<Option<Vec<_>>>()?;
+                    if fn_ty.c_variadic {
+                        params.push("...".to_string());
+                    }
+                    render_fn_type(&render(&fn_ty.ret)?, params.iter().map(|param| &param[..]))
+                }
+                Type::Const(ty) => format!("*const {}", render(ty)?),
+                ty => format!("*mut {}", render(ty)?),
+            },
+            // C++ lvalue references
+            Type::Ref(pointee) => match &**pointee {
+                Type::Const(ty) => format!("&{}", render(ty)?),
+                ty => format!("&mut {}", render(ty)?),
+            },
+            Type::Array { len, ty } => format!("[{}; {len}]", render(ty)?),
+            // repr(C) user-defined types for extern function types with the "C" calling
+            // convention, and extern types
+            Type::Name(name) => name.clone(),
+            // Structured user-defined encodings
+            Type::Class { prefix, names, template_args, .. } => {
+                let mut s = match prefix {
+                    Some(ClassPrefix::Std) => "std::".to_string(),
+                    Some(ClassPrefix::Substitution(_)) => return None,
+                    None => String::new(),
+                };
+                s.push_str(&names.join("::"));
+                if let Some(args) = template_args {
+                    s.push_str(&format!("<{}>", render_args(args)?.join(", ")));
+                }
+                s
+            }
+            // Literal arguments, whose element types are not rendered
+            Type::Literal { value, .. } => {
+                if value.is_empty() {
+                    // Const parameters
+                    "_".to_string()
+                } else if let Some(value) = value.strip_prefix('n') {
+                    format!("-{value}")
+                } else {
+                    value.clone()
+                }
+            }
+            // Substitutions are expanded, regions are omitted, and projections, const-qualified
+            // types, and function types are only rendered as part of the types they are in
+            Type::Substitution(_)
+            | Type::Region(_)
+            | Type::Projection { .. }
+            | Type::Const(_)
+            | Type::Fn(_) => return None,
+        };
+        Some(s)
+    })
+}
+
+/// Returns the Rust-like renderings of generic arguments, omitting regions.
+fn render_args(args: &[Type]) -> Option<Vec<String>> {
+    args.iter().filter(|arg| !matches!(arg, Type::Region(_))).map(render).collect()
+}
+
+/// Returns the Rust-like rendering of a trait object type with the specified predicates.
+fn render_dyn(star: bool, predicates: &[Type]) -> Option<String> {
+    let mut traits: Vec<(String, Vec<String>)> = Vec::new();
+    for predicate in predicates {
+        match (predicate, traits.first_mut()) {
+            // Projections are rendered as associated type bindings of the principal trait
+            (Type::Projection { name, term, .. }, Some((_, principal_args))) => {
+                let path = path(name)?;
+                let name = path.rsplit("::").next()?;
+                principal_args.push(format!("{name} = {}", render(term)?));
+            }
+            (Type::Vendor { name, args }, _) => {
+                traits.push((path(name)?, render_args(args.as_deref().unwrap_or_default())?));
+            }
+            _ => return None,
+        }
+    }
+    let traits = traits
+        .into_iter()
+        .map(|(path, args)| match &args[..] {
+            [] => path,
+            _ => format!("{path}<{}>", args.join(", ")),
+        })
+        .collect::<Vec<_>>();
+    Some(format!("{}{}", if star { "dyn* " } else { "dyn " }, traits.join(" + ")))
+}
+
+/// Returns the Rust-like rendering of a function type.
+fn render_fn_type<'a>(ret: &str, params: impl Iterator<Item = &'a str>) -> String {
+    let mut s = format!("fn({})", params.collect::<Vec<_>>().join(", "));
+    if ret != "()" {
+        s.push_str(&format!(" -> {ret}"));
+    }
+    s
+}
+
+/// Returns the Rust-like path for the specified encoded name (i.e., v0's <path> without v0's
+/// extended form of paths).
+fn path(name: &str) -> Option<String> {
+    // N<namespace-tagN>..N<namespace-tag1>
+    let mut rest = name;
+    let mut len = 0;
+    while let Some(tagged) = rest.strip_prefix('N') {
+        rest = tagged.get(1..)?;
+        len += 1;
+    }
+
+    // C<crate-disambiguator><crate-name>
+    let (crate_name, mut rest) = segment(rest.strip_prefix('C')?, false)?;
+    let mut segments = vec![crate_name];
+
+    // <path-disambiguator1><path-name1>..<path-disambiguatorN><path-nameN>
+    for _ in 0..len {
+        let (name, next) = segment(rest, true)?;
+        segments.push(name);
+        rest = next;
+    }
+
+    rest.is_empty().then(|| segments.join("::"))
+}
+
+/// Parses a segment of a path (i.e., `[s[<base-62-number>]_]<length><name>`), returning its name
+/// and the rest of the path. A '_' is prepended to path names starting with a digit or '_', which
+/// is skipped if `is_path_name` is set.
+fn segment(rest: &str, is_path_name: bool) -> Option<(&str, &str)> {
+    let rest = match rest.strip_prefix('s') {
+        Some(rest) => rest.split_once('_')?.1,
+        None => rest,
+    };
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let len: usize = rest[..digits].parse().ok()?;
+    let mut rest = &rest[digits..];
+    if is_path_name {
+        rest = rest.strip_prefix('_').unwrap_or(rest);
+    }
+    Some((rest.get(..len)?, &rest[len..]))
+}
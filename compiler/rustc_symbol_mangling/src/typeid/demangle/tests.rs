@@ -0,0 +1,209 @@
+use super::*;
+
+// Most of the type metadata identifiers are the ones the `tests/codegen/sanitizer/cfi` tests
+// expect.
+
+fn demangle(typeid: &str) -> String {
+    demangle_typeid(typeid).unwrap()
+}
+
+#[test]
+fn test_demangle_typeid_primitive_types() {
+    assert_eq!(demangle("_ZTSFvvE"), "fn()");
+    assert_eq!(demangle("_ZTSFvbE"), "fn(bool)");
+    assert_eq!(demangle("_ZTSFu3i32S_E"), "fn(i32) -> i32");
+    assert_eq!(demangle("_ZTSFu3i32S_S_E"), "fn(i32, i32) -> i32");
+    assert_eq!(demangle("_ZTSFvfdE"), "fn(f32, f64)");
+    assert_eq!(demangle("_ZTSFvu4charu3strE"), "fn(char, str)");
+    assert_eq!(demangle("_ZTSFu5nevervE"), "fn() -> !");
+    assert_eq!(demangle("_ZTSFvu3i32zE"), "fn(i32, ...)");
+    assert_eq!(demangle("_ZTSFviE"), "fn(c_int)");
+}
+
+#[test]
+fn test_demangle_typeid_pointer_types() {
+    assert_eq!(demangle("_ZTSFvu3refIu3i32EE"), "fn(&i32)");
+    assert_eq!(demangle("_ZTSFvU3mutu3refIu3i32ES0_E"), "fn(&mut i32, &i32)");
+    assert_eq!(demangle("_ZTSFvU3mutu3refIu3i32ES1_E"), "fn(&mut i32, &mut i32)");
+    assert_eq!(demangle("_ZTSFvPKu3i32PS_S2_E"), "fn(*const i32, *mut i32, *mut i32)");
+    assert_eq!(demangle("_ZTSFvPu3i32PKS_S2_E"), "fn(*mut i32, *const i32, *const i32)");
+    assert_eq!(demangle("_ZTSFvPFu3i32S_ES0_E"), "fn(fn(i32) -> i32, fn(i32) -> i32)");
+    assert_eq!(demangle("_ZTSFu3i32PFS_S_ES_E"), "fn(fn(i32) -> i32, i32) -> i32");
+}
+
+#[test]
+fn test_demangle_typeid_reference_types() {
+    assert_eq!(demangle("_ZTSFvRKu3i32RS_S2_E"), "fn(&i32, &mut i32, &mut i32)");
+    assert_eq!(demangle("_ZTSFvRu3i32RKS_S2_E"), "fn(&mut i32, &i32, &i32)");
+    assert_eq!(demangle("_ZTSFvRKu3strPS_E"), "fn(&str, *mut str)");
+}
+
+#[test]
+fn test_demangle_typeid_sequence_types() {
+    assert_eq!(demangle("_ZTSFvA32u3i32S0_E"), "fn([i32; 32], [i32; 32])");
+    assert_eq!(demangle("_ZTSFvu5tupleIu3i32S_EE"), "fn((i32, i32))");
+    assert_eq!(demangle("_ZTSFvu5tupleIu3i32EE"), "fn((i32,))");
+    assert_eq!(demangle("_ZTSFvu3refIu5sliceIu2u8EEE"), "fn(&[u8])");
+}
+
+#[test]
+fn test_demangle_typeid_user_defined_types() {
+    assert_eq!(demangle("_ZTSFvP5type1S0_E"), "fn(*mut type1, *mut type1)");
+    assert_eq!(
+        demangle("_ZTSFvu27NtNtCs1_4core6option6OptionIu3i32EE"),
+        "fn(core::option::Option<i32>)"
+    );
+    assert_eq!(demangle("_ZTSFvu3refIu22NtCs1234_5crate6Trait1Iu6regionEEE"), "fn(&crate::Trait1)");
+    assert_eq!(demangle("_ZTSFvu22NtCs1234_5crate6Trait1S_E"), "fn(crate::Trait1, crate::Trait1)");
+}
+
+#[test]
+fn test_demangle_typeid_class_names() {
+    assert_eq!(demangle("_ZTSFvN2ns3FooENS_3BarEE"), "fn(ns::Foo, ns::Bar)");
+    assert_eq!(demangle("_ZTSFvSt3FooS_E"), "fn(std::Foo, std::Foo)");
+    assert_eq!(demangle("_ZTSFv3VecIu3i32ES_Iu2u8EE"), "fn(Vec<i32>, Vec<u8>)");
+}
+
+#[test]
+fn test_demangle_typeid_trait_types() {
+    assert_eq!(
+        demangle(
+            "_ZTSFvu3refIu3dynIu27NtNtCs1_4core4iter8Iteratoru34NtNtNtCs1_4core4iter8Iterator\
+             4Itemu2u8u25NtNtCs1_4core6marker4Sendu6regionEEE"
+        ),
+        "fn(&dyn core::iter::Iterator<Item = u8> + core::marker::Send)"
+    );
+}
+
+#[test]
+fn test_demangle_typeid_member_function_pointer_types() {
+    assert_eq!(
+        demangle("_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFvvE"),
+        "<dyn crate::Trait1>::fn()"
+    );
+    assert_eq!(
+        demangle("_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFu3i32S2_E.normalized"),
+        "<dyn crate::Trait1>::fn(i32) -> i32 (normalized)"
+    );
+}
+
+#[test]
+fn test_demangle_typeid_with_suffixes() {
+    assert_eq!(
+        demangle("_ZTSFu3i32PKvS_E.normalized.generalized"),
+        "fn(*const (), i32) -> i32 (normalized, generalized)"
+    );
+    assert_eq!(demangle("_ZTSFvvE.saltAndroid"), "fn() (salt Android)");
+    assert_eq!(demangle("_ZTSFvvE.returns.saltAndroid"), "fn() (returns, salt Android)");
+}
+
+#[test]
+fn test_demangle_typeid_invalid() {
+    assert_eq!(demangle_typeid(""), None);
+    assert_eq!(demangle_typeid("_ZTSFvu3i32"), None);
+    assert_eq!(demangle_typeid("_ZTSFvvEE"), None);
+    assert_eq!(demangle_typeid("_ZTSFvS_E"), None);
+    assert_eq!(demangle_typeid("_ZTSFvvE.unknown"), None);
+    assert_eq!(demangle_typeid("_ZTSMu3i32E"), None);
+    assert_eq!(demangle_typeid("_ZTSMzFvvE"), None);
+}
+
+#[test]
+fn test_typeid_difference_identical() {
+    assert_eq!(typeid_difference("_ZTSFu3i32S_E", "_ZTSFu3i32S_E"), None);
+}
+
+#[test]
+fn test_typeid_difference_parameter() {
+    assert_eq!(
+        typeid_difference("_ZTSFvu3i32E", "_ZTSFvu3u32E"),
+        Some(TypeIdDifference {
+            component: "parameter #1".to_string(),
+            lhs: "i32".to_string(),
+            lhs_encoding: "u3i32".to_string(),
+            rhs: "u32".to_string(),
+            rhs_encoding: "u3u32".to_string(),
+            note: Some("the integer types have the same size but different signedness"),
+        })
+    );
+    // The second parameter of the first is a substitution for the first parameter.
+    let difference = typeid_difference("_ZTSFvu3i32S_E", "_ZTSFvu3i32u5usizeE").unwrap();
+    assert_eq!(difference.component, "parameter #2");
+    assert_eq!(difference.lhs_encoding, "S_");
+    assert_eq!(difference.rhs, "usize");
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-normalize-integers"));
+}
+
+#[test]
+fn test_typeid_difference_return_type() {
+    let difference = typeid_difference("_ZTSFPKvvE", "_ZTSFPu3i32vE").unwrap();
+    assert_eq!(difference.component, "return type");
+    assert_eq!(difference.lhs, "*const ()");
+    assert_eq!(difference.rhs, "*mut i32");
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-generalize-pointers"));
+
+    let difference = typeid_difference("_ZTSFu3refIu3i32EvE", "_ZTSFU3mutu3refIu3i32EvE").unwrap();
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-erase-ref-mut"));
+
+    let difference = typeid_difference("_ZTSFPKu3i32vE", "_ZTSFPu3i32vE").unwrap();
+    assert_eq!(difference.note, Some("the pointers differ in mutability"));
+}
+
+#[test]
+fn test_typeid_difference_number_of_parameters() {
+    let difference = typeid_difference("_ZTSFvu3i32E", "_ZTSFvu3i32S_E").unwrap();
+    assert_eq!(difference.component, "number of parameters");
+    assert_eq!((&difference.lhs[..], &difference.rhs[..]), ("1", "2"));
+    assert_eq!(difference.rhs_encoding, "u3i32S_");
+}
+
+#[test]
+fn test_typeid_difference_suffixes() {
+    let difference = typeid_difference("_ZTSFu3i32S_E", "_ZTSFu3i32S_E.normalized").unwrap();
+    assert_eq!(difference.component, "encoding suffixes");
+    assert_eq!(difference.rhs_encoding, ".normalized");
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-normalize-integers"));
+
+    let difference = typeid_difference("_ZTSFvvE", "_ZTSFvvE.arrays").unwrap();
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-generalize-arrays"));
+
+    let difference = typeid_difference("_ZTSFvvE.ptrsized", "_ZTSFvvE").unwrap();
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-normalize-ptr-sized-integers"));
+
+    let difference = typeid_difference("_ZTSFvvE.returns", "_ZTSFvvE").unwrap();
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-erase-return-types"));
+
+    let difference = typeid_difference("_ZTSFvvE.salt1", "_ZTSFvvE.salt2").unwrap();
+    assert_eq!(difference.lhs_encoding, ".salt1");
+    assert!(difference.note.unwrap().contains("cfi-typeid-salt"));
+}
+
+#[test]
+fn test_typeid_difference_class() {
+    let difference = typeid_difference(
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFvvE",
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait2u6regionEFvvE",
+    )
+    .unwrap();
+    assert_eq!(difference.component, "class");
+    assert_eq!(difference.lhs, "dyn crate::Trait1");
+    assert_eq!(difference.rhs, "dyn crate::Trait2");
+
+    let difference = typeid_difference(
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFvvE",
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait1u6regionEEE",
+    )
+    .unwrap();
+    assert_eq!(difference.component, "type metadata identifier");
+    assert!(difference.note.unwrap().contains("-Zsanitizer-cfi-member-function-pointers"));
+}
+
+#[test]
+fn test_typeid_difference_same_rendering() {
+    let difference =
+        typeid_difference("_ZTSFvu14NtCs1_3foo3BarE", "_ZTSFvu14NtCs2_3foo3BarE").unwrap();
+    assert_eq!(difference.component, "type metadata identifier");
+    assert_eq!(difference.lhs, "fn(foo::Bar)");
+    assert_eq!(difference.rhs, "fn(foo::Bar)");
+    assert!(difference.note.is_some());
+}
@@ -0,0 +1,204 @@
+//! The vendor extensions of the encoding of type metadata identifiers for LLVM Control Flow
+//! Integrity (CFI) and Kernel Control Flow Integrity (KCFI) support.
+//!
+//! Describes the vendor extended types, vendor extended type qualifiers, and encoding suffixes the
+//! encoder adds to the Itanium C++ ABI mangling grammar (see
+//! <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling>) for Rust types, for
+//! `--print cfi-grammar`, so that external demanglers and binary analysis tools don't need to
+//! reverse-engineer them. The examples are checked against the parser (see `itanium`), which is
+//! checked against the encoder.
+
+use serde_json::{json, Value};
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the JSON shape of `--print cfi-grammar`, which is only incremented when fields
+/// are removed or change meaning (i.e., not when productions or fields are added).
+pub const GRAMMAR_VERSION: u32 = 1;
+
+/// A vendor extended type or vendor extended type qualifier.
+#[derive(Clone, Copy, Debug)]
+pub struct Production {
+    /// The name of the vendor extended type or qualifier (e.g., `ref`), or `<name>` for the names
+    /// of user-defined types.
+    pub name: &'static str,
+    /// The production, in the notation of the Itanium C++ ABI mangling grammar.
+    pub syntax: &'static str,
+    /// The Rust type it encodes.
+    pub rust: &'static str,
+    /// A type metadata identifier with the production.
+    pub example: &'static str,
+}
+
+/// An encoding suffix, in the order the encoder adds them.
+#[derive(Clone, Copy, Debug)]
+pub struct Suffix {
+    /// The production, in the notation of the Itanium C++ ABI mangling grammar.
+    pub syntax: &'static str,
+    /// The option (or target option) the suffix is added with.
+    pub option: &'static str,
+    /// What the suffix marks.
+    pub description: &'static str,
+    /// A type metadata identifier with the suffix, if it can be parsed.
+    pub example: Option<&'static str>,
+}
+
+/// The vendor extended types (i.e., `u<length><name>[I<arg1..argN>E]`).
+pub const VENDOR_TYPES: &[Production] = &[
+    Production { name: "i8", syntax: "u2i8", rust: "i8", example: "_ZTSFvu2i8E" },
+    Production { name: "i16", syntax: "u3i16", rust: "i16", example: "_ZTSFvu3i16E" },
+    Production { name: "i32", syntax: "u3i32", rust: "i32", example: "_ZTSFvu3i32E" },
+    Production { name: "i64", syntax: "u3i64", rust: "i64", example: "_ZTSFvu3i64E" },
+    Production { name: "i128", syntax: "u4i128", rust: "i128", example: "_ZTSFvu4i128E" },
+    Production { name: "isize", syntax: "u5isize", rust: "isize", example: "_ZTSFvu5isizeE" },
+    Production { name: "u8", syntax: "u2u8", rust: "u8", example: "_ZTSFvu2u8E" },
+    Production { name: "u16", syntax: "u3u16", rust: "u16", example: "_ZTSFvu3u16E" },
+    Production { name: "u32", syntax: "u3u32", rust: "u32", example: "_ZTSFvu3u32E" },
+    Production { name: "u64", syntax: "u3u64", rust: "u64", example: "_ZTSFvu3u64E" },
+    Production { name: "u128", syntax: "u4u128", rust: "u128", example: "_ZTSFvu4u128E" },
+    Production { name: "usize", syntax: "u5usize", rust: "usize", example: "_ZTSFvu5usizeE" },
+    Production { name: "char", syntax: "u4char", rust: "char", example: "_ZTSFvu4charE" },
+    Production { name: "str", syntax: "u3str", rust: "str", example: "_ZTSFvu3refIu3strEE" },
+    Production { name: "never", syntax: "u5never", rust: "!", example: "_ZTSFu5nevervE" },
+    Production {
+        name: "tuple",
+        syntax: "u5tupleI<element-type1..element-typeN>E",
+        rust: "(T1, .., TN)",
+        example: "_ZTSFvu5tupleIu3i32u2u8EE",
+    },
+    Production {
+        name: "slice",
+        syntax: "u5sliceI<element-type>E",
+        rust: "[T]",
+        example: "_ZTSFvu3refIu5sliceIu2u8EEE",
+    },
+    Production {
+        name: "ref",
+        syntax: "u3refI<element-type>E",
+        rust: "&T",
+        example: "_ZTSFvu3refIu3i32EE",
+    },
+    Production {
+        name: "pat",
+        syntax: "u3patI<element-type><pattern>E",
+        rust: "T is <pattern>",
+        example: "_ZTSFvu3patIu3u321..=5EE",
+    },
+    Production {
+        name: "dyn",
+        syntax: "u3dynI<predicate1..predicateN><region>E",
+        rust: "dyn Trait",
+        example: "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait1u6regionEEE",
+    },
+    Production {
+        name: "dynstar",
+        syntax: "u7dynstarI<predicate1..predicateN><region>E",
+        rust: "dyn* Trait",
+        example: "_ZTSFvu3refIu7dynstarIu22NtCs1234_5crate6Trait1u6regionEEE",
+    },
+    Production {
+        name: "region",
+        syntax: "u6region[I[s<region-disambiguator>_]<region-index>E]",
+        rust: "'a (as a generic argument)",
+        example: "_ZTSFvu23NtCs1234_5crate7Struct1Iu6regionIs0_1EEE",
+    },
+    Production { name: "param", syntax: "u5param", rust: "T", example: "_ZTSFvu5paramE" },
+    Production {
+        name: "<name>",
+        syntax: "u<length><name>[I<element-type1..element-typeN>E]",
+        rust: "user-defined types, function items, closures, and coroutines (named by their v0 \
+               paths)",
+        example: "_ZTSFvu23NtCs1234_5crate7Struct1Iu3i32EE",
+    },
+];
+
+/// The vendor extended type qualifiers (i.e., `U<length><name>`).
+pub const VENDOR_QUALIFIERS: &[Production] = &[Production {
+    name: "mut",
+    syntax: "U3mutu3refI<element-type>E",
+    rust: "&mut T",
+    example: "_ZTSFvU3mutu3refIu3i32EE",
+}];
+
+/// The encoding suffixes, in the order the encoder adds them.
+pub const SUFFIXES: &[Suffix] = &[
+    Suffix {
+        syntax: ".normalized",
+        option: "-Zsanitizer-cfi-normalize-integers",
+        description: "integer types are normalized",
+        example: Some("_ZTSFvu3u32E.normalized"),
+    },
+    Suffix {
+        syntax: ".ptrsized",
+        option: "-Zsanitizer-cfi-normalize-ptr-sized-integers",
+        description: "pointer-sized integer types are normalized (never with `.normalized`)",
+        example: Some("_ZTSFvu3u64E.ptrsized"),
+    },
+    Suffix {
+        syntax: ".generalized",
+        option: "-Zsanitizer-cfi-generalize-pointers",
+        description: "pointer types are generalized",
+        example: Some("_ZTSFvu3refIvEE.generalized"),
+    },
+    Suffix {
+        syntax: ".arrays",
+        option: "-Zsanitizer-cfi-generalize-arrays",
+        description: "array types are generalized as slice types",
+        example: Some("_ZTSFvu3refIu5sliceIu2u8EEE.arrays"),
+    },
+    Suffix {
+        syntax: ".returns",
+        option: "-Zsanitizer-cfi-erase-return-types",
+        description: "return types are erased",
+        example: Some("_ZTSFvu3i32E.returns"),
+    },
+    Suffix {
+        syntax: ".salt<salt>",
+        option: "cfi-typeid-salt (target option)",
+        description: "the salt of the target",
+        example: Some("_ZTSFvvE.saltAndroid"),
+    },
+    Suffix {
+        syntax: ".h<hash>",
+        option: "-Zsanitizer-cfi-typeid-length-cap",
+        description: "the 64-bit hexadecimal hash of the whole type metadata identifier, which \
+                      replaces the tail of type metadata identifiers longer than the cap",
+        example: None,
+    },
+];
+
+/// Returns the vendor extensions of the encoding as the JSON of `--print cfi-grammar`.
+pub fn grammar_json() -> Value {
+    let productions = |productions: &[Production]| -> Vec<Value> {
+        productions
+            .iter()
+            .map(|production| {
+                json!({
+                    "name": production.name,
+                    "syntax": production.syntax,
+                    "rust": production.rust,
+                    "example": production.example,
+                })
+            })
+            .collect()
+    };
+    let suffixes: Vec<Value> = SUFFIXES
+        .iter()
+        .map(|suffix| {
+            json!({
+                "syntax": suffix.syntax,
+                "option": suffix.option,
+                "description": suffix.description,
+                "example": suffix.example,
+            })
+        })
+        .collect();
+    json!({
+        "version": GRAMMAR_VERSION,
+        "prefix": "_ZTS",
+        "vendor_types": productions(VENDOR_TYPES),
+        "vendor_qualifiers": productions(VENDOR_QUALIFIERS),
+        "suffixes": suffixes,
+    })
+}
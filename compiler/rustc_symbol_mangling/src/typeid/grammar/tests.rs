@@ -0,0 +1,55 @@
+use super::*;
+use crate::typeid::itanium::TypeId;
+
+/// Returns the literal prefix of the specified production (i.e., up to its first nonterminal or
+/// optional part).
+fn literal_prefix(syntax: &str) -> &str {
+    syntax.split(['<', '[']).next().unwrap()
+}
+
+#[test]
+fn test_grammar_examples_round_trip() {
+    for production in VENDOR_TYPES.iter().chain(VENDOR_QUALIFIERS) {
+        let parsed = TypeId::parse(production.example)
+            .unwrap_or_else(|| panic!("the example of `{}` cannot be parsed", production.name));
+        assert_eq!(parsed.to_string(), production.example);
+        assert!(
+            production.example.contains(literal_prefix(production.syntax)),
+            "the example of `{}` doesn't have the production",
+            production.name
+        );
+    }
+    for suffix in SUFFIXES {
+        if let Some(example) = suffix.example {
+            let parsed = TypeId::parse(example)
+                .unwrap_or_else(|| panic!("the example of `{}` cannot be parsed", suffix.syntax));
+            assert_eq!(parsed.to_string(), example);
+            assert!(example.contains(literal_prefix(suffix.syntax)));
+        }
+    }
+}
+
+#[test]
+fn test_grammar_suffix_order() {
+    // Every suffix but `.ptrsized` (which is exclusive with `.normalized`) and `.h<hash>` (which
+    // can't be parsed), in the order listed
+    let typeid: String = SUFFIXES
+        .iter()
+        .filter(|suffix| !matches!(suffix.syntax, ".ptrsized" | ".h<hash>"))
+        .map(|suffix| suffix.example.unwrap().split_once('.').unwrap().1)
+        .fold(String::from("_ZTSFvvE"), |typeid, suffix| typeid + "." + suffix);
+    let parsed = TypeId::parse(&typeid).unwrap_or_else(|| panic!("`{typeid}` cannot be parsed"));
+    assert!(parsed.normalized && parsed.generalized && parsed.arrays && parsed.returns);
+    assert_eq!(parsed.salt.as_deref(), Some("Android"));
+}
+
+#[test]
+fn test_grammar_json() {
+    let json = grammar_json();
+    assert_eq!(json["version"], GRAMMAR_VERSION);
+    assert_eq!(json["prefix"], "_ZTS");
+    assert_eq!(json["vendor_types"].as_array().unwrap().len(), VENDOR_TYPES.len());
+    assert_eq!(json["vendor_qualifiers"][0]["syntax"], "U3mutu3refI<element-type>E");
+    assert_eq!(json["suffixes"][0]["syntax"], ".normalized");
+    assert!(json["suffixes"][SUFFIXES.len() - 1]["example"].is_null());
+}
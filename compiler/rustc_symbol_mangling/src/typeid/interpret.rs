@@ -0,0 +1,77 @@
+/// Type metadata identifiers for emulating LLVM CFI checks in interpreters (e.g., Miri).
+///
+/// Indirect calls through function pointers are checked by LLVM CFI against the type metadata
+/// identifiers of the functions called. Interpreters can emulate these checks by comparing the
+/// type metadata identifier of the type of the function pointer with the one of the function
+/// called, which are computed here from their declared signatures (i.e., without computing their
+/// FnAbi, see `TypeIdOptions::ENCODE_FN_SIGS`), so only the layouts of the fields of
+/// `repr(transparent)` types are computed. This makes the emulated checks stricter than the ones of
+/// programs built without `-Zsanitizer-cfi-encode-fn-sigs` for functions with parameters of
+/// zero-sized types, which aren't encoded then.
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::{self, Instance, InstanceDef, PolyFnSig, TyCtxt};
+
+use crate::typeid::typeid_itanium_cxx_abi;
+use crate::typeid::TypeIdOptions;
+
+/// Returns the type metadata identifier that indirect calls through function pointers of the
+/// specified type are checked against.
+pub fn typeid_for_fn_ptr<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_ptr_sig: PolyFnSig<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    typeid_itanium_cxx_abi::typeid_for_fnsig(
+        tcx,
+        fn_ptr_sig,
+        options | TypeIdOptions::ENCODE_FN_SIGS,
+    )
+}
+
+/// Returns the type metadata identifier of the specified Instance (with its concrete self, as
+/// methods called through function pointers are), if it can be called through function pointers.
+pub fn typeid_for_callee<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    callee: Instance<'tcx>,
+    options: TypeIdOptions,
+) -> Option<String> {
+    callee_fn_sig(tcx, callee).map(|fn_sig| typeid_for_fn_ptr(tcx, fn_sig, options))
+}
+
+/// Returns whether an indirect call to the specified Instance through a function pointer of the
+/// specified type passes the LLVM CFI check.
+pub fn is_valid_fn_ptr_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_ptr_sig: PolyFnSig<'tcx>,
+    callee: Instance<'tcx>,
+    options: TypeIdOptions,
+) -> bool {
+    typeid_for_callee(tcx, callee, options)
+        .is_some_and(|typeid| typeid == typeid_for_fn_ptr(tcx, fn_ptr_sig, options))
+}
+
+/// Returns the signature the specified Instance is called with through function pointers (i.e.,
+/// the signature of the function pointer type of a pointer to it), if it can be called through
+/// function pointers.
+pub fn callee_fn_sig<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Option<PolyFnSig<'tcx>> {
+    match instance.def {
+        // Closures are called through function pointers without their receivers (see
+        // `rustc_middle::ty::adjustment::PointerCoercion::ClosureFnPointer`)
+        InstanceDef::ClosureOnceShim { .. } => match instance.args.type_at(0).kind() {
+            ty::Closure(_, args) => {
+                Some(tcx.signature_unclosure(args.as_closure().sig(), hir::Unsafety::Normal))
+            }
+            _ => None,
+        },
+        InstanceDef::Item(def_id) | InstanceDef::ReifyShim(def_id, _)
+            if matches!(
+                tcx.def_kind(def_id),
+                DefKind::Fn | DefKind::AssocFn | DefKind::Ctor(..)
+            ) =>
+        {
+            Some(tcx.fn_sig(def_id).instantiate(tcx, instance.args))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,923 @@
+//! Parsing of type metadata identifiers for LLVM Control Flow Integrity (CFI) and Kernel Control
+//! Flow Integrity (KCFI) support.
+//!
+//! Parses the subset of the Itanium C++ ABI mangling grammar (see
+//! <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling>) the encoder emits (including the
+//! C++ class names of structured user-defined encodings), plus the vendor extended types and type
+//! qualifiers it uses for Rust types, into a syntax tree that is encoded back (i.e., with
+//! `Display`) into the exact string it was parsed from. Parsing checks the structure of an encoding
+//! (including that substitutions refer to dictionary entries of the expected kind), not whether it
+//! is the encoding of a valid Rust type. The demangler (see
+//! `demangle`), the validation of user-defined encodings, and the type metadata identifiers of C
+//! function prototypes (see `c_prototype`) are built on it.
+
+use rustc_data_structures::base_n;
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use std::ops::Range;
+use std::{fmt, mem};
+
+#[cfg(test)]
+mod tests;
+
+/// Builtin types (see <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-builtin>),
+/// which are not compressed. The ones the encoder doesn't emit may be in user-defined encodings
+/// (e.g., `Ds` for `char16_t`).
+const BUILTIN_TYPES: &[&str] = &[
+    "v", "w", "b", "c", "a", "h", "s", "t", "i", "j", "l", "m", "x", "y", "n", "o", "f", "d", "e",
+    "g", "Dh", "Dd", "De", "Df", "Di", "Ds", "Du", "Dn",
+];
+
+/// A type metadata identifier (i.e.,
+/// `_ZTS[M<class-type>]<function-type>[.normalized|.ptrsized][.generalized][.arrays][.returns][.salt<salt>]`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeId {
+    /// The class type, if the function type is of a pointer to member function (see
+    /// `-Zsanitizer-cfi-member-function-pointers`).
+    pub class: Option<Type>,
+    /// The function type.
+    pub fn_ty: FnType,
+    /// Whether the `.normalized` suffix is present.
+    pub normalized: bool,
+    /// Whether the `.ptrsized` suffix is present (see
+    /// `-Zsanitizer-cfi-normalize-ptr-sized-integers`), which is exclusive with `.normalized`.
+    pub ptrsized: bool,
+    /// Whether the `.generalized` suffix is present.
+    pub generalized: bool,
+    /// Whether the `.arrays` suffix is present (see `-Zsanitizer-cfi-generalize-arrays`).
+    pub arrays: bool,
+    /// Whether the `.returns` suffix is present (see `-Zsanitizer-cfi-erase-return-types`).
+    pub returns: bool,
+    /// The target's salt of the `.salt<salt>` suffix, if present.
+    pub salt: Option<String>,
+}
+
+/// A function type (i.e., `F<return-type><parameter-type1..parameter-typeN>[z]E`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FnType {
+    pub ret: Type,
+    /// The parameter types, which are a single `v` if the function has no parameters and is not
+    /// variadic.
+    pub params: Vec<Type>,
+    pub c_variadic: bool,
+}
+
+/// A type, or a region or literal in a generic argument list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// A builtin type (e.g., `v` or `Dh`).
+    Builtin(&'static str),
+    /// A substitution (i.e., `S[<seq-id>]_`) of the dictionary entry with the specified index.
+    Substitution(usize),
+    /// A vendor extended type (i.e., `u<length><name>[I<arg1..argN>E]`), such as `u3i32`,
+    /// `u5tupleI..E`, `u3refI..E`, or a user-defined type.
+    Vendor { name: String, args: Option<Vec<Type>> },
+    /// A region (i.e., `u6region[I[<region-disambiguator>]<region-index>E]`).
+    Region(Option<String>),
+    /// A pattern type (i.e., `u3patI<element-type><pattern>E`).
+    Pattern { ty: Box<Type>, pat: String },
+    /// A trait object type (i.e., `u3dynI<predicate1..predicateN><region>E`, or `u7dynstarI..E`).
+    Dyn {
+        star: bool,
+        /// The principal trait, the projections, and the auto traits.
+        predicates: Vec<Type>,
+        region: Box<Type>,
+    },
+    /// A projection predicate of a trait object type (i.e., `u<length><name>[I<arg1..argN>E]<term>`,
+    /// where the name is the one of an associated type of the principal trait or its supertraits).
+    Projection { name: String, args: Option<Vec<Type>>, term: Box<Type> },
+    /// A mutable reference (i.e., `U3mut<type>` as vendor extended type qualifier).
+    Mut(Box<Type>),
+    /// A raw pointer or function pointer (i.e., `P<type>`).
+    Ptr(Box<Type>),
+    /// A C++ lvalue reference (i.e., `R<type>`; see `-Zsanitizer-cfi-cxx-references`).
+    Ref(Box<Type>),
+    /// A const-qualified type (i.e., `K<type>`), which is only the pointee of a raw pointer or
+    /// reference.
+    Const(Box<Type>),
+    /// A function type (i.e., `F..E`), which is only the pointee of a function pointer.
+    Fn(Box<FnType>),
+    /// An array type (i.e., `A<array-length><element-type>`).
+    Array { len: u64, ty: Box<Type> },
+    /// A name (i.e., `<length><name>`) of a repr(C) user-defined type or an extern type.
+    Name(String),
+    /// A C++ class name with enclosing namespaces (i.e., `St<length><name>`, or
+    /// `N<prefix><length><name>E`, where the prefix is `St`, a substitution, or names) or template
+    /// arguments (i.e., with `I<template-arg1..template-argN>E` after the name), as the ones of
+    /// structured user-defined encodings, whose enclosing namespaces and class template are
+    /// substitution candidates (see `encode_class_name`).
+    Class {
+        nested: bool,
+        /// The `St` or substitution the names are after, if any.
+        prefix: Option<ClassPrefix>,
+        names: Vec<String>,
+        template_args: Option<Vec<Type>>,
+    },
+    /// A literal argument (i.e., `L<element-type>[n][<element-value>]E`).
+    Literal { ty: Box<Type>, value: String },
+}
+
+/// The prefix of a C++ class name (see `Type::Class`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassPrefix {
+    /// The `std` namespace (i.e., `St`).
+    Std,
+    /// A substitution of the enclosing namespaces, or of the class template of a class template
+    /// specialization, with the index of the dictionary entry it refers to.
+    Substitution(usize),
+}
+
+/// Statistics about the compression of a type metadata identifier (see
+/// `-Zcfi-compression-stats`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// The number of substitutions.
+    pub substitutions: usize,
+    /// The length of the type metadata identifier with every substitution replaced by the
+    /// component it refers to.
+    pub uncompressed_len: usize,
+}
+
+/// Kind of a substitution dictionary entry (see
+/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression>).
+#[derive(Clone, Copy, PartialEq)]
+enum Entry {
+    Ty,
+    Const,
+    Region,
+    Literal,
+}
+
+impl TypeId {
+    /// Parses a type metadata identifier, returning `None` if it isn't valid.
+    pub fn parse(typeid: &str) -> Option<TypeId> {
+        TypeId::parse_with_stats(typeid).map(|(typeid, _)| typeid)
+    }
+
+    /// Returns the compression statistics of a type metadata identifier, or `None` if it isn't
+    /// valid.
+    pub fn compression_stats(typeid: &str) -> Option<CompressionStats> {
+        TypeId::parse_with_stats(typeid).map(|(_, stats)| stats)
+    }
+
+    /// Replaces every substitution of the type metadata identifier by the component it refers to,
+    /// so that type metadata identifiers that only differ in their compression (e.g., ones
+    /// computed with `TypeIdOptions::UNCOMPRESSED`) are equal, and compare structurally.
+    pub fn expand_substitutions(self) -> TypeId {
+        let mut expander = Expander { dict: Vec::new() };
+        // The class is encoded before the function type
+        let class = self.class.map(|class| expander.ty(class));
+        let fn_ty = expander.fn_ty(self.fn_ty);
+        TypeId { class, fn_ty, ..self }
+    }
+
+    fn parse_with_stats(typeid: &str) -> Option<(TypeId, CompressionStats)> {
+        let mut parser = Parser::new(typeid.strip_prefix("_ZTS")?);
+        let class = if parser.eat("M") { Some(parser.ty()?) } else { None };
+        parser.expect("F")?;
+        let fn_ty = parser.fn_ty()?;
+
+        // Encoding suffixes, in the order the encoder adds them
+        let normalized = parser.eat(".normalized");
+        let ptrsized = !normalized && parser.eat(".ptrsized");
+        let generalized = parser.eat(".generalized");
+        let arrays = parser.eat(".arrays");
+        let returns = parser.eat(".returns");
+        let salt = if parser.eat(".salt") {
+            // The salt is the rest of the type metadata identifier
+            let salt = mem::take(&mut parser.rest);
+            if salt.is_empty() || !salt.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+                return None;
+            }
+            Some(salt.to_string())
+        } else {
+            None
+        };
+
+        if !parser.rest.is_empty() {
+            return None;
+        }
+        let stats = CompressionStats {
+            substitutions: parser.substitutions,
+            uncompressed_len: usize::try_from(typeid.len() as isize + parser.saved).ok()?,
+        };
+        Some((
+            TypeId { class, fn_ty, normalized, ptrsized, generalized, arrays, returns, salt },
+            stats,
+        ))
+    }
+}
+
+impl Type {
+    /// Parses the encoding of a single type with an empty substitution dictionary (e.g., a
+    /// user-defined encoding), returning `None` if it isn't valid.
+    pub fn parse(encoding: &str) -> Option<Type> {
+        let mut parser = Parser::new(encoding);
+        let ty = parser.ty()?;
+        parser.rest.is_empty().then_some(ty)
+    }
+
+    /// Returns the byte ranges of the substitutions of the encoding of a single type (parsed with an
+    /// empty substitution dictionary), in order, with the indices of the dictionary entries they
+    /// refer to, or `None` if it isn't valid.
+    pub fn substitution_spans(encoding: &str) -> Option<Vec<(Range<usize>, usize)>> {
+        let mut parser = Parser::new(encoding);
+        parser.ty()?;
+        if !parser.rest.is_empty() {
+            return None;
+        }
+        let len = encoding.len();
+        let spans = parser.substitution_spans.into_iter();
+        Some(spans.map(|(start, end, index)| (len - start..len - end, index)).collect())
+    }
+
+    /// Replaces every substitution of the type (parsed with an empty substitution dictionary) by
+    /// the component it refers to, as `TypeId::expand_substitutions` does.
+    pub fn expand_substitutions(self) -> Type {
+        Expander { dict: Vec::new() }.ty(self)
+    }
+}
+
+/// Replays the dictionary entries the parser adds, in the same order, with the components they
+/// refer to expanded (see `TypeId::expand_substitutions`).
+struct Expander {
+    dict: Vec<Type>,
+}
+
+impl Expander {
+    fn fn_ty(&mut self, fn_ty: FnType) -> FnType {
+        let ret = self.ty(fn_ty.ret);
+        let params = fn_ty.params.into_iter().map(|param| self.ty(param)).collect();
+        FnType { ret, params, c_variadic: fn_ty.c_variadic }
+    }
+
+    fn ty(&mut self, ty: Type) -> Type {
+        // Types may be nested arbitrarily deeply (see `Parser::ty`)
+        ensure_sufficient_stack(|| {
+            let ty = match ty {
+                // Builtin types are not compressed, and substitutions don't add entries
+                Type::Builtin(_) => return ty,
+                Type::Substitution(index) => return self.dict[index].clone(),
+                // The function types of function pointers don't add entries (only the pointers)
+                Type::Fn(fn_ty) => return Type::Fn(Box::new(self.fn_ty(*fn_ty))),
+                Type::Vendor { name, args } => Type::Vendor {
+                    name,
+                    args: args.map(|args| args.into_iter().map(|arg| self.ty(arg)).collect()),
+                },
+                Type::Projection { name, args, term } => Type::Projection {
+                    name,
+                    args: args.map(|args| args.into_iter().map(|arg| self.ty(arg)).collect()),
+                    term: Box::new(self.ty(*term)),
+                },
+                Type::Region(_) | Type::Name(_) => ty,
+                Type::Class { nested, prefix, names, template_args } => {
+                    return self.class(nested, prefix, names, template_args);
+                }
+                Type::Pattern { ty, pat } => Type::Pattern { ty: Box::new(self.ty(*ty)), pat },
+                Type::Dyn { star, predicates, region } => Type::Dyn {
+                    star,
+                    predicates: predicates.into_iter().map(|pred| self.ty(pred)).collect(),
+                    region: Box::new(self.ty(*region)),
+                },
+                Type::Mut(ty) => Type::Mut(Box::new(self.ty(*ty))),
+                Type::Ptr(ty) => Type::Ptr(Box::new(self.ty(*ty))),
+                Type::Ref(ty) => Type::Ref(Box::new(self.ty(*ty))),
+                Type::Const(ty) => Type::Const(Box::new(self.ty(*ty))),
+                Type::Array { len, ty } => Type::Array { len, ty: Box::new(self.ty(*ty)) },
+                Type::Literal { ty, value } => Type::Literal { ty: Box::new(self.ty(*ty)), value },
+            };
+            self.dict.push(ty.clone());
+            ty
+        })
+    }
+
+    /// Expands a C++ class name, adding the entries of its enclosing namespaces and class template,
+    /// and of the class template specialization if it has template arguments (see
+    /// `Parser::class`).
+    fn class(
+        &mut self,
+        nested: bool,
+        prefix: Option<ClassPrefix>,
+        names: Vec<String>,
+        template_args: Option<Vec<Type>>,
+    ) -> Type {
+        // The names of the substituted prefix, which is a name or a class name without template
+        // arguments, unless the substitution refers to another kind of component
+        let base = match prefix {
+            Some(ClassPrefix::Std) => Some((true, Vec::new())),
+            Some(ClassPrefix::Substitution(index)) => match &self.dict[index] {
+                Type::Name(name) => Some((false, vec![name.clone()])),
+                Type::Class {
+                    prefix: prefix @ (None | Some(ClassPrefix::Std)),
+                    names,
+                    template_args: None,
+                    ..
+                } => Some((prefix.is_some(), names.clone())),
+                _ => None,
+            },
+            None => Some((false, Vec::new())),
+        };
+        let Some((is_std, mut all_names)) = base else {
+            // The prefix is kept as is, along with the entries of the names after it
+            for len in 1..=names.len() {
+                let names = names[..len].to_vec();
+                self.dict.push(Type::Class { nested: true, prefix, names, template_args: None });
+            }
+            let template_args = template_args
+                .map(|args| args.into_iter().map(|arg| self.ty(arg)).collect::<Vec<_>>());
+            let is_specialization = template_args.is_some();
+            let ty = Type::Class { nested, prefix, names, template_args };
+            if is_specialization {
+                self.dict.push(ty.clone());
+            }
+            return ty;
+        };
+        for name in names {
+            all_names.push(name);
+            let prefix = class_ty(is_std, all_names.clone(), None);
+            self.dict.push(prefix);
+        }
+        let template_args =
+            template_args.map(|args| args.into_iter().map(|arg| self.ty(arg)).collect::<Vec<_>>());
+        let is_specialization = template_args.is_some();
+        let ty = class_ty(is_std, all_names, template_args);
+        if is_specialization {
+            self.dict.push(ty.clone());
+        }
+        ty
+    }
+}
+
+/// Returns the type of a C++ class name with the specified names (after `St` if it is in the `std`
+/// namespace) and template arguments, as the encoder encodes it without substitutions (see
+/// `encode_class_name`).
+fn class_ty(is_std: bool, mut names: Vec<String>, template_args: Option<Vec<Type>>) -> Type {
+    if !is_std && names.len() == 1 && template_args.is_none() {
+        return Type::Name(names.pop().unwrap());
+    }
+    let prefix = is_std.then_some(ClassPrefix::Std);
+    Type::Class { nested: names.len() > 1, prefix, names, template_args }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+    dict: Vec<Entry>,
+    /// The uncompressed lengths of the components of the dictionary entries.
+    uncompressed_lens: Vec<usize>,
+    /// The names of the predicates of trait object types of the dictionary entries, so that the
+    /// projections on the associated types of substituted principal traits are told apart.
+    predicate_names: Vec<Option<&'a str>>,
+    /// The number of substitutions parsed.
+    substitutions: usize,
+    /// The lengths of the rest of the input before and after each substitution parsed, and the
+    /// index of the dictionary entry it refers to.
+    substitution_spans: Vec<(usize, usize, usize)>,
+    /// The number of bytes saved by the substitutions parsed (which is negative if substitutions
+    /// are longer than the components they refer to).
+    saved: isize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(rest: &'a str) -> Parser<'a> {
+        Parser {
+            rest,
+            dict: Vec::new(),
+            uncompressed_lens: Vec::new(),
+            predicate_names: Vec::new(),
+            substitutions: 0,
+            substitution_spans: Vec::new(),
+            saved: 0,
+        }
+    }
+
+    /// Returns the position the component of a dictionary entry starts at, for `push`.
+    fn start(&self) -> (usize, isize) {
+        (self.rest.len(), self.saved)
+    }
+
+    /// Adds a dictionary entry for the component parsed since the specified start.
+    fn push(&mut self, entry: Entry, (rest_len, saved): (usize, isize)) {
+        let len = (rest_len - self.rest.len()) as isize + self.saved - saved;
+        self.dict.push(entry);
+        self.uncompressed_lens.push(len as usize);
+        self.predicate_names.push(None);
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if let Some(rest) = self.rest.strip_prefix(s) {
+            self.rest = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Option<()> {
+        self.eat(s).then_some(())
+    }
+
+    /// Parses the characters up to (but not including) the next "E".
+    fn until_end(&mut self) -> Option<&'a str> {
+        let (s, _) = self.rest.split_once('E')?;
+        self.rest = &self.rest[s.len()..];
+        Some(s)
+    }
+
+    /// Parses a decimal number without leading zeros.
+    fn number(&mut self) -> Option<u64> {
+        let len = self.rest.bytes().take_while(u8::is_ascii_digit).count();
+        let (digits, rest) = self.rest.split_at(len);
+        if digits.len() > 1 && digits.starts_with('0') {
+            return None;
+        }
+        self.rest = rest;
+        digits.parse().ok()
+    }
+
+    /// Parses a <length><name> pair.
+    fn name(&mut self) -> Option<&'a str> {
+        let len = usize::try_from(self.number()?).ok()?;
+        let name = self.rest.get(..len).filter(|name| !name.is_empty())?;
+        self.rest = &self.rest[len..];
+        Some(name)
+    }
+
+    /// Parses a substitution, returning the index and kind of the dictionary entry it refers to.
+    fn substitution(&mut self) -> Option<(usize, Entry)> {
+        let start = self.rest.len();
+        let (index, rest) = substitution_index(self.rest)?;
+        let entry = *self.dict.get(index)?;
+        self.rest = rest;
+        self.substitutions += 1;
+        self.substitution_spans.push((start, self.rest.len(), index));
+        self.saved += self.uncompressed_lens[index] as isize - (start - self.rest.len()) as isize;
+        Some((index, entry))
+    }
+
+    /// Parses a substitution, or a class template specialization whose class template is
+    /// substituted (i.e., `S[<seq-id>]_I<template-arg1..template-argN>E`), returning it along with
+    /// the kind of the dictionary entry the substitution refers to.
+    fn substitution_ty(&mut self) -> Option<(Type, Entry)> {
+        let start = self.start();
+        let (index, entry) = self.substitution()?;
+        if entry != Entry::Ty || !self.eat("I") {
+            return Some((Type::Substitution(index), entry));
+        }
+        let template_args = Some(self.args()?);
+        self.push(Entry::Ty, start);
+        let prefix = Some(ClassPrefix::Substitution(index));
+        Some((Type::Class { nested: false, prefix, names: Vec::new(), template_args }, Entry::Ty))
+    }
+
+    /// Parses a C++ class name starting with `St` or `N` (see `Type::Class`), adding the dictionary
+    /// entries of its enclosing namespaces and class template, and of the class template
+    /// specialization if it has template arguments.
+    fn class(&mut self, start: (usize, isize)) -> Option<Type> {
+        let nested = self.eat("N");
+        let prefix_start = self.start();
+        let prefix = if self.eat("St") {
+            Some(ClassPrefix::Std)
+        } else if nested && self.rest.starts_with('S') {
+            match self.substitution()? {
+                (index, Entry::Ty) => Some(ClassPrefix::Substitution(index)),
+                _ => return None,
+            }
+        } else {
+            None
+        };
+        // Unnested names have a single name after `St`
+        let mut names = Vec::new();
+        while (nested || names.is_empty()) && self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            names.push(self.name()?.to_string());
+            self.push(Entry::Ty, prefix_start);
+        }
+        let template_args = if self.eat("I") { Some(self.args()?) } else { None };
+        // Nested names have more than one component besides `St`, and the ones after a
+        // substitution of their class template have template arguments
+        let is_valid = match prefix {
+            _ if !nested => names.len() == 1,
+            Some(ClassPrefix::Substitution(_)) => !names.is_empty() || template_args.is_some(),
+            Some(ClassPrefix::Std) | None => names.len() > 1,
+        };
+        if !is_valid {
+            return None;
+        }
+        if nested {
+            self.expect("E")?;
+        }
+        if template_args.is_some() {
+            self.push(Entry::Ty, start);
+        }
+        Some(Type::Class { nested, prefix, names, template_args })
+    }
+
+    /// Parses a function type after its "F".
+    fn fn_ty(&mut self) -> Option<FnType> {
+        let ret = self.ty()?;
+        let mut params = Vec::new();
+        let mut c_variadic = false;
+        while !self.eat("E") {
+            if self.eat("z") {
+                c_variadic = true;
+                self.expect("E")?;
+                break;
+            }
+            params.push(self.ty()?);
+        }
+        // Empty parameter lists are encoded with a void parameter specifier "v"
+        if params.is_empty() && !c_variadic {
+            return None;
+        }
+        Some(FnType { ret, params, c_variadic })
+    }
+
+    /// Parses a type.
+    fn ty(&mut self) -> Option<Type> {
+        // Types may be nested arbitrarily deeply, as the ones encoded (see `encode_ty`)
+        ensure_sufficient_stack(|| self.ty_kind())
+    }
+
+    /// Parses a type, as `ty` does, on the current stack.
+    fn ty_kind(&mut self) -> Option<Type> {
+        let start = self.start();
+        if let Some(&builtin) = BUILTIN_TYPES.iter().find(|&&ty| self.rest.starts_with(ty)) {
+            self.rest = &self.rest[builtin.len()..];
+            return Some(Type::Builtin(builtin));
+        }
+
+        let ty = match self.rest.as_bytes().first()? {
+            b'S' if self.rest.starts_with("St") => return self.class(start),
+            b'S' => {
+                let (ty, entry) = self.substitution_ty()?;
+                return (entry == Entry::Ty).then_some(ty);
+            }
+
+            // N<prefix><length><name>E
+            b'N' => return self.class(start),
+
+            // U3mut<element-type> as vendor extended type qualifier
+            b'U' => {
+                self.expect("U3mut")?;
+                Type::Mut(Box::new(self.ty()?))
+            }
+
+            // P[K]<element-type>, PF<return-type><parameter-type1..parameter-typeN>E, or
+            // R[K]<element-type>
+            b'P' | b'R' => {
+                let reference = self.eat("R");
+                if !reference {
+                    self.expect("P")?;
+                }
+                let pointee = if !reference && self.eat("F") {
+                    Type::Fn(Box::new(self.fn_ty()?))
+                } else if self.rest.starts_with('K') {
+                    let const_start = self.start();
+                    self.expect("K")?;
+                    let ty = Type::Const(Box::new(self.ty()?));
+                    self.push(Entry::Const, const_start);
+                    ty
+                } else if self.rest.starts_with('S') && !self.rest.starts_with("St") {
+                    // The substitution may be of a const-qualified type
+                    match self.substitution_ty()? {
+                        (ty, Entry::Ty | Entry::Const) => ty,
+                        (_, Entry::Region | Entry::Literal) => return None,
+                    }
+                } else {
+                    self.ty()?
+                };
+                if reference {
+                    Type::Ref(Box::new(pointee))
+                } else {
+                    Type::Ptr(Box::new(pointee))
+                }
+            }
+
+            // A<array-length><element-type>
+            b'A' => {
+                self.expect("A")?;
+                let len = self.number()?;
+                Type::Array { len, ty: Box::new(self.ty()?) }
+            }
+
+            // <length><name>
+            // <length><name>[I<template-arg1..template-argN>E]
+            b'0'..=b'9' => {
+                let name = self.name()?.to_string();
+                if self.eat("I") {
+                    // The class template is a substitution candidate as well
+                    self.push(Entry::Ty, start);
+                    let template_args = Some(self.args()?);
+                    Type::Class { nested: false, prefix: None, names: vec![name], template_args }
+                } else {
+                    Type::Name(name)
+                }
+            }
+
+            // u<length><name>[I<element-type1..element-typeN>E] as vendor extended type
+            b'u' => {
+                self.expect("u")?;
+                match self.name()? {
+                    // Regions are only generic arguments
+                    "region" => return None,
+                    "pat" => {
+                        self.expect("I")?;
+                        let ty = Box::new(self.ty()?);
+                        let pat = self.until_end().filter(|pat| !pat.is_empty())?.to_string();
+                        self.expect("E")?;
+                        Type::Pattern { ty, pat }
+                    }
+                    name @ ("dyn" | "dynstar") => {
+                        self.expect("I")?;
+                        let mut predicates = Vec::new();
+                        // The name of the principal trait (i.e., of the first predicate)
+                        let mut principal = None;
+                        // The region closes the list of predicates
+                        let region = loop {
+                            if self.rest.starts_with("u6region") {
+                                break self.arg()?;
+                            }
+                            let index = if self.rest.starts_with('S') {
+                                match self.substitution()? {
+                                    (index, Entry::Region) => break Type::Substitution(index),
+                                    (index, Entry::Ty | Entry::Literal) => {
+                                        predicates.push(Type::Substitution(index));
+                                        index
+                                    }
+                                    (_, Entry::Const) => return None,
+                                }
+                            } else {
+                                predicates.push(self.predicate(principal)?);
+                                self.dict.len() - 1
+                            };
+                            if predicates.len() == 1 {
+                                principal = self.predicate_names[index];
+                            }
+                        };
+                        self.expect("E")?;
+                        Type::Dyn { star: name == "dynstar", predicates, region: Box::new(region) }
+                    }
+                    name => {
+                        let args = if self.eat("I") { Some(self.args()?) } else { None };
+                        Type::Vendor { name: name.to_string(), args }
+                    }
+                }
+            }
+
+            _ => return None,
+        };
+        self.push(Entry::Ty, start);
+        Some(ty)
+    }
+
+    /// Parses a predicate of a trait object type (i.e., `u<length><name>[I<arg1..argN>E]`, followed
+    /// by its term if it is a projection), after the principal trait with the specified name, if
+    /// any.
+    fn predicate(&mut self, principal: Option<&str>) -> Option<Type> {
+        let start = self.start();
+        self.expect("u")?;
+        let name = self.name()?;
+        let args = if self.eat("I") { Some(self.args()?) } else { None };
+        // Projections are on the associated items of the principal trait, whose names are nested
+        // in its name (i.e., `N<t|v><trait-name><item-name>` without v0's extended form of paths;
+        // see `encode_ty_name`), or of its supertraits, which are told apart from auto traits by
+        // their terms, unless these are user-defined types without generic arguments
+        let is_projection = principal.is_some_and(|principal| {
+            let is_nested = ["Nt", "Nv"].iter().any(|tag| {
+                name.strip_prefix(tag)
+                    .and_then(|name| name.strip_prefix(principal))
+                    .is_some_and(|name| !name.is_empty())
+            });
+            is_nested || !self.at_predicate()
+        });
+        let ty = if is_projection {
+            // The projection is added to the dictionary after its term, as it is encoded
+            let term = Box::new(self.arg()?);
+            Type::Projection { name: name.to_string(), args, term }
+        } else {
+            Type::Vendor { name: name.to_string(), args }
+        };
+        self.push(Entry::Ty, start);
+        *self.predicate_names.last_mut()? = Some(name);
+        Some(ty)
+    }
+
+    /// Returns whether the rest of the input starts with a predicate of a trait object type
+    /// without generic arguments (e.g., an auto trait) or the region that closes the list of
+    /// predicates, rather than with the term of a projection.
+    fn at_predicate(&self) -> bool {
+        if self.rest.starts_with("u6region") {
+            return true;
+        }
+        // Predicates are only substituted by predicates (see `DictKey::Predicate`)
+        if let Some((index, _)) = substitution_index(self.rest) {
+            return self.dict.get(index) == Some(&Entry::Region)
+                || self.predicate_names.get(index).is_some_and(Option::is_some);
+        }
+        // The names of predicates are paths (i.e., v0's <path>, which starts with "N")
+        let Some(rest) = self.rest.strip_prefix('u') else {
+            return false;
+        };
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let Ok(len) = rest[..digits].parse::<usize>() else {
+            return false;
+        };
+        let rest = &rest[digits..];
+        rest.get(..len).is_some_and(|name| name.starts_with('N')) && !rest[len..].starts_with('I')
+    }
+
+    /// Parses generic arguments after their "I", up to and including their "E".
+    fn args(&mut self) -> Option<Vec<Type>> {
+        let mut args = Vec::new();
+        while !self.eat("E") {
+            args.push(self.arg()?);
+        }
+        Some(args)
+    }
+
+    /// Parses a generic argument (i.e., a type, region, or literal argument).
+    fn arg(&mut self) -> Option<Type> {
+        let start = self.start();
+        if self.eat("u6region") {
+            // u6region[I[<region-disambiguator>]<region-index>E]
+            let index = if self.eat("I") {
+                let index = self.until_end()?;
+                let digits = match index.strip_prefix('s') {
+                    Some(index) => {
+                        let (disambiguator, digits) = index.split_once('_')?;
+                        if !disambiguator.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                            return None;
+                        }
+                        digits
+                    }
+                    None => index,
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                self.expect("E")?;
+                Some(index.to_string())
+            } else {
+                None
+            };
+            self.push(Entry::Region, start);
+            return Some(Type::Region(index));
+        }
+
+        if self.rest.starts_with('S') && !self.rest.starts_with("St") {
+            return match self.substitution_ty()? {
+                (ty, Entry::Ty | Entry::Region | Entry::Literal) => Some(ty),
+                (_, Entry::Const) => None,
+            };
+        }
+
+        if self.eat("L") {
+            // L<element-type>[n][<element-value>]E as literal argument, where the value of a
+            // const parameter is empty
+            let ty = Box::new(self.ty()?);
+            let value = self.until_end()?;
+            let digits = value.strip_prefix('n').unwrap_or(value);
+            let is_valid = value.is_empty()
+                || ["true", "false"].contains(&value)
+                || (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()));
+            if !is_valid {
+                return None;
+            }
+            self.expect("E")?;
+            self.push(Entry::Literal, start);
+            return Some(Type::Literal { ty, value: value.to_string() });
+        }
+
+        self.ty()
+    }
+}
+
+/// Parses a substitution (i.e., `S[<seq-id>]_`) at the start of the specified input, returning the
+/// index of the dictionary entry it refers to and the rest of the input.
+fn substitution_index(s: &str) -> Option<(usize, &str)> {
+    let (seq_id, rest) = s.strip_prefix('S')?.split_once('_')?;
+    if !seq_id.bytes().all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()) {
+        return None;
+    }
+    let index = if seq_id.is_empty() { 0 } else { usize::from_str_radix(seq_id, 36).ok()? + 1 };
+    Some((index, rest))
+}
+
+impl fmt::Display for TypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("_ZTS")?;
+        if let Some(class) = &self.class {
+            write!(f, "M{class}")?;
+        }
+        write!(f, "{}", self.fn_ty)?;
+        if self.normalized {
+            f.write_str(".normalized")?;
+        }
+        if self.ptrsized {
+            f.write_str(".ptrsized")?;
+        }
+        if self.generalized {
+            f.write_str(".generalized")?;
+        }
+        if self.arrays {
+            f.write_str(".arrays")?;
+        }
+        if self.returns {
+            f.write_str(".returns")?;
+        }
+        if let Some(salt) = &self.salt {
+            write!(f, ".salt{salt}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "F{}", self.ret)?;
+        for param in &self.params {
+            write!(f, "{param}")?;
+        }
+        if self.c_variadic {
+            f.write_str("z")?;
+        }
+        f.write_str("E")
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Types may be nested arbitrarily deeply (see `Parser::ty`)
+        ensure_sufficient_stack(|| match self {
+            Type::Builtin(ty) => f.write_str(ty),
+            Type::Substitution(0) => f.write_str("S_"),
+            Type::Substitution(index) => {
+                write!(f, "S{}_", base_n::encode(*index as u128 - 1, 36).to_uppercase())
+            }
+            Type::Vendor { name, args } => {
+                write!(f, "u{}{name}", name.len())?;
+                if let Some(args) = args {
+                    f.write_str("I")?;
+                    for arg in args {
+                        write!(f, "{arg}")?;
+                    }
+                    f.write_str("E")?;
+                }
+                Ok(())
+            }
+            Type::Projection { name, args, term } => {
+                write!(f, "u{}{name}", name.len())?;
+                if let Some(args) = args {
+                    f.write_str("I")?;
+                    for arg in args {
+                        write!(f, "{arg}")?;
+                    }
+                    f.write_str("E")?;
+                }
+                write!(f, "{term}")
+            }
+            Type::Region(None) => f.write_str("u6region"),
+            Type::Region(Some(index)) => write!(f, "u6regionI{index}E"),
+            Type::Pattern { ty, pat } => write!(f, "u3patI{ty}{pat}E"),
+            Type::Dyn { star, predicates, region } => {
+                f.write_str(if *star { "u7dynstarI" } else { "u3dynI" })?;
+                for predicate in predicates {
+                    write!(f, "{predicate}")?;
+                }
+                write!(f, "{region}E")
+            }
+            Type::Mut(ty) => write!(f, "U3mut{ty}"),
+            Type::Ptr(ty) => write!(f, "P{ty}"),
+            Type::Ref(ty) => write!(f, "R{ty}"),
+            Type::Const(ty) => write!(f, "K{ty}"),
+            Type::Fn(fn_ty) => write!(f, "{fn_ty}"),
+            Type::Array { len, ty } => write!(f, "A{len}{ty}"),
+            Type::Name(name) => write!(f, "{}{name}", name.len()),
+            Type::Class { nested, prefix, names, template_args } => {
+                if *nested {
+                    f.write_str("N")?;
+                }
+                match prefix {
+                    Some(ClassPrefix::Std) => f.write_str("St")?,
+                    Some(ClassPrefix::Substitution(index)) => {
+                        write!(f, "{}", Type::Substitution(*index))?
+                    }
+                    None => {}
+                }
+                for name in names {
+                    write!(f, "{}{name}", name.len())?;
+                }
+                if let Some(args) = template_args {
+                    f.write_str("I")?;
+                    for arg in args {
+                        write!(f, "{arg}")?;
+                    }
+                    f.write_str("E")?;
+                }
+                if *nested {
+                    f.write_str("E")?;
+                }
+                Ok(())
+            }
+            Type::Literal { ty, value } => write!(f, "L{ty}{value}E"),
+        })
+    }
+}
@@ -0,0 +1,313 @@
+use super::*;
+
+// The type metadata identifiers are the ones the `tests/codegen/sanitizer` and `tests/ui/sanitizer`
+// tests expect (with concrete crate disambiguators).
+
+#[track_caller]
+fn assert_round_trips(typeid: &str) {
+    let parsed = TypeId::parse(typeid).unwrap_or_else(|| panic!("`{typeid}` cannot be parsed"));
+    assert_eq!(parsed.to_string(), typeid);
+}
+
+#[test]
+fn test_round_trip_primitive_types() {
+    for typeid in [
+        "_ZTSFvvE",
+        "_ZTSFvbbbE",
+        "_ZTSFvfffE",
+        "_ZTSFvdddE",
+        "_ZTSFvu2i8S_S_E",
+        "_ZTSFvu4i128S_S_E",
+        "_ZTSFvu5usizeS_S_E",
+        "_ZTSFvu4charS_S_E",
+        "_ZTSFvu3refIu3strES0_S0_E",
+        "_ZTSFu5nevervE",
+        "_ZTSFu3i32bu4charu2i8u3u64fdE",
+        "_ZTSFu3i32u2u8u3u32u2i8u3u64fdE.normalized",
+        "_ZTSFviiiE",
+        "_ZTSFvjjjE",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_pointer_types() {
+    for typeid in [
+        "_ZTSFvu3refIu3i32EU3mutS0_PKS_PS_E",
+        "_ZTSFvu3refIvEU3mutS_PKvPvE.normalized.generalized",
+        "_ZTSFvU3mutu3refIu3i32ES0_S0_E",
+        "_ZTSFvPKu3i32PS_S2_E",
+        "_ZTSFvPu3i32PKS_S2_E",
+        "_ZTSFvPKvS0_S0_E.generalized",
+        "_ZTSFvPFu3i32S_ES0_S0_E",
+        "_ZTSFu3i32PFS_S_S_S_ES_S_S_E",
+        "_ZTSFu3i32PKu2u8zE",
+        "_ZTSFu3i32PKvzE.normalized.generalized",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_reference_types() {
+    for typeid in [
+        "_ZTSFvRKu3i32RS_S2_E",
+        "_ZTSFvRu3i32RKS_S2_E",
+        "_ZTSFvRKu3strPS_E",
+        "_ZTSFvRKvS0_S0_E.generalized",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_sequence_types() {
+    for typeid in [
+        "_ZTSFvA32u3i32S0_S0_E",
+        "_ZTSFvu5tupleIu3i32S_ES0_S0_E",
+        "_ZTSFvu3refIu5sliceIu3i32EES1_S1_E",
+        "_ZTSFvu3refIu5sliceIu2u8EEu3refIu3strEu5tupleIu3i32bEA4u3u16E",
+        "_ZTSFvu3refIvES_u5tupleIu3i32u2u8EA4u3u16E.normalized.generalized",
+        "_ZTSFvu3refIu5sliceIu2u8EEu5sliceIu3u16EE.arrays",
+        "_ZTSFvPKvS0_E.normalized.generalized.arrays",
+        "_ZTSFvPKvS0_E.generalized.returns",
+        "_ZTSFvu3u64E.ptrsized.generalized",
+        "_ZTSFvu3patIu3u321..=5EE",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_user_defined_types() {
+    for typeid in [
+        "_ZTSFv3FooS_S_E",
+        "_ZTSFvP5type1S0_S0_E",
+        "_ZTSFu3i645PointPKS0_E",
+        "_ZTSFvu3refIu23NtCs1234_5crate7Struct1Iu3i32EES1_S1_E",
+        "_ZTSFvu3refIu40NtNCNvCs1234_5crate3foo11{{closure}}3FooES0_S0_E",
+        "_ZTSFvu25NtNvCs1234_5crate3foo3FooIu3i32Lu5usize32EES2_S2_E",
+        "_ZTSFvPFvu23NtCs1234_5crate7Struct1Iu6regionI0EEEE",
+        "_ZTSFvu23NtCs1234_5crate7Struct1Iu6regionIs0_1ELbtrueELu2i8n5EEE",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_class_names() {
+    // As `encode_class_name` encodes them
+    for typeid in [
+        "_ZTSFvN2ns3FooES0_E",
+        "_ZTSFvN2ns3FooENS_3BarEE",
+        "_ZTSFvSt3FooS_E",
+        "_ZTSFvNSt2ns3FooEE",
+        "_ZTSFvN2ns3VecIu3i32EES2_E",
+        "_ZTSFv3VecIu3i32ES_Iu2u8EE",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_trait_types() {
+    for typeid in [
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait1u6regionEES2_S2_E",
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait1u25NtNtCs1_4core6marker4Sendu6regionEES3_E",
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait4Iu6regionu21NtCs1234_5crate5Type4Eu31NtNtCs1234\
+         _5crate6Trait46OutputIS_S0_Eu3refIu3i32ES_EES6_S6_E",
+        "_ZTSFvu3refIu3dynIu31NtNtNtCs1_4core3ops8function2FnIu5tupleIu3i32EEu44NtNtNtNtCs1_4core3o\
+         ps8function6FnOnce6OutputIS0_ES_u6regionEES5_S5_E",
+        "_ZTSFvu3refIu7dynstarIu22NtCs1234_5crate6Trait1u6regionEEE",
+        // Associated const equality bounds
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait1u26NvNtCs1234_5crate6Trait11NLu5usize3Eu6region\
+         EES4_S4_E",
+        "_ZTSFvu3refIu3dynIu22NtCs1234_5crate6Trait2u26NvNtCs1234_5crate6Trait21MLu5usize3Eu26NvNtCs\
+         1234_5crate6Trait21NS0_u6regionEES5_S5_E",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_member_function_pointer_types() {
+    for typeid in [
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFvvE",
+        "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFu3i32S_S2_E.normalized",
+    ] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_round_trip_salted_types() {
+    for typeid in ["_ZTSFvvE.saltAndroid_1234", "_ZTSFu3i32S_E.normalized.salt0x1234abcd"] {
+        assert_round_trips(typeid);
+    }
+}
+
+#[test]
+fn test_parse_typeid() {
+    let ptr = |ty| Type::Ptr(Box::new(ty));
+    let i32 = Type::Vendor { name: "i32".to_string(), args: None };
+    assert_eq!(
+        TypeId::parse("_ZTSFvPKu3i32PS_S2_E.normalized"),
+        Some(TypeId {
+            class: None,
+            fn_ty: FnType {
+                ret: Type::Builtin("v"),
+                params: vec![
+                    ptr(Type::Const(Box::new(i32))),
+                    ptr(Type::Substitution(0)),
+                    Type::Substitution(3),
+                ],
+                c_variadic: false,
+            },
+            normalized: true,
+            ptrsized: false,
+            generalized: false,
+            arrays: false,
+            returns: false,
+            salt: None,
+        })
+    );
+}
+
+#[test]
+fn test_substitution_spans() {
+    assert_eq!(
+        Type::substitution_spans("u5tupleIu3refIu3i32ES0_S_E"),
+        Some(vec![(20..23, 1), (23..25, 0)])
+    );
+    assert_eq!(Type::substitution_spans("u3i32"), Some(vec![]));
+    assert_eq!(Type::substitution_spans("S_"), None);
+}
+
+#[test]
+fn test_parse_type() {
+    assert_eq!(Type::parse("3Foo"), Some(Type::Name("Foo".to_string())));
+    assert_eq!(Type::parse("l"), Some(Type::Builtin("l")));
+    assert_eq!(Type::parse("u3i32"), Some(Type::Vendor { name: "i32".to_string(), args: None }));
+    assert_eq!(
+        Type::parse("N2ns3FooE"),
+        Some(Type::Class {
+            nested: true,
+            prefix: None,
+            names: vec!["ns".to_string(), "Foo".to_string()],
+            template_args: None,
+        })
+    );
+    assert_eq!(
+        Type::parse("St3FooIlE"),
+        Some(Type::Class {
+            nested: false,
+            prefix: Some(ClassPrefix::Std),
+            names: vec!["Foo".to_string()],
+            template_args: Some(vec![Type::Builtin("l")]),
+        })
+    );
+    assert_eq!(Type::parse(""), None);
+    assert_eq!(Type::parse("3Foo1"), None);
+    // More than one type, or a nested name with a single component
+    assert_eq!(Type::parse("3Foo3Bar"), None);
+    assert_eq!(Type::parse("St3Foo3Bar"), None);
+    assert_eq!(Type::parse("N3FooE"), None);
+    assert_eq!(Type::parse("NSt3FooE"), None);
+    assert_eq!(Type::parse("encoding"), None);
+}
+
+#[test]
+fn test_compression_stats() {
+    assert_eq!(
+        TypeId::compression_stats("_ZTSFvu3i32S_S_E"),
+        Some(CompressionStats { substitutions: 2, uncompressed_len: 22 })
+    );
+    // `_ZTSFvu3refIu3i32EU3mutu3refIu3i32EPKu3i32Pu3i32E`, where the substitutions refer to a
+    // component with a substitution and to a const-qualified type
+    assert_eq!(
+        TypeId::compression_stats("_ZTSFvu3refIu3i32EU3mutS0_PKS_PS_E"),
+        Some(CompressionStats { substitutions: 3, uncompressed_len: 49 })
+    );
+    assert_eq!(
+        TypeId::compression_stats("_ZTSFvu3refIu3i32EU3mutS0_PKS_PS2_E.normalized"),
+        Some(CompressionStats { substitutions: 3, uncompressed_len: 61 })
+    );
+    assert_eq!(
+        TypeId::compression_stats("_ZTSFvvE"),
+        Some(CompressionStats { substitutions: 0, uncompressed_len: 8 })
+    );
+    assert_eq!(TypeId::compression_stats("_ZTSFvS_E"), None);
+}
+
+#[test]
+fn test_parse_typeid_invalid() {
+    for typeid in [
+        "",
+        "_ZTSFvE",
+        "_ZTSFvu3i32",
+        "_ZTSFvvEE",
+        "_ZTSFvvE.unknown",
+        // The suffixes are out of order
+        "_ZTSFvvE.generalized.normalized",
+        "_ZTSFvvE.arrays.generalized",
+        // The suffixes are exclusive
+        "_ZTSFvvE.normalized.ptrsized",
+        // The substitutions refer to missing dictionary entries
+        "_ZTSFvS_E",
+        "_ZTSFvu3i32S0_E",
+        // The substitution refers to a region in a type position
+        "_ZTSFvu3FooIu6regionES_E",
+        // Const-qualified types are only pointees of raw pointers
+        "_ZTSFvKu3i32E",
+        "_ZTSFvPKu3i32S0_E",
+        "_ZTSFvRFvvEE",
+        // Sequence ids are uppercase, and numbers have no leading zeros
+        "_ZTSFvu3i32PS_Sa_E",
+        "_ZTSFvA032u3i32E",
+        // The region doesn't close the list of predicates
+        "_ZTSFvu3dynIu22NtCs1234_5crate6Trait1EE",
+        // The class isn't followed by a function type
+        "_ZTSMu3i32E",
+        "_ZTSMu3i32u3i32FvvE",
+        // The salt is empty, has invalid characters, or isn't the last suffix
+        "_ZTSFvvE.salt",
+        "_ZTSFvvE.salt-1",
+        "_ZTSFvvE.salt1.normalized",
+    ] {
+        assert_eq!(TypeId::parse(typeid), None, "`{typeid}` was parsed");
+    }
+}
+
+#[test]
+fn test_expand_substitutions() {
+    for (compressed, uncompressed) in [
+        ("_ZTSFvu3refIu3i32EU3mutS0_PKS_PS_E", "_ZTSFvu3refIu3i32EU3mutu3refIu3i32EPKu3i32Pu3i32E"),
+        ("_ZTSFvPFu3i32S_ES0_E", "_ZTSFvPFu3i32u3i32EPFu3i32u3i32EE"),
+        (
+            "_ZTSFvu3refIu5sliceIu3i32EES1_E.arrays",
+            "_ZTSFvu3refIu5sliceIu3i32EEu3refIu5sliceIu3i32EEE.arrays",
+        ),
+        (
+            "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFu3i32S_S2_E",
+            "_ZTSMu3dynIu22NtCs1234_5crate6Trait1u6regionEFu3i32u22NtCs1234_5crate6Trait1u3i32E",
+        ),
+        // The enclosing namespaces and class templates are added to the dictionary
+        ("_ZTSFvN2ns3FooES0_E", "_ZTSFvN2ns3FooEN2ns3FooEE"),
+        ("_ZTSFvN2ns3FooENS_3BarEE", "_ZTSFvN2ns3FooEN2ns3BarEE"),
+        ("_ZTSFvSt3FooS_E", "_ZTSFvSt3FooSt3FooE"),
+        ("_ZTSFvN2ns3VecIu3i32EES2_E", "_ZTSFvN2ns3VecIu3i32EEN2ns3VecIu3i32EEE"),
+        ("_ZTSFv3VecIu3i32ES_Iu2u8EE", "_ZTSFv3VecIu3i32E3VecIu2u8EE"),
+        // The projection is added to the dictionary after its term
+        (
+            "_ZTSFvu3refIu3dynIu27NtNtCs1_4core4iter8Iteratoru34NtNtNtCs1_4core4iter8Iterator4Item\
+             u2u8u25NtNtCs1_4core6marker4Sendu6regionEES0_E",
+            "_ZTSFvu3refIu3dynIu27NtNtCs1_4core4iter8Iteratoru34NtNtNtCs1_4core4iter8Iterator4Item\
+             u2u8u25NtNtCs1_4core6marker4Sendu6regionEEu2u8E",
+        ),
+    ] {
+        let expanded = TypeId::parse(compressed).unwrap().expand_substitutions();
+        assert_eq!(expanded.to_string(), uncompressed);
+        assert_eq!(Some(expanded), TypeId::parse(uncompressed));
+    }
+}
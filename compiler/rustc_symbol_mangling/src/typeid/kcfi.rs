@@ -0,0 +1,117 @@
+/// Type metadata identifiers for LLVM Kernel Control Flow Integrity (KCFI) and cross-language LLVM
+/// KCFI support.
+///
+/// A KCFI type metadata identifier is a 32-bit constant produced by taking the lower half of the
+/// xxHash64 of the type metadata identifier (see llvm/llvm-project@cff5bef), which is identical to
+/// how Clang computes it for C and C++ functions. A seed may be provided by the target or with
+/// `-Zsanitizer-kcfi-seed` to salt KCFI type metadata identifiers per build (which Clang has no
+/// equivalent of, so a nonzero seed makes them incompatible with the ones of Clang-built code). It
+/// applies on top of the target's `cfi_typeid_salt`, which is part of the hashed type metadata
+/// identifier. The whole 64-bit hash may be used instead with `-Zsanitizer-kcfi-typeid-width=64`.
+use rustc_middle::ty::{Instance, InstanceDef, PolyFnSig, ReifyReason, Ty, TyCtxt};
+pub use rustc_session::config::KcfiTypeIdWidth;
+use rustc_target::abi::call::FnAbi;
+use std::hash::Hasher;
+use twox_hash::XxHash64;
+
+use crate::typeid::typeid_itanium_cxx_abi;
+use crate::typeid::TypeIdOptions;
+
+#[cfg(test)]
+mod tests;
+
+/// A KCFI type metadata identifier.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KcfiTypeId {
+    /// The lower half of the xxHash64 of the type metadata identifier.
+    U32(u32),
+    /// The whole xxHash64 of the type metadata identifier.
+    U64(u64),
+}
+
+/// Returns a KCFI type metadata identifier for the specified FnAbi.
+pub fn kcfi_typeid_for_fnabi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+    options: TypeIdOptions,
+) -> KcfiTypeId {
+    let typeid = typeid_itanium_cxx_abi::typeid_for_fnabi(tcx, fn_abi, options);
+    kcfi_typeid_for_session(tcx, &typeid)
+}
+
+/// Returns a KCFI type metadata identifier for the specified PolyFnSig (see `typeid_for_fnsig`).
+pub fn kcfi_typeid_for_fnsig<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_sig: PolyFnSig<'tcx>,
+    options: TypeIdOptions,
+) -> KcfiTypeId {
+    let typeid = typeid_itanium_cxx_abi::typeid_for_fnsig(tcx, fn_sig, options);
+    kcfi_typeid_for_session(tcx, &typeid)
+}
+
+/// Returns a KCFI type metadata identifier for the specified Instance.
+pub fn kcfi_typeid_for_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    options: TypeIdOptions,
+) -> KcfiTypeId {
+    let options = kcfi_options_for_instance(instance, options);
+    let typeid = typeid_itanium_cxx_abi::typeid_for_instance(tcx, instance, options);
+    kcfi_typeid_for_session(tcx, &typeid)
+}
+
+/// Returns the options the KCFI type metadata identifier of the specified Instance is computed
+/// with, from the specified options.
+pub(crate) fn kcfi_options_for_instance(
+    instance: Instance<'_>,
+    mut options: TypeIdOptions,
+) -> TypeIdOptions {
+    // KCFI support for Rust shares most of its implementation with the CFI support, with some key
+    // differences:
+    //
+    // 1. KCFI performs type tests differently and are implemented as different LLVM passes than CFI
+    //    to not require LTO.
+    // 2. KCFI has the limitation that a function or method may have one type id assigned only.
+    //
+    // Because of the limitation listed above (2), the current KCFI implementation (not CFI) does
+    // reifying of types (i.e., adds shims/trampolines for indirect calls in these cases) for:
+    //
+    // * Supporting casting between function items, closures, and Fn trait objects.
+    // * Supporting methods being cast as function pointers.
+    //
+    // This was implemented for KCFI support in #123106 and #123052 (which introduced the
+    // ReifyReason). The tracking issue for KCFI support for Rust is #123479.
+    if matches!(instance.def, InstanceDef::ReifyShim(_, Some(ReifyReason::FnPtr))) {
+        options.insert(TypeIdOptions::USE_CONCRETE_SELF);
+    }
+    options
+}
+
+/// Returns the KCFI type metadata identifier for the specified type metadata identifier, with the
+/// seed and width of the session.
+pub(crate) fn kcfi_typeid_for_session(tcx: TyCtxt<'_>, typeid: &str) -> KcfiTypeId {
+    kcfi_typeid(
+        typeid,
+        tcx.sess.sanitizer_kcfi_seed().unwrap_or(0),
+        tcx.sess.opts.unstable_opts.sanitizer_kcfi_typeid_width,
+    )
+}
+
+/// Returns the KCFI type metadata identifier for the specified type metadata identifier (i.e., the
+/// encoding string, such as `_ZTSFvu3i32E`), seed, and width.
+///
+/// This is the exact value the compiler emits for a function with that type metadata identifier,
+/// and is exposed so tools (e.g., kernel build tooling computing the KCFI type metadata identifiers
+/// of C prototypes or assembly stubs called from Rust) don't have to reimplement it. A seed of
+/// zero and a width of 32 bits produce the same KCFI type metadata identifiers as an unseeded
+/// Clang.
+pub fn kcfi_typeid(typeid: &str, seed: u64, width: KcfiTypeIdWidth) -> KcfiTypeId {
+    // A KCFI type metadata identifier is a 32-bit constant produced by taking the lower half of the
+    // xxHash64 of the type metadata identifier. (See llvm/llvm-project@cff5bef.)
+    let mut hash = XxHash64::with_seed(seed);
+    hash.write(typeid.as_bytes());
+    match width {
+        KcfiTypeIdWidth::Bits32 => KcfiTypeId::U32(hash.finish() as u32),
+        KcfiTypeIdWidth::Bits64 => KcfiTypeId::U64(hash.finish()),
+    }
+}
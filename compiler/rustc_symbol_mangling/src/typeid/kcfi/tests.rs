@@ -0,0 +1,61 @@
+use super::*;
+
+// The expected values are the ones Clang and the `tests/codegen/sanitizer/kcfi` tests expect for
+// the same type metadata identifiers.
+
+fn kcfi_typeid_u32(typeid: &str, seed: u64) -> u32 {
+    match kcfi_typeid(typeid, seed, KcfiTypeIdWidth::Bits32) {
+        KcfiTypeId::U32(id) => id,
+        KcfiTypeId::U64(_) => panic!("expected a 32-bit KCFI type metadata identifier"),
+    }
+}
+
+#[test]
+fn test_kcfi_typeid() {
+    // fn(i32) -> i32
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_E", 0), -1666898348i32 as u32);
+    // fn(i32, i32) -> i32
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_S_E", 0), -1789026986i32 as u32);
+    // fn(i32, i32, i32) -> i32
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_S_S_E", 0), 1248878270);
+    // fn(fn(i32) -> i32, i32) -> i32
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32PFS_S_ES_E", 0), 653723426);
+}
+
+#[test]
+fn test_kcfi_typeid_with_suffixes() {
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_E.normalized", 0), -841055669i32 as u32);
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_S_E.generalized", 0), 435418021);
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_E.normalized.generalized", 0), -686570305i32 as u32);
+}
+
+#[test]
+fn test_kcfi_typeid_is_lower_half_of_xxhash64() {
+    let mut hash: XxHash64 = Default::default();
+    hash.write(b"_ZTSFvvE");
+    assert_eq!(hash.finish(), 0x2f9af71da540670c);
+    assert_eq!(kcfi_typeid_u32("_ZTSFvvE", 0), 0xa540670c);
+}
+
+#[test]
+fn test_kcfi_typeid_with_seed() {
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_E", 0x1234abcd), 0xa7d13769);
+    assert_eq!(kcfi_typeid_u32("_ZTSFvvE", 0x1234abcd), 0xa22a85dc);
+    assert_eq!(kcfi_typeid_u32("_ZTSFu3i32S_E", u64::MAX), 0x7976f5b7);
+}
+
+#[test]
+fn test_kcfi_typeid_64_bits() {
+    assert_eq!(
+        kcfi_typeid("_ZTSFvvE", 0, KcfiTypeIdWidth::Bits64),
+        KcfiTypeId::U64(0x2f9af71da540670c)
+    );
+    assert_eq!(
+        kcfi_typeid("_ZTSFu3i32S_E", 0, KcfiTypeIdWidth::Bits64),
+        KcfiTypeId::U64(0x261aaf709ca52654)
+    );
+    assert_eq!(
+        kcfi_typeid("_ZTSFu3i32S_E", 0x1234abcd, KcfiTypeIdWidth::Bits64),
+        KcfiTypeId::U64(0x8155e3d9a7d13769)
+    );
+}
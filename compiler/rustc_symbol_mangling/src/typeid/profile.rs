@@ -0,0 +1,214 @@
+//! CFI encoding profiles (see `-Zcfi-encoding-profile`), which override the CFI encodings of
+//! user-defined types and extern types the `cfi_encoding` attribute can't be put on (e.g., the
+//! ones of the standard library or of third-party crates).
+//!
+//! A profile is a JSON object mapping the fully-qualified paths of types (e.g.,
+//! `std::ffi::CStr`, including through re-exports) to one of:
+//!
+//! - `{ "encoding": "<encoding>" }`, an encoding as is, as with `#[cfi_encoding = "<encoding>"]`;
+//! - `{ "namespace": "<path>", "name": "<name>", "template_args": "<none|generic>" }`, a C++
+//!   class, as with the structured `cfi_encoding` attribute (`namespace` and `template_args` are
+//!   optional);
+//! - `{ "transform": "void" }`, which transforms the type into the unit type, so it is encoded as
+//!   `void` (as `core::ffi::c_void`); or
+//! - `{ "transform": "automatic" }`, which encodes the type as if it didn't have a `cfi_encoding`
+//!   attribute.
+//!
+//! Entries take precedence over the `cfi_encoding` attribute of their types. Entries of crates
+//! that aren't in the crate graph are ignored, so the same profile can be used for all the crates
+//! of a program.
+
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, LOCAL_CRATE};
+use rustc_middle::mir::mono::CfiEncodingOverride;
+use rustc_middle::ty::TyCtxt;
+use serde_json::Value;
+
+use crate::errors::{InvalidCfiEncodingProfile, UnresolvedCfiEncodingProfilePath};
+use crate::typeid::typeid_itanium_cxx_abi::{invalid_raw_cfi_encoding, is_cxx_ident};
+
+#[cfg(test)]
+mod tests;
+
+/// Parses the contents of a CFI encoding profile into its entries.
+fn parse_profile(contents: &str) -> Result<Vec<(String, CfiEncodingOverride)>, String> {
+    let value: Value = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+    let Value::Object(entries) = value else {
+        return Err("the profile must be a JSON object".to_string());
+    };
+    entries
+        .into_iter()
+        .map(|(path, entry)| {
+            let encoding = parse_entry(&entry).map_err(|reason| format!("`{path}`: {reason}"))?;
+            Ok((path, encoding))
+        })
+        .collect()
+}
+
+/// Parses an entry of a CFI encoding profile (see the module documentation).
+fn parse_entry(entry: &Value) -> Result<CfiEncodingOverride, String> {
+    let Value::Object(fields) = entry else {
+        return Err("entries must be JSON objects".to_string());
+    };
+    let mut strs = Vec::new();
+    for (key, value) in fields {
+        let Value::String(value) = value else {
+            return Err(format!("the value of `{key}` must be a string"));
+        };
+        strs.push((key.as_str(), value.as_str()));
+    }
+    let field = |key: &str| strs.iter().find(|(other, _)| *other == key).map(|&(_, value)| value);
+
+    if let Some(encoding) = field("encoding") {
+        if strs.len() > 1 {
+            return Err("`encoding` must be the only field".to_string());
+        }
+        let encoding = encoding.trim();
+        if let Some(reason) = invalid_raw_cfi_encoding(encoding) {
+            return Err(reason.to_string());
+        }
+        return Ok(CfiEncodingOverride::Raw(encoding.to_string()));
+    }
+
+    if let Some(transform) = field("transform") {
+        if strs.len() > 1 {
+            return Err("`transform` must be the only field".to_string());
+        }
+        return match transform {
+            "void" => Ok(CfiEncodingOverride::Void),
+            "automatic" => Ok(CfiEncodingOverride::Automatic),
+            _ => Err(format!(
+                "`transform` must be `\"void\"` or `\"automatic\"`, not `\"{transform}\"`"
+            )),
+        };
+    }
+
+    let mut namespace = Vec::new();
+    let mut name = None;
+    let mut template_args = false;
+    for &(key, value) in &strs {
+        match key {
+            "namespace" => {
+                namespace = value.split("::").map(str::to_string).collect();
+                if !namespace.iter().all(|name| is_cxx_ident(name)) {
+                    return Err(format!("`{value}` is not a path of identifiers"));
+                }
+            }
+            "name" => {
+                if !is_cxx_ident(value) {
+                    return Err(format!("`{value}` is not an identifier"));
+                }
+                name = Some(value.to_string());
+            }
+            "template_args" => {
+                template_args = match value {
+                    "none" => false,
+                    "generic" => true,
+                    _ => {
+                        return Err(format!(
+                            "`template_args` must be `\"none\"` or `\"generic\"`, not \
+                             `\"{value}\"`"
+                        ));
+                    }
+                };
+            }
+            _ => return Err(format!("unknown field `{key}`")),
+        }
+    }
+    let name = name.ok_or_else(|| {
+        "entries must have an `encoding`, `name`, or `transform` field".to_string()
+    })?;
+    Ok(CfiEncodingOverride::Class { namespace, name, template_args })
+}
+
+/// Resolves the path of a type (without its crate name) in the specified crate to the
+/// user-defined type or extern type it names, if any.
+pub(super) fn resolve_ty_path<'a>(
+    tcx: TyCtxt<'_>,
+    krate: CrateNum,
+    segments: impl Iterator<Item = &'a str>,
+) -> Option<DefId> {
+    resolve_path(tcx, krate, segments, |def_kind| {
+        matches!(def_kind, DefKind::Struct | DefKind::Enum | DefKind::Union | DefKind::ForeignTy)
+    })
+}
+
+/// Resolves the path of an item (without its crate name) in the specified crate to the item of one
+/// of the specified kinds it names, if any.
+pub(super) fn resolve_path<'a>(
+    tcx: TyCtxt<'_>,
+    krate: CrateNum,
+    segments: impl Iterator<Item = &'a str>,
+    is_ty: impl Fn(DefKind) -> bool,
+) -> Option<DefId> {
+    let mut def_id = krate.as_def_id();
+    for segment in segments {
+        if tcx.def_kind(def_id) != DefKind::Mod {
+            return None;
+        }
+        let children = match def_id.as_local() {
+            Some(def_id) => tcx.module_children_local(def_id),
+            None => tcx.module_children(def_id),
+        };
+        def_id = children.iter().find_map(|child| match child.res {
+            Res::Def(def_kind, def_id)
+                if child.ident.as_str() == segment
+                    && (def_kind == DefKind::Mod || is_ty(def_kind)) =>
+            {
+                Some(def_id)
+            }
+            _ => None,
+        })?;
+    }
+    is_ty(tcx.def_kind(def_id)).then_some(def_id)
+}
+
+/// Reads the CFI encoding profile of `-Zcfi-encoding-profile`, if any, and resolves the paths of
+/// its entries (see `TyCtxt::cfi_encoding_profile`).
+pub(crate) fn cfi_encoding_profile(tcx: TyCtxt<'_>, (): ()) -> DefIdMap<CfiEncodingOverride> {
+    let mut profile = DefIdMap::default();
+    let Some(path) = &tcx.sess.opts.unstable_opts.cfi_encoding_profile else {
+        return profile;
+    };
+    let entries = std::fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| parse_profile(&contents));
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(reason) => {
+            let path = path.display().to_string();
+            tcx.dcx().emit_err(InvalidCfiEncodingProfile { path, reason });
+            return profile;
+        }
+    };
+
+    for (ty_path, encoding) in entries {
+        let mut segments = ty_path.split("::");
+        let crate_name = segments.next().unwrap_or_default();
+        let crates: Vec<_> = tcx
+            .crates(())
+            .iter()
+            .copied()
+            .chain([LOCAL_CRATE])
+            .filter(|&cnum| tcx.crate_name(cnum).as_str() == crate_name)
+            .collect();
+        // Entries of crates that aren't in the crate graph are ignored
+        if crates.is_empty() {
+            continue;
+        }
+        let mut resolved = false;
+        for cnum in crates {
+            if let Some(def_id) = resolve_ty_path(tcx, cnum, segments.clone()) {
+                profile.insert(def_id, encoding.clone());
+                resolved = true;
+            }
+        }
+        if !resolved {
+            tcx.dcx().emit_warn(UnresolvedCfiEncodingProfilePath {
+                ty_path,
+                path: path.display().to_string(),
+            });
+        }
+    }
+    profile
+}
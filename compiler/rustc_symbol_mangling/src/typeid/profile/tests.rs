@@ -0,0 +1,76 @@
+use super::*;
+
+#[track_caller]
+fn assert_entry(entry: &str, expected: CfiEncodingOverride) {
+    let contents = format!(r#"{{ "foo::Bar": {entry} }}"#);
+    assert_eq!(parse_profile(&contents), Ok(vec![("foo::Bar".to_string(), expected)]));
+}
+
+#[track_caller]
+fn assert_invalid(contents: &str, expected: &str) {
+    assert_eq!(parse_profile(contents), Err(expected.to_string()));
+}
+
+#[test]
+fn test_entries() {
+    assert_entry(r#"{ "encoding": " 3Foo " }"#, CfiEncodingOverride::Raw("3Foo".to_string()));
+    assert_entry(
+        r#"{ "name": "Foo" }"#,
+        CfiEncodingOverride::Class {
+            namespace: vec![],
+            name: "Foo".to_string(),
+            template_args: false,
+        },
+    );
+    assert_entry(
+        r#"{ "namespace": "a::b", "name": "C", "template_args": "generic" }"#,
+        CfiEncodingOverride::Class {
+            namespace: vec!["a".to_string(), "b".to_string()],
+            name: "C".to_string(),
+            template_args: true,
+        },
+    );
+    assert_entry(r#"{ "transform": "void" }"#, CfiEncodingOverride::Void);
+    assert_entry(r#"{ "transform": "automatic" }"#, CfiEncodingOverride::Automatic);
+    assert_eq!(parse_profile("{}"), Ok(vec![]));
+}
+
+#[test]
+fn test_invalid_entries() {
+    assert_invalid("[]", "the profile must be a JSON object");
+    assert_invalid(r#"{ "a::B": "3Foo" }"#, "`a::B`: entries must be JSON objects");
+    assert_invalid(
+        r#"{ "a::B": { "encoding": 1 } }"#,
+        "`a::B`: the value of `encoding` must be a string",
+    );
+    assert_invalid(r#"{ "a::B": { "encoding": " " } }"#, "`a::B`: the encoding is empty");
+    assert_invalid(
+        r#"{ "a::B": { "encoding": "3Foo3Bar" } }"#,
+        "`a::B`: the encoding is not the encoding of a single type",
+    );
+    assert_invalid(
+        r#"{ "a::B": { "encoding": "3Foo", "name": "Foo" } }"#,
+        "`a::B`: `encoding` must be the only field",
+    );
+    assert_invalid(
+        r#"{ "a::B": { "transform": "int" } }"#,
+        "`a::B`: `transform` must be `\"void\"` or `\"automatic\"`, not `\"int\"`",
+    );
+    assert_invalid(r#"{ "a::B": { "name": "1Foo" } }"#, "`a::B`: `1Foo` is not an identifier");
+    assert_invalid(
+        r#"{ "a::B": { "namespace": "a::", "name": "Foo" } }"#,
+        "`a::B`: `a::` is not a path of identifiers",
+    );
+    assert_invalid(
+        r#"{ "a::B": { "name": "Foo", "template_args": "all" } }"#,
+        "`a::B`: `template_args` must be `\"none\"` or `\"generic\"`, not `\"all\"`",
+    );
+    assert_invalid(
+        r#"{ "a::B": { "name": "Foo", "kind": "class" } }"#,
+        "`a::B`: unknown field `kind`",
+    );
+    assert_invalid(
+        r#"{ "a::B": { "namespace": "a" } }"#,
+        "`a::B`: entries must have an `encoding`, `name`, or `transform` field",
+    );
+}
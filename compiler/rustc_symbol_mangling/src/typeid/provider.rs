@@ -0,0 +1,127 @@
+/// A backend-agnostic interface to type metadata identifiers.
+///
+/// Backends other than LLVM (e.g., Cranelift or GCC) that implement CFI- or KCFI-style checks of
+/// indirect calls need the same type metadata identifiers, KCFI type metadata identifiers, and
+/// per-vtable identifiers the LLVM backend emits (so that their checks interoperate with code built
+/// with it or with Clang), without depending on the LLVM backend. `TypeIdProvider` provides them,
+/// and `ItaniumTypeIdProvider` implements it with the encoding the compiler itself uses.
+use rustc_middle::ty::{self, Instance, PolyFnSig, Ty, TyCtxt};
+use rustc_target::abi::call::FnAbi;
+
+use crate::typeid::kcfi::{self, KcfiTypeId};
+use crate::typeid::typeid_itanium_cxx_abi;
+use crate::typeid::TypeIdOptions;
+
+/// Provides type metadata identifiers of functions and vtables for CFI- and KCFI-style checks of
+/// indirect calls.
+///
+/// Only the type metadata identifiers have to be provided; the KCFI type metadata identifiers are
+/// computed from them with the seed and width of the session.
+pub trait TypeIdProvider<'tcx> {
+    /// Returns a type metadata identifier for the specified FnAbi.
+    fn typeid_for_fnabi(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        options: TypeIdOptions,
+    ) -> String;
+
+    /// Returns a type metadata identifier for the specified PolyFnSig, without computing its FnAbi.
+    fn typeid_for_fnsig(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_sig: PolyFnSig<'tcx>,
+        options: TypeIdOptions,
+    ) -> String;
+
+    /// Returns a type metadata identifier for the specified Instance.
+    fn typeid_for_instance(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        instance: Instance<'tcx>,
+        options: TypeIdOptions,
+    ) -> String;
+
+    /// Returns the identifier of the vtables of the specified trait, which virtual calls through
+    /// trait objects of it are checked against.
+    fn typeid_for_vtable(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        trait_ref: ty::PolyExistentialTraitRef<'tcx>,
+    ) -> String;
+
+    /// Returns a KCFI type metadata identifier for the specified FnAbi.
+    fn kcfi_typeid_for_fnabi(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        options: TypeIdOptions,
+    ) -> KcfiTypeId {
+        kcfi::kcfi_typeid_for_session(tcx, &self.typeid_for_fnabi(tcx, fn_abi, options))
+    }
+
+    /// Returns a KCFI type metadata identifier for the specified PolyFnSig.
+    fn kcfi_typeid_for_fnsig(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_sig: PolyFnSig<'tcx>,
+        options: TypeIdOptions,
+    ) -> KcfiTypeId {
+        kcfi::kcfi_typeid_for_session(tcx, &self.typeid_for_fnsig(tcx, fn_sig, options))
+    }
+
+    /// Returns a KCFI type metadata identifier for the specified Instance (with its concrete self
+    /// if it is a shim for casting a method as a function pointer, see `kcfi_typeid_for_instance`).
+    fn kcfi_typeid_for_instance(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        instance: Instance<'tcx>,
+        options: TypeIdOptions,
+    ) -> KcfiTypeId {
+        let options = kcfi::kcfi_options_for_instance(instance, options);
+        kcfi::kcfi_typeid_for_session(tcx, &self.typeid_for_instance(tcx, instance, options))
+    }
+}
+
+/// The `TypeIdProvider` of the compiler, which encodes type metadata identifiers using the Itanium
+/// C++ ABI mangling (or the scheme selected with `-Zsanitizer-cfi-typeid-scheme`), and vtable
+/// identifiers using the v0 mangling.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ItaniumTypeIdProvider;
+
+impl<'tcx> TypeIdProvider<'tcx> for ItaniumTypeIdProvider {
+    fn typeid_for_fnabi(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        options: TypeIdOptions,
+    ) -> String {
+        typeid_itanium_cxx_abi::typeid_for_fnabi(tcx, fn_abi, options)
+    }
+
+    fn typeid_for_fnsig(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        fn_sig: PolyFnSig<'tcx>,
+        options: TypeIdOptions,
+    ) -> String {
+        typeid_itanium_cxx_abi::typeid_for_fnsig(tcx, fn_sig, options)
+    }
+
+    fn typeid_for_instance(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        instance: Instance<'tcx>,
+        options: TypeIdOptions,
+    ) -> String {
+        typeid_itanium_cxx_abi::typeid_for_instance(tcx, instance, options)
+    }
+
+    fn typeid_for_vtable(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        trait_ref: ty::PolyExistentialTraitRef<'tcx>,
+    ) -> String {
+        crate::typeid_for_trait_ref(tcx, trait_ref)
+    }
+}
@@ -0,0 +1,136 @@
+//! CFI encoding registries (see `#![cfi_encodings]`), which override the CFI encodings of
+//! user-defined types and extern types the `cfi_encoding` attribute can't be put on (e.g., the
+//! ones of the standard library or of third-party crates) from the source of a crate, as
+//! `-Zcfi-encoding-profile` does from a file.
+//!
+//! `#![cfi_encodings(core::ffi::c_void = "v", some_sys::Handle = "P6handle")]` maps the
+//! fully-qualified paths of types (starting with the name of their crate, or with `crate` for the
+//! crate of the attribute) to encodings as is, as with `#[cfi_encoding = "<encoding>"]`. The
+//! registries of all the crates in the crate graph apply to every crate, so the types are encoded
+//! the same way by the crates registering them and the crates depending on these.
+//!
+//! The overrides of `-Zcfi-encoding-profile` take precedence over registries. Types registered
+//! with different encodings (by the same crate or by different crates), or with an encoding other
+//! than the one of their own `cfi_encoding` attribute, are reported, and the first encoding
+//! registered (the ones of the local crate first) or their own attribute is used, respectively.
+
+use rustc_hir::def_id::{CrateNum, DefIdMap, LOCAL_CRATE};
+use rustc_middle::mir::mono::CfiEncodingOverride;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{sym, Span, Symbol};
+use std::iter;
+
+use crate::errors::{
+    CfiEncodingsAttrConflict, ConflictingCfiEncodings, InvalidCfiEncodingsEntry,
+    UnresolvedCfiEncodingsPath,
+};
+use crate::typeid::profile::resolve_ty_path;
+use crate::typeid::typeid_itanium_cxx_abi::invalid_raw_cfi_encoding;
+
+/// An entry of a `#![cfi_encodings]` attribute.
+struct Entry {
+    span: Span,
+    ty_path: String,
+    encoding: String,
+}
+
+/// Parses the entries of the `#![cfi_encodings]` attributes of the specified crate, reporting the
+/// invalid ones of the local crate (the invalid ones of other crates are ignored).
+fn entries(tcx: TyCtxt<'_>, krate: CrateNum) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for attr in tcx.get_attrs(krate.as_def_id(), sym::cfi_encodings) {
+        for item in attr.meta_item_list().unwrap_or_default() {
+            let entry = item.meta_item().and_then(|meta| {
+                let encoding = meta.value_str()?;
+                let ty_path = meta
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                Some(Entry {
+                    span: item.span(),
+                    ty_path,
+                    encoding: encoding.as_str().trim().into(),
+                })
+            });
+            let reason = match entry {
+                Some(entry) => match invalid_raw_cfi_encoding(&entry.encoding) {
+                    Some(reason) => reason,
+                    None => {
+                        entries.push(entry);
+                        continue;
+                    }
+                },
+                None => "entries must be of the form `<path> = \"<encoding>\"`",
+            };
+            if krate == LOCAL_CRATE {
+                tcx.dcx().emit_err(InvalidCfiEncodingsEntry { span: item.span(), reason });
+            }
+        }
+    }
+    entries
+}
+
+/// Collects the entries of the `#![cfi_encodings]` attributes of the crates in the crate graph and
+/// resolves their paths (see `TyCtxt::cfi_encoding_registry`).
+pub(crate) fn cfi_encoding_registry(tcx: TyCtxt<'_>, (): ()) -> DefIdMap<CfiEncodingOverride> {
+    let mut registry = DefIdMap::default();
+    // The encodings registered, along with the crates they were registered by
+    let mut registered: DefIdMap<(String, Symbol)> = DefIdMap::default();
+    for krate in iter::once(LOCAL_CRATE).chain(tcx.crates(()).iter().copied()) {
+        let is_local = krate == LOCAL_CRATE;
+        for Entry { span, ty_path, encoding } in entries(tcx, krate) {
+            let mut segments = ty_path.split("::");
+            let crate_name = segments.next().unwrap_or_default();
+            let def_ids: Vec<_> = iter::once(LOCAL_CRATE)
+                .chain(tcx.crates(()).iter().copied())
+                .filter(|&cnum| {
+                    if crate_name == "crate" {
+                        cnum == krate
+                    } else {
+                        tcx.crate_name(cnum).as_str() == crate_name
+                    }
+                })
+                .filter_map(|cnum| resolve_ty_path(tcx, cnum, segments.clone()))
+                .collect();
+            if def_ids.is_empty() && is_local {
+                tcx.dcx().emit_err(UnresolvedCfiEncodingsPath { span, ty_path: ty_path.clone() });
+            }
+
+            for def_id in def_ids {
+                // The `cfi_encoding` attribute of the type takes precedence
+                if let Some(attr) = tcx.get_attr(def_id, sym::cfi_encoding) {
+                    let same = attr.value_str().is_some_and(|str| str.as_str().trim() == encoding);
+                    if !same && is_local {
+                        tcx.dcx().emit_err(CfiEncodingsAttrConflict {
+                            span,
+                            attr_span: attr.span,
+                            ty_path: ty_path.clone(),
+                            encoding: encoding.clone(),
+                        });
+                    }
+                    continue;
+                }
+
+                if let Some((prev_encoding, prev_krate)) = registered.get(&def_id) {
+                    if *prev_encoding != encoding {
+                        tcx.dcx().emit_err(ConflictingCfiEncodings {
+                            span: is_local.then_some(span),
+                            ty_path: ty_path.clone(),
+                            encoding: encoding.clone(),
+                            krate: tcx.crate_name(krate),
+                            prev_encoding: prev_encoding.clone(),
+                            prev_krate: *prev_krate,
+                        });
+                    }
+                    continue;
+                }
+                registered.insert(def_id, (encoding.clone(), tcx.crate_name(krate)));
+                registry.insert(def_id, CfiEncodingOverride::Raw(encoding.clone()));
+            }
+        }
+    }
+    registry
+}
@@ -8,43 +8,149 @@
 /// For more information about LLVM CFI and cross-language LLVM CFI support for the Rust compiler,
 /// see design document in the tracking issue #89653.
 use rustc_data_structures::base_n;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
+use rustc_data_structures::stable_hasher::{Hash64, StableHasher};
+use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_hir::lang_items::LangItem;
+use rustc_middle::mir::mono::{CfiDictKey as DictKey, CfiTyQ as TyQ};
+use rustc_middle::mir::mono::{CfiEncodingOverride, CfiFragment, CfiFragmentPart};
 use rustc_middle::ty::fold::{TypeFolder, TypeSuperFoldable};
 use rustc_middle::ty::layout::IntegerExt;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{
     self, Const, ExistentialPredicate, FloatTy, FnSig, Instance, IntTy, List, Region, RegionKind,
     TermKind, Ty, TyCtxt, UintTy,
 };
 use rustc_middle::ty::{GenericArg, GenericArgKind, GenericArgsRef};
 use rustc_middle::ty::{TypeFoldable, TypeVisitableExt};
-use rustc_span::def_id::DefId;
-use rustc_span::sym;
-use rustc_target::abi::call::{Conv, FnAbi, PassMode};
+use rustc_session::code_stats::CfiTypeIdSizeInfo;
+use rustc_session::config::{CfiTraitObjectProjections, CfiTypeIdScheme, EntryFnType};
+use rustc_span::def_id::{DefId, LOCAL_CRATE};
+use rustc_span::{sym, Span, Symbol};
+use rustc_target::abi::call::{ArgAbi, Conv, FnAbi, PassMode};
 use rustc_target::abi::Integer;
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::Target;
 use rustc_trait_selection::traits;
+use std::cmp::Ordering;
 use std::fmt::Write as _;
+use std::hash::Hash;
 use std::iter;
+use std::mem;
 
+use crate::errors::{
+    IntrinsicWithoutFallbackBody, InvalidCfiEncoding, InvalidStructuredCfiEncoding,
+    UnsupportedConstParam,
+};
+use crate::typeid::c_prototype::CDataModel;
+use crate::typeid::profile::resolve_path;
+use crate::typeid::itanium;
+use crate::typeid::typeid_msvc_abi;
 use crate::typeid::TypeIdOptions;
 
-/// Type and extended type qualifiers.
-#[derive(Eq, Hash, PartialEq)]
-enum TyQ {
-    None,
-    Const,
-    Mut,
+/// Substitution dictionary (see
+/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression>), whose entries are
+/// only substituted when compressing (i.e., without `TypeIdOptions::UNCOMPRESSED`).
+#[derive(Default)]
+struct Dict<'tcx> {
+    entries: FxHashMap<DictKey<'tcx>, usize>,
+    uncompressed: bool,
+    /// Whether the types are encoded as they are, rather than spliced from the encodings computed
+    /// already (see `CfiFragment`), to check that these are the same.
+    unspliced: bool,
+    /// The components looked up but not found, if the dictionary is the one of a `CfiFragment`.
+    misses: Option<FxIndexSet<DictKey<'tcx>>>,
 }
 
-/// Substitution dictionary key.
-#[derive(Eq, Hash, PartialEq)]
-enum DictKey<'tcx> {
-    Ty(Ty<'tcx>, TyQ),
-    Region(Region<'tcx>),
-    Const(Const<'tcx>),
-    Predicate(ExistentialPredicate<'tcx>),
+impl<'tcx> Dict<'tcx> {
+    fn new(options: TypeIdOptions) -> Dict<'tcx> {
+        let uncompressed = options.contains(TypeIdOptions::UNCOMPRESSED);
+        Dict { uncompressed, ..Dict::default() }
+    }
+
+    /// Returns the sequence number of the entry to substitute the specified component with, if
+    /// any.
+    fn get(&mut self, key: &DictKey<'tcx>) -> Option<usize> {
+        if self.uncompressed {
+            return None;
+        }
+        let num = self.entries.get(key).copied();
+        if num.is_none()
+            && let Some(misses) = &mut self.misses
+        {
+            misses.insert(key.clone());
+        }
+        num
+    }
+
+    /// Adds an entry for the specified component, unless it already has one.
+    fn insert(&mut self, key: DictKey<'tcx>) {
+        let num = self.entries.len();
+        self.entries.entry(key).or_insert(num);
+    }
+
+    /// Returns the substitution of the entry with the specified sequence number.
+    fn substitution(&self, num: usize) -> String {
+        format!("S{}_", to_seq_id(num))
+    }
+
+    /// Returns the encoding of the specified `CfiFragment` with its substitutions relocated after
+    /// the entries of this dictionary, adding the entries of the fragment, or `None` if any of the
+    /// components it looks up have entries already.
+    fn splice(&mut self, fragment: &CfiFragment<'tcx>) -> Option<String> {
+        let mut keys = fragment.entries.iter().chain(&fragment.lookups);
+        if !self.uncompressed && keys.any(|key| self.entries.contains_key(key)) {
+            return None;
+        }
+        let base = self.entries.len();
+        let mut s = String::new();
+        for part in &fragment.parts {
+            match part {
+                CfiFragmentPart::Encoding(encoding) => s.push_str(encoding),
+                CfiFragmentPart::Substitution(num) => s.push_str(&self.substitution(base + num)),
+            }
+        }
+        if let Some(misses) = &mut self.misses {
+            misses.extend(fragment.lookups.iter().cloned());
+        }
+        for key in &fragment.entries {
+            self.insert(key.clone());
+        }
+        Some(s)
+    }
+
+    /// Returns the `CfiFragment` of the specified encoding computed with this dictionary, split at
+    /// the substitutions the parser finds in it, or `None` if it has components with user-defined
+    /// encodings, as these aren't necessarily valid productions of the grammar (see
+    /// `finish_typeid`) and substitutions couldn't be told apart from the rest of it.
+    fn into_fragment(self, tcx: TyCtxt<'tcx>, encoding: String) -> Option<CfiFragment<'tcx>> {
+        let misses = self.misses.unwrap_or_default();
+        if self.entries.keys().chain(&misses).any(|key| is_user_encoded(tcx, key)) {
+            return None;
+        }
+        let spans = itanium::Type::substitution_spans(&encoding)?;
+        let mut parts = Vec::with_capacity(2 * spans.len() + 1);
+        let mut pos = 0;
+        for (span, num) in spans {
+            if span.start > pos {
+                parts.push(CfiFragmentPart::Encoding(encoding[pos..span.start].to_string()));
+            }
+            parts.push(CfiFragmentPart::Substitution(num));
+            pos = span.end;
+        }
+        if pos < encoding.len() {
+            parts.push(CfiFragmentPart::Encoding(encoding[pos..].to_string()));
+        }
+        let lookups = misses.into_iter().filter(|key| !self.entries.contains_key(key)).collect();
+        // The entries are sorted by sequence number
+        #[allow(rustc::potential_query_instability)]
+        let mut entries: Vec<_> = self.entries.into_iter().collect();
+        entries.sort_by_key(|&(_, num)| num);
+        let entries = entries.into_iter().map(|(key, _)| key).collect();
+        Some(CfiFragment { parts, entries, lookups })
+    }
 }
 
 /// Options for encode_ty.
@@ -75,30 +181,26 @@ fn to_seq_id(num: usize) -> String {
 
 /// Substitutes a component if found in the substitution dictionary (see
 /// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression>).
-fn compress<'tcx>(
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
-    key: DictKey<'tcx>,
-    comp: &mut String,
-) {
+fn compress<'tcx>(dict: &mut Dict<'tcx>, key: DictKey<'tcx>, comp: &mut String) {
     match dict.get(&key) {
         Some(num) => {
-            comp.clear();
-            let _ = write!(comp, "S{}_", to_seq_id(*num));
+            *comp = dict.substitution(num);
         }
         None => {
-            dict.insert(key, dict.len());
+            dict.insert(key);
         }
     }
 }
 
 /// Encodes a const using the Itanium C++ ABI as a literal argument (see
-/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling.literal>).
+/// <https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling.literal>), or returns the type of
+/// the const if its values can't be encoded.
 fn encode_const<'tcx>(
     tcx: TyCtxt<'tcx>,
     c: Const<'tcx>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
     options: EncodeTyOptions,
-) -> String {
+) -> Result<String, Ty<'tcx>> {
     // L<element-type>[n][<element-value>]E as literal argument
     let mut s = String::from('L');
 
@@ -139,7 +241,7 @@ fn encode_const<'tcx>(
                     let _ = write!(s, "{val}");
                 }
                 _ => {
-                    bug!("encode_const: unexpected type `{:?}`", c.ty());
+                    return Err(c.ty());
                 }
             }
         }
@@ -154,7 +256,27 @@ fn encode_const<'tcx>(
 
     compress(dict, DictKey::Const(c), &mut s);
 
-    s
+    Ok(s)
+}
+
+/// Reports a const argument of the specified item whose type can't be encoded (see
+/// `encode_const`), pointing at the declaration of its parameter.
+fn report_unsupported_const_arg<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    args: GenericArgsRef<'tcx>,
+    index: usize,
+    ty: Ty<'tcx>,
+) {
+    // Existential trait references and projections don't have the `Self` argument
+    let generics = tcx.generics_of(def_id);
+    let index = index + generics.count().saturating_sub(args.len());
+    let span = if index < generics.count() {
+        tcx.def_span(generics.param_at(index, tcx).def_id)
+    } else {
+        tcx.def_span(def_id)
+    };
+    tcx.dcx().emit_err(UnsupportedConstParam { span, ty, item: tcx.def_path_str(def_id) });
 }
 
 /// Encodes a FnSig using the Itanium C++ ABI with vendor extended type qualifiers and types for
@@ -163,7 +285,7 @@ fn encode_const<'tcx>(
 fn encode_fnsig<'tcx>(
     tcx: TyCtxt<'tcx>,
     fn_sig: &FnSig<'tcx>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
     options: TypeIdOptions,
 ) -> String {
     // Function types are delimited by an "F..E" pair
@@ -171,24 +293,45 @@ fn encode_fnsig<'tcx>(
 
     let mut encode_ty_options = EncodeTyOptions::from_bits(options.bits())
         .unwrap_or_else(|| bug!("encode_fnsig: invalid option(s) `{:?}`", options.bits()));
-    match fn_sig.abi {
-        Abi::C { .. } => {
+    let mut transform_ty_options = TransformTyOptions::from_bits(options.bits())
+        .unwrap_or_else(|| bug!("encode_fnsig: invalid option(s) `{:?}`", options.bits()));
+    // The ABI as adjusted for the target (e.g., `efiapi` into `win64` on x86_64), so function
+    // pointers are encoded as the functions they point to (see `is_c_conv`)
+    match tcx.sess.target.adjust_abi(fn_sig.abi, fn_sig.c_variadic) {
+        Abi::C { .. } | Abi::Aapcs { .. } | Abi::SysV64 { .. } | Abi::Win64 { .. } => {
             encode_ty_options.insert(EncodeTyOptions::GENERALIZE_REPR_C);
         }
         _ => {
             encode_ty_options.remove(EncodeTyOptions::GENERALIZE_REPR_C);
+            encode_ty_options.remove(EncodeTyOptions::C_INTEGER_TYPES);
+            transform_ty_options.remove(TransformTyOptions::GENERALIZE_ARRAYS);
+            transform_ty_options.remove(TransformTyOptions::BOX_AS_PTR);
+            transform_ty_options.remove(TransformTyOptions::REFS_AS_PTRS);
         }
     }
 
-    // Encode the return type
-    let transform_ty_options = TransformTyOptions::from_bits(options.bits())
-        .unwrap_or_else(|| bug!("encode_fnsig: invalid option(s) `{:?}`", options.bits()));
+    // Encode the return type (as void if return types are erased)
     let mut type_folder = TransformTy::new(tcx, transform_ty_options);
-    let ty = fn_sig.output().fold_with(&mut type_folder);
-    s.push_str(&encode_ty(tcx, ty, dict, encode_ty_options));
+    if options.contains(EncodeTyOptions::ERASE_RETURN_TYPES) {
+        s.push('v');
+    } else {
+        let ty = fn_sig.output().fold_with(&mut type_folder);
+        s.push_str(&encode_ty(tcx, ty, dict, encode_ty_options));
+    }
 
-    // Encode the parameter types
-    let tys = fn_sig.inputs();
+    // Encode the parameter types. The tupled parameters of "rust-call" functions are encoded
+    // untupled (i.e., `extern "rust-call" fn(&C, (A, B))` as `fn(&C, A, B)`), as their FnAbi has
+    // them (see `encode_fnabi`), so pointers to these functions are encoded as the functions they
+    // point to whatever way they are called.
+    let mut tys = fn_sig.inputs().to_vec();
+    if fn_sig.abi == Abi::RustCall
+        && let Some(ty) = tys.pop()
+    {
+        match ty.kind() {
+            ty::Tuple(tupled_tys) => tys.extend(tupled_tys.iter()),
+            _ => tys.push(ty),
+        }
+    }
     if !tys.is_empty() {
         for ty in tys {
             let ty = ty.fold_with(&mut type_folder);
@@ -219,7 +362,7 @@ fn encode_fnsig<'tcx>(
 fn encode_predicate<'tcx>(
     tcx: TyCtxt<'tcx>,
     predicate: ty::PolyExistentialPredicate<'tcx>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
     options: EncodeTyOptions,
 ) -> String {
     // u<length><name>[I<element-type1..element-typeN>E], where <element-type> is <subst>, as vendor
@@ -229,15 +372,31 @@ fn encode_predicate<'tcx>(
         ty::ExistentialPredicate::Trait(trait_ref) => {
             let name = encode_ty_name(tcx, trait_ref.def_id);
             let _ = write!(s, "u{}{}", name.len(), &name);
-            s.push_str(&encode_args(tcx, trait_ref.args, dict, options));
+            s.push_str(&encode_args(tcx, trait_ref.def_id, trait_ref.args, dict, options));
         }
         ty::ExistentialPredicate::Projection(projection) => {
             let name = encode_ty_name(tcx, projection.def_id);
             let _ = write!(s, "u{}{}", name.len(), &name);
-            s.push_str(&encode_args(tcx, projection.args, dict, options));
+            s.push_str(&encode_args(tcx, projection.def_id, projection.args, dict, options));
             match projection.term.unpack() {
                 TermKind::Ty(ty) => s.push_str(&encode_ty(tcx, ty, dict, options)),
-                TermKind::Const(c) => s.push_str(&encode_const(tcx, c, dict, options)),
+                TermKind::Const(c) => match encode_const(
+                    tcx,
+                    // Associated const equality bounds may name a const expression (e.g.,
+                    // `dyn Trait<CONST = { 1 + 2 }>`), so evaluate it to its value first
+                    tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), c),
+                    dict,
+                    options,
+                ) {
+                    Ok(encoding) => s.push_str(&encoding),
+                    Err(ty) => {
+                        tcx.dcx().emit_err(UnsupportedConstParam {
+                            span: tcx.def_span(projection.def_id),
+                            ty,
+                            item: tcx.def_path_str(projection.def_id),
+                        });
+                    }
+                },
             }
         }
         ty::ExistentialPredicate::AutoTrait(def_id) => {
@@ -254,7 +413,7 @@ fn encode_predicate<'tcx>(
 fn encode_predicates<'tcx>(
     tcx: TyCtxt<'tcx>,
     predicates: &List<ty::PolyExistentialPredicate<'tcx>>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
     options: EncodeTyOptions,
 ) -> String {
     // <predicate1[..predicateN]>E as part of vendor extended type
@@ -267,7 +426,7 @@ fn encode_predicates<'tcx>(
 }
 
 /// Encodes a region using the Itanium C++ ABI as a vendor extended type.
-fn encode_region<'tcx>(region: Region<'tcx>, dict: &mut FxHashMap<DictKey<'tcx>, usize>) -> String {
+fn encode_region<'tcx>(region: Region<'tcx>, dict: &mut Dict<'tcx>) -> String {
     // u6region[I[<region-disambiguator>][<region-index>]E] as vendor extended type
     let mut s = String::new();
     match region.kind() {
@@ -303,26 +462,36 @@ fn encode_region<'tcx>(region: Region<'tcx>, dict: &mut FxHashMap<DictKey<'tcx>,
 /// types that are not used at the FFI boundary.
 fn encode_args<'tcx>(
     tcx: TyCtxt<'tcx>,
+    def_id: DefId,
     args: GenericArgsRef<'tcx>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
     options: EncodeTyOptions,
 ) -> String {
     // [I<subst1..substN>E] as part of vendor extended type
     let mut s = String::new();
-    let args: Vec<GenericArg<'_>> = args.iter().collect();
     if !args.is_empty() {
         s.push('I');
-        for arg in args {
+        for (i, arg) in args.iter().enumerate() {
             match arg.unpack() {
                 GenericArgKind::Lifetime(region) => {
                     s.push_str(&encode_region(region, dict));
                 }
+                GenericArgKind::Type(ty)
+                    if options.contains(EncodeTyOptions::ERASE_PHANTOM_DATA)
+                        && ty.ty_adt_def().is_some_and(|adt_def| adt_def.is_phantom_data()) =>
+                {
+                    // Erased when encoded rather than when transformed, so the repr(transparent)
+                    // types referring to themselves through PhantomData are still detected (see
+                    // `TransformTy::parents`)
+                    s.push_str(&encode_ty(tcx, tcx.types.unit, dict, options));
+                }
                 GenericArgKind::Type(ty) => {
                     s.push_str(&encode_ty(tcx, ty, dict, options));
                 }
-                GenericArgKind::Const(c) => {
-                    s.push_str(&encode_const(tcx, c, dict, options));
-                }
+                GenericArgKind::Const(c) => match encode_const(tcx, c, dict, options) {
+                    Ok(encoding) => s.push_str(&encoding),
+                    Err(ty) => report_unsupported_const_arg(tcx, def_id, args, i, ty),
+                },
             }
         }
         s.push('E');
@@ -330,8 +499,18 @@ fn encode_args<'tcx>(
     s
 }
 
+/// Returns the parent generic arguments of a coroutine or coroutine closure followed by the tuple of
+/// the types of its captures, for encoding them with `TypeIdOptions::ENCODE_CAPTURES`.
+fn with_captures<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    parent_args: &[GenericArg<'tcx>],
+    tupled_upvars_ty: Ty<'tcx>,
+) -> GenericArgsRef<'tcx> {
+    tcx.mk_args_from_iter(parent_args.iter().copied().chain([tupled_upvars_ty.into()]))
+}
+
 /// Encodes a ty:Ty name, including its crate and path disambiguators and names.
-fn encode_ty_name(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+pub(super) fn encode_ty_name(tcx: TyCtxt<'_>, def_id: DefId) -> String {
     // Encode <name> for use in u<length><name>[I<element-type1..element-typeN>E], where
     // <element-type> is <subst>, using v0's <path> without v0's extended form of paths:
     //
@@ -427,12 +606,490 @@ fn encode_ty_name(tcx: TyCtxt<'_>, def_id: DefId) -> String {
     s
 }
 
+/// The encoding of a user-defined type or extern type provided with the `cfi_encoding` attribute.
+enum CfiEncoding {
+    /// An encoding as is (i.e., `#[cfi_encoding = "<encoding>"]`).
+    Raw(String),
+    /// A C++ class whose encoding is composed by the compiler (i.e., `#[cfi_encoding(namespace =
+    /// "<path>", name = "<name>", template_args = "<none|generic>")]`).
+    Structured(ClassName),
+    /// The encoding of the user-defined type, extern type, or type alias of the specified path
+    /// (i.e., `#[cfi_encoding(same_as = "<path>")]`), resolved to its definition (see
+    /// `same_as_ty`).
+    SameAs(DefId),
+}
+
+/// The name of a C++ class (see `encode_class_name`).
+struct ClassName {
+    /// The names of the enclosing namespaces, outermost first.
+    namespace: Vec<String>,
+    name: String,
+    /// Whether the type and const generic arguments of the type are encoded as template arguments.
+    template_args: bool,
+}
+
+/// Why the `cfi_encoding` attribute of a type is invalid.
+enum InvalidCfiEncodingReason {
+    /// The encoding provided as is is invalid for the specified reason (see
+    /// `invalid_raw_cfi_encoding`).
+    Raw(&'static str),
+    /// The structured encoding is invalid for the specified reason.
+    Structured(String),
+}
+
+/// Returns why the specified encoding provided as is (e.g., with `#[cfi_encoding = "<encoding>"]`)
+/// is invalid, if it is (i.e., if it is empty, or is not the encoding of a single type with its
+/// substitutions referring to its own components; see `itanium::Type::parse`).
+pub(super) fn invalid_raw_cfi_encoding(encoding: &str) -> Option<&'static str> {
+    if encoding.is_empty() {
+        Some("the encoding is empty")
+    } else if itanium::Type::parse(encoding).is_none() {
+        Some("the encoding is not the encoding of a single type")
+    } else {
+        None
+    }
+}
+
+/// Returns whether the specified string is a C++ identifier.
+pub(super) fn is_cxx_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parses the `cfi_encoding` attribute of the specified type, if any, returning the span of the
+/// attribute along with its encoding. The overrides of `-Zcfi-encoding-profile` take precedence
+/// over the attribute, and the ones of `#![cfi_encodings]` apply to the types without it (with the
+/// span of the type, as they are validated when read).
+fn cfi_encoding(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+) -> Option<(Span, Result<CfiEncoding, InvalidCfiEncodingReason>)> {
+    let encoding = tcx
+        .cfi_encoding_profile(())
+        .get(&def_id)
+        .or_else(|| tcx.cfi_encoding_registry(()).get(&def_id));
+    if let Some(encoding) = encoding {
+        let encoding = match encoding {
+            CfiEncodingOverride::Raw(str) => CfiEncoding::Raw(str.clone()),
+            CfiEncodingOverride::Class { namespace, name, template_args } => {
+                CfiEncoding::Structured(ClassName {
+                    namespace: namespace.clone(),
+                    name: name.clone(),
+                    template_args: *template_args,
+                })
+            }
+            CfiEncodingOverride::Void | CfiEncodingOverride::Automatic => return None,
+        };
+        return Some((tcx.def_span(def_id), Ok(encoding)));
+    }
+
+    let attr = tcx.get_attr(def_id, sym::cfi_encoding)?;
+    if let Some(value_str) = attr.value_str() {
+        let value_str = value_str.to_string();
+        let str = value_str.trim();
+        let encoding = match invalid_raw_cfi_encoding(str) {
+            Some(reason) => Err(InvalidCfiEncodingReason::Raw(reason)),
+            None => Ok(CfiEncoding::Raw(str.to_string())),
+        };
+        return Some((attr.span, encoding));
+    }
+    let Some(items) = attr.meta_item_list() else {
+        bug!("cfi_encoding: invalid `cfi_encoding` for `{:?}`", def_id);
+    };
+    // Types transformed into the unit type are encoded as it (see `is_void_equivalent`)
+    if has_void_transform(tcx, def_id) {
+        return None;
+    }
+    if let [item] = &items[..]
+        && item.ident().is_some_and(|key| key.as_str() == "same_as")
+        && let Some(path) = item.value_str()
+    {
+        let encoding = resolve_same_as(tcx, def_id, path.as_str())
+            .map(CfiEncoding::SameAs)
+            .map_err(InvalidCfiEncodingReason::Structured);
+        return Some((attr.span, encoding));
+    }
+
+    let structured = || {
+        let mut namespace = Vec::new();
+        let mut name = None;
+        let mut template_args = false;
+        for item in &items {
+            let (Some(key), Some(value)) = (item.ident(), item.value_str()) else {
+                return Err("fields must be of the form `<field> = \"<value>\"`".to_string());
+            };
+            let value = value.to_string();
+            match key.as_str() {
+                "namespace" => {
+                    namespace = value.split("::").map(str::to_string).collect();
+                    if !namespace.iter().all(|name| is_cxx_ident(name)) {
+                        return Err(format!("`{value}` is not a path of identifiers"));
+                    }
+                }
+                "name" => {
+                    if !is_cxx_ident(&value) {
+                        return Err(format!("`{value}` is not an identifier"));
+                    }
+                    name = Some(value);
+                }
+                "template_args" => {
+                    template_args = match value.as_str() {
+                        "none" => false,
+                        "generic" => true,
+                        _ => {
+                            return Err(format!(
+                                "`template_args` must be `\"none\"` or `\"generic\"`, not \
+                                 `\"{value}\"`"
+                            ));
+                        }
+                    };
+                }
+                "transform" if value == "void" => {
+                    return Err("`transform` must be the only field".to_string());
+                }
+                "same_as" => {
+                    return Err("`same_as` must be the only field".to_string());
+                }
+                "transform" => {
+                    return Err(format!("`transform` must be `\"void\"`, not `\"{value}\"`"));
+                }
+                _ => return Err(format!("unknown field `{key}`")),
+            }
+        }
+        let name = name.ok_or_else(|| "the `name` field is missing".to_string())?;
+        Ok(CfiEncoding::Structured(ClassName { namespace, name, template_args }))
+    };
+    Some((attr.span, structured().map_err(InvalidCfiEncodingReason::Structured)))
+}
+
+/// Returns whether the specified type has a `#[cfi_encoding(transform = "void")]` attribute.
+fn has_void_transform(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let Some(attr) = tcx.get_attr(def_id, sym::cfi_encoding) else { return false };
+    let Some(items) = attr.meta_item_list() else { return false };
+    let [item] = &items[..] else { return false };
+    item.ident().is_some_and(|key| key.as_str() == "transform")
+        && item.value_str().is_some_and(|value| value.as_str() == "void")
+}
+
+/// Returns the `same_as` path of the `cfi_encoding` attribute of the specified type, if any.
+fn same_as_path(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Symbol> {
+    let attr = tcx.get_attr(def_id, sym::cfi_encoding)?;
+    let items = attr.meta_item_list()?;
+    let [item] = &items[..] else { return None };
+    if item.ident()?.as_str() != "same_as" {
+        return None;
+    }
+    item.value_str()
+}
+
+/// Resolves the path of `#[cfi_encoding(same_as = "<path>")]` of the specified type to the
+/// user-defined type, extern type, or type alias it names, which must not be generic. The path
+/// starts with the name of the crate of the type it names, or with `crate` for the crate of the
+/// specified type. The `same_as` paths of the types it names in turn are also resolved, so that
+/// cycles are reported instead of overflowing the stack when encoding them.
+fn resolve_same_as(tcx: TyCtxt<'_>, def_id: DefId, path: &str) -> Result<DefId, String> {
+    let resolve = |def_id: DefId, path: &str| {
+        let mut segments = path.split("::");
+        let crate_name = segments.next().unwrap_or_default();
+        let target = tcx
+            .crates(())
+            .iter()
+            .copied()
+            .chain([LOCAL_CRATE])
+            .filter(|&cnum| {
+                if crate_name == "crate" {
+                    cnum == def_id.krate
+                } else {
+                    tcx.crate_name(cnum).as_str() == crate_name
+                }
+            })
+            .find_map(|cnum| {
+                resolve_path(tcx, cnum, segments.clone(), |def_kind| {
+                    matches!(
+                        def_kind,
+                        DefKind::Struct
+                            | DefKind::Enum
+                            | DefKind::Union
+                            | DefKind::ForeignTy
+                            | DefKind::TyAlias
+                    )
+                })
+            })
+            .ok_or_else(|| {
+                format!("`{path}` doesn't name a user-defined type, extern type, or type alias")
+            })?;
+        if tcx.generics_of(target).requires_monomorphization(tcx) {
+            return Err(format!("`{path}` is generic"));
+        }
+        Ok(target)
+    };
+
+    let target = resolve(def_id, path)?;
+    let mut visited = vec![def_id];
+    let mut next = target;
+    loop {
+        // Type aliases are encoded as the types they alias
+        let next_def_id = match tcx.def_kind(next) {
+            DefKind::TyAlias => match same_as_ty(tcx, next).kind() {
+                ty::Adt(adt_def, _) => adt_def.did(),
+                ty::Foreign(def_id) => *def_id,
+                _ => break,
+            },
+            _ => next,
+        };
+        if visited.contains(&next_def_id) {
+            let ty = tcx.def_path_str(def_id);
+            return Err(format!("the `same_as` paths from `{path}` lead back to `{ty}`"));
+        }
+        let Some(next_path) = same_as_path(tcx, next_def_id) else { break };
+        visited.push(next_def_id);
+        // Invalid paths are reported for the types they're in
+        let Ok(next_target) = resolve(next_def_id, next_path.as_str()) else { break };
+        next = next_target;
+    }
+    Ok(target)
+}
+
+/// Returns the type the `same_as` path of the `cfi_encoding` attribute of a type was resolved to
+/// (see `resolve_same_as`), which it's encoded as.
+fn same_as_ty<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> Ty<'tcx> {
+    let ty = tcx.type_of(def_id).instantiate_identity();
+    tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), ty)
+}
+
+/// Returns whether the specified type is void-equivalent (e.g., an alternative definition of
+/// `c_void`, as in older bindgen output), and so transformed into the unit type and encoded as
+/// `void` as `core::ffi::c_void` is, by `-Zcfi-encoding-profile` or by `#[cfi_encoding(transform =
+/// "void")]`.
+fn is_void_equivalent(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if let Some(encoding) = tcx.cfi_encoding_profile(()).get(&def_id) {
+        return *encoding == CfiEncodingOverride::Void;
+    }
+    has_void_transform(tcx, def_id)
+}
+
+/// Returns the encoding of the specified user-defined type or extern type (without its generic
+/// arguments) if it had no `cfi_encoding` attribute.
+fn default_cfi_encoding(tcx: TyCtxt<'_>, def_id: DefId, options: EncodeTyOptions) -> String {
+    let is_repr_c = tcx.def_kind(def_id) == DefKind::ForeignTy
+        || (options.contains(EncodeTyOptions::GENERALIZE_REPR_C) && tcx.adt_def(def_id).repr().c());
+    if is_repr_c {
+        let name = tcx.item_name(def_id).to_string();
+        format!("{}{name}", name.len())
+    } else {
+        let name = encode_ty_name(tcx, def_id);
+        format!("u{}{name}", name.len())
+    }
+}
+
+/// Reports the invalid `cfi_encoding` attribute of the specified type, suggesting the encoding
+/// the type would otherwise have (with the specified options) as a starting point.
+fn report_invalid_cfi_encoding<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    ty: Ty<'tcx>,
+    span: Span,
+    reason: InvalidCfiEncodingReason,
+    options: EncodeTyOptions,
+) {
+    let ty_span = tcx.def_span(def_id);
+    let default = default_cfi_encoding(tcx, def_id, options);
+    match reason {
+        InvalidCfiEncodingReason::Raw(reason) => {
+            tcx.dcx().emit_err(InvalidCfiEncoding { span, ty_span, ty, reason, default });
+        }
+        InvalidCfiEncodingReason::Structured(reason) => {
+            let err = InvalidStructuredCfiEncoding { span, ty_span, ty, reason, default };
+            tcx.dcx().emit_err(err);
+        }
+    }
+}
+
+/// Encodes a user-defined type or extern type with a structured `cfi_encoding` attribute as Clang
+/// encodes the C++ class it corresponds to (i.e., `<length><name>`, `St<length><name>` in the `std`
+/// namespace, or `N<prefix><length><name>E` in other namespaces, with `I<template-arg1..
+/// template-argN>E` after the name if it has template arguments), where the enclosing namespaces
+/// and the class template are substitution candidates as well.
+fn encode_class_name<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    def_id: DefId,
+    args: GenericArgsRef<'tcx>,
+    class_name: &ClassName,
+    dict: &mut Dict<'tcx>,
+    options: EncodeTyOptions,
+) -> String {
+    if let Some(num) = dict.get(&DictKey::Ty(ty, TyQ::None)) {
+        return dict.substitution(num);
+    }
+
+    let mut names: Vec<&str> = class_name.namespace.iter().map(String::as_str).collect();
+    names.push(&class_name.name);
+    // C++ templates don't have lifetime parameters
+    let template_args = class_name.template_args
+        && args.iter().any(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)));
+
+    // The longest prefix (i.e., enclosing namespace, or the class itself) already encoded
+    let substitution = (1..=names.len())
+        .rev()
+        .find_map(|len| dict.get(&DictKey::Prefix(names[..len].join("::"))).map(|num| (len, num)));
+    if let Some((len, num)) = substitution
+        && len == names.len()
+        && !template_args
+    {
+        return dict.substitution(num);
+    }
+
+    // The `std` namespace is abbreviated, and its names aren't nested
+    let nested = names.len() > 1 && names[..names.len() - 1] != ["std"];
+    let mut s = String::new();
+    if nested {
+        s.push('N');
+    }
+    let start = match substitution {
+        Some((len, num)) => {
+            s.push_str(&dict.substitution(num));
+            len
+        }
+        None if names.len() > 1 && names[0] == "std" => {
+            s.push_str("St");
+            1
+        }
+        None => 0,
+    };
+    for len in start + 1..=names.len() {
+        let name = names[len - 1];
+        let _ = write!(s, "{}{}", name.len(), name);
+        dict.insert(DictKey::Prefix(names[..len].join("::")));
+    }
+
+    if template_args {
+        s.push('I');
+        for (i, arg) in args.iter().enumerate() {
+            match arg.unpack() {
+                GenericArgKind::Lifetime(_) => {}
+                GenericArgKind::Type(ty) => s.push_str(&encode_ty(tcx, ty, dict, options)),
+                GenericArgKind::Const(c) => match encode_const(tcx, c, dict, options) {
+                    Ok(encoding) => s.push_str(&encoding),
+                    Err(ty) => report_unsupported_const_arg(tcx, def_id, args, i, ty),
+                },
+            }
+        }
+        s.push('E');
+    }
+    if nested {
+        s.push('E');
+    }
+
+    // Class template specializations are substitution candidates after their template arguments
+    if template_args {
+        dict.insert(DictKey::Ty(ty, TyQ::None));
+    }
+    s
+}
+
+/// Returns the encoding of the C integer type Clang encodes the well-known libc typedefs of the
+/// size and signedness of the specified integer type as on the target, as the `core::ffi` and
+/// `libc` type aliases are erased from types. The pointer-sized integer types are encoded as
+/// `size_t`, `ssize_t`, `ptrdiff_t`, and `intptr_t` (and `off_t` on LP64 targets), and the other
+/// ones as the fixed-width integer typedefs (e.g., `int64_t`), so the aliases of other C integer
+/// types of the same size (e.g., `c_longlong` on LP64 targets) still require normalization.
+fn c_integer_encoding(target: &Target, ty: Ty<'_>) -> &'static str {
+    let data_model = CDataModel::for_target(target);
+    let (signed, bits, pointer_sized) = match ty.kind() {
+        ty::Int(IntTy::Isize) => (true, u64::from(target.pointer_width), true),
+        ty::Uint(UintTy::Usize) => (false, u64::from(target.pointer_width), true),
+        ty::Int(int_ty) => (true, int_ty.bit_width().unwrap(), false),
+        ty::Uint(uint_ty) => (false, uint_ty.bit_width().unwrap(), false),
+        _ => bug!("c_integer_encoding: unexpected `{:?}`", ty.kind()),
+    };
+    let codes = match bits {
+        // `__int128` and `unsigned __int128`
+        128 => ["n", "o"],
+        // `size_t` is `unsigned long long` on 64-bit Windows, `unsigned long` on Apple and
+        // WebAssembly targets and on the ones where `long` is wider than `int`, and `unsigned
+        // int` otherwise
+        _ if pointer_sized && target.is_like_windows && bits == 64 => ["x", "y"],
+        _ if pointer_sized
+            && bits == data_model.long_bits
+            && (bits != data_model.int_bits || target.is_like_osx || target.is_like_wasm) =>
+        {
+            ["l", "m"]
+        }
+        _ if pointer_sized && bits == data_model.int_bits => ["i", "j"],
+        // `int64_t` is `long long` on Apple targets
+        64 if target.is_like_osx => ["x", "y"],
+        8 => ["a", "h"],
+        16 => ["s", "t"],
+        _ if bits == data_model.int_bits => ["i", "j"],
+        _ if bits == data_model.long_bits => ["l", "m"],
+        _ => ["x", "y"],
+    };
+    codes[!signed as usize]
+}
+
 /// Encodes a ty:Ty using the Itanium C++ ABI with vendor extended type qualifiers and types for
 /// Rust types that are not used at the FFI boundary.
 fn encode_ty<'tcx>(
     tcx: TyCtxt<'tcx>,
     ty: Ty<'tcx>,
-    dict: &mut FxHashMap<DictKey<'tcx>, usize>,
+    dict: &mut Dict<'tcx>,
+    options: EncodeTyOptions,
+) -> String {
+    // Types may be nested arbitrarily deeply (e.g., in generated code), and are encoded recursively
+    // (including through `encode_args`, `encode_predicate`, and `encode_fnsig`).
+    ensure_sufficient_stack(|| {
+        // The encodings of primitive types are cheaper to compute than to look up
+        if dict.unspliced || ty.is_primitive() {
+            return encode_ty_kind(tcx, ty, dict, options);
+        }
+        let mut fragment_options = options;
+        fragment_options.set(TypeIdOptions::UNCOMPRESSED, dict.uncompressed);
+        let fragment = tcx.cfi_type_fragment((ty, fragment_options.bits()));
+        let s = fragment.as_ref().and_then(|fragment| dict.splice(fragment));
+        if tcx.sess.opts.unstable_opts.cfi_fragment_stats {
+            tcx.sess.code_stats.record_cfi_fragment_splice(s.is_some());
+        }
+        if let Some(s) = s {
+            return s;
+        }
+        encode_ty_kind(tcx, ty, dict, options)
+    })
+}
+
+/// Returns the `CfiFragment` of the specified type encoded with the specified options (i.e., its
+/// encoding computed with a dictionary of its own, to be spliced wherever it is encoded), or `None`
+/// if it has errors, so these are reported wherever it is encoded.
+pub(crate) fn cfi_type_fragment<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (ty, options): (Ty<'tcx>, u32),
+) -> Option<CfiFragment<'tcx>> {
+    let options = TypeIdOptions::from_bits_retain(options);
+    let errors = tcx.dcx().err_count();
+    let mut dict = Dict {
+        uncompressed: options.contains(TypeIdOptions::UNCOMPRESSED),
+        misses: Some(Default::default()),
+        ..Dict::default()
+    };
+    let encoding = encode_ty_kind(tcx, ty, &mut dict, options);
+    if tcx.dcx().err_count() != errors {
+        return None;
+    }
+    let bytes = encoding.len();
+    let fragment = dict.into_fragment(tcx, encoding)?;
+    if tcx.sess.opts.unstable_opts.cfi_fragment_stats {
+        let dictionary_keys = fragment.entries.len() + fragment.lookups.len();
+        tcx.sess.code_stats.record_cfi_fragment(bytes, dictionary_keys);
+    }
+    Some(fragment)
+}
+
+/// Encodes a ty:Ty as `encode_ty` does, on the current stack.
+fn encode_ty_kind<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    dict: &mut Dict<'tcx>,
     options: EncodeTyOptions,
 ) -> String {
     let mut typeid = String::new();
@@ -449,6 +1106,15 @@ fn encode_ty<'tcx>(
             typeid.push('b');
         }
 
+        ty::Int(..) | ty::Uint(..)
+            if options.contains(EncodeTyOptions::C_INTEGER_TYPES)
+                && !options.contains(EncodeTyOptions::NORMALIZE_INTEGERS) =>
+        {
+            // <builtin-type> of the C integer type Clang encodes the libc typedefs of the same
+            // size as (e.g., `size_t` as `m` on x86_64 Linux)
+            typeid.push_str(c_integer_encoding(&tcx.sess.target, ty));
+        }
+
         ty::Int(..) | ty::Uint(..) => {
             // u<length><type-name> as vendor extended type
             let mut s = String::from(match ty.kind() {
@@ -556,13 +1222,11 @@ fn encode_ty<'tcx>(
         ty::Adt(adt_def, args) => {
             let mut s = String::new();
             let def_id = adt_def.did();
-            if let Some(cfi_encoding) = tcx.get_attr(def_id, sym::cfi_encoding) {
+            if let Some((span, encoding)) = cfi_encoding(tcx, def_id) {
                 // Use user-defined CFI encoding for type
-                if let Some(value_str) = cfi_encoding.value_str() {
-                    let value_str = value_str.to_string();
-                    let str = value_str.trim();
-                    if !str.is_empty() {
-                        s.push_str(str);
+                match encoding {
+                    Ok(CfiEncoding::Raw(str)) => {
+                        s.push_str(&str);
                         // Don't compress user-defined builtin types (see
                         // https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-builtin and
                         // https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression).
@@ -570,23 +1234,19 @@ fn encode_ty<'tcx>(
                             "v", "w", "b", "c", "a", "h", "s", "t", "i", "j", "l", "m", "x", "y",
                             "n", "o", "f", "d", "e", "g", "z", "Dh",
                         ];
-                        if !builtin_types.contains(&str) {
+                        if !builtin_types.contains(&str.as_str()) {
                             compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
                         }
-                    } else {
-                        #[allow(
-                            rustc::diagnostic_outside_of_impl,
-                            rustc::untranslatable_diagnostic
-                        )]
-                        tcx.dcx()
-                            .struct_span_err(
-                                cfi_encoding.span,
-                                format!("invalid `cfi_encoding` for `{:?}`", ty.kind()),
-                            )
-                            .emit();
                     }
-                } else {
-                    bug!("encode_ty: invalid `cfi_encoding` for `{:?}`", ty.kind());
+                    Ok(CfiEncoding::Structured(class_name)) => {
+                        s = encode_class_name(tcx, ty, def_id, args, &class_name, dict, options);
+                    }
+                    Ok(CfiEncoding::SameAs(target)) => {
+                        s = encode_ty(tcx, same_as_ty(tcx, target), dict, options);
+                    }
+                    Err(reason) => {
+                        report_invalid_cfi_encoding(tcx, def_id, ty, span, reason, options);
+                    }
                 }
             } else if options.contains(EncodeTyOptions::GENERALIZE_REPR_C) && adt_def.repr().c() {
                 // For cross-language LLVM CFI support, the encoding must be compatible at the FFI
@@ -610,7 +1270,7 @@ fn encode_ty<'tcx>(
                 // <subst>, as vendor extended type.
                 let name = encode_ty_name(tcx, def_id);
                 let _ = write!(s, "u{}{}", name.len(), &name);
-                s.push_str(&encode_args(tcx, args, dict, options));
+                s.push_str(&encode_args(tcx, def_id, args, dict, options));
                 compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
             }
             typeid.push_str(&s);
@@ -619,31 +1279,35 @@ fn encode_ty<'tcx>(
         ty::Foreign(def_id) => {
             // <length><name>, where <name> is <unscoped-name>
             let mut s = String::new();
-            if let Some(cfi_encoding) = tcx.get_attr(*def_id, sym::cfi_encoding) {
+            match cfi_encoding(tcx, *def_id) {
                 // Use user-defined CFI encoding for type
-                if let Some(value_str) = cfi_encoding.value_str() {
-                    if !value_str.to_string().trim().is_empty() {
-                        s.push_str(value_str.to_string().trim());
-                    } else {
-                        #[allow(
-                            rustc::diagnostic_outside_of_impl,
-                            rustc::untranslatable_diagnostic
-                        )]
-                        tcx.dcx()
-                            .struct_span_err(
-                                cfi_encoding.span,
-                                format!("invalid `cfi_encoding` for `{:?}`", ty.kind()),
-                            )
-                            .emit();
-                    }
-                } else {
-                    bug!("encode_ty: invalid `cfi_encoding` for `{:?}`", ty.kind());
+                Some((_, Ok(CfiEncoding::Raw(str)))) => {
+                    s.push_str(&str);
+                    compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
+                }
+                Some((_, Ok(CfiEncoding::Structured(class_name)))) => {
+                    s = encode_class_name(
+                        tcx,
+                        ty,
+                        *def_id,
+                        List::empty(),
+                        &class_name,
+                        dict,
+                        options,
+                    );
+                }
+                Some((_, Ok(CfiEncoding::SameAs(target)))) => {
+                    s = encode_ty(tcx, same_as_ty(tcx, target), dict, options);
+                }
+                Some((span, Err(reason))) => {
+                    report_invalid_cfi_encoding(tcx, *def_id, ty, span, reason, options);
+                }
+                None => {
+                    let name = tcx.item_name(*def_id).to_string();
+                    let _ = write!(s, "{}{}", name.len(), &name);
+                    compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
                 }
-            } else {
-                let name = tcx.item_name(*def_id).to_string();
-                let _ = write!(s, "{}{}", name.len(), &name);
             }
-            compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
             typeid.push_str(&s);
         }
 
@@ -654,7 +1318,7 @@ fn encode_ty<'tcx>(
             let mut s = String::new();
             let name = encode_ty_name(tcx, *def_id);
             let _ = write!(s, "u{}{}", name.len(), &name);
-            s.push_str(&encode_args(tcx, args, dict, options));
+            s.push_str(&encode_args(tcx, *def_id, args, dict, options));
             compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
             typeid.push_str(&s);
         }
@@ -665,8 +1329,13 @@ fn encode_ty<'tcx>(
             let mut s = String::new();
             let name = encode_ty_name(tcx, *def_id);
             let _ = write!(s, "u{}{}", name.len(), &name);
-            let parent_args = tcx.mk_args(args.as_coroutine_closure().parent_args());
-            s.push_str(&encode_args(tcx, parent_args, dict, options));
+            let args = args.as_coroutine_closure();
+            let parent_args = if options.contains(EncodeTyOptions::ENCODE_CAPTURES) {
+                with_captures(tcx, args.parent_args(), args.tupled_upvars_ty())
+            } else {
+                tcx.mk_args(args.parent_args())
+            };
+            s.push_str(&encode_args(tcx, *def_id, parent_args, dict, options));
             compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
             typeid.push_str(&s);
         }
@@ -677,18 +1346,32 @@ fn encode_ty<'tcx>(
             let mut s = String::new();
             let name = encode_ty_name(tcx, *def_id);
             let _ = write!(s, "u{}{}", name.len(), &name);
-            // Encode parent args only
-            s.push_str(&encode_args(
-                tcx,
-                tcx.mk_args(args.as_coroutine().parent_args()),
-                dict,
-                options,
-            ));
+            // Encode parent args only (and the captures if enabled)
+            let args = args.as_coroutine();
+            let parent_args = if options.contains(EncodeTyOptions::ENCODE_CAPTURES) {
+                with_captures(tcx, args.parent_args(), args.tupled_upvars_ty())
+            } else {
+                tcx.mk_args(args.parent_args())
+            };
+            s.push_str(&encode_args(tcx, *def_id, parent_args, dict, options));
             compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
             typeid.push_str(&s);
         }
 
         // Pointer types
+        ty::Ref(_, ty0, _) if options.contains(EncodeTyOptions::CXX_REFERENCES) => {
+            // R[K]<element-type>
+            let mut s = String::new();
+            s.push_str(&encode_ty(tcx, *ty0, dict, options));
+            if !ty.is_mutable_ptr() {
+                s = format!("{}{}", "K", &s);
+                compress(dict, DictKey::Ty(*ty0, TyQ::Const), &mut s);
+            }
+            s = format!("{}{}", "R", &s);
+            compress(dict, DictKey::Ty(ty, TyQ::None), &mut s);
+            typeid.push_str(&s);
+        }
+
         ty::Ref(region, ty0, ..) => {
             // [U3mut]u3refI<element-type>E as vendor extended type qualifier and type
             let mut s = String::new();
@@ -762,25 +1445,107 @@ fn encode_ty<'tcx>(
     typeid
 }
 
-struct TransformTy<'tcx> {
+pub(crate) struct TransformTy<'tcx> {
     tcx: TyCtxt<'tcx>,
     options: TransformTyOptions,
     parents: Vec<Ty<'tcx>>,
+    trace: bool,
+    /// The transformed types, so the subtrees shared by the return and parameter types of a
+    /// signature are only transformed once.
+    cache: FxHashMap<Ty<'tcx>, Ty<'tcx>>,
 }
 
 impl<'tcx> TransformTy<'tcx> {
     fn new(tcx: TyCtxt<'tcx>, options: TransformTyOptions) -> Self {
-        TransformTy { tcx, options, parents: Vec::new() }
+        TransformTy { tcx, options, parents: Vec::new(), trace: false, cache: Default::default() }
+    }
+
+    /// Logs the transformation of a type by the specified rule if tracing is enabled (see
+    /// `-Zcfi-trace-transform`), and returns the transformed type.
+    fn trace(&self, rule: &str, input: Ty<'tcx>, output: Ty<'tcx>) -> Ty<'tcx> {
+        if self.trace {
+            with_no_trimmed_paths!(info!("{rule}: `{input}` => `{output}`"));
+        }
+        output
     }
 }
 
 impl<'tcx> TypeFolder<TyCtxt<'tcx>> for TransformTy<'tcx> {
+    fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+        // The transformation of a type within a repr(transparent) type being flattened depends on
+        // the types being flattened (see `parents`), so it isn't cached. Neither are the ones being
+        // traced, so every transformation is logged.
+        if !self.parents.is_empty() || self.trace {
+            return ensure_sufficient_stack(|| self.transform_ty(t));
+        }
+        if let Some(&ty) = self.cache.get(&t) {
+            return ty;
+        }
+        // Types may be nested arbitrarily deeply (e.g., in generated code), and are transformed
+        // recursively.
+        let ty = ensure_sufficient_stack(|| self.transform_ty(t));
+        self.cache.insert(t, ty);
+        ty
+    }
+
+    fn interner(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+}
+
+impl<'tcx> TransformTy<'tcx> {
     // Transforms a ty:Ty for being encoded and used in the substitution dictionary. It transforms
     // all c_void types into unit types unconditionally, generalizes pointers if
-    // TransformTyOptions::GENERALIZE_POINTERS option is set, and normalizes integers if
-    // TransformTyOptions::NORMALIZE_INTEGERS option is set.
-    fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+    // TransformTyOptions::GENERALIZE_POINTERS option is set, normalizes integers if
+    // TransformTyOptions::NORMALIZE_INTEGERS option is set, and transforms arrays into slices if
+    // TransformTyOptions::GENERALIZE_ARRAYS option is set.
+    fn transform_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
         match t.kind() {
+            ty::Adt(adt_def, _) if is_void_equivalent(self.tcx, adt_def.did()) => {
+                self.trace("transform type encoded as void into unit", t, self.tcx.types.unit)
+            }
+
+            ty::Foreign(def_id) if is_void_equivalent(self.tcx, *def_id) => {
+                self.trace("transform type encoded as void into unit", t, self.tcx.types.unit)
+            }
+
+            ty::Array(ty0, _) if self.options.contains(TransformTyOptions::GENERALIZE_ARRAYS) => {
+                let ty = Ty::new_slice(self.tcx, ty0.fold_with(self));
+                self.trace("generalize arrays", t, ty)
+            }
+
+            ty::Adt(..)
+                if self.options.contains(TransformTyOptions::BOX_AS_PTR)
+                    && t.is_box_global(self.tcx)
+                    && t.boxed_ty().is_sized(self.tcx, ty::ParamEnv::reveal_all()) =>
+            {
+                // Transformed as the raw pointer it is passed as (e.g., for generalization)
+                let ty = self.fold_ty(Ty::new_mut_ptr(self.tcx, t.boxed_ty()));
+                self.trace("encode box as raw pointer", t, ty)
+            }
+
+            ty::Dynamic(preds, region, kind)
+                if self.tcx.sess.opts.unstable_opts.sanitizer_cfi_trait_object_projections
+                    != CfiTraitObjectProjections::All =>
+            {
+                let tcx = self.tcx;
+                let principal = preds.principal_def_id();
+                let preds =
+                    tcx.mk_poly_existential_predicates_from_iter(preds.iter().filter(|pred| {
+                        let ty::ExistentialPredicate::Projection(projection) = pred.skip_binder()
+                        else {
+                            return true;
+                        };
+                        // Only the projections of the associated types of the principal trait
+                        // are kept with `-Zsanitizer-cfi-trait-object-projections=principal`
+                        tcx.sess.opts.unstable_opts.sanitizer_cfi_trait_object_projections
+                            == CfiTraitObjectProjections::Principal
+                            && Some(tcx.parent(projection.def_id)) == principal
+                    }));
+                let ty = Ty::new_dynamic(tcx, preds, *region, *kind).super_fold_with(self);
+                self.trace("restrict trait object projections", t, ty)
+            }
+
             ty::Array(..)
             | ty::Closure(..)
             | ty::Coroutine(..)
@@ -804,7 +1569,7 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
                     // (See https://rust-lang.github.io/unsafe-code-guidelines/layout/scalars.html#bool.)
                     //
                     // Clang represents bool as an 8-bit unsigned integer.
-                    self.tcx.types.u8
+                    self.trace("normalize integers", t, self.tcx.types.u8)
                 } else {
                     t
                 }
@@ -814,20 +1579,24 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
                 if self.options.contains(EncodeTyOptions::NORMALIZE_INTEGERS) {
                     // Since #118032, char is guaranteed to have the same size, alignment, and
                     // function call ABI as u32 on all platforms.
-                    self.tcx.types.u32
+                    self.trace("normalize integers", t, self.tcx.types.u32)
                 } else {
                     t
                 }
             }
 
             ty::Int(..) | ty::Uint(..) => {
-                if self.options.contains(EncodeTyOptions::NORMALIZE_INTEGERS) {
+                let normalize_ptr_sized =
+                    self.options.contains(TransformTyOptions::NORMALIZE_PTR_SIZED_INTEGERS)
+                        && matches!(t.kind(), ty::Int(IntTy::Isize) | ty::Uint(UintTy::Usize));
+                if self.options.contains(EncodeTyOptions::NORMALIZE_INTEGERS) || normalize_ptr_sized
+                {
                     // Note: C99 7.18.2.4 requires uintptr_t and intptr_t to be at least 16-bit
                     // wide. All platforms we currently support have a C platform, and as a
                     // consequence, isize/usize are at least 16-bit wide for all of them.
                     //
                     // (See https://rust-lang.github.io/unsafe-code-guidelines/layout/scalars.html#isize-and-usize.)
-                    match t.kind() {
+                    let ty = match t.kind() {
                         ty::Int(IntTy::Isize) => match self.tcx.sess.target.pointer_width {
                             16 => self.tcx.types.i16,
                             32 => self.tcx.types.i32,
@@ -849,21 +1618,24 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
                             ),
                         },
                         _ => t,
-                    }
+                    };
+                    self.trace("normalize integers", t, ty)
                 } else {
                     t
                 }
             }
 
-            ty::Adt(..) if t.is_c_void(self.tcx) => self.tcx.types.unit,
+            ty::Adt(..) if t.is_c_void(self.tcx) => {
+                self.trace("transform c_void into unit", t, self.tcx.types.unit)
+            }
 
             ty::Adt(adt_def, args) => {
                 if adt_def.repr().transparent() && adt_def.is_struct() && !self.parents.contains(&t)
                 {
                     // Don't transform repr(transparent) types with an user-defined CFI encoding to
                     // preserve the user-defined CFI encoding.
-                    if let Some(_) = self.tcx.get_attr(adt_def.did(), sym::cfi_encoding) {
-                        return t;
+                    if let Some(_) = cfi_encoding(self.tcx, adt_def.did()) {
+                        return self.trace("keep type with user-defined encoding", t, t);
                     }
                     let variant = adt_def.non_enum_variant();
                     let param_env = self.tcx.param_env(variant.def_id);
@@ -884,45 +1656,78 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
                         // If the self reference is not through a pointer, for example, due
                         // to using `PhantomData`, need to skip normalizing it if we hit it again.
                         self.parents.push(t);
-                        let ty = if ty0.is_any_ptr() && ty0.contains(t) {
+                        let (rule, ty) = if ty0.is_any_ptr() && ty0.contains(t) {
                             let options = self.options;
                             self.options |= TransformTyOptions::GENERALIZE_POINTERS;
                             let ty = ty0.fold_with(self);
                             self.options = options;
-                            ty
+                            ("flatten self-referential repr(transparent) type", ty)
                         } else {
-                            ty0.fold_with(self)
+                            ("flatten repr(transparent) type", ty0.fold_with(self))
                         };
                         self.parents.pop();
-                        ty
+                        self.trace(rule, t, ty)
                     } else {
                         // Transform repr(transparent) types without non-ZST field into ()
-                        self.tcx.types.unit
+                        let ty = self.tcx.types.unit;
+                        self.trace("transform repr(transparent) type without non-ZST field", t, ty)
                     }
                 } else {
                     t.super_fold_with(self)
                 }
             }
 
-            ty::Ref(..) => {
+            ty::Ref(_, ty0, mutbl)
+                if self.options.contains(TransformTyOptions::REFS_AS_PTRS)
+                    && ty0.is_sized(self.tcx, ty::ParamEnv::reveal_all()) =>
+            {
+                // Transformed as the raw pointer it is passed as (e.g., for const erasure)
+                let ty = self.fold_ty(Ty::new_ptr(self.tcx, *ty0, *mutbl));
+                self.trace("encode reference as raw pointer", t, ty)
+            }
+
+            ty::Ref(region, ty0, mutbl) => {
+                // References are only const-qualified when encoded as C++ references
+                let erase_const = !mutbl.is_mut()
+                    && self.options.contains(TransformTyOptions::ERASE_CONST)
+                    && self.options.contains(TransformTyOptions::CXX_REFERENCES);
+                // Mutable references are encoded as shared ones, unless both are already encoded
+                // as mutable ones
+                let erase_mut = mutbl.is_mut()
+                    && self.options.contains(TransformTyOptions::ERASE_REF_MUT)
+                    && !(self.options.contains(TransformTyOptions::ERASE_CONST)
+                        && self.options.contains(TransformTyOptions::CXX_REFERENCES));
                 if self.options.contains(TransformTyOptions::GENERALIZE_POINTERS) {
-                    if t.is_mutable_ptr() {
+                    let ty = if (t.is_mutable_ptr() && !erase_mut) || erase_const {
                         Ty::new_mut_ref(self.tcx, self.tcx.lifetimes.re_static, self.tcx.types.unit)
                     } else {
                         Ty::new_imm_ref(self.tcx, self.tcx.lifetimes.re_static, self.tcx.types.unit)
-                    }
+                    };
+                    self.trace("generalize pointers", t, ty)
+                } else if erase_const {
+                    let ty = Ty::new_mut_ref(self.tcx, *region, ty0.fold_with(self));
+                    self.trace("erase const", t, ty)
+                } else if erase_mut {
+                    let ty = Ty::new_imm_ref(self.tcx, *region, ty0.fold_with(self));
+                    self.trace("erase reference mutability", t, ty)
                 } else {
                     t.super_fold_with(self)
                 }
             }
 
-            ty::RawPtr(..) => {
+            ty::RawPtr(ty0, mutbl) => {
+                let erase_const =
+                    !mutbl.is_mut() && self.options.contains(TransformTyOptions::ERASE_CONST);
                 if self.options.contains(TransformTyOptions::GENERALIZE_POINTERS) {
-                    if t.is_mutable_ptr() {
+                    let ty = if t.is_mutable_ptr() || erase_const {
                         Ty::new_mut_ptr(self.tcx, self.tcx.types.unit)
                     } else {
                         Ty::new_imm_ptr(self.tcx, self.tcx.types.unit)
-                    }
+                    };
+                    self.trace("generalize pointers", t, ty)
+                } else if erase_const {
+                    let ty = Ty::new_mut_ptr(self.tcx, ty0.fold_with(self));
+                    self.trace("erase const", t, ty)
                 } else {
                     t.super_fold_with(self)
                 }
@@ -930,14 +1735,33 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
 
             ty::FnPtr(..) => {
                 if self.options.contains(TransformTyOptions::GENERALIZE_POINTERS) {
-                    Ty::new_imm_ptr(self.tcx, self.tcx.types.unit)
+                    let ty = if self.options.contains(TransformTyOptions::ERASE_CONST) {
+                        Ty::new_mut_ptr(self.tcx, self.tcx.types.unit)
+                    } else {
+                        Ty::new_imm_ptr(self.tcx, self.tcx.types.unit)
+                    };
+                    self.trace("generalize pointers", t, ty)
                 } else {
                     t.super_fold_with(self)
                 }
             }
 
+            ty::Alias(ty::Opaque, alias_ty)
+                if self.options.contains(TransformTyOptions::IMPL_TRAIT_BOUNDS) =>
+            {
+                let bounds = self
+                    .tcx
+                    .explicit_item_bounds(alias_ty.def_id)
+                    .iter_instantiated_copied(self.tcx, alias_ty.args)
+                    .map(|(clause, _)| clause);
+                let ty = bounds_ty(self.tcx, t, bounds).fold_with(self);
+                self.trace("encode opaque type via its bounds", t, ty)
+            }
+
             ty::Alias(..) => {
-                self.fold_ty(self.tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), t))
+                let ty =
+                    self.fold_ty(self.tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), t));
+                self.trace("normalize alias", t, ty)
             }
 
             ty::Bound(..) | ty::Error(..) | ty::Infer(..) | ty::Param(..) | ty::Placeholder(..) => {
@@ -945,9 +1769,415 @@ fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
             }
         }
     }
+}
 
-    fn interner(&self) -> TyCtxt<'tcx> {
-        self.tcx
+/// The cause of a parameter or return type of a function type with the "C" calling convention
+/// being encoded as a Rust type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossLanguageIncompatibility {
+    /// A reference, which is encoded as a vendor extended type qualifier (whereas a raw pointer to
+    /// the same type is encoded as a C pointer).
+    Reference,
+    /// An `Option` of a reference, `NonNull`, or raw pointer, which is encoded as a Rust type
+    /// (whereas a raw pointer, which is already nullable, is encoded as a C pointer).
+    NullablePointer,
+    /// A Rust string type (i.e., `str`, `String`, `CStr`, or `CString`, or a reference to one of
+    /// them), whose C counterpart is a pointer to `c_char`.
+    String,
+    /// A local user-defined type that is neither repr(C) nor repr(transparent).
+    NotReprC(DefId),
+    /// Any other type without a C counterpart.
+    Other,
+}
+
+/// Returns the first type in the specified parameter or return type of a function type with the "C"
+/// calling convention that is encoded as a Rust type (i.e., a vendor extended type qualifier or type
+/// without a C counterpart) and the cause of it being encoded as a Rust type, if any.
+pub fn cross_language_incompatible_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    options: TypeIdOptions,
+) -> Option<(Ty<'tcx>, CrossLanguageIncompatibility)> {
+    let transform_ty_options = TransformTyOptions::from_bits(options.bits()).unwrap_or_else(|| {
+        bug!("cross_language_incompatible_ty: invalid option(s) `{:?}`", options.bits())
+    });
+    let transformed_ty = ty.fold_with(&mut TransformTy::new(tcx, transform_ty_options));
+    let rust_ty = rust_only_ty(tcx, transformed_ty, true)?;
+    Some((rust_ty, classify_rust_only_ty(tcx, ty, rust_ty)))
+}
+
+/// Classifies the cause of the specified Rust-only type in the specified (untransformed) parameter
+/// or return type being encoded as a Rust type.
+fn classify_rust_only_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    rust_ty: Ty<'tcx>,
+) -> CrossLanguageIncompatibility {
+    let lang_items = tcx.lang_items();
+    let is_string_ty = |ty: Ty<'tcx>| match ty.kind() {
+        ty::Str => true,
+        ty::Adt(adt_def, _) => {
+            let did = adt_def.did();
+            lang_items.get(LangItem::String) == Some(did)
+                || lang_items.get(LangItem::CStr) == Some(did)
+                || tcx.is_diagnostic_item(sym::cstring_type, did)
+        }
+        _ => false,
+    };
+    // repr(transparent) string types are flattened when transformed (e.g., `&CStr` into a
+    // reference to a slice), so they're only recognizable in the untransformed type.
+    let ty0 = if let ty::Ref(_, ty0, _) = ty.kind() { *ty0 } else { ty };
+    if is_string_ty(ty0) || is_string_ty(rust_ty) {
+        return CrossLanguageIncompatibility::String;
+    }
+    match rust_ty.kind() {
+        ty::Ref(..) => CrossLanguageIncompatibility::Reference,
+        // `NonNull` is flattened into a raw pointer when transformed, so `Option<NonNull<T>>` is
+        // `Option<*const T>` at this point.
+        ty::Adt(adt_def, args)
+            if lang_items.get(LangItem::Option) == Some(adt_def.did())
+                && matches!(args.type_at(0).kind(), ty::Ref(..) | ty::RawPtr(..)) =>
+        {
+            CrossLanguageIncompatibility::NullablePointer
+        }
+        ty::Adt(adt_def, _)
+            if adt_def.did().is_local() && !adt_def.repr().c() && !adt_def.repr().transparent() =>
+        {
+            CrossLanguageIncompatibility::NotReprC(adt_def.did())
+        }
+        _ => CrossLanguageIncompatibility::Other,
+    }
+}
+
+/// Returns the first type in the specified transformed type that is encoded as a Rust type, if any
+/// (see `encode_ty`). repr(C) user-defined types are only encoded as C types for function types with
+/// the "C" calling convention (i.e., not for the function pointer types they contain).
+fn rust_only_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    generalize_repr_c: bool,
+) -> Option<Ty<'tcx>> {
+    match ty.kind() {
+        ty::Bool | ty::Int(..) | ty::Uint(..) | ty::Float(..) | ty::Foreign(..) => None,
+        _ if ty.is_unit() => None,
+        ty::Adt(adt_def, _) => match cfi_encoding(tcx, adt_def.did()) {
+            Some((_, Ok(CfiEncoding::SameAs(target)))) => {
+                rust_only_ty(tcx, same_as_ty(tcx, target), generalize_repr_c)
+            }
+            Some(_) => None,
+            None if generalize_repr_c && adt_def.repr().c() => None,
+            None => Some(ty),
+        },
+        ty::Array(ty0, _) | ty::RawPtr(ty0, _) => rust_only_ty(tcx, *ty0, generalize_repr_c),
+        ty::FnPtr(fn_sig) => fn_sig
+            .skip_binder()
+            .inputs_and_output
+            .iter()
+            .find_map(|ty| rust_only_ty(tcx, ty, false)),
+        // References, trait objects, and the remaining Rust types are encoded as vendor extended
+        // type qualifiers and types
+        _ => Some(ty),
+    }
+}
+
+/// The encodings of a user-defined type or extern type (i.e., ty::Foreign) definition (see
+/// `encode_ty`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeDefEncoding {
+    /// The encoding provided with the `cfi_encoding` attribute.
+    UserDefined(String),
+    /// The encodings used in the absence of the `cfi_encoding` attribute (i.e., as vendor extended
+    /// type if not generic, and as <length><name> if repr(C) or an extern type).
+    Automatic(Vec<String>),
+}
+
+/// Returns the encodings of the specified user-defined type or extern type definition, if it is one
+/// and its `cfi_encoding` attribute (if any) is valid and doesn't encode its generic arguments.
+pub fn type_def_encoding(tcx: TyCtxt<'_>, def_id: DefId) -> Option<TypeDefEncoding> {
+    let def_kind = tcx.def_kind(def_id);
+    if !matches!(def_kind, DefKind::Struct | DefKind::Enum | DefKind::Union | DefKind::ForeignTy) {
+        return None;
+    }
+
+    if is_void_equivalent(tcx, def_id) {
+        return Some(TypeDefEncoding::UserDefined("v".to_string()));
+    }
+    if let Some((_, encoding)) = cfi_encoding(tcx, def_id) {
+        return match encoding.ok()? {
+            CfiEncoding::Raw(str) => Some(TypeDefEncoding::UserDefined(str)),
+            // The encodings of class template specializations depend on their generic arguments
+            CfiEncoding::Structured(class_name)
+                if class_name.template_args
+                    && tcx.generics_of(def_id).requires_monomorphization(tcx) =>
+            {
+                None
+            }
+            CfiEncoding::Structured(class_name) => {
+                let ty = tcx.type_of(def_id).instantiate_identity();
+                let encoding = encode_class_name(
+                    tcx,
+                    ty,
+                    def_id,
+                    List::empty(),
+                    &class_name,
+                    &mut Dict::default(),
+                    EncodeTyOptions::empty(),
+                );
+                Some(TypeDefEncoding::UserDefined(encoding))
+            }
+            // The encodings of the types named by `same_as` depend on the options, and are
+            // intentionally shared
+            CfiEncoding::SameAs(_) => None,
+        };
+    }
+
+    let mut encodings = Vec::new();
+    if def_kind != DefKind::ForeignTy && tcx.generics_of(def_id).count() == 0 {
+        let name = encode_ty_name(tcx, def_id);
+        encodings.push(format!("u{}{}", name.len(), &name));
+    }
+    if def_kind == DefKind::ForeignTy || tcx.adt_def(def_id).repr().c() {
+        let name = tcx.item_name(def_id).to_string();
+        encodings.push(format!("{}{}", name.len(), &name));
+    }
+    Some(TypeDefEncoding::Automatic(encodings))
+}
+
+/// Returns the encoding of the specified type on its own (i.e., with an empty substitution
+/// dictionary).
+pub(crate) fn encode_standalone_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    encode_ty(tcx, ty, &mut Dict::new(options), options)
+}
+
+/// Returns the encodings of the primitive types.
+pub fn primitive_ty_encodings<'tcx>(tcx: TyCtxt<'tcx>) -> Vec<(Ty<'tcx>, String)> {
+    let types = &tcx.types;
+    [
+        types.bool,
+        types.char,
+        types.str_,
+        types.i8,
+        types.i16,
+        types.i32,
+        types.i64,
+        types.i128,
+        types.isize,
+        types.u8,
+        types.u16,
+        types.u32,
+        types.u64,
+        types.u128,
+        types.usize,
+        types.f16,
+        types.f32,
+        types.f64,
+        types.f128,
+    ]
+    .into_iter()
+    .map(|ty| (ty, encode_ty(tcx, ty, &mut Dict::default(), EncodeTyOptions::empty())))
+    .collect()
+}
+
+/// Transforms and encodes the specified type with the specified options, as the types of the
+/// parameters and return types of functions are.
+///
+/// This is for benchmarking only (see `tests/ui-fulldeps/cfi-encoder-bench.rs`).
+pub fn transform_and_encode_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    let ty = ty.fold_with(&mut TransformTy::new(tcx, options));
+    encode_ty(tcx, ty, &mut Dict::new(options), options)
+}
+
+/// Builds a type of bounded depth from the specified bytes (e.g., the input of a fuzz target), and
+/// transforms and encodes it with every combination of the options that apply to types, checking
+/// that each encoding is deterministic, a valid production of the grammar, the same whether or not
+/// it is spliced from the encodings computed already (see `CfiFragment`), and equivalent to the
+/// uncompressed encoding (i.e., that its substitutions refer to the components they replace).
+/// Returns the type built, so failures can be reproduced.
+///
+/// This is for testing only, as the encoder can otherwise only be reached through compilations.
+pub fn check_arbitrary_ty_encoding<'tcx>(tcx: TyCtxt<'tcx>, data: &[u8]) -> Ty<'tcx> {
+    let ty = ArbitraryTy { tcx, data }.ty(0);
+    let ty_options = TypeIdOptions::GENERALIZE_POINTERS
+        | TypeIdOptions::GENERALIZE_REPR_C
+        | TypeIdOptions::NORMALIZE_INTEGERS
+        | TypeIdOptions::GENERALIZE_ARRAYS
+        | TypeIdOptions::ERASE_CONST
+        | TypeIdOptions::ERASE_REF_MUT;
+    for options in (0..=ty_options.bits())
+        .filter_map(TypeIdOptions::from_bits)
+        .filter(|options| ty_options.contains(*options))
+    {
+        let encode = || {
+            let ty = ty.fold_with(&mut TransformTy::new(tcx, options));
+            encode_ty(tcx, ty, &mut Dict::new(options), options)
+        };
+        let encoding = encode();
+        assert_eq!(
+            encoding,
+            encode(),
+            "check_arbitrary_ty_encoding: encoding of `{ty:?}` with `{options:?}` isn't deterministic"
+        );
+        assert!(
+            itanium::Type::parse(&encoding).is_some_and(|parsed| parsed.to_string() == encoding),
+            "check_arbitrary_ty_encoding: encoding `{encoding}` of `{ty:?}` with `{options:?}` \
+             isn't valid"
+        );
+        // The encodings spliced from the ones computed already are the ones computed as is
+        let unspliced = {
+            let ty = ty.fold_with(&mut TransformTy::new(tcx, options));
+            encode_ty(tcx, ty, &mut Dict { unspliced: true, ..Dict::new(options) }, options)
+        };
+        assert_eq!(
+            encoding, unspliced,
+            "check_arbitrary_ty_encoding: spliced encoding of `{ty:?}` with `{options:?}` isn't \
+             the encoding computed as is"
+        );
+        // The compressed and uncompressed encodings encode the same type (i.e., every substitution
+        // refers to the component it replaces)
+        let uncompressed = {
+            let options = options | TypeIdOptions::UNCOMPRESSED;
+            let ty = ty.fold_with(&mut TransformTy::new(tcx, options));
+            encode_ty(tcx, ty, &mut Dict::new(options), options)
+        };
+        assert_eq!(
+            itanium::Type::parse(&encoding).map(itanium::Type::expand_substitutions),
+            itanium::Type::parse(&uncompressed),
+            "check_arbitrary_ty_encoding: encodings `{encoding}` and `{uncompressed}` of `{ty:?}` \
+             with `{options:?}` aren't equivalent"
+        );
+        // Function pointer types are also checked as the types of functions
+        if let ty::FnPtr(fn_sig) = ty.kind() {
+            let typeid = typeid_for_fnsig(tcx, *fn_sig, options);
+            assert_eq!(
+                typeid,
+                typeid_for_fnsig(tcx, *fn_sig, options),
+                "check_arbitrary_ty_encoding: type metadata identifier of `{ty:?}` with \
+                 `{options:?}` isn't deterministic"
+            );
+            assert!(
+                itanium::TypeId::parse(&typeid).is_some_and(|parsed| parsed.to_string() == typeid),
+                "check_arbitrary_ty_encoding: type metadata identifier `{typeid}` of `{ty:?}` with \
+                 `{options:?}` isn't valid"
+            );
+            let uncompressed =
+                typeid_for_fnsig(tcx, *fn_sig, options | TypeIdOptions::UNCOMPRESSED);
+            assert_eq!(
+                itanium::TypeId::parse(&typeid).map(itanium::TypeId::expand_substitutions),
+                itanium::TypeId::parse(&uncompressed),
+                "check_arbitrary_ty_encoding: type metadata identifiers `{typeid}` and \
+                 `{uncompressed}` of `{ty:?}` with `{options:?}` aren't equivalent"
+            );
+        }
+    }
+    ty
+}
+
+/// Builds types from bytes for `check_arbitrary_ty_encoding`.
+struct ArbitraryTy<'tcx, 'a> {
+    tcx: TyCtxt<'tcx>,
+    data: &'a [u8],
+}
+
+impl<'tcx> ArbitraryTy<'tcx, '_> {
+    /// The maximum depth of the types built.
+    const MAX_DEPTH: usize = 4;
+
+    /// Returns the next byte, or zero if all of them were used.
+    fn byte(&mut self) -> u8 {
+        let Some((&byte, rest)) = self.data.split_first() else {
+            return 0;
+        };
+        self.data = rest;
+        byte
+    }
+
+    /// Builds a type of at most `MAX_DEPTH - depth` levels.
+    fn ty(&mut self, depth: usize) -> Ty<'tcx> {
+        let tcx = self.tcx;
+        let types = &tcx.types;
+        let mut leaves = vec![
+            types.bool,
+            types.char,
+            types.str_,
+            types.never,
+            types.unit,
+            types.i8,
+            types.i16,
+            types.i32,
+            types.i64,
+            types.i128,
+            types.isize,
+            types.u8,
+            types.u16,
+            types.u32,
+            types.u64,
+            types.u128,
+            types.usize,
+            types.f16,
+            types.f32,
+            types.f64,
+            types.f128,
+        ];
+        // The types that need lang items are only built if the crate graph defines them
+        if let Some(def_id) = tcx.lang_items().get(LangItem::CVoid) {
+            leaves.push(Ty::new_adt(tcx, tcx.adt_def(def_id), ty::List::empty()));
+        }
+        if let Some(def_id) = tcx.lang_items().get(LangItem::Sync) {
+            let predicates = tcx.mk_poly_existential_predicates(&[ty::Binder::dummy(
+                ExistentialPredicate::AutoTrait(def_id),
+            )]);
+            leaves.push(Ty::new_dynamic(tcx, predicates, tcx.lifetimes.re_erased, ty::Dyn));
+        }
+
+        let kinds = if depth < Self::MAX_DEPTH { leaves.len() + 9 } else { leaves.len() };
+        let kind = usize::from(self.byte()) % kinds;
+        if let Some(&ty) = leaves.get(kind) {
+            return ty;
+        }
+        let depth = depth + 1;
+        match kind - leaves.len() {
+            0 => {
+                let tys: Vec<_> = (0..self.byte() % 4).map(|_| self.ty(depth)).collect();
+                Ty::new_tup(tcx, &tys)
+            }
+            1 => {
+                let len = self.byte();
+                Ty::new_array(tcx, self.ty(depth), len.into())
+            }
+            2 => Ty::new_slice(tcx, self.ty(depth)),
+            3 => Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, self.ty(depth)),
+            4 => Ty::new_mut_ref(tcx, tcx.lifetimes.re_erased, self.ty(depth)),
+            5 => Ty::new_imm_ptr(tcx, self.ty(depth)),
+            6 => Ty::new_mut_ptr(tcx, self.ty(depth)),
+            7 => {
+                let inputs: Vec<_> = (0..self.byte() % 4).map(|_| self.ty(depth)).collect();
+                let output = self.ty(depth);
+                let (abi, c_variadic) = match self.byte() % 3 {
+                    0 => (Abi::Rust, false),
+                    1 => (Abi::C { unwind: false }, false),
+                    _ => (Abi::C { unwind: false }, true),
+                };
+                let fn_sig = tcx.mk_fn_sig(inputs, output, c_variadic, hir::Unsafety::Normal, abi);
+                Ty::new_fn_ptr(tcx, ty::Binder::dummy(fn_sig))
+            }
+            _ => {
+                let ty = self.ty(depth);
+                match tcx.lang_items().get(LangItem::Option) {
+                    Some(def_id) => {
+                        Ty::new_adt(tcx, tcx.adt_def(def_id), tcx.mk_args(&[ty.into()]))
+                    }
+                    None => Ty::new_tup(tcx, &[ty]),
+                }
+            }
+        }
     }
 }
 
@@ -958,6 +2188,45 @@ pub fn typeid_for_fnabi<'tcx>(
     tcx: TyCtxt<'tcx>,
     fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
     options: TypeIdOptions,
+) -> String {
+    let typeid = encode_fnabi(tcx, fn_abi, None, &[], options, false);
+    tcx.override_cfi_typeid(None, options.bits(), &typeid).unwrap_or(typeid)
+}
+
+/// Returns a type metadata identifier for the specified PolyFnSig using the Itanium C++ ABI with
+/// vendor extended type qualifiers and types for Rust types that are not used at the FFI boundary,
+/// without computing its FnAbi. It's the same as the one for the FnAbi of the PolyFnSig when no
+/// parameter is ignored, or when encoding functions from their declared signatures.
+#[instrument(level = "trace", skip(tcx))]
+pub fn typeid_for_fnsig<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_sig: ty::PolyFnSig<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    // A name is mangled by prefixing "_Z" to an encoding of its name, and in the case of functions
+    // its type, and the typeinfo name encoding is "TS" prefixed to the type encoding (see
+    // `encode_fnabi`).
+    let mut typeid = String::from("_ZTS");
+    let mut dict = Dict::new(options);
+    let fn_sig = tcx.instantiate_bound_regions_with_erased(fn_sig);
+    typeid.push_str(&encode_fnsig(tcx, &fn_sig, &mut dict, options));
+    let typeid = finish_typeid(tcx, typeid, &dict, options);
+    tcx.override_cfi_typeid(None, options.bits(), &typeid).unwrap_or(typeid)
+}
+
+/// Encodes the type metadata identifier for the specified FnAbi (see `typeid_for_fnabi`), logging
+/// the type transformations applied to its return and parameter types if `trace` is set (see
+/// `-Zcfi-trace-transform`). If a class is specified, the FnAbi is encoded as a pointer to member
+/// function type of the class, without its receiver (i.e., its first parameter). The declared
+/// types specified (for the return type and then for each parameter, see `impl_trait_tys`) are
+/// encoded instead of the ones of the FnAbi.
+fn encode_fnabi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+    class: Option<Ty<'tcx>>,
+    declared_tys: &[Option<Ty<'tcx>>],
+    options: TypeIdOptions,
+    trace: bool,
 ) -> String {
     // A name is mangled by prefixing "_Z" to an encoding of its name, and in the case of functions
     // its type.
@@ -968,54 +2237,100 @@ pub fn typeid_for_fnabi<'tcx>(
     // code (i.e., 'TS') prefixed to the type encoding for the function.
     typeid.push_str("TS");
 
-    // Function types are delimited by an "F..E" pair
-    typeid.push('F');
-
     // A dictionary of substitution candidates used for compression (see
     // https://itanium-cxx-abi.github.io/cxx-abi/abi.html#mangling-compression).
-    let mut dict: FxHashMap<DictKey<'tcx>, usize> = FxHashMap::default();
+    let mut dict = Dict::new(options);
 
     let mut encode_ty_options = EncodeTyOptions::from_bits(options.bits())
         .unwrap_or_else(|| bug!("typeid_for_fnabi: invalid option(s) `{:?}`", options.bits()));
-    match fn_abi.conv {
-        Conv::C => {
-            encode_ty_options.insert(EncodeTyOptions::GENERALIZE_REPR_C);
-        }
-        _ => {
-            encode_ty_options.remove(EncodeTyOptions::GENERALIZE_REPR_C);
-        }
+    let mut transform_ty_options = TransformTyOptions::from_bits(options.bits())
+        .unwrap_or_else(|| bug!("typeid_for_fnabi: invalid option(s) `{:?}`", options.bits()));
+    if is_c_conv(fn_abi.conv) {
+        encode_ty_options.insert(EncodeTyOptions::GENERALIZE_REPR_C);
+    } else {
+        encode_ty_options.remove(EncodeTyOptions::GENERALIZE_REPR_C);
+        // Only the signatures reachable from C have their array lengths erased, their integer
+        // types encoded as C integer types, and their boxes and references encoded as raw
+        // pointers
+        encode_ty_options.remove(EncodeTyOptions::C_INTEGER_TYPES);
+        transform_ty_options.remove(TransformTyOptions::GENERALIZE_ARRAYS);
+        transform_ty_options.remove(TransformTyOptions::BOX_AS_PTR);
+        transform_ty_options.remove(TransformTyOptions::REFS_AS_PTRS);
     }
 
-    // Encode the return type
-    let transform_ty_options = TransformTyOptions::from_bits(options.bits())
-        .unwrap_or_else(|| bug!("typeid_for_fnabi: invalid option(s) `{:?}`", options.bits()));
     let mut type_folder = TransformTy::new(tcx, transform_ty_options);
-    let ty = fn_abi.ret.layout.ty.fold_with(&mut type_folder);
-    typeid.push_str(&encode_ty(tcx, ty, &mut dict, encode_ty_options));
+    type_folder.trace = trace;
+
+    // Functions with the "C" calling convention are encoded using the Microsoft C++ ABI type name
+    // decorations with `-Zsanitizer-cfi-typeid-scheme=msvc`
+    if is_c_conv(fn_abi.conv)
+        && tcx.sess.opts.unstable_opts.sanitizer_cfi_typeid_scheme == CfiTypeIdScheme::Msvc
+    {
+        return typeid_msvc_abi::encode_fnabi(tcx, fn_abi, &mut type_folder, options);
+    }
+
+    // Pointer to member types are encoded as "M" followed by the class type and the member type
+    if let Some(class) = class {
+        typeid.push('M');
+        let ty = class.fold_with(&mut type_folder);
+        typeid.push_str(&encode_ty(tcx, ty, &mut dict, encode_ty_options));
+    }
+
+    // Function types are delimited by an "F..E" pair
+    typeid.push('F');
+
+    let declared_ty =
+        |index: usize, ty: Ty<'tcx>| declared_tys.get(index).copied().flatten().unwrap_or(ty);
+
+    // Encode the return type (as void if return types are erased)
+    if options.contains(EncodeTyOptions::ERASE_RETURN_TYPES) {
+        typeid.push('v');
+    } else {
+        let ty = declared_ty(0, fn_abi.ret.layout.ty).fold_with(&mut type_folder);
+        typeid.push_str(&encode_ty(tcx, ty, &mut dict, encode_ty_options));
+    }
 
     // Encode the parameter types
 
     // We erase ZSTs as we go if the argument is skipped. This is an implementation detail of how
     // MIR is currently treated by rustc, and subject to change in the future. Specifically, MIR
     // interpretation today will allow skipped arguments to simply not be passed at a call-site.
+    // (Skipped arguments are still encoded when encoding from declared signatures, as the types
+    // of the functions don't depend on how their arguments are passed, except for the receivers of
+    // closures, which are skipped as the closures are called through function pointers without
+    // them.)
+    //
+    // The receiver of a member function is implied by its class, so it's not encoded.
+    let is_skipped = |arg: &ArgAbi<'tcx, Ty<'tcx>>| {
+        arg.mode == PassMode::Ignore
+            && (!options.contains(EncodeTyOptions::ENCODE_FN_SIGS)
+                || matches!(arg.layout.ty.kind(), ty::Closure(..) | ty::CoroutineClosure(..)))
+    };
+    let mut is_receiver = class.is_some();
     if !fn_abi.c_variadic {
-        let mut pushed_arg = false;
-        for arg in fn_abi.args.iter().filter(|arg| arg.mode != PassMode::Ignore) {
-            pushed_arg = true;
-            let ty = arg.layout.ty.fold_with(&mut type_folder);
+        let mut params = 0;
+        for (n, arg) in fn_abi.args.iter().enumerate().filter(|(_, arg)| !is_skipped(arg)) {
+            if mem::take(&mut is_receiver) {
+                continue;
+            }
+            params += 1;
+            let ty = declared_ty(n + 1, arg.layout.ty).fold_with(&mut type_folder);
             typeid.push_str(&encode_ty(tcx, ty, &mut dict, encode_ty_options));
         }
-        if !pushed_arg {
+        if params == 0 {
             // Empty parameter lists, whether declared as () or conventionally as (void), are
             // encoded with a void parameter specifier "v".
             typeid.push('v');
         }
     } else {
         for n in 0..fn_abi.fixed_count as usize {
-            if fn_abi.args[n].mode == PassMode::Ignore {
+            if is_skipped(&fn_abi.args[n]) {
+                continue;
+            }
+            if mem::take(&mut is_receiver) {
                 continue;
             }
-            let ty = fn_abi.args[n].layout.ty.fold_with(&mut type_folder);
+            let ty = declared_ty(n + 1, fn_abi.args[n].layout.ty).fold_with(&mut type_folder);
             typeid.push_str(&encode_ty(tcx, ty, &mut dict, encode_ty_options));
         }
 
@@ -1025,18 +2340,118 @@ pub fn typeid_for_fnabi<'tcx>(
     // Close the "F..E" pair
     typeid.push('E');
 
+    finish_typeid(tcx, typeid, &dict, options)
+}
+
+/// Adds the encoding suffixes of the specified options to the specified type metadata identifier,
+/// checking that it can be parsed back into itself.
+fn finish_typeid<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mut typeid: String,
+    dict: &Dict<'tcx>,
+    options: TypeIdOptions,
+) -> String {
     // Add encoding suffixes
     if options.contains(EncodeTyOptions::NORMALIZE_INTEGERS) {
         typeid.push_str(".normalized");
+    } else if options.contains(EncodeTyOptions::NORMALIZE_PTR_SIZED_INTEGERS) {
+        // (Which normalizes a subset of the integer types `.normalized` ones do.)
+        typeid.push_str(".ptrsized");
     }
 
     if options.contains(EncodeTyOptions::GENERALIZE_POINTERS) {
         typeid.push_str(".generalized");
     }
 
+    if options.contains(EncodeTyOptions::GENERALIZE_ARRAYS) {
+        // Keeps the type metadata identifiers with erased array lengths from being mixed with
+        // precise ones (for every calling convention, as for the other options)
+        typeid.push_str(".arrays");
+    }
+
+    if options.contains(EncodeTyOptions::ERASE_RETURN_TYPES) {
+        typeid.push_str(".returns");
+    }
+
+    // The target's salt (which also salts the KCFI type metadata identifiers, as they are computed
+    // from the type metadata identifiers)
+    if let Some(salt) = &tcx.sess.target.cfi_typeid_salt {
+        let _ = write!(typeid, ".salt{salt}");
+    }
+
+    // User-defined encodings provided as is are only validated on their own (i.e., with their
+    // substitutions referring to their own components; see `invalid_raw_cfi_encoding`), so they
+    // aren't necessarily valid where they are used, and only the type metadata identifiers without
+    // user-defined encodings are checked.
+    debug_assert!(
+        tcx.dcx().has_errors().is_some()
+            || dict.entries.keys().any(|key| is_user_encoded(tcx, key))
+            || itanium::TypeId::parse(&typeid).is_some_and(|parsed| parsed.to_string() == typeid),
+        "finish_typeid: `{typeid}` cannot be parsed back into itself"
+    );
+
+    if tcx.sess.opts.unstable_opts.cfi_compression_stats && !dict.uncompressed {
+        // User-encoded type metadata identifiers may not be parsable, and are counted as not
+        // compressed.
+        let info = match itanium::TypeId::compression_stats(&typeid) {
+            Some(stats) => CfiTypeIdSizeInfo {
+                substitutions: stats.substitutions,
+                uncompressed_len: stats.uncompressed_len,
+            },
+            None => CfiTypeIdSizeInfo { substitutions: 0, uncompressed_len: typeid.len() },
+        };
+        tcx.sess.code_stats.record_cfi_typeid_size(&typeid, info);
+    }
+
+    if !options.contains(TypeIdOptions::UNCAPPED)
+        && let Some(cap) = tcx.sess.opts.unstable_opts.sanitizer_cfi_typeid_length_cap
+        && typeid.len() > cap
+    {
+        return cap_typeid_length(&typeid, cap);
+    }
+
     typeid
 }
 
+/// Replaces the tail of `typeid` with `.h` followed by the hexadecimal 64-bit stable hash of the
+/// whole type metadata identifier, so that it is `cap` bytes long. The hash only depends on the
+/// type metadata identifier, so the capped type metadata identifier is the same in every crate.
+/// Capped type metadata identifiers can no longer be parsed or demangled, and never match the ones
+/// of Clang, so the ones of functions called from C code shouldn't be capped.
+fn cap_typeid_length(typeid: &str, cap: usize) -> String {
+    let mut hasher = StableHasher::new();
+    typeid.hash(&mut hasher);
+    let suffix = format!(".h{:016x}", hasher.finish::<Hash64>().as_u64());
+
+    let mut end = cap - suffix.len();
+    while !typeid.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{suffix}", &typeid[..end])
+}
+
+/// Returns whether functions with the specified calling convention are encoded as functions with
+/// the "C" calling convention. Clang doesn't encode the calling conventions that only differ in
+/// how arguments are passed (e.g., `efiapi`, `win64`, and `sysv64` on x86_64, and `aapcs` on ARM)
+/// in type metadata identifiers, so functions with them are compatible with C functions.
+fn is_c_conv(conv: Conv) -> bool {
+    matches!(conv, Conv::C | Conv::ArmAapcs | Conv::X86_64SysV | Conv::X86_64Win64)
+}
+
+/// Returns whether the specified substitution dictionary key is of a type with a user-defined
+/// encoding (i.e., a `cfi_encoding` attribute).
+fn is_user_encoded(tcx: TyCtxt<'_>, key: &DictKey<'_>) -> bool {
+    match key {
+        DictKey::Ty(ty, _) => match ty.kind() {
+            ty::Adt(adt_def, _) => cfi_encoding(tcx, adt_def.did()).is_some(),
+            ty::Foreign(def_id) => cfi_encoding(tcx, *def_id).is_some(),
+            _ => false,
+        },
+        DictKey::Prefix(..) => true,
+        DictKey::Region(..) | DictKey::Const(..) | DictKey::Predicate(..) => false,
+    }
+}
+
 /// Returns a type metadata identifier for the specified Instance using the Itanium C++ ABI with
 /// vendor extended type qualifiers and types for Rust types that are not used at the FFI boundary.
 pub fn typeid_for_instance<'tcx>(
@@ -1044,6 +2459,47 @@ pub fn typeid_for_instance<'tcx>(
     mut instance: Instance<'tcx>,
     options: TypeIdOptions,
 ) -> String {
+    let original_instance = instance;
+
+    // Log the type transformations for the function selected with `-Zcfi-trace-transform`
+    let trace = match &tcx.sess.opts.unstable_opts.cfi_trace_transform {
+        Some(path) => *path == with_no_trimmed_paths!(tcx.def_path_str(instance.def_id())),
+        None => false,
+    };
+    let _guard = trace.then(|| info_span!("cfi_trace_transform", ?instance, ?options).entered());
+
+    if let ty::InstanceDef::Item(def_id) | ty::InstanceDef::Intrinsic(def_id) = instance.def
+        && let Some(intrinsic) = tcx.intrinsic(def_id)
+    {
+        // Intrinsics can't be reified, but the ones with fallback bodies are codegened as their
+        // fallback bodies when the codegen backend doesn't override them, and encoded as any other
+        // function (i.e., as callers of a pointer to a function with the same signature encode it).
+        // Intrinsics without fallback bodies are never codegened as functions.
+        if intrinsic.must_be_overridden || !tcx.has_attr(def_id, sym::rustc_intrinsic) {
+            tcx.dcx().emit_err(IntrinsicWithoutFallbackBody {
+                span: tcx.def_span(def_id),
+                name: intrinsic.name,
+            });
+            let fn_sig = tcx.fn_sig(def_id).instantiate(tcx, instance.args);
+            return typeid_for_fnsig(tcx, fn_sig, options);
+        }
+        instance.def = ty::InstanceDef::Item(def_id);
+    }
+
+    if let ty::InstanceDef::Item(def_id) = instance.def
+        && let Some((entry_def_id, EntryFnType::Main { .. })) = tcx.entry_fn(())
+        && def_id == entry_def_id
+    {
+        // The entry function (i.e., the user's `main`) is passed by the synthesized C `main` to the
+        // `start` lang item (i.e., `lang_start`) as a `fn() -> T` function pointer, and called
+        // through it, so it's encoded as that function pointer type (i.e., with the type its return
+        // type hides instead of via its bounds, and without the overrides of `main` itself),
+        // whatever the options.
+        let fn_sig = tcx.fn_sig(def_id).instantiate_identity();
+        let fn_sig = tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), fn_sig);
+        return typeid_for_fnsig(tcx, ty::Binder::dummy(fn_sig), options);
+    }
+
     if (matches!(instance.def, ty::InstanceDef::Virtual(..))
         && Some(instance.def_id()) == tcx.lang_items().drop_in_place_fn())
         || matches!(instance.def, ty::InstanceDef::DropGlue(..))
@@ -1190,7 +2646,44 @@ pub fn typeid_for_instance<'tcx>(
             bug!("typeid_for_instance: couldn't get fn_abi of instance {instance:?}: {error:?}")
         });
 
-    typeid_for_fnabi(tcx, fn_abi, options)
+    // Trait methods are encoded as pointer to member function types of their trait objects (which
+    // doesn't apply to DropGlues, as they're declared without a defining trait).
+    let class = match instance.def {
+        ty::InstanceDef::Virtual(def_id, _)
+            if options.contains(TypeIdOptions::MEMBER_FUNCTION_POINTERS)
+                && tcx.trait_of_item(def_id).is_some() =>
+        {
+            Some(instance.args.type_at(0))
+        }
+        _ => None,
+    };
+
+    // Functions are encoded from their declared signatures when enabled
+    let mut declared_tys = if options.contains(TypeIdOptions::ENCODE_FN_SIGS) {
+        fn_sig_tys(tcx, instance)
+    } else {
+        Vec::new()
+    };
+
+    // Argument-position `impl Trait` types and opaque types are encoded via their bounds as
+    // declared, as the FnAbi only has the types they hide
+    if options.contains(TypeIdOptions::IMPL_TRAIT_BOUNDS)
+        && let ty::InstanceDef::Item(def_id) = instance.def
+        && matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+    {
+        let impl_trait_tys = impl_trait_tys(tcx, instance);
+        if declared_tys.len() < impl_trait_tys.len() {
+            declared_tys.resize(impl_trait_tys.len(), None);
+        }
+        for (declared_ty, impl_trait_ty) in iter::zip(&mut declared_tys, impl_trait_tys) {
+            if impl_trait_ty.is_some() {
+                *declared_ty = impl_trait_ty;
+            }
+        }
+    }
+
+    let typeid = encode_fnabi(tcx, fn_abi, class, &declared_tys, options, trace);
+    tcx.override_cfi_typeid(Some(original_instance), options.bits(), &typeid).unwrap_or(typeid)
 }
 
 fn strip_receiver_auto<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Ty<'tcx> {
@@ -1221,19 +2714,36 @@ fn trait_object_ty<'tcx>(tcx: TyCtxt<'tcx>, poly_trait_ref: ty::PolyTraitRef<'tc
         .flat_map(|super_poly_trait_ref| {
             tcx.associated_items(super_poly_trait_ref.def_id())
                 .in_definition_order()
-                .filter(|item| item.kind == ty::AssocKind::Type)
-                .map(move |assoc_ty| {
+                .filter(|item| matches!(item.kind, ty::AssocKind::Type | ty::AssocKind::Const))
+                .map(move |assoc_item| {
                     super_poly_trait_ref.map_bound(|super_trait_ref| {
-                        let alias_ty = ty::AliasTy::new(tcx, assoc_ty.def_id, super_trait_ref.args);
-                        let resolved = tcx.normalize_erasing_regions(
-                            ty::ParamEnv::reveal_all(),
-                            alias_ty.to_ty(tcx),
-                        );
-                        debug!("Resolved {:?} -> {resolved}", alias_ty.to_ty(tcx));
+                        let term: ty::Term<'tcx> = if assoc_item.kind == ty::AssocKind::Const {
+                            // Associated consts are projected by their value, as in
+                            // `dyn Trait<CONST = 3>`
+                            let uv =
+                                ty::UnevaluatedConst::new(assoc_item.def_id, super_trait_ref.args);
+                            let ty = tcx
+                                .type_of(assoc_item.def_id)
+                                .instantiate(tcx, super_trait_ref.args);
+                            let alias_const = Const::new_unevaluated(tcx, uv, ty);
+                            let resolved = tcx
+                                .normalize_erasing_regions(ty::ParamEnv::reveal_all(), alias_const);
+                            debug!("Resolved {alias_const:?} -> {resolved}");
+                            resolved.into()
+                        } else {
+                            let alias_ty =
+                                ty::AliasTy::new(tcx, assoc_item.def_id, super_trait_ref.args);
+                            let resolved = tcx.normalize_erasing_regions(
+                                ty::ParamEnv::reveal_all(),
+                                alias_ty.to_ty(tcx),
+                            );
+                            debug!("Resolved {:?} -> {resolved}", alias_ty.to_ty(tcx));
+                            resolved.into()
+                        };
                         ty::ExistentialPredicate::Projection(ty::ExistentialProjection {
-                            def_id: assoc_ty.def_id,
+                            def_id: assoc_item.def_id,
                             args: ty::ExistentialTraitRef::erase_self_ty(tcx, super_trait_ref).args,
-                            term: resolved.into(),
+                            term,
                         })
                     })
                 })
@@ -1245,3 +2755,124 @@ fn trait_object_ty<'tcx>(tcx: TyCtxt<'tcx>, poly_trait_ref: ty::PolyTraitRef<'tc
     );
     Ty::new_dynamic(tcx, preds, tcx.lifetimes.re_erased, ty::Dyn)
 }
+
+/// Returns the trait object type of the specified bounds of the specified type (e.g., of an `impl
+/// Trait` type) for encoding the type via its bounds (see `TypeIdOptions::IMPL_TRAIT_BOUNDS`), or
+/// the unit type if it has no bounds other than `Sized` and lifetime bounds.
+fn bounds_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    self_ty: Ty<'tcx>,
+    bounds: impl IntoIterator<Item = ty::Clause<'tcx>>,
+) -> Ty<'tcx> {
+    let sized_trait = tcx.lang_items().sized_trait();
+    let mut preds: Vec<ty::PolyExistentialPredicate<'tcx>> = bounds
+        .into_iter()
+        .filter_map(|clause| {
+            let kind = clause.kind();
+            let pred = match kind.skip_binder() {
+                ty::ClauseKind::Trait(pred)
+                    if pred.self_ty() == self_ty && Some(pred.def_id()) != sized_trait =>
+                {
+                    if tcx.trait_is_auto(pred.def_id()) {
+                        ExistentialPredicate::AutoTrait(pred.def_id())
+                    } else {
+                        ExistentialPredicate::Trait(ty::ExistentialTraitRef::erase_self_ty(
+                            tcx,
+                            pred.trait_ref,
+                        ))
+                    }
+                }
+                ty::ClauseKind::Projection(pred) if pred.self_ty() == self_ty => {
+                    ExistentialPredicate::Projection(ty::ExistentialProjection::erase_self_ty(
+                        tcx, pred,
+                    ))
+                }
+                _ => return None,
+            };
+            Some(kind.rebind(pred))
+        })
+        .collect();
+    // Unlike trait objects, `impl Trait` types may have several non-auto traits, which are also
+    // sorted so the encoding doesn't depend on the order the bounds are written in
+    preds.sort_by(|a, b| {
+        a.skip_binder().stable_cmp(tcx, &b.skip_binder()).then_with(|| {
+            match (a.skip_binder(), b.skip_binder()) {
+                (ExistentialPredicate::Trait(a), ExistentialPredicate::Trait(b)) => {
+                    tcx.def_path_hash(a.def_id).cmp(&tcx.def_path_hash(b.def_id))
+                }
+                _ => Ordering::Equal,
+            }
+        })
+    });
+    preds.dedup();
+    if preds.is_empty() {
+        return tcx.types.unit;
+    }
+    Ty::new_dynamic(
+        tcx,
+        tcx.mk_poly_existential_predicates(&preds),
+        tcx.lifetimes.re_erased,
+        ty::Dyn,
+    )
+}
+
+/// Returns the return and parameter types (in this order) of the specified function item as
+/// declared, with its argument-position `impl Trait` types replaced by the trait objects of their
+/// bounds (see `bounds_ty`), for the ones that have argument-position `impl Trait` types or opaque
+/// types, and `None` for the others.
+fn impl_trait_tys<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Vec<Option<Ty<'tcx>>> {
+    let def_id = instance.def_id();
+    let sig = tcx.instantiate_bound_regions_with_erased(tcx.fn_sig(def_id).instantiate_identity());
+    if sig.abi == Abi::RustCall {
+        // The FnAbi of "rust-call" functions has the untupled parameters
+        return Vec::new();
+    }
+    let generics = tcx.generics_of(def_id);
+    let predicates = tcx.predicates_of(def_id).instantiate_identity(tcx).predicates;
+    let mut folder = ty::fold::BottomUpFolder {
+        tcx,
+        ty_op: |ty: Ty<'tcx>| match ty.kind() {
+            ty::Param(param) if generics.type_param(param, tcx).kind.is_synthetic() => {
+                bounds_ty(tcx, ty, predicates.iter().copied())
+            }
+            _ => ty,
+        },
+        lt_op: |lt| lt,
+        ct_op: |ct| ct,
+    };
+    iter::once(sig.output())
+        .chain(sig.inputs().iter().copied())
+        .map(|ty| {
+            let declared_ty = ty.fold_with(&mut folder);
+            (declared_ty != ty || ty.has_opaque_types())
+                .then(|| ty::EarlyBinder::bind(declared_ty).instantiate(tcx, instance.args))
+        })
+        .collect()
+}
+
+/// Returns the return and parameter types (in this order) of the specified instance as declared
+/// (see `TypeIdOptions::ENCODE_FN_SIGS`), with the tupled parameters of "rust-call" functions
+/// untupled (see `encode_fnsig`), or an empty list for the instances encoded from their FnAbi
+/// (i.e., closures with their concrete self and shims).
+fn fn_sig_tys<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Vec<Option<Ty<'tcx>>> {
+    let def_id = match instance.def {
+        ty::InstanceDef::Item(def_id) | ty::InstanceDef::Virtual(def_id, _)
+            if !tcx.is_closure_like(def_id) =>
+        {
+            def_id
+        }
+        _ => return Vec::new(),
+    };
+    let sig = tcx.fn_sig(def_id).instantiate(tcx, instance.args);
+    let sig = tcx.instantiate_bound_regions_with_erased(sig);
+    let mut inputs = sig.inputs().to_vec();
+    if sig.abi == Abi::RustCall
+        && let Some(ty) = inputs.pop()
+    {
+        match ty.kind() {
+            ty::Tuple(tupled_tys) => inputs.extend(tupled_tys.iter()),
+            _ => inputs.push(ty),
+        }
+    }
+    iter::once(sig.output()).chain(inputs).map(Some).collect()
+}
@@ -0,0 +1,244 @@
+//! Type metadata identifiers (using the Microsoft C++ ABI type name decorations) for LLVM Control
+//! Flow Integrity (CFI) and cross-language LLVM CFI support with C and C++ code compiled for MSVC
+//! targets (e.g., with clang-cl).
+//!
+//! Only functions with the "C" calling convention (or one Clang encodes as it, such as `efiapi` or
+//! `win64`) are encoded using this scheme (see `-Zsanitizer-cfi-typeid-scheme`), as the
+//! decorations can only represent types with a C or C++ counterpart. Types without one are encoded
+//! as classes named after their Itanium C++ ABI encodings, so they still have distinct (but
+//! Rust-only) encodings.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_middle::ty::layout::IntegerExt;
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable};
+use rustc_target::abi::call::{FnAbi, PassMode};
+use rustc_target::abi::Integer;
+
+use crate::typeid::typeid_itanium_cxx_abi::{encode_standalone_ty, TransformTy};
+use crate::typeid::TypeIdOptions;
+
+/// Back references (see
+/// <https://learn.microsoft.com/en-us/cpp/build/reference/decorated-names>), which are the first
+/// ten names and the first ten parameter types whose encodings are longer than one character.
+#[derive(Default)]
+struct BackRefs<'tcx> {
+    names: Vec<String>,
+    params: FxHashMap<Ty<'tcx>, usize>,
+}
+
+/// Encodes a name (i.e., `<name>@`), or its back reference if it has already been encoded.
+fn encode_name(name: &str, backrefs: &mut BackRefs<'_>) -> String {
+    if let Some(index) = backrefs.names.iter().position(|other| other == name) {
+        return index.to_string();
+    }
+    if backrefs.names.len() < 10 {
+        backrefs.names.push(name.to_string());
+    }
+    format!("{name}@")
+}
+
+/// Encodes a class, union, or enum type (i.e., `<prefix><name>@@`), prefixed with `?A` if it is a
+/// return type.
+fn encode_tag_ty(prefix: &str, name: &str, ret: bool, backrefs: &mut BackRefs<'_>) -> String {
+    let s = format!("{prefix}{}@", encode_name(name, backrefs));
+    if ret {
+        format!("?A{s}")
+    } else {
+        s
+    }
+}
+
+/// Encodes a function type (i.e.,
+/// `A<return-type><parameter-type1..parameter-typeN>@Z`, `A<return-type>XZ` without parameters,
+/// or `A<return-type><parameter-type1..parameter-typeN>ZZ` if variadic) with the C calling
+/// convention.
+fn encode_fn<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ret: Ty<'tcx>,
+    params: &[Ty<'tcx>],
+    c_variadic: bool,
+    backrefs: &mut BackRefs<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    // __cdecl
+    let mut s = String::from("A");
+    s.push_str(&encode_ty(tcx, ret, true, backrefs, options));
+    if params.is_empty() && !c_variadic {
+        s.push('X');
+    } else {
+        for &param in params {
+            if let Some(index) = backrefs.params.get(&param) {
+                s.push_str(&index.to_string());
+                continue;
+            }
+            let encoding = encode_ty(tcx, param, false, backrefs, options);
+            if encoding.len() > 1 && backrefs.params.len() < 10 {
+                let index = backrefs.params.len();
+                backrefs.params.insert(param, index);
+            }
+            s.push_str(&encoding);
+        }
+        s.push(if c_variadic { 'Z' } else { '@' });
+    }
+    // Without an exception specification
+    s.push('Z');
+    s
+}
+
+/// Encodes a ty:Ty using the Microsoft C++ ABI type name decorations.
+fn encode_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    ret: bool,
+    backrefs: &mut BackRefs<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    // As in `typeid_itanium_cxx_abi::encode_ty`, types may be nested arbitrarily deeply
+    ensure_sufficient_stack(|| encode_ty_kind(tcx, ty, ret, backrefs, options))
+}
+
+/// Encodes a ty:Ty as `encode_ty` does, on the current stack.
+fn encode_ty_kind<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    ret: bool,
+    backrefs: &mut BackRefs<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    let ptr64 = tcx.sess.target.pointer_width == 64;
+    match ty.kind() {
+        ty::Bool => "_N".to_string(),
+        // char32_t
+        ty::Char => "_U".to_string(),
+        ty::Int(int_ty) => match Integer::from_int_ty(&tcx, *int_ty) {
+            Integer::I8 => "C",
+            Integer::I16 => "F",
+            Integer::I32 => "H",
+            Integer::I64 => "_J",
+            Integer::I128 => "_L",
+        }
+        .to_string(),
+        ty::Uint(uint_ty) => match Integer::from_uint_ty(&tcx, *uint_ty) {
+            Integer::I8 => "E",
+            Integer::I16 => "G",
+            Integer::I32 => "I",
+            Integer::I64 => "_K",
+            Integer::I128 => "_M",
+        }
+        .to_string(),
+        ty::Float(ty::FloatTy::F32) => "M".to_string(),
+        ty::Float(ty::FloatTy::F64) => "N".to_string(),
+        // void (which c_void is transformed into)
+        _ if ty.is_unit() || ty.is_never() => "X".to_string(),
+
+        // U<name>@@ for structs, T<name>@@ for unions, and W4<name>@@ for enums (with an int
+        // underlying type), where the names of repr(C) user-defined types and extern types are
+        // their unqualified names (i.e., C types)
+        ty::Adt(adt_def, _) if adt_def.repr().c() => {
+            let prefix = match adt_def.adt_kind() {
+                ty::AdtKind::Struct => "U",
+                ty::AdtKind::Union => "T",
+                ty::AdtKind::Enum => "W4",
+            };
+            let name = tcx.item_name(adt_def.did()).to_string();
+            encode_tag_ty(prefix, &name, ret, backrefs)
+        }
+        ty::Foreign(def_id) => {
+            encode_tag_ty("U", &tcx.item_name(*def_id).to_string(), ret, backrefs)
+        }
+
+        // P[E]<A|B><pointee-type> for raw pointers and references (which are pointers in C), or
+        // A[E]<A|B><pointee-type> for references encoded as C++ references, where E is the
+        // __ptr64 qualifier and A and B are the unqualified and const qualifiers, respectively
+        ty::RawPtr(pointee, mutbl) | ty::Ref(_, pointee, mutbl) => {
+            let cxx_ref = ty.is_ref() && options.contains(TypeIdOptions::CXX_REFERENCES);
+            let mut s = String::from(if cxx_ref { "A" } else { "P" });
+            if ptr64 {
+                s.push('E');
+            }
+            s.push(if mutbl.is_mut() { 'A' } else { 'B' });
+            s.push_str(&encode_ty(tcx, *pointee, false, backrefs, options));
+            s
+        }
+
+        // P6<function-type> (without the __ptr64 qualifier)
+        ty::FnPtr(fn_sig) => {
+            let fn_sig = fn_sig.skip_binder();
+            let s = encode_fn(
+                tcx,
+                fn_sig.output(),
+                fn_sig.inputs(),
+                fn_sig.c_variadic,
+                backrefs,
+                options,
+            );
+            format!("P6{s}")
+        }
+
+        ty::Bound(..) | ty::Error(..) | ty::Infer(..) | ty::Param(..) | ty::Placeholder(..) => {
+            bug!("encode_ty: unexpected `{:?}`", ty.kind());
+        }
+
+        // Types without a C or C++ counterpart
+        _ => encode_tag_ty("U", &encode_standalone_ty(tcx, ty, options), ret, backrefs),
+    }
+}
+
+/// Returns a type metadata identifier for the specified FnAbi using the Microsoft C++ ABI type name
+/// decorations (i.e., `?$$A6<function-type>`, as Clang encodes canonical function type names for
+/// MSVC targets), with the types transformed by the specified folder.
+pub(crate) fn encode_fnabi<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+    type_folder: &mut TransformTy<'tcx>,
+    options: TypeIdOptions,
+) -> String {
+    let ret = if options.contains(TypeIdOptions::ERASE_RETURN_TYPES) {
+        tcx.types.unit
+    } else {
+        fn_abi.ret.layout.ty.fold_with(type_folder)
+    };
+    let args = if fn_abi.c_variadic {
+        &fn_abi.args[..fn_abi.fixed_count as usize]
+    } else {
+        &fn_abi.args[..]
+    };
+    let params: Vec<_> = args
+        .iter()
+        .filter(|arg| arg.mode != PassMode::Ignore)
+        .map(|arg| arg.layout.ty.fold_with(type_folder))
+        .collect();
+
+    let mut typeid = String::from("?$$A6");
+    typeid.push_str(&encode_fn(
+        tcx,
+        ret,
+        &params,
+        fn_abi.c_variadic,
+        &mut BackRefs::default(),
+        options,
+    ));
+
+    // Encoding suffixes (which are not part of the decoration, but keep the type metadata
+    // identifiers computed with different options distinct, as the Itanium C++ ABI ones)
+    if options.contains(TypeIdOptions::NORMALIZE_INTEGERS) {
+        typeid.push_str(".normalized");
+    } else if options.contains(TypeIdOptions::NORMALIZE_PTR_SIZED_INTEGERS) {
+        typeid.push_str(".ptrsized");
+    }
+    if options.contains(TypeIdOptions::GENERALIZE_POINTERS) {
+        typeid.push_str(".generalized");
+    }
+    if options.contains(TypeIdOptions::GENERALIZE_ARRAYS) {
+        typeid.push_str(".arrays");
+    }
+    if options.contains(TypeIdOptions::ERASE_RETURN_TYPES) {
+        typeid.push_str(".returns");
+    }
+    if let Some(salt) = &tcx.sess.target.cfi_typeid_salt {
+        typeid.push_str(&format!(".salt{salt}"));
+    }
+
+    typeid
+}
@@ -0,0 +1,208 @@
+/// WebAssembly function types (i.e., the signatures WebAssembly engines check `call_indirect`
+/// instructions against) of functions, derived from their FnAbis.
+///
+/// WebAssembly engines already check that the function type of the callee of an indirect call is
+/// identical to the function type expected at the call site, which is coarser than LLVM CFI (e.g.,
+/// all pointers and 32-bit integers are `i32`s) but can't be bypassed. The function types are
+/// derived the way the LLVM WebAssembly backend lowers the LLVM function types rustc declares
+/// functions with, so tooling can compare them with the function types of imported host functions.
+use rustc_middle::ty::layout::LayoutCx;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::call::{ArgAbi, CastTarget, FnAbi, PassMode, Reg, RegKind};
+use rustc_target::abi::{
+    Abi, FieldsShape, HasDataLayout, Integer, Primitive, Scalar, Size, TyAndLayout,
+};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// A WebAssembly value type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValType {
+    I32,
+    I64,
+    F32,
+    F64,
+    V128,
+}
+
+/// A WebAssembly function type.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FuncType {
+    pub params: Vec<ValType>,
+    pub results: Vec<ValType>,
+}
+
+impl fmt::Display for ValType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValType::I32 => "i32",
+            ValType::I64 => "i64",
+            ValType::F32 => "f32",
+            ValType::F64 => "f64",
+            ValType::V128 => "v128",
+        })
+    }
+}
+
+/// Formats the function type in the WebAssembly text format (e.g., `(func (param i32) (result
+/// i64))`).
+impl fmt::Display for FuncType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(func")?;
+        for (keyword, types) in [("param", &self.params), ("result", &self.results)] {
+            if !types.is_empty() {
+                write!(f, " ({keyword}")?;
+                for ty in types {
+                    write!(f, " {ty}")?;
+                }
+                f.write_str(")")?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+/// Returns the value types an integer of the specified size is lowered to (i.e., `i32` for sizes up
+/// to 32 bits, and as many `i64`s as needed otherwise).
+fn int_val_types(size: Size) -> Vec<ValType> {
+    if size.bits() <= 32 {
+        vec![ValType::I32]
+    } else {
+        vec![ValType::I64; size.bits().div_ceil(64) as usize]
+    }
+}
+
+/// Returns the value types a scalar is lowered to.
+fn scalar_val_types(cx: &impl HasDataLayout, scalar: Scalar) -> Vec<ValType> {
+    match scalar.primitive() {
+        Primitive::Int(integer, _) => int_val_types(integer.size()),
+        Primitive::Pointer(_) => int_val_types(cx.data_layout().pointer_size),
+        // WebAssembly has no half precision floating-point type, and its 128-bit floating-point
+        // types are implemented in software
+        Primitive::F16 | Primitive::F32 => vec![ValType::F32],
+        Primitive::F64 => vec![ValType::F64],
+        Primitive::F128 => int_val_types(Integer::I128.size()),
+    }
+}
+
+/// Returns the value types a register is lowered to.
+fn reg_val_types(reg: Reg) -> Vec<ValType> {
+    match reg.kind {
+        RegKind::Integer => int_val_types(reg.size),
+        RegKind::Float if reg.size.bits() <= 32 => vec![ValType::F32],
+        RegKind::Float => vec![ValType::F64],
+        RegKind::Vector => vec![ValType::V128],
+    }
+}
+
+/// Returns the value types the casted value is lowered to (see `CastTarget::llvm_type`).
+fn cast_val_types(cast: &CastTarget) -> Vec<ValType> {
+    let mut val_types: Vec<_> =
+        cast.prefix.iter().flatten().flat_map(|&reg| reg_val_types(reg)).collect();
+    let unit = cast.rest.unit;
+    if unit.size.bytes() > 0 {
+        let count = cast.rest.total.bytes() / unit.size.bytes();
+        for _ in 0..count {
+            val_types.extend(reg_val_types(unit));
+        }
+        let rem = cast.rest.total.bytes() % unit.size.bytes();
+        if rem != 0 {
+            val_types.extend(int_val_types(Size::from_bytes(rem)));
+        }
+    }
+    val_types
+}
+
+/// Returns the value types the leaves of an aggregate passed directly are lowered to (i.e., its
+/// scalars in increasing offset order, as the LLVM WebAssembly backend flattens aggregates).
+fn layout_val_types<'tcx>(
+    cx: &LayoutCx<'tcx, TyCtxt<'tcx>>,
+    layout: TyAndLayout<'tcx>,
+) -> Vec<ValType> {
+    match layout.abi {
+        Abi::Uninhabited => Vec::new(),
+        Abi::Scalar(scalar) => scalar_val_types(cx, scalar),
+        Abi::ScalarPair(a, b) => {
+            let mut val_types = scalar_val_types(cx, a);
+            val_types.extend(scalar_val_types(cx, b));
+            val_types
+        }
+        Abi::Vector { .. } => vec![ValType::V128],
+        // The fields of enums with multiple variants are their tags only
+        Abi::Aggregate { .. } => match layout.fields {
+            FieldsShape::Primitive => Vec::new(),
+            FieldsShape::Array { count, .. } if count > 0 => {
+                let element = layout_val_types(cx, layout.field(cx, 0));
+                (0..count).flat_map(|_| element.iter().copied()).collect()
+            }
+            _ => layout
+                .fields
+                .index_by_increasing_offset()
+                .flat_map(|i| layout_val_types(cx, layout.field(cx, i)))
+                .collect(),
+        },
+    }
+}
+
+/// Returns the value types an argument is lowered to.
+fn arg_val_types<'tcx>(
+    cx: &LayoutCx<'tcx, TyCtxt<'tcx>>,
+    arg: &ArgAbi<'tcx, Ty<'tcx>>,
+) -> Vec<ValType> {
+    let ptr = || int_val_types(cx.data_layout().pointer_size);
+    match &arg.mode {
+        PassMode::Ignore => Vec::new(),
+        PassMode::Direct(_) | PassMode::Pair(..) => layout_val_types(cx, arg.layout),
+        PassMode::Cast { pad_i32, cast } => {
+            let mut val_types = if *pad_i32 { vec![ValType::I32] } else { Vec::new() };
+            val_types.extend(cast_val_types(cast));
+            val_types
+        }
+        // The metadata of unsized arguments (i.e., a length or a vtable pointer) is pointer-sized
+        PassMode::Indirect { meta_attrs: Some(_), .. } => [ptr(), ptr()].concat(),
+        PassMode::Indirect { meta_attrs: None, .. } => ptr(),
+    }
+}
+
+/// Returns the WebAssembly function type of the specified FnAbi (i.e., the function type the LLVM
+/// WebAssembly backend lowers the declaration of a function with this FnAbi to).
+pub fn func_type_for_fnabi<'tcx>(tcx: TyCtxt<'tcx>, fn_abi: &FnAbi<'tcx, Ty<'tcx>>) -> FuncType {
+    let cx = LayoutCx { tcx, param_env: ty::ParamEnv::reveal_all() };
+    let ptr = int_val_types(tcx.data_layout().pointer_size);
+
+    let mut func_type = FuncType::default();
+    match fn_abi.ret.mode {
+        PassMode::Indirect { .. } => func_type.params.extend(&ptr),
+        _ => func_type.results = arg_val_types(&cx, &fn_abi.ret),
+    }
+    // Multiple results are returned through a hidden pointer to the return value unless the
+    // multivalue target feature is enabled
+    if func_type.results.len() > 1 && !tcx.sess.target_features.contains(&sym::multivalue) {
+        func_type.params.extend(&ptr);
+        func_type.results.clear();
+    }
+
+    let fixed_count =
+        if fn_abi.c_variadic { fn_abi.fixed_count as usize } else { fn_abi.args.len() };
+    for arg in &fn_abi.args[..fixed_count] {
+        func_type.params.extend(arg_val_types(&cx, arg));
+    }
+    // Variadic arguments are passed through a pointer to a buffer
+    if fn_abi.c_variadic {
+        func_type.params.extend(&ptr);
+    }
+    func_type
+}
+
+/// Returns the WebAssembly function type of the specified instance.
+pub fn func_type_for_instance<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> FuncType {
+    let fn_abi = tcx
+        .fn_abi_of_instance(tcx.param_env(instance.def_id()).and((instance, ty::List::empty())))
+        .unwrap_or_else(|error| {
+            bug!("func_type_for_instance: couldn't get fn_abi of instance {instance:?}: {error:?}")
+        });
+    func_type_for_fnabi(tcx, fn_abi)
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn test_int_val_types() {
+    assert_eq!(int_val_types(Size::from_bits(8)), [ValType::I32]);
+    assert_eq!(int_val_types(Size::from_bits(32)), [ValType::I32]);
+    assert_eq!(int_val_types(Size::from_bits(64)), [ValType::I64]);
+    assert_eq!(int_val_types(Size::from_bits(128)), [ValType::I64, ValType::I64]);
+}
+
+#[test]
+fn test_reg_val_types() {
+    assert_eq!(reg_val_types(Reg::i32()), [ValType::I32]);
+    assert_eq!(reg_val_types(Reg::i64()), [ValType::I64]);
+    assert_eq!(reg_val_types(Reg::f32()), [ValType::F32]);
+    assert_eq!(reg_val_types(Reg::f64()), [ValType::F64]);
+    assert_eq!(
+        reg_val_types(Reg { kind: RegKind::Vector, size: Size::from_bits(128) }),
+        [ValType::V128]
+    );
+}
+
+#[test]
+fn test_display_func_type() {
+    let func_type = |params: &[ValType], results: &[ValType]| {
+        FuncType { params: params.to_vec(), results: results.to_vec() }.to_string()
+    };
+    assert_eq!(func_type(&[], &[]), "(func)");
+    assert_eq!(func_type(&[ValType::I32, ValType::I64], &[]), "(func (param i32 i64))");
+    assert_eq!(func_type(&[], &[ValType::F32]), "(func (result f32))");
+    assert_eq!(
+        func_type(&[ValType::F64, ValType::V128], &[ValType::I32, ValType::I32]),
+        "(func (param f64 v128) (result i32 i32))"
+    );
+}
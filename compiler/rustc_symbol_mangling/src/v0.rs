@@ -3,10 +3,10 @@
 use rustc_data_structures::intern::Interned;
 use rustc_hir as hir;
 use rustc_hir::def::CtorKind;
-use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_hir::def_id::{CrateNum, DefId, StableCrateId};
 use rustc_hir::definitions::{DefPathData, DisambiguatedDefPathData};
 use rustc_middle::ty::layout::IntegerExt;
-use rustc_middle::ty::print::{Print, PrintError, Printer};
+use rustc_middle::ty::print::{with_no_trimmed_paths, Print, PrintError, Printer};
 use rustc_middle::ty::{
     self, EarlyBinder, FloatTy, Instance, IntTy, ReifyReason, Ty, TyCtxt, TypeVisitable,
     TypeVisitableExt, UintTy,
@@ -25,6 +25,36 @@ pub(super) fn mangle<'tcx>(
     instance: Instance<'tcx>,
     instantiating_crate: Option<CrateNum>,
 ) -> String {
+    mangle_annotated(tcx, instance, instantiating_crate, None).0
+}
+
+/// Mangles `instance` like `mangle`, and renders the symbol followed by a line per production
+/// printed for a path, a generic argument, or a self type, which underlines the production and
+/// describes it.
+pub(super) fn explain<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    instantiating_crate: Option<CrateNum>,
+) -> String {
+    let (symbol, mut annotations) =
+        mangle_annotated(tcx, instance, instantiating_crate, Some(vec![]));
+    // Outer productions come before the ones nested in them.
+    annotations.sort_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)));
+
+    let mut explanation = symbol;
+    for (range, label) in annotations {
+        let (indent, underline) = (" ".repeat(range.start), "^".repeat(range.len()));
+        let _ = write!(explanation, "\n{indent}{underline} {label}");
+    }
+    explanation
+}
+
+fn mangle_annotated<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    instantiating_crate: Option<CrateNum>,
+    annotations: Option<Vec<(Range<usize>, String)>>,
+) -> (String, Vec<(Range<usize>, String)>) {
     let def_id = instance.def_id();
     // FIXME(eddyb) this should ideally not be needed.
     let args = tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), instance.args);
@@ -38,7 +68,9 @@ pub(super) fn mangle<'tcx>(
         consts: FxHashMap::default(),
         binders: vec![],
         out: String::from(prefix),
+        annotations,
     };
+    cx.annotate(0, |_| "v0 prefix".to_string());
 
     // Append `::{shim:...#0}` to shims that can coexist with a non-shim instance.
     let shim_kind = match instance.def {
@@ -55,14 +87,20 @@ pub(super) fn mangle<'tcx>(
     };
 
     if let Some(shim_kind) = shim_kind {
-        cx.path_append_ns(|cx| cx.print_def_path(def_id, args), 'S', 0, shim_kind).unwrap()
+        let start = cx.out.len();
+        cx.path_append_ns(|cx| cx.print_def_path(def_id, args), 'S', 0, shim_kind).unwrap();
+        cx.annotate(start, |_| format!("`{shim_kind}` shim"));
     } else {
         cx.print_def_path(def_id, args).unwrap()
     };
     if let Some(instantiating_crate) = instantiating_crate {
+        let start = cx.out.len();
         cx.print_def_path(instantiating_crate.as_def_id(), &[]).unwrap();
+        cx.annotate(start, |tcx| {
+            format!("instantiating crate `{}`", tcx.crate_name(instantiating_crate))
+        });
     }
-    std::mem::take(&mut cx.out)
+    (std::mem::take(&mut cx.out), cx.annotations.unwrap_or_default())
 }
 
 pub(super) fn mangle_typeid_for_trait_ref<'tcx>(
@@ -78,12 +116,46 @@ pub(super) fn mangle_typeid_for_trait_ref<'tcx>(
         consts: FxHashMap::default(),
         binders: vec![],
         out: String::new(),
+        annotations: None,
     };
     cx.print_def_path(trait_ref.def_id(), &[]).unwrap();
     std::mem::take(&mut cx.out)
 }
 
-struct BinderLevel {
+/// Mangles `item_name` as `__rustc::<item_name>`, where `__rustc` is a crate with the disambiguator
+/// of `crate_id` (see `mangle_internal_symbol`).
+pub(super) fn mangle_internal_symbol<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    crate_id: StableCrateId,
+    item_name: &str,
+) -> String {
+    let prefix = "_R";
+    let mut cx: SymbolMangler<'_> = SymbolMangler {
+        tcx,
+        start_offset: prefix.len(),
+        paths: FxHashMap::default(),
+        types: FxHashMap::default(),
+        consts: FxHashMap::default(),
+        binders: vec![],
+        out: String::from(prefix),
+        annotations: None,
+    };
+    cx.path_append_ns(
+        |cx| {
+            cx.push("C");
+            cx.push_disambiguator(crate_id.as_u64());
+            cx.push_ident("__rustc");
+            Ok(())
+        },
+        'v',
+        0,
+        item_name,
+    )
+    .unwrap();
+    std::mem::take(&mut cx.out)
+}
+
+struct BinderLevel<'tcx> {
     /// The range of distances from the root of what's
     /// being printed, to the lifetimes in a binder.
     /// Specifically, a `BrAnon` lifetime has depth
@@ -95,11 +167,17 @@ struct BinderLevel {
     /// by subtracting the depth of a certain lifetime, from
     /// the innermost depth at its use site.
     lifetime_depths: Range<u32>,
+
+    /// The paths and types (which refer to this binder or to an enclosing one) printed while this
+    /// binder is the innermost one, as they're printed the same way (and so can be referred to by
+    /// backrefs) only until it's exited. The values are start positions in `out`, in bytes.
+    paths: FxHashMap<(DefId, &'tcx [GenericArg<'tcx>]), usize>,
+    types: FxHashMap<Ty<'tcx>, usize>,
 }
 
 struct SymbolMangler<'tcx> {
     tcx: TyCtxt<'tcx>,
-    binders: Vec<BinderLevel>,
+    binders: Vec<BinderLevel<'tcx>>,
     out: String,
 
     /// The length of the prefix in `out` (e.g. 2 for `_R`).
@@ -108,6 +186,10 @@ struct SymbolMangler<'tcx> {
     paths: FxHashMap<(DefId, &'tcx [GenericArg<'tcx>]), usize>,
     types: FxHashMap<Ty<'tcx>, usize>,
     consts: FxHashMap<ty::Const<'tcx>, usize>,
+
+    /// When explaining the symbol (see `explain`), the ranges of `out` printed for each annotated
+    /// production, and their descriptions.
+    annotations: Option<Vec<(Range<usize>, String)>>,
 }
 
 impl<'tcx> SymbolMangler<'tcx> {
@@ -115,6 +197,17 @@ fn push(&mut self, s: &str) {
         self.out.push_str(s);
     }
 
+    /// Records that `out[start..]` was printed for the production described by `label`, if the
+    /// symbol is being explained.
+    fn annotate(&mut self, start: usize, label: impl FnOnce(TyCtxt<'tcx>) -> String) {
+        let end = self.out.len();
+        if let Some(annotations) = &mut self.annotations
+            && start < end
+        {
+            annotations.push((start..end, label(self.tcx)));
+        }
+    }
+
     /// Push a `_`-terminated base 62 integer, using the format
     /// specified in the RFC as `<base-62-number>`, that is:
     /// * `x = 0` is encoded as just the `"_"` terminator
@@ -185,12 +278,38 @@ fn in_binder<T>(
         self.push_opt_integer_62("G", lifetimes as u64);
         lifetime_depths.end += lifetimes;
 
-        self.binders.push(BinderLevel { lifetime_depths });
+        self.binders.push(BinderLevel {
+            lifetime_depths,
+            paths: FxHashMap::default(),
+            types: FxHashMap::default(),
+        });
         print_value(self, value.as_ref().skip_binder())?;
         self.binders.pop();
 
         Ok(())
     }
+
+    /// Prints the tuple elements a pattern is mangled as, mirroring its encoding in CFI type
+    /// metadata identifiers (i.e., `u3patI<element-type><pattern>E`). A range pattern is
+    /// mangled as arrays of `()` whose lengths are its start, its end, and whether its end is
+    /// included, with placeholders (i.e., `_`) for the bounds it doesn't have.
+    fn print_pat(&mut self, pat: ty::Pattern<'tcx>) -> Result<(), PrintError> {
+        match *pat {
+            ty::PatternKind::Range { start, end, include_end } => {
+                for bound in [start, end] {
+                    self.push("Au");
+                    match bound {
+                        Some(ct) => self.print_const(ct)?,
+                        // Never cached (single-character).
+                        None => self.push("p"),
+                    }
+                }
+                self.push("Au");
+                self.print_const(ty::Const::from_bool(self.tcx, include_end))?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<'tcx> Printer<'tcx> for SymbolMangler<'tcx> {
@@ -203,17 +322,28 @@ fn print_def_path(
         def_id: DefId,
         args: &'tcx [GenericArg<'tcx>],
     ) -> Result<(), PrintError> {
-        if let Some(&i) = self.paths.get(&(def_id, args)) {
-            return self.print_backref(i);
-        }
+        let escaping = args.iter().any(|k| k.has_escaping_bound_vars());
+        let cached = if escaping {
+            self.binders.last().and_then(|binder| binder.paths.get(&(def_id, args)))
+        } else {
+            self.paths.get(&(def_id, args))
+        };
         let start = self.out.len();
+        if let Some(&i) = cached {
+            self.print_backref(i)?;
+            self.annotate(start, |tcx| format!("backref to {}", describe_path(tcx, def_id, args)));
+            return Ok(());
+        }
 
         self.default_print_def_path(def_id, args)?;
+        self.annotate(start, |tcx| describe_path(tcx, def_id, args));
 
-        // Only cache paths that do not refer to an enclosing
-        // binder (which would change depending on context).
-        if !args.iter().any(|k| k.has_escaping_bound_vars()) {
+        // Paths that refer to an enclosing binder (which would change
+        // depending on context) are only cached in the innermost binder.
+        if !escaping {
             self.paths.insert((def_id, args), start);
+        } else if let Some(binder) = self.binders.last_mut() {
+            binder.paths.insert((def_id, args), start);
         }
         Ok(())
     }
@@ -268,7 +398,9 @@ fn print_impl_path(
             self.print_def_path(parent_def_id, &[])?;
         }
 
+        let start = self.out.len();
         self_ty.print(self)?;
+        self.annotate(start, |_| format!("self type `{self_ty}`"));
 
         if let Some(trait_ref) = impl_trait_ref {
             self.print_def_path(trait_ref.def_id, trait_ref.args)?;
@@ -335,7 +467,13 @@ fn print_type(&mut self, ty: Ty<'tcx>) -> Result<(), PrintError> {
             return Ok(());
         }
 
-        if let Some(&i) = self.types.get(&ty) {
+        let escaping = ty.has_escaping_bound_vars();
+        let cached = if escaping {
+            self.binders.last().and_then(|binder| binder.types.get(&ty))
+        } else {
+            self.types.get(&ty)
+        };
+        if let Some(&i) = cached {
             return self.print_backref(i);
         }
         let start = self.out.len();
@@ -371,24 +509,16 @@ fn print_type(&mut self, ty: Ty<'tcx>) -> Result<(), PrintError> {
                 ty.print(self)?;
             }
 
-            ty::Pat(ty, pat) => match *pat {
-                ty::PatternKind::Range { start, end, include_end } => {
-                    let consts = [
-                        start.unwrap_or(self.tcx.consts.unit),
-                        end.unwrap_or(self.tcx.consts.unit),
-                        ty::Const::from_bool(self.tcx, include_end).into(),
-                    ];
-                    // HACK: Represent as tuple until we have something better.
-                    // HACK: constants are used in arrays, even if the types don't match.
-                    self.push("T");
-                    ty.print(self)?;
-                    for ct in consts {
-                        Ty::new_array_with_const_len(self.tcx, self.tcx.types.unit, ct)
-                            .print(self)?;
-                    }
-                    self.push("E");
-                }
-            },
+            // HACK(pattern_types): v0 has no production for pattern types (yet), so they're
+            // mangled as tuples of their base types and their patterns, which existing
+            // demanglers can demangle (e.g., `(u32, [(); 1u32], [(); _], [(); false])` for
+            // `pattern_type!(u32 is 1..)`).
+            ty::Pat(ty, pat) => {
+                self.push("T");
+                ty.print(self)?;
+                self.print_pat(pat)?;
+                self.push("E");
+            }
 
             ty::Array(ty, len) => {
                 self.push("A");
@@ -451,14 +581,21 @@ fn print_type(&mut self, ty: Ty<'tcx>) -> Result<(), PrintError> {
                 })?;
             }
 
+            // `dyn*` trait objects are mangled as tuples of the `dyn` trait objects with the same
+            // bounds and of a placeholder (i.e., `(dyn Trait, _)`), which no other type can be
+            // mangled as (as all but the last fields of tuples are sized), and which existing
+            // demanglers can demangle.
             ty::Dynamic(predicates, r, kind) => {
-                self.push(match kind {
-                    ty::Dyn => "D",
-                    // FIXME(dyn-star): need to update v0 mangling docs
-                    ty::DynStar => "D*",
-                });
+                let dyn_star = matches!(kind, ty::DynStar);
+                if dyn_star {
+                    self.push("T");
+                }
+                self.push("D");
                 self.print_dyn_existential(predicates)?;
                 r.print(self)?;
+                if dyn_star {
+                    self.push("pE");
+                }
             }
 
             ty::Alias(ty::Inherent, _) => bug!("symbol_names: unexpected inherent projection"),
@@ -466,10 +603,12 @@ fn print_type(&mut self, ty: Ty<'tcx>) -> Result<(), PrintError> {
             ty::CoroutineWitness(..) => bug!("symbol_names: unexpected `CoroutineWitness`"),
         }
 
-        // Only cache types that do not refer to an enclosing
-        // binder (which would change depending on context).
-        if !ty.has_escaping_bound_vars() {
+        // Types that refer to an enclosing binder (which would change
+        // depending on context) are only cached in the innermost binder.
+        if !escaping {
             self.types.insert(ty, start);
+        } else if let Some(binder) = self.binders.last_mut() {
+            binder.types.insert(ty, start);
         }
         Ok(())
     }
@@ -588,54 +727,54 @@ fn print_const(&mut self, ct: ty::Const<'tcx>) -> Result<(), PrintError> {
                 let _ = write!(self.out, "{bits:x}_");
             }
 
-            // FIXME(valtrees): Remove the special case for `str`
-            // here and fully support unsized constants.
-            ty::Ref(_, inner_ty, mutbl) => {
+            // Float constants (which can't be the values of const parameters yet, but are mangled
+            // ahead of time behind `adt_const_params`) are mangled as the bits of their IEEE 754
+            // representation in hex, as integers are (e.g., `f3f800000_` for `1.0f32`), so that
+            // every value (including NaNs and signed zeros) has its own symbol.
+            ty::Float(_) if self.tcx.features().adt_const_params => {
+                ty.print(self)?;
+
+                let bits = ct.eval_bits(self.tcx, ty::ParamEnv::reveal_all());
+                let _ = write!(self.out, "{bits:x}_");
+            }
+
+            // Unsized `str` constants (behind references, which are transparent in valtrees) are
+            // mangled as their hex-encoded UTF-8 bytes.
+            ty::Str => match ct.kind() {
+                ty::ConstKind::Value(valtree) => {
+                    let bytes: Vec<u8> = valtree
+                        .unwrap_branch()
+                        .iter()
+                        .map(|byte| byte.unwrap_leaf().try_to_u8().unwrap())
+                        .collect();
+                    let s = std::str::from_utf8(&bytes).expect("non utf8 str from MIR interpreter");
+
+                    self.push("e");
+
+                    // FIXME(eddyb) use a specialized hex-encoding loop.
+                    for byte in s.bytes() {
+                        let _ = write!(self.out, "{byte:02x}");
+                    }
+
+                    self.push("_");
+                }
+
+                _ => {
+                    bug!("symbol_names: unsupported `str` constant: {:?}", ct);
+                }
+            },
+
+            ty::Ref(_, _, mutbl) => {
                 self.push(match mutbl {
                     hir::Mutability::Not => "R",
                     hir::Mutability::Mut => "Q",
                 });
 
-                match inner_ty.kind() {
-                    ty::Str if mutbl.is_not() => {
-                        match ct.kind() {
-                            ty::ConstKind::Value(valtree) => {
-                                let slice =
-                                    valtree.try_to_raw_bytes(self.tcx(), ty).unwrap_or_else(|| {
-                                        bug!(
-                                        "expected to get raw bytes from valtree {:?} for type {:}",
-                                        valtree, ty
-                                    )
-                                    });
-                                let s = std::str::from_utf8(slice)
-                                    .expect("non utf8 str from MIR interpreter");
-
-                                self.push("e");
-
-                                // FIXME(eddyb) use a specialized hex-encoding loop.
-                                for byte in s.bytes() {
-                                    let _ = write!(self.out, "{byte:02x}");
-                                }
-
-                                self.push("_");
-                            }
-
-                            _ => {
-                                bug!("symbol_names: unsupported `&str` constant: {:?}", ct);
-                            }
-                        }
-                    }
-                    _ => {
-                        let pointee_ty = ct
-                            .ty()
-                            .builtin_deref(true)
-                            .expect("tried to dereference on non-ptr type")
-                            .ty;
-                        // FIXME(const_generics): add an assert that we only do this for valtrees.
-                        let dereferenced_const = self.tcx.mk_ct_from_kind(ct.kind(), pointee_ty);
-                        dereferenced_const.print(self)?;
-                    }
-                }
+                let pointee_ty =
+                    ty.builtin_deref(true).expect("tried to dereference on non-ptr type").ty;
+                // FIXME(const_generics): add an assert that we only do this for valtrees.
+                let dereferenced_const = self.tcx.mk_ct_from_kind(ct.kind(), pointee_ty);
+                dereferenced_const.print(self)?;
             }
 
             ty::Array(..) | ty::Tuple(..) | ty::Adt(..) | ty::Slice(_) => {
@@ -729,7 +868,9 @@ fn path_qualified(
         let trait_ref = trait_ref.unwrap();
 
         self.push("Y");
+        let start = self.out.len();
         self_ty.print(self)?;
+        self.annotate(start, |_| format!("self type `{self_ty}`"));
         self.print_def_path(trait_ref.def_id, trait_ref.args)
     }
 
@@ -806,6 +947,7 @@ fn path_generic_args(
         self.push("I");
         print_prefix(self)?;
         for arg in args {
+            let start = self.out.len();
             match arg.unpack() {
                 GenericArgKind::Lifetime(lt) => {
                     lt.print(self)?;
@@ -818,12 +960,22 @@ fn path_generic_args(
                     c.print(self)?;
                 }
             }
+            self.annotate(start, |_| format!("generic argument `{arg}`"));
         }
         self.push("E");
 
         Ok(())
     }
 }
+/// Describes the path printed for `def_id` and `args` in an explanation (see `explain`).
+fn describe_path<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId, args: &'tcx [GenericArg<'tcx>]) -> String {
+    if def_id.is_crate_root() {
+        return format!("crate root `{}`", tcx.crate_name(def_id.krate));
+    }
+    let path = with_no_trimmed_paths!(tcx.def_path_str_with_args(def_id, args));
+    format!("path `{path}`")
+}
+
 /// Push a `_`-terminated base 62 integer, using the format
 /// specified in the RFC as `<base-62-number>`, that is:
 /// * `x = 0` is encoded as just the `"_"` terminator
@@ -836,12 +988,6 @@ pub(crate) fn push_integer_62(x: u64, output: &mut String) {
     output.push('_');
 }
 
-pub(crate) fn encode_integer_62(x: u64) -> String {
-    let mut output = String::new();
-    push_integer_62(x, &mut output);
-    output
-}
-
 pub(crate) fn push_ident(ident: &str, output: &mut String) {
     let mut use_punycode = false;
     for b in ident.bytes() {
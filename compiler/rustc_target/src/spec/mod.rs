@@ -2199,6 +2199,17 @@ pub struct TargetOptions {
     /// distributed with the target, the sanitizer should still appear in this list for the target.
     pub supported_sanitizers: SanitizerSet,
 
+    /// The seed KCFI type metadata identifiers are hashed with, if any. Clang has no equivalent, so
+    /// a nonzero seed makes them incompatible with the ones of Clang-built code. The type metadata
+    /// identifiers hashed include the `cfi_typeid_salt`, if any.
+    ///
+    /// Can be overridden with `-Zsanitizer-kcfi-seed`.
+    pub kcfi_seed: Option<u64>,
+
+    /// The salt CFI and KCFI type metadata identifiers are suffixed with (as `.salt<salt>`), if
+    /// any, so functions of builds with different salts are incompatible.
+    pub cfi_typeid_salt: Option<StaticCow<str>>,
+
     /// If present it's a default value to use for adjusting the C ABI.
     pub default_adjusted_cabi: Option<Abi>,
 
@@ -2222,6 +2233,16 @@ pub struct TargetOptions {
 
     /// Whether the target supports XRay instrumentation.
     pub supports_xray: bool,
+
+    /// The prefix the linker-visible names of symbols are decorated with (e.g., `_` on 32-bit x86
+    /// Windows). Defaults to that of the architecture on Windows and to none elsewhere, and may
+    /// not be set on Mach-O targets, whose `_` prefix the `object` crate and LLVM add themselves.
+    pub symbol_prefix: Option<StaticCow<str>>,
+
+    /// Whether the linker-visible names of `stdcall`, `fastcall` and `vectorcall` functions are
+    /// decorated with the size of their arguments (e.g., `_f@8`, `@f@8` and `f@@8`).
+    /// Defaults to true on x86, x86_64 and Arm64EC Windows and to false elsewhere.
+    pub call_conv_symbol_decoration: Option<bool>,
 }
 
 /// Add arguments for the given flavor and also for its "twin" flavors
@@ -2433,6 +2454,8 @@ fn default() -> TargetOptions {
             // `Off` is supported by default, but targets can remove this manually, e.g. Windows.
             supported_split_debuginfo: Cow::Borrowed(&[SplitDebuginfo::Off]),
             supported_sanitizers: SanitizerSet::empty(),
+            kcfi_seed: None,
+            cfi_typeid_salt: None,
             default_adjusted_cabi: None,
             c_enum_min_bits: None,
             generate_arange_section: true,
@@ -2440,6 +2463,8 @@ fn default() -> TargetOptions {
             entry_name: "main".into(),
             entry_abi: Conv::C,
             supports_xray: false,
+            symbol_prefix: None,
+            call_conv_symbol_decoration: None,
         }
     }
 }
@@ -2574,6 +2599,27 @@ pub fn max_atomic_width(&self) -> u64 {
         self.max_atomic_width.unwrap_or_else(|| self.pointer_width.into())
     }
 
+    /// The prefix the linker-visible names of symbols are decorated with.
+    pub fn symbol_prefix(&self) -> &str {
+        match &self.symbol_prefix {
+            Some(prefix) => prefix,
+            None if !self.is_like_windows => "",
+            None => match &self.arch[..] {
+                "x86" => "_",
+                "arm64ec" => "#",
+                _ => "",
+            },
+        }
+    }
+
+    /// Whether the linker-visible names of `stdcall`, `fastcall` and `vectorcall` functions are
+    /// decorated with the size of their arguments.
+    pub fn call_conv_symbol_decoration(&self) -> bool {
+        self.call_conv_symbol_decoration.unwrap_or_else(|| {
+            self.is_like_windows && ["x86", "x86_64", "arm64ec"].contains(&&self.arch[..])
+        })
+    }
+
     /// Loads a target descriptor from a JSON object.
     pub fn from_json(obj: Json) -> Result<(Target, TargetWarnings), String> {
         // While ugly, this code must remain this way to retain
@@ -3164,12 +3210,33 @@ macro_rules! key {
         key!(split_debuginfo, SplitDebuginfo)?;
         key!(supported_split_debuginfo, fallible_list)?;
         key!(supported_sanitizers, SanitizerSet)?;
+        key!(kcfi_seed, Option<u64>);
+        key!(cfi_typeid_salt, optional);
         key!(default_adjusted_cabi, Option<Abi>)?;
         key!(generate_arange_section, bool);
         key!(supports_stack_protector, bool);
         key!(entry_name);
         key!(entry_abi, Conv)?;
         key!(supports_xray, bool);
+        key!(symbol_prefix, optional);
+        key!(call_conv_symbol_decoration, Option<bool>);
+
+        // The salt is part of the type metadata identifiers, so it must not contain characters with a
+        // meaning in them.
+        if let Some(salt) = &base.cfi_typeid_salt {
+            if salt.is_empty() || !salt.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+                return Err(format!(
+                    "cfi-typeid-salt must only contain ASCII alphanumeric characters and \
+                     underscores, but is `{salt}`"
+                ));
+            }
+        }
+
+        // Mach-O has a global `_` prefix, which the `object` crate and LLVM add themselves, so
+        // symbols would be prefixed twice.
+        if base.is_like_osx && base.symbol_prefix.as_deref().is_some_and(|p| !p.is_empty()) {
+            return Err("symbol-prefix may not be set for Mach-O targets".into());
+        }
 
         if base.is_builtin {
             // This can cause unfortunate ICEs later down the line.
@@ -3421,12 +3488,16 @@ macro_rules! target_option_val {
         target_option_val!(split_debuginfo);
         target_option_val!(supported_split_debuginfo);
         target_option_val!(supported_sanitizers);
+        target_option_val!(kcfi_seed);
+        target_option_val!(cfi_typeid_salt);
         target_option_val!(c_enum_min_bits);
         target_option_val!(generate_arange_section);
         target_option_val!(supports_stack_protector);
         target_option_val!(entry_name);
         target_option_val!(entry_abi);
         target_option_val!(supports_xray);
+        target_option_val!(symbol_prefix);
+        target_option_val!(call_conv_symbol_decoration);
 
         if let Some(abi) = self.default_adjusted_cabi {
             d.insert("default-adjusted-cabi".into(), Abi::name(abi).to_json());
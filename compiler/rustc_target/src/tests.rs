@@ -1,4 +1,4 @@
-use crate::spec::Target;
+use crate::spec::{Target, TargetTriple};
 
 #[test]
 fn report_unused_fields() {
@@ -55,3 +55,47 @@ fn no_warnings_for_valid_target() {
     let warnings = Target::from_json(json).unwrap().1;
     assert_eq!(warnings.warning_messages().len(), 0);
 }
+
+#[test]
+fn symbol_decoration() {
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "x86",
+        "data-layout": "e-m:x-p:32:32-p270:32:32-p271:32:32-p272:64:64-i64:64-i128:128-f80:32-n8:16:32-a:0:32-S32",
+        "llvm-target": "i686-unknown-none",
+        "target-pointer-width": "32",
+        "symbol-prefix": "$",
+        "call-conv-symbol-decoration": true
+    }
+    "#,
+    )
+    .unwrap();
+    let (target, warnings) = Target::from_json(json).unwrap();
+    assert_eq!(warnings.warning_messages().len(), 0);
+    assert_eq!(target.symbol_prefix(), "$");
+    assert!(target.call_conv_symbol_decoration());
+
+    let target = Target::expect_builtin(&TargetTriple::from_triple("i686-pc-windows-msvc"));
+    assert_eq!(target.symbol_prefix(), "_");
+    assert!(target.call_conv_symbol_decoration());
+
+    let target = Target::expect_builtin(&TargetTriple::from_triple("i686-unknown-linux-gnu"));
+    assert_eq!(target.symbol_prefix(), "");
+    assert!(!target.call_conv_symbol_decoration());
+
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "aarch64",
+        "data-layout": "e-m:o-i64:64-i128:128-n32:64-S128",
+        "llvm-target": "aarch64-apple-macosx",
+        "target-pointer-width": "64",
+        "is-like-osx": true,
+        "symbol-prefix": "_"
+    }
+    "#,
+    )
+    .unwrap();
+    assert!(Target::from_json(json).is_err());
+}
@@ -146,8 +146,10 @@ unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::
 
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`. Note that
 /// this is only inline(never) when backtraces in std are enabled, otherwise
-/// it's fine to optimize away.
+/// it's fine to optimize away. Its symbol is never hashed, as it is looked up
+/// by name.
 #[cfg_attr(feature = "backtrace", inline(never))]
+#[cfg_attr(not(bootstrap), rustc_unhashed_symbol)]
 pub fn __rust_begin_short_backtrace<F, T>(f: F) -> T
 where
     F: FnOnce() -> T,
@@ -162,8 +164,10 @@ pub fn __rust_begin_short_backtrace<F, T>(f: F) -> T
 
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`. Note that
 /// this is only inline(never) when backtraces in std are enabled, otherwise
-/// it's fine to optimize away.
+/// it's fine to optimize away. Its symbol is never hashed, as it is looked up
+/// by name.
 #[cfg_attr(feature = "backtrace", inline(never))]
+#[cfg_attr(not(bootstrap), rustc_unhashed_symbol)]
 pub fn __rust_end_short_backtrace<F, T>(f: F) -> T
 where
     F: FnOnce() -> T,
@@ -45,6 +45,8 @@
     "tests/ui/proc-macro/auxiliary/included-file.txt", // more include
     "tests/ui/invalid/foo.natvis.xml", // sample debugger visualizer
     "tests/ui/sanitizer/dataflow-abilist.txt", // dataflow sanitizer ABI list file
+    "tests/ui/sanitizer/cfi-typeid-stability.txt", // type metadata identifier snapshot
+    "tests/ui/sanitizer/cfi-typeid-stability-mismatch.txt", // type metadata identifier snapshot
     "tests/ui/shell-argfiles/shell-argfiles.args", // passing args via a file
     "tests/ui/shell-argfiles/shell-argfiles-badquotes.args", // passing args via a file
     "tests/ui/shell-argfiles/shell-argfiles-via-argfile-shell.args", // passing args via a file
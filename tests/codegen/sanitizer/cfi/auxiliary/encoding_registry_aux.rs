@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+#![feature(cfi_encoding)]
+#![cfi_encodings(crate::Handle = "6handle")]
+
+pub struct Handle(pub i32);
+
+pub struct Other(pub i32);
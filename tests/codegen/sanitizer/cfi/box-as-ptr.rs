@@ -0,0 +1,21 @@
+// Verifies that boxes are encoded as raw pointers in functions with the C calling convention with
+// `-Zsanitizer-cfi-box-as-ptr`.
+//
+//@ revisions: DEFAULT BOX
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [BOX] compile-flags: -Zsanitizer-cfi-box-as-ptr
+
+#![crate_type="lib"]
+
+pub extern "C" fn foo(_: Box<i32>) { }
+// CHECK: define{{.*}}foo{{.*}}!type !{{[0-9]+}}
+pub extern "C" fn bar(_: Box<[i32]>) { }
+// CHECK: define{{.*}}bar{{.*}}!type !{{[0-9]+}}
+pub fn baz(_: Box<i32>) { }
+// CHECK: define{{.*}}baz{{.*}}!type !{{[0-9]+}}
+
+// DEFAULT-NOT: !{i64 0, !"_ZTSFvPu3i32E"}
+// BOX: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPu3i32E"}
+// BOX: !{{[0-9]+}} = !{i64 0, !"_ZTSFv{{.*}}5boxed3BoxIu5sliceIu3i32E{{.*}}E"}
+// BOX: !{{[0-9]+}} = !{i64 0, !"_ZTSFv{{.*}}5boxed3BoxIu3i32{{.*}}E"}
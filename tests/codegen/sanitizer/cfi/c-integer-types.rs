@@ -0,0 +1,20 @@
+// Verifies that integer types are encoded as the C integer types of the libc typedefs in
+// functions with the C calling convention with `-Zsanitizer-cfi-c-integer-types`.
+//
+//@ revisions: DEFAULT INTEGERS
+//@ only-x86_64
+//@ only-linux
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [INTEGERS] compile-flags: -Zsanitizer-cfi-c-integer-types
+
+#![crate_type="lib"]
+
+pub extern "C" fn foo(_: usize, _: isize, _: *const u64, _: i32, _: u8) -> i16 { 0 }
+// CHECK: define{{.*}}foo{{.*}}!type ![[TYPE0:[0-9]+]]
+pub fn bar(_: usize) { }
+// CHECK: define{{.*}}bar{{.*}}!type ![[TYPE1:[0-9]+]]
+
+// DEFAULT: ![[TYPE0]] = !{i64 0, !"_ZTSFu3i16u5usizeu5isizePKu3u64u3i32u2u8E"}
+// INTEGERS: ![[TYPE0]] = !{i64 0, !"_ZTSFsmlPKmihE"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvu5usizeE"}
@@ -0,0 +1,20 @@
+// Verifies that references are encoded as C++ references with `-Zsanitizer-cfi-cxx-references`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-cxx-references -Copt-level=0
+
+#![crate_type="lib"]
+
+pub fn foo0(_: &i32) { }
+// CHECK: define{{.*}}foo0{{.*}}!type !{{[0-9]+}}
+pub fn foo1(_: &mut i32, _: &i32) { }
+// CHECK: define{{.*}}foo1{{.*}}!type !{{[0-9]+}}
+pub fn foo2(_: &i32, _: &mut i32, _: &mut i32) { }
+// CHECK: define{{.*}}foo2{{.*}}!type !{{[0-9]+}}
+pub fn foo3(_: &str, _: *mut str) { }
+// CHECK: define{{.*}}foo3{{.*}}!type !{{[0-9]+}}
+
+// CHECK: !{{[0-9]+}} = !{i64 0, !"_ZTSFvRKu3i32E"}
+// CHECK: !{{[0-9]+}} = !{i64 0, !"_ZTSFvRu3i32RKS_E"}
+// CHECK: !{{[0-9]+}} = !{i64 0, !"_ZTSFvRKu3i32RS_S2_E"}
+// CHECK: !{{[0-9]+}} = !{i64 0, !"_ZTSFvRKu3strPS_E"}
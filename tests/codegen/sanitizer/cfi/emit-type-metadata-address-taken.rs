@@ -0,0 +1,28 @@
+// Verifies that type metadata identifiers are only emitted for functions that may be called
+// indirectly, unless `-Zcfi-eager-typeids` is passed.
+//
+//@ revisions: LAZY EAGER
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Zsanitizer=cfi -Ctarget-feature=-crt-static
+//@ [EAGER] compile-flags: -Zcfi-eager-typeids
+
+#![crate_type="lib"]
+
+#[inline(never)]
+fn called_directly(x: i32) -> i32 {
+    // LAZY-DAG: define{{.*}}15called_directly{{[^!]*}} {
+    // EAGER-DAG: define{{.*}}15called_directly{{.*}}!type !{{[0-9]+}}
+    x
+}
+
+#[inline(never)]
+fn address_taken(x: i32) -> i32 {
+    // CHECK-DAG: define{{.*}}13address_taken{{.*}}!type !{{[0-9]+}}
+    x
+}
+
+pub fn foo(x: i32) -> i32 {
+    // CHECK-DAG: define{{.*}}3foo{{.*}}!type !{{[0-9]+}}
+    let f: fn(i32) -> i32 = address_taken;
+    called_directly(x) + f(x)
+}
@@ -0,0 +1,38 @@
+// Verifies that types given the encodings of other types with the cfi_encoding attribute are
+// encoded as these types.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type="lib"]
+#![feature(cfi_encoding, extern_types)]
+
+#[cfi_encoding = "3Foo"]
+pub struct Type1(i32);
+
+#[cfi_encoding(same_as = "crate::Type1")]
+pub struct Type2(i32);
+
+#[cfi_encoding(same_as = "crate::Type2")]
+pub struct Type3(i32);
+
+pub type Alias = i64;
+
+#[cfi_encoding(same_as = "crate::Alias")]
+pub struct Type4(i64);
+
+extern {
+    #[cfi_encoding(same_as = "crate::Type1")]
+    type Type5;
+}
+
+pub fn foo0(_: Type1, _: Type2, _: Type3) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo1(_: Type4, _: i64) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE1:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo2(_: *const Type5) { }
+// CHECK: define{{.*}}foo2{{.*}}!type ![[TYPE2:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFv3FooS_S_E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvu3i64S_E"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvPK3FooE"}
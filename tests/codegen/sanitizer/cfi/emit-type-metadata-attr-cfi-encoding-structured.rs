@@ -0,0 +1,50 @@
+// Verifies that user-defined CFI encodings composed from C++ class names are emitted.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type="lib"]
+#![feature(cfi_encoding, extern_types)]
+
+#[cfi_encoding(name = "Foo")]
+pub struct Type1(i32);
+
+#[cfi_encoding(namespace = "ns", name = "Bar")]
+pub struct Type2(i32);
+
+extern {
+    #[cfi_encoding(namespace = "ns", name = "Baz")]
+    type Type3;
+}
+
+#[cfi_encoding(namespace = "std", name = "Qux")]
+pub struct Type4(i32);
+
+#[cfi_encoding(namespace = "ns", name = "Vec", template_args = "generic")]
+pub struct Type5<T>(T);
+
+#[cfi_encoding(namespace = "a::b", name = "C", template_args = "none")]
+pub struct Type6<T>(T);
+
+pub fn foo0(_: Type1, _: Type1) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo1(_: Type2, _: Type2) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE1:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo2(_: Type2, _: *mut Type3) { }
+// CHECK: define{{.*}}foo2{{.*}}!type ![[TYPE2:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo3(_: Type4, _: Type4) { }
+// CHECK: define{{.*}}foo3{{.*}}!type ![[TYPE3:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo4(_: Type5<i32>, _: Type5<i32>) { }
+// CHECK: define{{.*}}foo4{{.*}}!type ![[TYPE4:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo5(_: Type5<i32>, _: Type5<u8>) { }
+// CHECK: define{{.*}}foo5{{.*}}!type ![[TYPE5:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo6(_: Type6<i32>, _: Type6<u8>) { }
+// CHECK: define{{.*}}foo6{{.*}}!type ![[TYPE6:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFv3FooS_E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvN2ns3BarES0_E"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvN2ns3BarEPNS_3BazEE"}
+// CHECK: ![[TYPE3]] = !{i64 0, !"_ZTSFvSt3QuxS_E"}
+// CHECK: ![[TYPE4]] = !{i64 0, !"_ZTSFvN2ns3VecIu3i32EES2_E"}
+// CHECK: ![[TYPE5]] = !{i64 0, !"_ZTSFvN2ns3VecIu3i32EENS0_Iu2u8EEE"}
+// CHECK: ![[TYPE6]] = !{i64 0, !"_ZTSFvN1a1b1CES1_E"}
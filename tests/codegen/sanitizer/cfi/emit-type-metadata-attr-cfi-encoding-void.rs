@@ -0,0 +1,29 @@
+// Verifies that types marked as void-equivalent with the cfi_encoding attribute are encoded as
+// `core::ffi::c_void`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type="lib"]
+#![feature(cfi_encoding, extern_types)]
+#![allow(non_camel_case_types)]
+
+#[cfi_encoding(transform = "void")]
+#[repr(u8)]
+pub enum c_void {
+    __variant1,
+    __variant2,
+}
+
+extern {
+    #[cfi_encoding(transform = "void")]
+    type Opaque;
+}
+
+pub fn foo0(_: *mut c_void, _: *const c_void) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo1(_: *mut Opaque, _: *mut core::ffi::c_void) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE1:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvPvPKvE"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvPvS_E"}
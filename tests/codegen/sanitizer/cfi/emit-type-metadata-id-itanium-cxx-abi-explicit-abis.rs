@@ -0,0 +1,36 @@
+// Verifies that type metadata identifiers of functions with the "efiapi", "win64", and "sysv64"
+// calling conventions are emitted as for functions with the "C" calling convention (i.e., as Clang
+// emits them, since it doesn't encode these calling conventions).
+//
+//@ needs-sanitizer-cfi
+//@ only-x86_64
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Ctarget-feature=-crt-static -Zsanitizer=cfi
+
+#![crate_type="lib"]
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub extern "C" fn foo0(_: Point, _: *mut Point) { }
+// CHECK: define{{.*}}4foo0{{.*}}!type ![[TYPE0:[0-9]+]]
+pub extern "efiapi" fn foo1(_: Point, _: *mut Point) { }
+// CHECK: define{{.*}}4foo1{{.*}}!type ![[TYPE0]]
+pub extern "win64" fn foo2(_: Point, _: *mut Point) { }
+// CHECK: define{{.*}}4foo2{{.*}}!type ![[TYPE0]]
+pub extern "sysv64" fn foo3(_: Point, _: *mut Point) { }
+// CHECK: define{{.*}}4foo3{{.*}}!type ![[TYPE0]]
+pub extern "efiapi" fn foo4(_: extern "efiapi" fn(Point)) { }
+// CHECK: define{{.*}}4foo4{{.*}}!type ![[TYPE1:[0-9]+]]
+pub extern "C" fn foo5(_: extern "win64" fn(Point)) { }
+// CHECK: define{{.*}}4foo5{{.*}}!type ![[TYPE1]]
+pub extern "sysv64" fn foo6(_: extern "C" fn(Point)) { }
+// CHECK: define{{.*}}4foo6{{.*}}!type ![[TYPE1]]
+pub fn foo7(_: Point) { }
+// CHECK: define{{.*}}4foo7{{.*}}!type ![[TYPE2:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFv5PointPS_E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvPFv5PointEE"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5PointE"}
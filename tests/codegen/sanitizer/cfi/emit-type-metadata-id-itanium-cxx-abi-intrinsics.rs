@@ -0,0 +1,38 @@
+// Verifies that type metadata identifiers of the fallback bodies of intrinsics and of functions that
+// wrap intrinsics (e.g., `transmute`) are emitted as for any other function with the same signature
+// (i.e., as callers of pointers to these functions compute them).
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Zsanitizer=cfi -Ctarget-feature=-crt-static
+
+#![crate_type="lib"]
+#![feature(rustc_attrs)]
+
+#[rustc_intrinsic]
+pub unsafe fn vtable_size(_ptr: *const ()) -> usize {
+    // CHECK-LABEL: define{{.*}}11vtable_size{{.*}}!type ![[TYPE1:[0-9]+]]
+    0
+}
+
+pub unsafe fn not_an_intrinsic(_ptr: *const ()) -> usize {
+    // CHECK-LABEL: define{{.*}}16not_an_intrinsic{{.*}}!type ![[TYPE1]]
+    0
+}
+
+pub fn to_bits(x: f32) -> u32 {
+    // CHECK-LABEL: define{{.*}}7to_bits{{.*}}!type ![[TYPE2:[0-9]+]]
+    unsafe { std::mem::transmute(x) }
+}
+
+pub fn to_bits_fn() -> fn(f32) -> u32 {
+    to_bits
+}
+
+pub fn call(f: fn(f32) -> u32, x: f32) -> u32 {
+    // CHECK-LABEL: define{{.*}}4call{{.*}}!type !{{[0-9]+}}
+    // CHECK:       call i1 @llvm.type.test(ptr {{%f|%0}}, metadata !"_ZTSFu3u32fE")
+    f(x)
+}
+
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFu5usizePKvE"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFu3u32fE"}
@@ -0,0 +1,34 @@
+// Verifies that type metadata identifiers of functions with the "C" calling convention are emitted
+// using the Microsoft C++ ABI type name decorations with `-Zsanitizer-cfi-typeid-scheme=msvc`.
+//
+//@ needs-sanitizer-cfi
+//@ only-64bit
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-typeid-scheme=msvc
+
+#![crate_type="lib"]
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub extern "C" fn foo0(_: i32) { }
+// CHECK: define{{.*}}4foo0{{.*}}!type ![[TYPE0:[0-9]+]]
+pub extern "C" fn foo1(_: *const i32, _: *mut i32, _: *mut i32) { }
+// CHECK: define{{.*}}4foo1{{.*}}!type ![[TYPE1:[0-9]+]]
+pub extern "C" fn foo2(p: Point) -> Point { p }
+// CHECK: define{{.*}}4foo2{{.*}}!type ![[TYPE2:[0-9]+]]
+pub extern "C" fn foo3(_: extern "C" fn(i32) -> i32) { }
+// CHECK: define{{.*}}4foo3{{.*}}!type ![[TYPE3:[0-9]+]]
+pub extern "C" fn foo4() -> u64 { 0 }
+// CHECK: define{{.*}}4foo4{{.*}}!type ![[TYPE4:[0-9]+]]
+pub fn foo5(_: i32) { }
+// CHECK: define{{.*}}4foo5{{.*}}!type ![[TYPE5:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"?$$A6AXH@Z"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"?$$A6AXPEBHPEAH1@Z"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"?$$A6A?AUPoint@@U0@@Z"}
+// CHECK: ![[TYPE3]] = !{i64 0, !"?$$A6AXP6AHH@Z@Z"}
+// CHECK: ![[TYPE4]] = !{i64 0, !"?$$A6A_KXZ"}
+// CHECK: ![[TYPE5]] = !{i64 0, !"_ZTSFvu3i32E"}
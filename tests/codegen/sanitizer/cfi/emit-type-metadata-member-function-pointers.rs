@@ -0,0 +1,37 @@
+// Verifies that type metadata identifiers for trait methods called through trait objects are
+// emitted as pointer to member function types with `-Zsanitizer-cfi-member-function-pointers`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-member-function-pointers
+
+#![crate_type="lib"]
+
+pub trait Trait1 {
+    fn foo(&self);
+    fn bar(&self, _: i32) -> i32;
+}
+
+pub struct Type1;
+
+impl Trait1 for Type1 {
+    fn foo(&self) {
+    }
+    fn bar(&self, x: i32) -> i32 {
+        x
+    }
+}
+
+pub fn foo1(a: &dyn Trait1) {
+    a.foo();
+    // CHECK-LABEL: define{{.*}}4foo1{{.*}}!type !{{[0-9]+}}
+    // CHECK:       call i1 @llvm.type.test(ptr {{%f|%[0-9]}}, metadata !"[[TYPE1:_ZTSMu3dynIu[0-9]+NtC[[:print:]]+6Trait1u6regionEFvvE]]")
+}
+
+pub fn bar1(a: &dyn Trait1) -> i32 {
+    a.bar(1)
+    // CHECK-LABEL: define{{.*}}4bar1{{.*}}!type !{{[0-9]+}}
+    // CHECK:       call i1 @llvm.type.test(ptr {{%f|%[0-9]}}, metadata !"[[TYPE2:_ZTSMu3dynIu[0-9]+NtC[[:print:]]+6Trait1u6regionEFu3i32S[0-9]*_E]]")
+}
+
+// CHECK: !{{[0-9]+}} = !{i64 0, !"[[TYPE1]]"}
+// CHECK: !{{[0-9]+}} = !{i64 0, !"[[TYPE2]]"}
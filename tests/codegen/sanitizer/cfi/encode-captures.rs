@@ -0,0 +1,23 @@
+// ignore-tidy-linelength
+// Verifies that the captures of coroutines are encoded with `-Zsanitizer-cfi-encode-captures`.
+//
+//@ revisions: DEFAULT CAPTURES
+//@ needs-sanitizer-cfi
+//@ edition: 2021
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [CAPTURES] compile-flags: -Zsanitizer-cfi-encode-captures
+
+#![crate_type="lib"]
+
+#[inline(never)]
+pub fn take<T>(_: T) { }
+
+pub fn foo(x: i32, y: &'static str) {
+    take(async move {
+        let _ = (x, y);
+    });
+}
+// CHECK: define{{.*}}4take{{.*}}!type !{{[0-9]+}}
+
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu{{[0-9]+}}NCNvC{{[[:print:]]+}}_{{[[:print:]]+}}3foo11{{[{}][{}]}}closure{{[}][}]}}E"}
+// CAPTURES: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu{{[0-9]+}}NCNvC{{[[:print:]]+}}_{{[[:print:]]+}}3foo11{{[{}][{}]}}closure{{[}][}]}}Iu5tupleIu3i32u3refIu3strEEEE"}
@@ -0,0 +1,27 @@
+// ignore-tidy-linelength
+// Verifies that functions are encoded from their declared signatures, including the parameters of
+// zero-sized types, with `-Zsanitizer-cfi-encode-fn-sigs`.
+//
+//@ revisions: DEFAULT FNSIGS
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [FNSIGS] compile-flags: -Zsanitizer-cfi-encode-fn-sigs
+
+#![crate_type="lib"]
+
+pub struct Type1;
+
+pub fn foo(_: (), _: i32) { }
+// CHECK: define{{.*}}3foo{{.*}}!type ![[TYPE0:[0-9]+]]
+pub fn bar(_: Type1, _: i32) { }
+// DEFAULT: define{{.*}}3bar{{.*}}!type ![[TYPE0]]
+// FNSIGS: define{{.*}}3bar{{.*}}!type ![[TYPE1:[0-9]+]]
+pub fn baz(f: fn(Type1, i32)) {
+    // DEFAULT: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"_ZTSFvu3i32E")
+    // FNSIGS: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"_ZTSFvu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1u3i32E")
+    f(Type1, 1)
+}
+
+// DEFAULT: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3i32E"}
+// FNSIGS: ![[TYPE0]] = !{i64 0, !"_ZTSFvvu3i32E"}
+// FNSIGS: ![[TYPE1]] = !{i64 0, !"_ZTSFvu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1u3i32E"}
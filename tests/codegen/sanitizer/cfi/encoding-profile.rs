@@ -0,0 +1,31 @@
+// Verifies that the CFI encodings of -Zcfi-encoding-profile take precedence over the
+// automatic encodings and the cfi_encoding attribute.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0 -Zcfi-encoding-profile={{src-base}}/sanitizer/cfi/auxiliary/encoding-profile.json
+
+#![crate_type="lib"]
+#![feature(cfi_encoding)]
+
+pub struct Type1(i32);
+
+pub struct Type2(i32);
+
+pub struct Type3(i32);
+
+#[cfi_encoding = "3Qux"]
+pub struct Type4(i32);
+
+pub fn foo0(_: Type1, _: Type1) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo1(_: Type2, _: Type2) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE1:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo2(_: *const Type3) { }
+// CHECK: define{{.*}}foo2{{.*}}!type ![[TYPE2:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo3(_: Type4) { }
+// CHECK: define{{.*}}foo3{{.*}}!type ![[TYPE3:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFv3FooS_E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvN2ns3BarES0_E"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvPKvE"}
+// CHECK: ![[TYPE3]] = !{i64 0, !"_ZTSFvu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type4E"}
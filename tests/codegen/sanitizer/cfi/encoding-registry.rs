@@ -0,0 +1,27 @@
+// Verifies that the CFI encodings of the `cfi_encodings` attributes of a crate and of its
+// dependencies override the automatic encodings of the types they name.
+//
+//@ needs-sanitizer-cfi
+//@ aux-build:encoding_registry_aux.rs
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type="lib"]
+#![feature(cfi_encoding)]
+#![cfi_encodings(encoding_registry_aux::Other = "5other", crate::Type1 = "3Foo")]
+
+extern crate encoding_registry_aux;
+
+use encoding_registry_aux::{Handle, Other};
+
+pub struct Type1(i32);
+
+pub fn foo0(_: Handle, _: Handle) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo1(_: Other) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE1:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+pub fn foo2(_: *const Type1) { }
+// CHECK: define{{.*}}foo2{{.*}}!type ![[TYPE2:[0-9]+]] !type !{{[0-9]+}} !type !{{[0-9]+}} !type !{{[0-9]+}}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFv6handleS_E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFv5otherE"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvPK3FooE"}
@@ -0,0 +1,15 @@
+// Verifies that the const qualifier of pointee types is erased with `-Zsanitizer-cfi-erase-const`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-erase-const -Zsanitizer-cfi-cxx-references -Copt-level=0
+
+#![crate_type="lib"]
+
+pub fn foo0(_: *const i32) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type ![[TYPE1:[0-9]+]]
+pub fn foo1(_: &i32, _: *mut i32) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE2:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvPu3i32E"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvPvE.generalized"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvRu3i32PS_E"}
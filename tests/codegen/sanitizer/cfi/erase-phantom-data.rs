@@ -0,0 +1,24 @@
+// ignore-tidy-linelength
+// Verifies that PhantomData generic arguments are encoded as the unit type with
+// `-Zsanitizer-cfi-erase-phantom-data`.
+//
+//@ revisions: DEFAULT PHANTOM
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [PHANTOM] compile-flags: -Zsanitizer-cfi-erase-phantom-data
+
+#![crate_type="lib"]
+
+use std::marker::PhantomData;
+
+pub struct Type1<T>(i32, T);
+
+pub fn foo(_: &Type1<PhantomData<*mut ()>>) { }
+// CHECK: define{{.*}}3foo{{.*}}!type ![[TYPE0:[0-9]+]]
+pub fn bar(_: &Type1<PhantomData<u8>>) { }
+// DEFAULT: define{{.*}}3bar{{.*}}!type ![[TYPE1:[0-9]+]]
+// PHANTOM: define{{.*}}3bar{{.*}}!type ![[TYPE0]]
+
+// DEFAULT: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3refIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1Iu{{[0-9]+}}NtNtC{{[[:print:]]+}}_4core6marker11PhantomDataIPvEEEE"}
+// DEFAULT: ![[TYPE1]] = !{i64 0, !"_ZTSFvu3refIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1Iu{{[0-9]+}}NtNtC{{[[:print:]]+}}_4core6marker11PhantomDataIu2u8EEEE"}
+// PHANTOM: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3refIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1IvEEE"}
@@ -0,0 +1,15 @@
+// Verifies that the mutability of references is erased with `-Zsanitizer-cfi-erase-ref-mut`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-erase-ref-mut -Copt-level=0
+
+#![crate_type="lib"]
+
+pub fn foo0(_: &mut i32) { }
+// CHECK: define{{.*}}foo0{{.*}}!type ![[TYPE0:[0-9]+]] !type ![[TYPE1:[0-9]+]]
+pub fn foo1(_: &i32, _: *mut i32) { }
+// CHECK: define{{.*}}foo1{{.*}}!type ![[TYPE2:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3refIu3i32EE"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvu3refIvEE.generalized"}
+// CHECK: ![[TYPE2]] = !{i64 0, !"_ZTSFvu3refIu3i32EPS_E"}
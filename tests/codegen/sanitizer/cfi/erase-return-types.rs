@@ -0,0 +1,22 @@
+// Verifies that return types are encoded as void with `-Zsanitizer-cfi-erase-return-types`.
+//
+//@ revisions: DEFAULT RETURNS
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [RETURNS] compile-flags: -Zsanitizer-cfi-erase-return-types
+
+#![crate_type="lib"]
+
+pub extern "C" fn foo(_: i32) -> i32 { 0 }
+// CHECK: define{{.*}}foo{{.*}}!type !{{[0-9]+}}
+pub fn bar(_: i32) { }
+// CHECK: define{{.*}}bar{{.*}}!type !{{[0-9]+}}
+pub fn baz(_: fn() -> i32) -> u8 { 0 }
+// CHECK: define{{.*}}baz{{.*}}!type !{{[0-9]+}}
+
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFu3i32S_E"}
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3i32E"}
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFu2u8PFu3i32vEE"}
+// RETURNS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3i32E.returns"}
+// RETURNS-NOT: !{i64 0, !"_ZTSFu3i32S_E
+// RETURNS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPFu3i32vEE.returns"}
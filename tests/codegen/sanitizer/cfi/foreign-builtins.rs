@@ -0,0 +1,44 @@
+// Verifies that builtins (e.g., `memcmp`) declared for intrinsics lowered to calls to them are also
+// given the type metadata identifiers of the foreign items they're reached through, and that the
+// foreign items declaring them with different pointee types are given the same ones with the
+// cross-language options.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-normalize-integers -Zsanitizer-cfi-generalize-pointers
+
+#![crate_type="lib"]
+#![feature(core_intrinsics)]
+#![allow(clashing_extern_declarations)]
+
+use std::ffi::{c_int, c_void};
+
+mod hal {
+    extern "C" {
+        pub fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> i32;
+    }
+}
+
+mod libc {
+    use std::ffi::{c_int, c_void};
+
+    extern "C" {
+        pub fn memcmp(s1: *const c_void, s2: *const c_void, n: usize) -> c_int;
+    }
+}
+
+pub unsafe fn foo(s1: *const u8, s2: *const u8, n: usize) -> i32 {
+    // CHECK-LABEL: define{{.*}}3foo
+    // CHECK:       call{{.*}}@memcmp(
+    std::intrinsics::compare_bytes(s1, s2, n)
+}
+
+pub fn bar() -> unsafe extern "C" fn(*const u8, *const u8, usize) -> i32 {
+    hal::memcmp
+}
+
+pub fn baz() -> unsafe extern "C" fn(*const c_void, *const c_void, usize) -> c_int {
+    libc::memcmp
+}
+
+// CHECK: declare !type {{.*}}@memcmp(
+// CHECK: !{i64 0, !"_ZTSFu3i32PKvS_u3u64E.normalized.generalized"}
@@ -0,0 +1,29 @@
+// Verifies that `-Zcfi-foreign-exceptions` emits the same normalized type metadata for
+// `extern "C-unwind"` functions and function pointers as for `extern "C"` ones (i.e., those Clang
+// emits for the C and C++ functions they correspond to), also when they are generalized.
+//
+//@ revisions: NORMALIZED GENERALIZED
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clinker-plugin-lto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Cpanic=unwind -Zsanitizer=cfi -Zcfi-foreign-exceptions
+//@ [GENERALIZED] compile-flags: -Zsanitizer-cfi-generalize-pointers
+
+#![crate_type="lib"]
+
+pub extern "C-unwind" fn foo(f: extern "C-unwind" fn(i32), arg: i32) {
+    // CHECK-LABEL: define{{.*}}foo
+    // CHECK-SAME:  {{.*}}![[TYPE1:[0-9]+]]
+    // NORMALIZED:  call i1 @llvm.type.test(ptr {{%f|%0}}, metadata !"_ZTSFvu3i32E.normalized")
+    // GENERALIZED: call i1 @llvm.type.test(ptr {{%f|%0}}, metadata !"_ZTSFvu3i32E.normalized.generalized")
+    f(arg)
+}
+
+pub extern "C" fn bar(f: extern "C" fn(i32), arg: i32) {
+    // CHECK-LABEL: define{{.*}}bar
+    // CHECK-SAME:  {{.*}}![[TYPE1]]
+    // NORMALIZED:  call i1 @llvm.type.test(ptr {{%f|%0}}, metadata !"_ZTSFvu3i32E.normalized")
+    // GENERALIZED: call i1 @llvm.type.test(ptr {{%f|%0}}, metadata !"_ZTSFvu3i32E.normalized.generalized")
+    f(arg)
+}
+
+// NORMALIZED:  ![[TYPE1]] = !{i64 0, !"_ZTSFvPFvu3i32ES_E.normalized"}
+// GENERALIZED: ![[TYPE1]] = !{i64 0, !"_ZTSFvPKvu3i32E.normalized.generalized"}
@@ -0,0 +1,18 @@
+// Verifies that arrays are encoded as slices in functions with the C calling convention with
+// `-Zsanitizer-cfi-generalize-arrays`.
+//
+//@ revisions: DEFAULT ARRAYS
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [ARRAYS] compile-flags: -Zsanitizer-cfi-generalize-arrays
+
+#![crate_type="lib"]
+
+pub extern "C" fn foo(_: *const [u8; 16]) { }
+// CHECK: define{{.*}}foo{{.*}}!type !{{[0-9]+}}
+pub fn bar(_: *const [u8; 16]) { }
+// CHECK: define{{.*}}bar{{.*}}!type !{{[0-9]+}}
+
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPKA16u2u8E"}
+// ARRAYS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPKu5sliceIu2u8EE.arrays"}
+// ARRAYS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPKA16u2u8E.arrays"}
@@ -0,0 +1,35 @@
+// ignore-tidy-linelength
+// Verifies that argument-position `impl Trait` types and opaque types are encoded via their bounds
+// with `-Zsanitizer-cfi-impl-trait-bounds`, in addition to the types they hide.
+//
+//@ revisions: DEFAULT BOUNDS
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [BOUNDS] compile-flags: -Zsanitizer-cfi-impl-trait-bounds
+
+#![crate_type="lib"]
+
+pub trait Plugin {
+    fn run(&self);
+}
+
+pub struct Type1;
+
+impl Plugin for Type1 {
+    fn run(&self) { }
+}
+
+pub fn foo(_: &impl Plugin) { }
+// CHECK: define{{.*}}3foo{{.*}}!type ![[TYPE0:[0-9]+]]
+pub fn bar() {
+    foo(&Type1);
+}
+pub fn baz() -> impl Plugin {
+// CHECK: define{{.*}}3baz{{.*}}!type ![[TYPE1:[0-9]+]]
+    Type1
+}
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3refIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1EE"}
+// BOUNDS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3refIu3dynIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}6Pluginu6regionEEE"}
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1vE"}
+// BOUNDS: !{{[0-9]+}} = !{i64 0, !"_ZTSFu3dynIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}6Pluginu6regionEvE"}
@@ -0,0 +1,13 @@
+// Verifies that only the pointer-sized integer types are normalized with
+// `-Zsanitizer-cfi-normalize-ptr-sized-integers`.
+//
+//@ only-64bit
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-normalize-ptr-sized-integers -Copt-level=0
+
+#![crate_type="lib"]
+
+pub fn foo(_: usize, _: isize, _: bool, _: char) { }
+// CHECK: define{{.*}}foo{{.*}}!type ![[TYPE0:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3u64u3i64bu4charE.ptrsized"}
@@ -0,0 +1,21 @@
+// Verifies that references are encoded as raw pointers in functions with the C calling convention
+// with `-Zsanitizer-cfi-refs-as-ptrs`.
+//
+//@ revisions: DEFAULT REFS
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [REFS] compile-flags: -Zsanitizer-cfi-refs-as-ptrs
+
+#![crate_type="lib"]
+
+pub extern "C" fn foo(_: &i32, _: &mut i32) { }
+// CHECK: define{{.*}}foo{{.*}}!type !{{[0-9]+}}
+pub extern "C" fn bar(_: &[i32]) { }
+// CHECK: define{{.*}}bar{{.*}}!type !{{[0-9]+}}
+pub fn baz(_: &i32) { }
+// CHECK: define{{.*}}baz{{.*}}!type !{{[0-9]+}}
+
+// DEFAULT: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3refIu3i32EU3mutS0_E"}
+// REFS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvPKu3i32PS_E"}
+// REFS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3refIu5sliceIu3i32EEE"}
+// REFS: !{{[0-9]+}} = !{i64 0, !"_ZTSFvu3refIu3i32EE"}
@@ -0,0 +1,42 @@
+// ignore-tidy-linelength
+// Verifies that functions with the "rust-call" calling convention are encoded with their
+// parameters untupled whatever way they are called.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+
+#![crate_type="lib"]
+#![feature(fn_traits)]
+#![feature(unboxed_closures)]
+
+pub fn foo(f: &dyn Fn(i32, i64)) {
+    // CHECK-LABEL: define{{.*}}3foo
+    // CHECK: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"[[TYPE0:_ZTSFvu3refIu3dynI[[:print:]]+Eu3i32u3i64E]]")
+    f(1, 2)
+}
+
+pub fn bar(f: &dyn Fn(i32, i64)) {
+    // CHECK-LABEL: define{{.*}}3bar
+    // CHECK: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"[[TYPE0]]")
+    f.call((1, 2))
+}
+
+pub fn baz(f: fn(i32, i64)) {
+    // CHECK-LABEL: define{{.*}}3baz
+    // CHECK: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"_ZTSFvu3i32u3i64E")
+    f(1, 2)
+}
+
+pub fn qux(f: extern "rust-call" fn(&(), (i32, i64))) {
+    // CHECK-LABEL: define{{.*}}3qux{{.*}}!type ![[TYPE1:[0-9]+]]
+    // CHECK: call i1 @llvm.type.test(ptr {{%.+}}, metadata !"_ZTSFvu3refIvEu3i32u3i64E")
+    f(&(), (1, 2))
+}
+
+pub fn quux() {
+    let f = |_: i32, _: i64| {};
+    foo(&f);
+    baz(f);
+}
+
+// CHECK: ![[TYPE1]] = !{i64 0, !"_ZTSFvPFvu3refIvEu3i32u3i64EE"}
@@ -0,0 +1,32 @@
+// Verifies that the associated type projections encoded in trait objects are restricted with
+// `-Zsanitizer-cfi-trait-object-projections`.
+//
+//@ revisions: ALL PRINCIPAL NONE
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Copt-level=0 -Ctarget-feature=-crt-static -Zsanitizer=cfi
+//@ [PRINCIPAL] compile-flags: -Zsanitizer-cfi-trait-object-projections=principal
+//@ [NONE] compile-flags: -Zsanitizer-cfi-trait-object-projections=none
+
+#![crate_type="lib"]
+
+pub trait Super {
+    type AssocSuper;
+}
+
+pub trait Sub: Super {
+    type AssocSub;
+    fn baz(&self);
+}
+
+pub fn foo(x: &dyn Sub<AssocSuper = u8, AssocSub = u16>) {
+    x.baz()
+}
+// CHECK-LABEL: define{{.*}}3foo
+// ALL-DAG: 10AssocSuper
+// ALL-DAG: 8AssocSub
+// PRINCIPAL-NOT: AssocSuper
+// PRINCIPAL: 8AssocSub
+// NONE-NOT: AssocSu
+
+pub fn bar() { }
+// CHECK-LABEL: define{{.*}}3bar
@@ -0,0 +1,21 @@
+// ignore-tidy-linelength
+// Verifies that the tails of type metadata identifiers longer than
+// `-Zsanitizer-cfi-typeid-length-cap` are replaced with a hash of the full type metadata identifier.
+//
+//@ revisions: DEFAULT CAP
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Copt-level=0
+//@ [CAP] compile-flags: -Zsanitizer-cfi-typeid-length-cap=64
+
+#![crate_type="lib"]
+
+pub struct Type1<T>(T);
+
+pub fn foo(_: i32) { }
+// CHECK: define{{.*}}3foo{{.*}}!type ![[TYPE0:[0-9]+]]
+pub fn bar(_: &Type1<(i32, i64, u8, u16, u32, u64, f32, f64)>) { }
+// CHECK: define{{.*}}3bar{{.*}}!type ![[TYPE1:[0-9]+]]
+
+// CHECK: ![[TYPE0]] = !{i64 0, !"_ZTSFvu3i32E"}
+// DEFAULT: ![[TYPE1]] = !{i64 0, !"_ZTSFvu3refIu{{[0-9]+}}NtC{{[[:print:]]+}}_{{[[:print:]]+}}5Type1Iu5tupleIu3i32u3i64u2u8u3u16u3u32u3u64fdEEEE"}
+// CAP: ![[TYPE1]] = !{i64 0, !"_ZTSFvu3refIu{{[[:print:]]{33}}}.h{{[0-9a-f]{16}}}"}
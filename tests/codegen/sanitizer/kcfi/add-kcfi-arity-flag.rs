@@ -0,0 +1,20 @@
+// Verifies that "kcfi-arity" module flag is added.
+//
+//@ min-llvm-version: 21.0.0
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-none -Ctarget-feature=-crt-static -Zsanitizer=kcfi
+//@ compile-flags: -Zsanitizer-kcfi-arity
+
+#![feature(no_core, lang_items)]
+#![crate_type="lib"]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+pub fn foo() {
+}
+
+// CHECK: !{{[0-9]+}} = !{i32 4, !"kcfi-arity", i32 1}
@@ -0,0 +1,30 @@
+// ignore-tidy-linelength
+// Checks that `-Zsymbol-mangling-legacy-aliases` defines the legacy symbols of v0-mangled items as
+// aliases of them, with their linkage, and leaves the items with unmangled symbols alone.
+
+//@ compile-flags: -Csymbol-mangling-version=v0 -Zsymbol-mangling-legacy-aliases
+//@ compile-flags: -Copt-level=0 --crate-type=lib --crate-name=c
+
+// Only the three items with v0 symbols have an alias, which is used by the compiler.
+// CHECK: @llvm.compiler.used = appending global [3 x ptr]
+
+// CHECK-DAG: @_ZN1c6STATIC17h{{[0-9a-f]+}}E = {{.*}}alias {{.*}} @_RNvCs{{[0-9a-zA-Z]+}}_1c6STATIC
+// CHECK-DAG: @_ZN1c3foo17h{{[0-9a-f]+}}E = {{.*}}alias {{.*}} @_RNvCs{{[0-9a-zA-Z]+}}_1c3foo
+// CHECK-DAG: @_ZN1c6helper17h{{[0-9a-f]+}}E = internal {{.*}}alias {{.*}} @_RNvCs{{[0-9a-zA-Z]+}}_1c6helper
+
+#[no_mangle]
+pub static STATIC_UNMANGLED: i32 = 0;
+
+pub static STATIC: i32 = 0;
+
+#[no_mangle]
+pub fn unmangled() {
+    helper();
+}
+
+pub fn foo() {
+    helper();
+}
+
+#[inline(never)]
+fn helper() {}
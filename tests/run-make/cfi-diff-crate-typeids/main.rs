@@ -0,0 +1 @@
+// Empty crate the two versions of `abi` are loaded into.
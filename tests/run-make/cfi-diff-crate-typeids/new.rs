@@ -0,0 +1,9 @@
+#![crate_name = "abi"]
+
+pub fn foo(x: u32) -> i32 {
+    x as i32
+}
+
+pub fn bar(_: &mut [u8]) {}
+
+pub fn qux() {}
@@ -0,0 +1,9 @@
+#![crate_name = "abi"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub fn bar(_: &mut [u8]) {}
+
+pub fn baz() {}
@@ -0,0 +1,40 @@
+// Verifies that `-Zcfi-diff-crate-typeids` reports the exported functions whose type metadata
+// identifiers were changed, added, or removed between two versions of a crate.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    // Both versions are named `abi`, so they are written to different directories
+    for (version, input) in [("1", "old.rs"), ("2", "new.rs")] {
+        std::fs::create_dir_all(tmp_dir().join(version)).unwrap();
+        rustc()
+            .input(input)
+            .crate_type("lib")
+            .emit("metadata")
+            .arg(format!("-Cmetadata={version}"))
+            .arg("-o")
+            .arg(tmp_dir().join(version).join("libabi.rmeta"))
+            .run();
+    }
+
+    let output = rustc()
+        .input("main.rs")
+        .crate_type("lib")
+        .arg(format!("--extern=force:old={}", tmp_dir().join("1/libabi.rmeta").display()))
+        .arg(format!("--extern=force:new={}", tmp_dir().join("2/libabi.rmeta").display()))
+        .arg("-Zcfi-diff-crate-typeids=old,new")
+        .run();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "changed: foo\n  old: _ZTSFu3i32S_E\n  new: _ZTSFu3i32u3u32E\n  \
+         first difference: parameter #1: `i32` => `u32`\n  \
+         note: the integer types have the same size but different signedness\n\
+         removed: baz\n  old: _ZTSFvvE\n\
+         added: qux\n  new: _ZTSFvvE\n\
+         1 changed, 1 added, 1 removed\n"
+    );
+}
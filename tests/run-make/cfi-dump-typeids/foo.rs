@@ -0,0 +1,3 @@
+pub fn bar(x: i32) -> i32 {
+    x
+}
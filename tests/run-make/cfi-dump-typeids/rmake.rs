@@ -0,0 +1,22 @@
+// Verifies that `-Zcfi-dump-typeids` writes the type metadata identifier and KCFI type metadata
+// identifier of every function mono item to the specified file.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let path = tmp_dir().join("typeids.json");
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg(format!("-Zcfi-dump-typeids={}", path.display()))
+        .run();
+
+    let dump = std::fs::read_to_string(&path).unwrap();
+    assert!(dump.contains(r#""name": "bar""#));
+    assert!(dump.contains(r#""typeid": "_ZTSFu3i32S_E""#));
+    assert!(dump.contains(r#""signature": "fn(i32) -> i32""#));
+    // 0x9ca52654, the lower half of the xxHash64 of `_ZTSFu3i32S_E`
+    assert!(dump.contains(r#""kcfi_typeid": 2628068948"#));
+}
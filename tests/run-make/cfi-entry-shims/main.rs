@@ -0,0 +1,12 @@
+use std::process::Termination;
+
+// The type the return type of `main` hides, which is the one of the `fn() -> T` function pointer
+// `lang_start` calls `main` through
+pub fn concrete() -> Result<(), std::fmt::Error> {
+    Ok(())
+}
+
+fn main() -> impl Termination {
+    println!("reached main");
+    concrete()
+}
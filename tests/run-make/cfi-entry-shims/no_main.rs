@@ -0,0 +1,15 @@
+#![no_main]
+
+use std::ffi::{c_char, c_int};
+use std::hint::black_box;
+
+fn hello() {
+    println!("reached main");
+}
+
+#[no_mangle]
+pub extern "C" fn main(_argc: c_int, _argv: *const *const c_char) -> c_int {
+    let f: fn() = black_box(hello);
+    f();
+    0
+}
@@ -0,0 +1,60 @@
+// Verifies that binaries built with `-Zsanitizer=cfi` reach the user's code, both when `main` is
+// called by `lang_start` through a function pointer (i.e., with `std`'s entry shims, even when its
+// return type is an `impl Trait` type encoded via its bounds elsewhere) and with `#![no_main]`,
+// and that the user's `main` has the KCFI type metadata identifier of the `fn() -> T` function
+// pointer `lang_start` calls it through.
+
+//@ needs-sanitizer-cfi
+//@ needs-sanitizer-kcfi
+
+extern crate run_make_support;
+
+use run_make_support::{run, rustc, tmp_dir};
+
+fn main() {
+    for input in ["main", "no_main"] {
+        rustc()
+            .input(format!("{input}.rs"))
+            .arg("-Zsanitizer=cfi")
+            .arg("-Zsanitizer-cfi-impl-trait-bounds")
+            .arg("-Clto")
+            .arg("-Ctarget-feature=-crt-static")
+            .codegen_units(1)
+            .run();
+        let output = run(input);
+        assert!(String::from_utf8(output.stdout).unwrap().contains("reached main"));
+    }
+
+    let output = rustc()
+        .input("main.rs")
+        .arg("-Zsanitizer=kcfi")
+        .arg("-Zsanitizer-cfi-impl-trait-bounds")
+        .arg("-Cpanic=abort")
+        .arg("-Zunstable-options")
+        .arg("--print=cfi-type-id=concrete")
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let kcfi_typeid = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("kcfi-typeid: 0x"))
+        .expect("no KCFI type metadata identifier printed");
+    // LLVM prints the KCFI type metadata identifiers as signed integers
+    let kcfi_typeid = u32::from_str_radix(kcfi_typeid, 16).unwrap() as i32;
+
+    rustc()
+        .input("main.rs")
+        .arg("-Zsanitizer=kcfi")
+        .arg("-Zsanitizer-cfi-impl-trait-bounds")
+        .arg("-Cpanic=abort")
+        .codegen_units(1)
+        .emit("llvm-ir")
+        .run();
+    let ir = std::fs::read_to_string(tmp_dir().join("main.ll")).unwrap();
+    let define = ir
+        .lines()
+        .find(|line| line.starts_with("define") && line.contains("4main4main"))
+        .expect("no definition of `main::main`");
+    let (_, md) = define.rsplit_once("!kcfi_type !").expect("no KCFI type metadata identifier");
+    let md = md.split(|c: char| !c.is_ascii_digit()).next().unwrap();
+    assert!(ir.contains(&format!("!{md} = !{{i32 {kcfi_typeid}}}")));
+}
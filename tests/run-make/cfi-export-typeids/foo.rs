@@ -0,0 +1,11 @@
+#[no_mangle]
+pub extern "C" fn foo(x: *const i32) -> i32 {
+    unsafe { *x }
+}
+
+// Not exported with an unmangled symbol
+pub extern "C" fn bar() {}
+
+// Not using the "C" calling convention
+#[no_mangle]
+pub fn baz() {}
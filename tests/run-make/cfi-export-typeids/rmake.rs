@@ -0,0 +1,21 @@
+// Verifies that `-Zcfi-export-typeids` writes the generalized type metadata identifier and KCFI
+// type metadata identifier of every exported extern "C" function (and only those) to the specified
+// file.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let path = tmp_dir().join("typeids.txt");
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg(format!("-Zcfi-export-typeids={}", path.display()))
+        .run();
+
+    let export = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = export.lines().filter(|line| !line.starts_with('#')).collect();
+    // 0x0765fa0a, the lower half of the xxHash64 of `_ZTSFu3i32PKS_E`
+    assert_eq!(lines, ["foo\t_ZTSFu3i32PKvE.generalized\t0x0765fa0a"]);
+}
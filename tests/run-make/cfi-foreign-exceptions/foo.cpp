@@ -0,0 +1,45 @@
+#include <assert.h>
+#include <stdint.h>
+#include <stdio.h>
+
+void println(const char* s) {
+    puts(s);
+    fflush(stdout);
+}
+
+struct exception {};
+
+extern "C" {
+    // Called indirectly by Rust, so the call is checked by Rust.
+    void throw_cxx_exception(int32_t value) {
+        assert(value == 42);
+        println("throwing C++ exception");
+        throw exception();
+    }
+
+    void rust_catch_callback(void (*cb)(int32_t), int32_t value, bool* rust_ok);
+
+    void test_cxx_exception() {
+        bool rust_ok = false;
+        try {
+            rust_catch_callback(throw_cxx_exception, 42, &rust_ok);
+            assert(false && "unreachable");
+        } catch (exception e) {
+            println("caught C++ exception");
+            assert(rust_ok);
+            return;
+        }
+        assert(false && "did not catch thrown C++ exception");
+    }
+
+    // Calls a Rust function indirectly, so the call is checked by Clang.
+    void cxx_catch_callback(void (*cb)(int32_t), int32_t value, bool* cxx_ok) {
+        try {
+            cb(value);
+        } catch (...) {
+            println("caught foreign exception in catch (...)");
+            *cxx_ok = true;
+            throw;
+        }
+    }
+}
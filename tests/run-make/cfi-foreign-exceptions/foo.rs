@@ -0,0 +1,58 @@
+// Tests that C++ exceptions and Rust panics unwind through `extern "C-unwind"` functions called
+// through function pointers on the other side of the language boundary, with both sides checking
+// these calls with LLVM CFI.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct DropCheck<'a>(&'a mut bool);
+impl<'a> Drop for DropCheck<'a> {
+    fn drop(&mut self) {
+        println!("DropCheck::drop");
+        *self.0 = true;
+    }
+}
+
+extern "C" {
+    fn test_cxx_exception();
+}
+
+extern "C-unwind" {
+    fn cxx_catch_callback(cb: extern "C-unwind" fn(i32), value: i32, ok: *mut bool);
+}
+
+#[no_mangle]
+extern "C-unwind" fn rust_catch_callback(
+    cb: extern "C-unwind" fn(i32),
+    value: i32,
+    rust_ok: &mut bool,
+) {
+    let _drop = DropCheck(rust_ok);
+    cb(value);
+    unreachable!("should have unwound instead of returned");
+}
+
+fn test_rust_panic() {
+    extern "C-unwind" fn callback(value: i32) {
+        println!("throwing rust panic");
+        panic!("{}", value);
+    }
+
+    let mut dropped = false;
+    let mut cxx_ok = false;
+    let caught_unwind = catch_unwind(AssertUnwindSafe(|| {
+        let _drop = DropCheck(&mut dropped);
+        unsafe {
+            cxx_catch_callback(callback, 42, &mut cxx_ok);
+        }
+        unreachable!("should have unwound instead of returned");
+    }));
+    println!("caught rust panic");
+    assert!(dropped);
+    assert!(caught_unwind.is_err());
+    assert!(cxx_ok);
+}
+
+fn main() {
+    unsafe { test_cxx_exception() };
+    test_rust_panic();
+}
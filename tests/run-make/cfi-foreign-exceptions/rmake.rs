@@ -0,0 +1,47 @@
+// Verifies that C++ exceptions and Rust panics unwind through `extern "C-unwind"` functions called
+// through function pointers with cross-language LLVM CFI enabled on both sides, i.e., that with
+// `-Zcfi-foreign-exceptions` these functions have the type metadata identifiers Clang checks calls
+// to them with, and vice versa.
+
+//@ needs-matching-clang
+//@ needs-sanitizer-cfi
+//@ needs-unwind
+//@ ignore-cross-compile
+
+extern crate run_make_support;
+
+use run_make_support::{extra_cxx_flags, run, rustc, tmp_dir};
+use std::process::Command;
+
+fn main() {
+    let object = tmp_dir().join("foo.o");
+    let status = Command::new(std::env::var("CLANG").unwrap())
+        .args(["-x", "c++", "foo.cpp", "-c", "-o"])
+        .arg(&object)
+        .args(["-flto=thin", "-fvisibility=hidden", "-fsanitize=cfi-icall"])
+        .arg("-fsanitize-cfi-icall-experimental-normalize-integers")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut rustc = rustc();
+    rustc
+        .input("foo.rs")
+        .arg(format!("-Clink-arg={}", object.display()))
+        .arg("-Clinker-plugin-lto")
+        .arg(format!("-Clinker={}", std::env::var("CLANG").unwrap()))
+        .arg("-Clink-arg=-fuse-ld=lld")
+        .arg("-Ctarget-feature=-crt-static")
+        .arg("-Zsanitizer=cfi")
+        .arg("-Zcfi-foreign-exceptions");
+    for flag in extra_cxx_flags() {
+        rustc.arg(format!("-Clink-arg={flag}"));
+    }
+    rustc.run();
+
+    let output = run("foo");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("caught C++ exception"));
+    assert!(stdout.contains("caught foreign exception in catch (...)"));
+    assert!(stdout.contains("caught rust panic"));
+}
@@ -0,0 +1,8 @@
+use std::ffi::c_void;
+
+#[repr(transparent)]
+pub struct Handle(*mut c_void);
+
+pub fn bar(_: Handle) {}
+
+pub fn baz(_: *mut c_void) {}
@@ -0,0 +1,22 @@
+// Verifies that `-Zcfi-trace-transform` logs the type transformations applied when computing the
+// type metadata identifiers of the selected function only.
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+fn main() {
+    let output = rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .arg("-Zunstable-options")
+        .arg("--print=cfi-type-id=bar")
+        .arg("-Zcfi-trace-transform=bar")
+        .env("RUSTC_LOG", "rustc_symbol_mangling::typeid=info")
+        .run();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("transform c_void into unit: `core::ffi::c_void` => `()`"));
+    assert!(stderr.contains("flatten repr(transparent) type: `Handle` => `*mut ()`"));
+    assert!(!stderr.contains("baz"));
+}
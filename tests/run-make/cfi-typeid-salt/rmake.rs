@@ -0,0 +1,26 @@
+// Verifies that the `cfi-typeid-salt` of a custom target is appended to the type metadata
+// identifiers (and thereby salts the KCFI type metadata identifiers), and that salts that can't be
+// part of a type metadata identifier are rejected.
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+fn main() {
+    let output = rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .target("salted-platform.json")
+        .arg("-Zunstable-options")
+        .arg("--print=cfi-type-id=foo")
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("typeid: _ZTSFu3i32S_E.saltAndroid_1234\n"));
+    // The lower half of the xxHash64 of `_ZTSFu3i32S_E.saltAndroid_1234`
+    assert!(stdout.contains("kcfi-typeid: 0x084aa117\n"));
+
+    let output =
+        rustc().input("foo.rs").crate_type("lib").target("invalid-salt-platform.json").run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cfi-typeid-salt must only contain ASCII alphanumeric characters"));
+}
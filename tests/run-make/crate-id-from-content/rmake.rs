@@ -0,0 +1,33 @@
+// Verifies that `-Zcrate-id-from-content` makes the symbols of a crate only depend on its source
+// and declared version, and not on the `-Cmetadata` passed to it or on where it is compiled from.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn symbols(input: &str, metadata: &str, version: &str) -> String {
+    let path = tmp_dir().join("symbols.txt");
+    rustc()
+        .input(input)
+        .arg("-Csymbol-mangling-version=v0")
+        .arg(format!("-Cmetadata={metadata}"))
+        .arg(format!("-Zcrate-id-from-content={version}"))
+        .arg(format!("-Zemit-symbol-map={}", path.display()))
+        .run();
+    std::fs::read_to_string(&path).unwrap()
+}
+
+fn main() {
+    let copy = tmp_dir().join("copy");
+    std::fs::create_dir_all(&copy).unwrap();
+    std::fs::copy("foo.rs", copy.join("foo.rs")).unwrap();
+    let copy = copy.join("foo.rs");
+
+    let original = symbols("foo.rs", "machine-a", "1.0.0");
+    assert_eq!(symbols("foo.rs", "machine-b", "1.0.0"), original);
+    assert_eq!(symbols(copy.to_str().unwrap(), "machine-c", "1.0.0"), original);
+    assert_ne!(symbols("foo.rs", "machine-a", "1.0.1"), original);
+
+    std::fs::write(&copy, "#![crate_type = \"rlib\"]\n\npub fn foo() { let _ = 1; }\n").unwrap();
+    assert_ne!(symbols(copy.to_str().unwrap(), "machine-a", "1.0.0"), original);
+}
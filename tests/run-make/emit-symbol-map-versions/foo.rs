@@ -0,0 +1,17 @@
+#![crate_type = "rlib"]
+
+#[no_mangle]
+pub extern "C" fn foo_init(flags: i32) -> i32 {
+    flags
+}
+
+#[no_mangle]
+pub extern "C" fn foo_read(buf: *mut u8, len: usize) -> usize {
+    let _ = buf;
+    len
+}
+
+#[no_mangle]
+pub extern "C" fn bar(x: u32) -> u32 {
+    x
+}
@@ -0,0 +1,34 @@
+// Verifies that `-Zemit-symbol-map` records the versions the version scripts passed to the linker
+// assign to symbols (with exact names taking precedence over patterns), and the type metadata
+// identifiers the functions are checked with.
+
+//@ needs-sanitizer-cfi
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let path = tmp_dir().join("symbols.txt");
+    rustc()
+        .input("foo.rs")
+        .arg("-Clink-arg=-Wl,--version-script=libfoo.map")
+        .arg("-Clinker-plugin-lto")
+        .arg("-Ctarget-feature=-crt-static")
+        .arg("-Zsanitizer=cfi")
+        .arg(format!("-Zemit-symbol-map={}", path.display()))
+        .run();
+
+    let map = std::fs::read_to_string(&path).unwrap();
+    let fields = |symbol: &str| {
+        map.lines()
+            .map(|line| line.split('\t').collect::<Vec<_>>())
+            .find(|fields| fields[0] == symbol)
+            .unwrap_or_else(|| panic!("no line for {symbol} in:\n{map}"))
+    };
+
+    assert_eq!(fields("foo_init")[5..], ["FOO_1.0", "_ZTSFu3i32S_E"]);
+    assert_eq!(fields("foo_read")[5..], ["FOO_1.1", "_ZTSFu5usizePhS_E"]);
+    // `bar` is local, and so has no version
+    assert_eq!(fields("bar")[5..], ["-", "_ZTSFu3u32S_E"]);
+}
@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+
+pub mod bar {
+    #[inline(never)]
+    pub fn baz() -> u32 {
+        42
+    }
+}
+
+#[no_mangle]
+pub static QUX: u32 = 42;
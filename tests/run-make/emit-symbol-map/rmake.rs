@@ -0,0 +1,33 @@
+// Verifies that `-Zemit-symbol-map` writes the symbol, full symbol, demangled symbol, def path,
+// codegen unit, version and type metadata identifier of every mono item to the specified file.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let path = tmp_dir().join("symbols.txt");
+    rustc()
+        .input("foo.rs")
+        .arg("-Csymbol-mangling-version=v0")
+        .arg(format!("-Zemit-symbol-map={}", path.display()))
+        .run();
+
+    let map = std::fs::read_to_string(&path).unwrap();
+    let mut lines = map.lines().filter(|line| !line.starts_with('#'));
+    let mut fields = || lines.next().unwrap().split('\t').collect::<Vec<_>>();
+
+    // The lines are sorted by symbol
+    let qux = fields();
+    assert_eq!(qux[..3], ["QUX", "QUX", "QUX"]);
+    assert!(qux[3].starts_with("foo[") && qux[3].ends_with("]::QUX"));
+    let baz = fields();
+    assert!(baz[0].starts_with("_RNvNtCs") && baz[0].ends_with("_3foo3bar3baz"));
+    assert_eq!(baz[1], baz[0]);
+    assert!(baz[2].starts_with("foo[") && baz[2].ends_with("]::bar::baz"));
+    assert!(baz[3].starts_with("foo[") && baz[3].ends_with("]::bar::baz"));
+    assert!(baz[4].starts_with("foo."));
+    // Without version scripts and CFI, there are no versions and type metadata identifiers
+    assert_eq!(baz[5..], ["-", "-"]);
+    assert!(lines.next().is_none());
+}
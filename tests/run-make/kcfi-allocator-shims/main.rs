@@ -0,0 +1,26 @@
+// The same signatures as the declarations of the allocator shims in `alloc`
+
+pub unsafe fn alloc(size: usize, align: usize) -> *mut u8 {
+    std::alloc::alloc(std::alloc::Layout::from_size_align_unchecked(size, align))
+}
+
+pub unsafe fn dealloc(ptr: *mut u8, size: usize, align: usize) {
+    std::alloc::dealloc(ptr, std::alloc::Layout::from_size_align_unchecked(size, align))
+}
+
+pub unsafe fn realloc(ptr: *mut u8, size: usize, align: usize, new_size: usize) -> *mut u8 {
+    std::alloc::realloc(ptr, std::alloc::Layout::from_size_align_unchecked(size, align), new_size)
+}
+
+pub unsafe fn alloc_zeroed(size: usize, align: usize) -> *mut u8 {
+    std::alloc::alloc_zeroed(std::alloc::Layout::from_size_align_unchecked(size, align))
+}
+
+pub unsafe fn oom(size: usize, align: usize) -> ! {
+    panic!("memory allocation of {size} bytes (aligned to {align}) failed")
+}
+
+fn main() {
+    let v = vec![0u8; 16];
+    assert_eq!(v.len(), 16);
+}
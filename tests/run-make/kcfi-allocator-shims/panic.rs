@@ -0,0 +1,14 @@
+#![crate_type = "staticlib"]
+#![no_std]
+
+use core::panic::PanicInfo;
+
+// The same signature as the declaration of the `panic_impl` lang item in `core`
+pub fn panic_impl(_info: &PanicInfo<'_>) -> ! {
+    loop {}
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    panic_impl(info)
+}
@@ -0,0 +1,120 @@
+// Verifies that the allocator shims generated by the compiler (i.e., `__rust_alloc`,
+// `__rust_dealloc`, `__rust_realloc`, `__rust_alloc_zeroed`, and `__rust_alloc_error_handler`) and
+// the `#[panic_handler]` (i.e., the definition of the `panic_impl` weak lang item) have the KCFI
+// type metadata identifiers and type metadata identifiers of their declarations in `alloc` and
+// `core` (i.e., of functions with the same signatures), as they're defined in a different crate
+// than the one they're declared and called from.
+
+//@ needs-sanitizer-cfi
+//@ needs-sanitizer-kcfi
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+
+const ALLOCATOR_SHIMS: [(&str, &str); 5] = [
+    ("alloc", "__rust_alloc"),
+    ("dealloc", "__rust_dealloc"),
+    ("realloc", "__rust_realloc"),
+    ("alloc_zeroed", "__rust_alloc_zeroed"),
+    ("oom", "__rust_alloc_error_handler"),
+];
+
+/// Returns the type metadata identifiers and KCFI type metadata identifiers printed for the
+/// functions with the specified paths.
+fn print_typeids(input: &str, sanitizer: &str, paths: &[&str]) -> Vec<(String, i32)> {
+    let mut cmd = rustc();
+    cmd.input(input).arg(format!("-Zsanitizer={sanitizer}")).arg("-Cpanic=abort");
+    if sanitizer == "cfi" {
+        cmd.arg("-Clto=fat").codegen_units(1);
+    }
+    cmd.arg("-Zunstable-options");
+    for path in paths {
+        cmd.arg(format!("--print=cfi-type-id={path}"));
+    }
+    let stdout = String::from_utf8(cmd.run().stdout).unwrap();
+    let typeids = stdout.lines().filter_map(|line| line.trim().strip_prefix("typeid: "));
+    let kcfi_typeids =
+        stdout.lines().filter_map(|line| line.trim().strip_prefix("kcfi-typeid: 0x"));
+    let typeids: Vec<_> = typeids
+        .zip(kcfi_typeids)
+        // LLVM prints the KCFI type metadata identifiers as signed integers
+        .map(|(typeid, kcfi_typeid)| {
+            (typeid.to_string(), u32::from_str_radix(kcfi_typeid, 16).unwrap() as i32)
+        })
+        .collect();
+    assert_eq!(typeids.len(), paths.len(), "not all type metadata identifiers printed");
+    typeids
+}
+
+/// Emits the LLVM IR of the specified input, with all modules (including the allocator shims)
+/// linked into one and without optimizations, so the type metadata isn't lowered.
+fn emit_ir(input: &str, sanitizer: &str) -> String {
+    rustc()
+        .input(input)
+        .arg(format!("-Zsanitizer={sanitizer}"))
+        .arg("-Cpanic=abort")
+        .arg("-Clto=fat")
+        .arg("-Cno-prepopulate-passes")
+        .codegen_units(1)
+        .emit("llvm-ir")
+        .run();
+    let stem = input.strip_suffix(".rs").unwrap();
+    std::fs::read_to_string(tmp_dir().join(format!("{stem}.ll"))).unwrap()
+}
+
+/// Returns the numbers of the metadata nodes of the specified kind attached to the definition of
+/// the specified symbol.
+fn attached_metadata<'a>(ir: &'a str, symbol: &str, kind: &str) -> Vec<&'a str> {
+    let define = ir
+        .lines()
+        .find(|line| line.starts_with("define") && line.contains(&format!("@{symbol}(")))
+        .unwrap_or_else(|| panic!("no definition of `{symbol}`"));
+    define
+        .split(&format!("!{kind} !"))
+        .skip(1)
+        .map(|md| md.split(|c: char| !c.is_ascii_digit()).next().unwrap())
+        .collect()
+}
+
+fn check_kcfi_typeid(ir: &str, symbol: &str, kcfi_typeid: i32) {
+    let mds = attached_metadata(ir, symbol, "kcfi_type");
+    assert_eq!(mds.len(), 1, "`{symbol}` doesn't have one KCFI type metadata identifier");
+    let md = mds[0];
+    assert!(
+        ir.contains(&format!("!{md} = !{{i32 {kcfi_typeid}}}")),
+        "`{symbol}` doesn't have the KCFI type metadata identifier of its declaration"
+    );
+}
+
+fn check_typeid(ir: &str, symbol: &str, typeid: &str) {
+    let mds = attached_metadata(ir, symbol, "type");
+    assert!(
+        mds.iter().any(|md| ir.contains(&format!("!{md} = !{{i64 0, !\"{typeid}\"}}"))),
+        "`{symbol}` doesn't have the type metadata identifier of its declaration"
+    );
+}
+
+fn main() {
+    let (paths, symbols): (Vec<_>, Vec<_>) = ALLOCATOR_SHIMS.into_iter().unzip();
+
+    let typeids = print_typeids("main.rs", "kcfi", &paths);
+    let ir = emit_ir("main.rs", "kcfi");
+    for (symbol, (_, kcfi_typeid)) in symbols.iter().zip(typeids) {
+        check_kcfi_typeid(&ir, symbol, kcfi_typeid);
+    }
+
+    let typeids = print_typeids("main.rs", "cfi", &paths);
+    let ir = emit_ir("main.rs", "cfi");
+    for (symbol, (typeid, _)) in symbols.iter().zip(typeids) {
+        check_typeid(&ir, symbol, &typeid);
+    }
+
+    let (_, kcfi_typeid) = print_typeids("panic.rs", "kcfi", &["panic_impl"]).pop().unwrap();
+    let ir = emit_ir("panic.rs", "kcfi");
+    check_kcfi_typeid(&ir, "rust_begin_unwind", kcfi_typeid);
+
+    let (typeid, _) = print_typeids("panic.rs", "cfi", &["panic_impl"]).pop().unwrap();
+    let ir = emit_ir("panic.rs", "cfi");
+    check_typeid(&ir, "rust_begin_unwind", &typeid);
+}
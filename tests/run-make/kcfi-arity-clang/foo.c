@@ -0,0 +1,7 @@
+void foo0(void) {}
+
+int foo1(int a) { return a; }
+
+int foo2(int a, int b) { return a + b; }
+
+int foo3(int a, int b, int c) { return a + b + c; }
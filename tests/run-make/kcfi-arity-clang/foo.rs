@@ -0,0 +1,19 @@
+#![no_std]
+
+#[no_mangle]
+pub extern "C" fn foo0() {}
+
+#[no_mangle]
+pub extern "C" fn foo1(a: i32) -> i32 {
+    a
+}
+
+#[no_mangle]
+pub extern "C" fn foo2(a: i32, b: i32) -> i32 {
+    a.wrapping_add(b)
+}
+
+#[no_mangle]
+pub extern "C" fn foo3(a: i32, b: i32, c: i32) -> i32 {
+    a.wrapping_add(b).wrapping_add(c)
+}
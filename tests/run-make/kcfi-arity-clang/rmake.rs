@@ -0,0 +1,57 @@
+// Verifies that with `-Zsanitizer-kcfi-arity` the KCFI preambles of functions load the same KCFI
+// type metadata identifiers into the same registers as Clang `-fsanitize-kcfi-arity` does for the
+// C functions with the same types, i.e., that the arity is encoded as Clang encodes it.
+
+//@ needs-matching-clang
+//@ needs-sanitizer-kcfi
+//@ min-llvm-version: 21.0.0
+//@ only-x86_64
+//@ ignore-cross-compile
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+use std::process::Command;
+
+/// Returns the instruction loading the KCFI type metadata identifier in the preamble of `function`
+/// (e.g., `movl $-1666898348, %ecx`).
+fn preamble_load(asm: &str, function: &str) -> String {
+    let label = format!("__cfi_{function}:");
+    asm.lines()
+        .skip_while(|line| line.trim() != label)
+        .find(|line| line.trim_start().starts_with("movl"))
+        .unwrap_or_else(|| panic!("no KCFI preamble for `{function}`"))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn main() {
+    let c_asm = tmp_dir().join("foo_c.s");
+    let status = Command::new(std::env::var("CLANG").unwrap())
+        .args(["-x", "c", "foo.c", "-S", "-O1", "-o"])
+        .arg(&c_asm)
+        .args(["-fsanitize=kcfi", "-fsanitize-kcfi-arity"])
+        .arg("-fsanitize-cfi-icall-experimental-normalize-integers")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    rustc()
+        .input("foo.rs")
+        .crate_type("lib")
+        .emit("asm")
+        .opt()
+        .arg("-Cpanic=abort")
+        .arg("-Zsanitizer=kcfi")
+        .arg("-Zsanitizer-kcfi-arity")
+        .arg("-Zsanitizer-cfi-normalize-integers")
+        .run();
+
+    let c_asm = fs::read_to_string(c_asm).unwrap();
+    let rust_asm = fs::read_to_string(tmp_dir().join("foo.s")).unwrap();
+    for function in ["foo0", "foo1", "foo2", "foo3"] {
+        assert_eq!(preamble_load(&rust_asm, function), preamble_load(&c_asm, function));
+    }
+}
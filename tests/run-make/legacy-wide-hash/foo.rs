@@ -0,0 +1,25 @@
+#![crate_type = "rlib"]
+
+pub struct Wrapper<T>(pub T);
+
+#[inline(never)]
+pub fn generic<T>(value: T) -> Wrapper<T> {
+    Wrapper(value)
+}
+
+macro_rules! instantiate {
+    ($($name:ident: $ty:ty,)*) => {
+        $(
+            pub fn $name(value: $ty) -> Wrapper<$ty> {
+                generic(value)
+            }
+        )*
+    };
+}
+
+instantiate! {
+    a: u8, b: u16, c: u32, d: u64, e: u128, f: usize,
+    g: i8, h: i16, i: i32, j: i64, k: i128, l: isize,
+    m: (u8, u8), n: (u8, u16), o: [u8; 1], p: [u8; 2],
+    q: &'static str, r: &'static [u8], s: Option<u8>, t: Option<u16>,
+}
@@ -0,0 +1,57 @@
+// Regression test for the collisions `-Zsymbol-mangling-wide-legacy-hash` guards against: checks
+// that it gives the legacy symbols of many instantiations of a generic function distinct 128-bit
+// hashes, and that their lower 64 bits are the hashes the default 64-bit form would use, so that
+// the default stays compatible with the symbols of existing builds.
+
+extern crate run_make_support;
+
+use std::collections::BTreeSet;
+
+use run_make_support::{rustc, tmp_dir};
+
+/// Returns the hash of every legacy symbol of `foo.rs`, keyed by its demangled symbol without the
+/// hash.
+fn hashes(wide: bool) -> Vec<(String, String)> {
+    let path = tmp_dir().join(if wide { "wide.txt" } else { "narrow.txt" });
+    let mut cmd = rustc();
+    cmd.input("foo.rs")
+        .arg("-Zunstable-options")
+        .arg("-Csymbol-mangling-version=legacy")
+        .arg(format!("-Zemit-symbol-map={}", path.display()));
+    if wide {
+        cmd.arg("-Zsymbol-mangling-wide-legacy-hash");
+    }
+    cmd.run();
+
+    let (len, digits) = if wide { ("33h", 32) } else { ("17h", 16) };
+    let mut hashes: Vec<_> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let symbol = line.split('\t').next().unwrap();
+            let (path, hash) = symbol.strip_suffix('E').unwrap().rsplit_once(len).unwrap();
+            assert_eq!(hash.len(), digits, "unexpected hash in `{symbol}`");
+            assert!(hash.bytes().all(|b| b.is_ascii_hexdigit()), "unexpected hash in `{symbol}`");
+            (path.to_string(), hash.to_string())
+        })
+        .collect();
+    hashes.sort();
+    hashes.dedup();
+    hashes
+}
+
+fn main() {
+    let narrow = hashes(false);
+    let wide = hashes(true);
+    assert!(wide.len() >= 20, "expected every instantiation to be in the symbol map");
+
+    let distinct: BTreeSet<_> = wide.iter().map(|(_, hash)| hash).collect();
+    assert_eq!(distinct.len(), wide.len(), "colliding wide hashes: {wide:#?}");
+
+    assert_eq!(narrow.len(), wide.len());
+    for ((narrow_path, narrow_hash), (wide_path, wide_hash)) in narrow.iter().zip(&wide) {
+        assert_eq!(narrow_path, wide_path);
+        assert_eq!(narrow_hash, &wide_hash[16..], "`{wide_path}` changed its 64-bit hash");
+    }
+}
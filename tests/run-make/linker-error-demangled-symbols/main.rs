@@ -0,0 +1,8 @@
+extern "Rust" {
+    #[link_name = "_RNvCs1234_3bar3qux"]
+    fn qux();
+}
+
+fn main() {
+    unsafe { qux() }
+}
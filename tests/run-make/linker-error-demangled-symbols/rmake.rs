@@ -0,0 +1,14 @@
+// Verifies that linker errors about undefined Rust symbols show the demangled paths of the symbols.
+
+//@ ignore-cross-compile
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+fn main() {
+    let output = rustc().input("main.rs").run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("the Rust symbols in the linker output demangle to:"));
+    assert!(stderr.contains("_RNvCs1234_3bar3qux`: `bar::qux`"));
+}
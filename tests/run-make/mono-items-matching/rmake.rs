@@ -0,0 +1,41 @@
+// Verifies that `-Zprint-mono-items-matching` lists the mono items whose paths match a glob
+// pattern, with their symbols and codegen units.
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+/// Returns the lines printed for the mono items whose paths match `pattern`.
+fn matching(pattern: &str) -> Vec<Vec<String>> {
+    let output = rustc()
+        .input("foo.rs")
+        .arg("-Csymbol-mangling-version=v0")
+        .arg(format!("-Zprint-mono-items-matching={pattern}"))
+        .run();
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.split('\t').map(str::to_string).collect())
+        .collect()
+}
+
+fn main() {
+    let bar = matching("foo::bar::*");
+    assert_eq!(bar.len(), 2, "{bar:?}");
+    assert_eq!(bar[0][0], "fn bar::baz");
+    assert!(bar[0][1].starts_with("_R"));
+    assert!(bar[0][2].ends_with("::bar::baz"));
+    assert!(!bar[0][3].is_empty());
+    assert_eq!(bar[1][0], "fn bar::qux");
+
+    let new = matching("*::new");
+    assert_eq!(new.len(), 1, "{new:?}");
+    assert_eq!(new[0][0], "fn Foo::new");
+
+    let generic = matching("foo::generic");
+    assert_eq!(generic.len(), 1, "{generic:?}");
+    assert_eq!(generic[0][0], "fn generic::<u32>");
+    assert!(generic[0][2].ends_with("::generic::<u32>"));
+
+    assert!(matching("bar::*").is_empty());
+}
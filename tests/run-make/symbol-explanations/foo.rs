@@ -0,0 +1,19 @@
+#![crate_type = "lib"]
+
+pub mod bar {
+    pub fn baz() {}
+}
+
+pub struct Foo;
+
+impl Foo {
+    pub fn new() -> Foo {
+        Foo
+    }
+}
+
+pub fn generic<T>(_: T) {}
+
+pub fn user() {
+    generic(1u32);
+}
@@ -0,0 +1,54 @@
+// Verifies that `-Zprint-symbol-explanations` underlines the productions of v0 symbols that it
+// describes.
+
+extern crate run_make_support;
+
+use run_make_support::rustc;
+
+/// Returns the explanation whose symbol is described as `label`.
+fn explanation<'a>(stdout: &'a str, label: &str) -> Vec<&'a str> {
+    stdout
+        .split("\n\n")
+        .map(|explanation| explanation.lines().collect::<Vec<_>>())
+        .find(|lines| lines.iter().skip(1).any(|line| line.ends_with(&format!("^ {label}"))))
+        .unwrap_or_else(|| panic!("no explanation for {label} in:\n{stdout}"))
+}
+
+/// Returns the part of the symbol of `explanation` that is underlined for `label`.
+fn underlined<'a>(explanation: &[&'a str], label: &str) -> &'a str {
+    let line = explanation
+        .iter()
+        .skip(1)
+        .find(|line| line.ends_with(&format!("^ {label}")))
+        .unwrap_or_else(|| panic!("no line for {label} in:\n{}", explanation.join("\n")));
+    let start = line.len() - line.trim_start().len();
+    let len = line[start..].chars().take_while(|&c| c == '^').count();
+    &explanation[0][start..start + len]
+}
+
+fn main() {
+    let output = rustc()
+        .input("foo.rs")
+        .arg("-Csymbol-mangling-version=v0")
+        .arg("-Zprint-symbol-explanations")
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let baz = explanation(&stdout, "path `bar::baz`");
+    assert_eq!(underlined(&baz, "v0 prefix"), "_R");
+    assert_eq!(underlined(&baz, "path `bar::baz`"), &baz[0][2..]);
+    let bar = underlined(&baz, "path `bar`");
+    assert!(bar.starts_with("NtC") && bar.ends_with("_3foo3bar"));
+    let foo = underlined(&baz, "crate root `foo`");
+    assert!(foo.starts_with("Cs") && foo.ends_with("_3foo"));
+
+    let new = explanation(&stdout, "path `Foo::new`");
+    let self_ty = underlined(&new, "self type `Foo`");
+    assert!(self_ty.starts_with("NtB") && self_ty.ends_with("_3Foo"));
+
+    let generic = explanation(&stdout, "path `generic::<u32>`");
+    assert_eq!(underlined(&generic, "generic argument `u32`"), "m");
+    let instantiating_crate = underlined(&generic, "instantiating crate `foo`");
+    assert!(instantiating_crate.starts_with('B'));
+    assert!(generic[0].ends_with(instantiating_crate));
+}
@@ -0,0 +1,8 @@
+#![crate_type = "rlib"]
+
+pub mod a_rather_long_module_name_for_testing {
+    #[inline(never)]
+    pub fn a_rather_long_function_name_for_testing() -> u32 {
+        42
+    }
+}
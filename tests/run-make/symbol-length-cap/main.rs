@@ -0,0 +1,7 @@
+extern crate capped;
+
+use capped::a_rather_long_module_name_for_testing::a_rather_long_function_name_for_testing;
+
+fn main() {
+    assert_eq!(a_rather_long_function_name_for_testing(), 42);
+}
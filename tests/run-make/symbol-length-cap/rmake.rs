@@ -0,0 +1,33 @@
+// Verifies that `-Zsymbol-length-cap` replaces the tails of longer symbols with a hash, that
+// `-Zemit-symbol-map` maps the capped symbols back to the full symbols, and that downstream crates
+// compiled without a cap agree on the capped symbols of the upstream crate.
+
+//@ ignore-cross-compile
+
+extern crate run_make_support;
+
+use run_make_support::{run, rustc, tmp_dir};
+
+fn main() {
+    let path = tmp_dir().join("symbols.txt");
+    rustc()
+        .input("capped.rs")
+        .arg("-Csymbol-mangling-version=v0")
+        .arg("-Zsymbol-length-cap=64")
+        .arg(format!("-Zemit-symbol-map={}", path.display()))
+        .run();
+    rustc().input("main.rs").arg("-Csymbol-mangling-version=v0").run();
+    run("main");
+
+    let map = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = map.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(lines.len(), 1);
+    let fields: Vec<_> = lines[0].split('\t').collect();
+    let (capped, full) = (fields[0], fields[1]);
+    assert_eq!(capped.len(), 64);
+    assert!(full.len() > 64);
+    assert!(full.ends_with("a_rather_long_function_name_for_testing"));
+    // The capped symbol keeps the head of the full symbol, followed by `_h` and a 64-bit hash
+    assert_eq!(capped[..46], full[..46]);
+    assert!(capped[46..].starts_with("_h"));
+}
@@ -0,0 +1,50 @@
+//@ run-pass
+// Test that the encodings of types built from arbitrary bytes are deterministic and valid type
+// metadata identifier productions, and that their substitutions refer to the components they
+// replace (i.e., that `check_arbitrary_ty_encoding` doesn't panic).
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_symbol_mangling::typeid::check_arbitrary_ty_encoding;
+
+struct CheckEncodings;
+
+impl Callbacks for CheckEncodings {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            // A linear congruential generator, so the inputs are the same on every run.
+            let mut state: u32 = 1;
+            let mut next = || {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8
+            };
+            for len in 0..1024 {
+                let data: Vec<u8> = (0..len % 64).map(|_| next()).collect();
+                check_arbitrary_ty_encoding(tcx, &data);
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let path = "cfi_arbitrary_ty_encoding_input.rs";
+    std::fs::write(path, "").unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut CheckEncodings).run().unwrap();
+}
@@ -0,0 +1,125 @@
+//@ run-pass
+// Test that the type metadata identifiers of `extern "C"` functions are the ones Clang computes for
+// their C counterparts (see `rustc_symbol_mangling::typeid::c_prototype`), and that the known
+// differences are still known.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_symbol_mangling::typeid::c_prototype::{typeid_for_c_prototype, CDataModel};
+use rustc_symbol_mangling::typeid::{typeid_for_instance, TypeIdOptions};
+
+const INPUT: &str = r#"
+#![feature(c_variadic)]
+
+use core::ffi::{c_char, c_int, c_long};
+
+#[repr(C)]
+pub struct Point {
+    pub x: c_int,
+    pub y: c_int,
+}
+
+#[no_mangle]
+pub extern "C" fn f1() {}
+#[no_mangle]
+pub extern "C" fn f2(x: c_int) -> c_int { x }
+#[no_mangle]
+pub extern "C" fn f3(_: *const u8, _: *mut i64) {}
+#[no_mangle]
+pub extern "C" fn f4(_: Point, _: *mut Point) {}
+#[no_mangle]
+pub extern "C" fn f5(_: bool, _: u16, _: i8) -> u64 { 0 }
+#[no_mangle]
+pub unsafe extern "C" fn f6(_: *const u8, _args: ...) {}
+#[no_mangle]
+pub extern "C" fn f7(_: extern "C" fn(c_int) -> c_int) {}
+#[no_mangle]
+pub extern "C" fn f8(_: *const c_char, _: c_long) {}
+#[no_mangle]
+pub extern "C" fn f9(_: extern "C" fn(), _: extern "C" fn()) {}
+"#;
+
+// The C counterparts of the functions, and whether their type metadata identifiers are expected to
+// be identical without and with `TypeIdOptions::GENERALIZE_POINTERS`, respectively.
+const PROTOTYPES: &[(&str, &str, bool, bool)] = &[
+    ("f1", "void f1(void)", true, true),
+    ("f2", "int f2(int)", true, true),
+    ("f3", "void f3(const unsigned char *, int64_t *)", true, true),
+    ("f4", "void f4(struct Point, struct Point *)", true, true),
+    ("f5", "uint64_t f5(_Bool, unsigned short, signed char)", true, true),
+    ("f6", "void f6(const unsigned char *, ...)", true, true),
+    // Known difference: Rust generalizes function pointers into `*const ()` (i.e., `PKv`), and
+    // Clang into `void *` (i.e., `Pv`).
+    ("f7", "void f7(int (*)(int))", true, false),
+    ("f8", "void f8(const char *, long)", true, true),
+    // Known differences: Clang adds the function types of function pointers to the substitution
+    // dictionary, and Rust doesn't (i.e., `_ZTSFvPFvvES0_E` vs `_ZTSFvPFvvES_E`), and the
+    // generalized function pointers differ as for `f7`.
+    ("f9", "void f9(void (*)(void), void (*)(void))", false, false),
+];
+
+struct CheckConformance;
+
+impl Callbacks for CheckConformance {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_conformance);
+        Compilation::Stop
+    }
+}
+
+fn check_conformance(tcx: TyCtxt<'_>) {
+    let data_model = CDataModel::for_target(&tcx.sess.target);
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::Fn {
+            continue;
+        }
+        let name = tcx.item_name(def_id.to_def_id());
+        let &(_, prototype, identical, identical_generalized) =
+            PROTOTYPES.iter().find(|(fn_name, ..)| *fn_name == name.as_str()).unwrap();
+        let instance = Instance::mono(tcx, def_id.to_def_id());
+        for (options, identical) in [
+            (TypeIdOptions::NORMALIZE_INTEGERS, identical),
+            (
+                TypeIdOptions::NORMALIZE_INTEGERS | TypeIdOptions::GENERALIZE_POINTERS,
+                identical_generalized,
+            ),
+        ] {
+            let typeid = typeid_for_instance(tcx, instance, options);
+            let expected = typeid_for_c_prototype(prototype, data_model, options).unwrap();
+            if identical {
+                assert_eq!(typeid, expected, "`{prototype}` with {options:?}");
+            } else {
+                assert_ne!(typeid, expected, "`{prototype}` with {options:?}");
+            }
+        }
+        checked += 1;
+    }
+    assert_eq!(checked, PROTOTYPES.len());
+}
+
+fn main() {
+    let path = "cfi_clang_conformance_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut CheckConformance).run().unwrap();
+}
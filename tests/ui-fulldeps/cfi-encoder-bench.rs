@@ -0,0 +1,165 @@
+//@ run-pass
+// Benchmark of the CFI type metadata identifier encoder (i.e., of transforming and encoding types
+// and function signatures), on representative deep generic signatures.
+//
+// It only checks that the encodings are deterministic by default. To measure the encoder (e.g., to
+// evaluate a change to `rustc_symbol_mangling::typeid`), set `RUSTC_CFI_ENCODER_BENCH_ITERATIONS`
+// and run it with `--nocapture`, which prints the time taken by each signature with each set of
+// options:
+//
+//     RUSTC_CFI_ENCODER_BENCH_ITERATIONS=10000 ./x test tests/ui-fulldeps \
+//         --test-args cfi-encoder-bench --test-args --nocapture
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use std::time::{Duration, Instant};
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::TyCtxt;
+use rustc_symbol_mangling::typeid::{transform_and_encode_ty, typeid_for_fnsig, TypeIdOptions};
+
+// Signatures with deeply nested generic types, trait objects, references, arrays, and function
+// pointers, as found in real crates, and a wide signature whose types share subtrees.
+const INPUT: &str = r#"
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub struct Wrapper<T, const N: usize>(pub [T; N]);
+
+pub trait Visitor<T> {
+    type Output;
+    fn visit(&mut self, value: &T) -> Self::Output;
+}
+
+pub fn nested_options(_: Option<Option<Option<Result<Vec<u8>, String>>>>) {}
+
+pub fn collections(
+    _: HashMap<String, Vec<(u32, Option<Box<[i64]>>)>>,
+    _: Vec<Vec<Vec<Vec<&'static str>>>>,
+) -> Result<HashMap<u64, HashMap<u64, Vec<String>>>, Box<dyn std::error::Error + Send + Sync>> {
+    loop {}
+}
+
+pub fn shared(
+    _: Arc<Mutex<HashMap<String, Rc<Vec<Arc<[u8]>>>>>>,
+) -> Option<Arc<dyn Fn(u8) -> u8>> {
+    None
+}
+
+pub fn tuples(_: ((u8, (u16, (u32, (u64, (u128, ()))))), [(i8, &mut [&[u8; 16]]); 4])) {}
+
+pub fn fn_ptrs(
+    _: fn(fn(&mut Vec<u8>) -> usize, extern "C" fn(*const u8, usize) -> i32) -> fn() -> bool,
+) -> unsafe fn(&dyn Fn(&str) -> Option<&str>) {
+    loop {}
+}
+
+pub fn trait_objects(
+    _: &mut dyn Visitor<Wrapper<Option<u8>, 8>, Output = Result<(), String>>,
+    _: Box<dyn Iterator<Item = (usize, &'static dyn std::fmt::Debug)> + Send>,
+) {
+}
+
+pub fn wide(
+    _: &HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>,
+    _: &HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>,
+    _: Vec<Option<Arc<Mutex<Vec<u8>>>>>,
+    _: Option<Arc<Mutex<Vec<u8>>>>,
+    _: Arc<Mutex<Vec<u8>>>,
+    _: &mut HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>,
+    _: Box<[HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>]>,
+    _: Rc<HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>>,
+) -> Option<HashMap<String, Vec<Option<Arc<Mutex<Vec<u8>>>>>>> {
+    None
+}
+
+pub fn arrays(_: [[[Wrapper<[u8; 4], 2>; 2]; 3]; 4], _: &[&[&[f64]]]) -> [Option<char>; 32] {
+    loop {}
+}
+"#;
+
+struct Bench;
+
+impl Callbacks for Bench {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(bench);
+        Compilation::Stop
+    }
+}
+
+fn bench(tcx: TyCtxt<'_>) {
+    let iterations: Option<u32> = std::env::var("RUSTC_CFI_ENCODER_BENCH_ITERATIONS")
+        .ok()
+        .map(|iterations| iterations.parse().expect("invalid number of iterations"));
+    let all_options = [
+        TypeIdOptions::empty(),
+        TypeIdOptions::GENERALIZE_POINTERS | TypeIdOptions::NORMALIZE_INTEGERS,
+        TypeIdOptions::ERASE_CONST | TypeIdOptions::GENERALIZE_ARRAYS,
+    ];
+
+    let mut benched = 0;
+    let mut total = Duration::ZERO;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::Fn {
+            continue;
+        }
+        let name = tcx.item_name(def_id.to_def_id());
+        let fn_sig = tcx.fn_sig(def_id).instantiate_identity();
+        for options in all_options {
+            // The types of the parameters and return type on their own, and the whole signature
+            let encode = || {
+                let tys = tcx.instantiate_bound_regions_with_erased(fn_sig).inputs_and_output;
+                let encodings: Vec<_> =
+                    tys.iter().map(|ty| transform_and_encode_ty(tcx, ty, options)).collect();
+                (encodings, typeid_for_fnsig(tcx, fn_sig, options))
+            };
+            let expected = encode();
+
+            let Some(iterations) = iterations else {
+                assert_eq!(encode(), expected, "encoding of `{name}` isn't deterministic");
+                continue;
+            };
+            let start = Instant::now();
+            for _ in 0..iterations {
+                assert_eq!(encode(), expected, "encoding of `{name}` isn't deterministic");
+            }
+            let elapsed = start.elapsed();
+            total += elapsed;
+            println!(
+                "{name:>16} {options:?}: {:.2?}/iter ({})",
+                elapsed / iterations.max(1),
+                expected.1
+            );
+        }
+        benched += 1;
+    }
+    assert_eq!(benched, 8);
+    if iterations.is_some() {
+        println!("{:>16}: {total:.2?}", "total");
+    }
+}
+
+fn main() {
+    let path = "cfi_encoder_bench_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut Bench).run().unwrap();
+}
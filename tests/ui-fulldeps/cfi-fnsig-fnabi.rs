@@ -0,0 +1,93 @@
+//@ run-pass
+// Test that the type metadata identifiers of function pointer types computed from their signatures
+// (i.e., without computing their FnAbi) are the ones computed from their FnAbi when no parameter
+// is ignored, including for the "rust-call" and "C" calling conventions, C-variadic functions,
+// functions returning `!`, and repr(transparent) parameter and return types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_symbol_mangling::typeid::{typeid_for_fnabi, typeid_for_fnsig, TypeIdOptions};
+
+const INPUT: &str = r#"
+#![feature(unboxed_closures)]
+
+#[repr(transparent)]
+pub struct Transparent(u32);
+
+pub struct S(u64);
+
+pub type Plain = fn(i32, &u8, *const S) -> u64;
+pub type RustCall = extern "rust-call" fn(&S, (u8, u16)) -> u32;
+pub type RustCallUnit = extern "rust-call" fn(&mut S, ());
+pub type CVariadic = unsafe extern "C" fn(i32, ...) -> i32;
+pub type CVariadicPtr = unsafe extern "C" fn(*mut S, ...);
+pub type Never = fn(u32) -> !;
+pub type CNever = extern "C" fn(*const u8) -> !;
+pub type RustTransparent = fn(Transparent, &Transparent) -> Transparent;
+pub type CTransparent = extern "C" fn(Transparent, *mut Transparent) -> Transparent;
+"#;
+
+const OPTIONS: [TypeIdOptions; 4] = [
+    TypeIdOptions::empty(),
+    TypeIdOptions::NORMALIZE_INTEGERS,
+    TypeIdOptions::GENERALIZE_POINTERS,
+    TypeIdOptions::NORMALIZE_INTEGERS.union(TypeIdOptions::GENERALIZE_POINTERS),
+];
+
+struct CheckFnSigs;
+
+impl Callbacks for CheckFnSigs {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_fn_sigs);
+        Compilation::Stop
+    }
+}
+
+fn check_fn_sigs(tcx: TyCtxt<'_>) {
+    let param_env = ty::ParamEnv::reveal_all();
+
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::TyAlias {
+            continue;
+        }
+        let name = tcx.item_name(def_id.to_def_id());
+        let fn_sig = tcx.type_of(def_id).instantiate_identity().fn_sig(tcx);
+        let fn_abi = tcx.fn_abi_of_fn_ptr(param_env.and((fn_sig, ty::List::empty()))).unwrap();
+        for options in OPTIONS {
+            assert_eq!(
+                typeid_for_fnsig(tcx, fn_sig, options),
+                typeid_for_fnabi(tcx, fn_abi, options),
+                "`{name}` with {options:?}"
+            );
+        }
+        checked += 1;
+    }
+    assert_eq!(checked, 9);
+}
+
+fn main() {
+    let path = "cfi_fnsig_fnabi_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut CheckFnSigs).run().unwrap();
+}
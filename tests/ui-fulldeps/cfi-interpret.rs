@@ -0,0 +1,151 @@
+//@ run-pass
+// Test that the `rustc_symbol_mangling::typeid::interpret` helpers interpreters emulate LLVM CFI
+// checks with accept indirect calls through function pointers of the types of the functions
+// called (including closures through their `FnOnce` shims and methods with their concrete self
+// types) and reject the others, and that these functions are consistent with the function pointer
+// types of pointers to them.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_span;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, Instance, PolyFnSig, TyCtxt};
+use rustc_span::def_id::DefId;
+use rustc_symbol_mangling::typeid::interpret::{
+    is_valid_fn_ptr_call, typeid_for_callee, typeid_for_fn_ptr,
+};
+use rustc_symbol_mangling::typeid::{typeid_inconsistency, TypeIdOptions};
+
+const INPUT: &str = r#"
+pub type BinOp = fn(i32, i32) -> i32;
+pub type UnOp = fn(u32) -> u32;
+pub type Method = fn(&S, u8) -> u8;
+
+pub fn add(a: i32, b: i32) -> i32 { a + b }
+pub fn neg(a: i64) -> i64 { -a }
+
+pub struct S;
+
+impl S {
+    pub fn inherent(&self, x: u8) -> u8 { x }
+}
+
+pub trait Trait {
+    fn method(&self, x: u8) -> u8;
+}
+
+impl Trait for S {
+    fn method(&self, x: u8) -> u8 { x }
+}
+
+pub fn closure() -> u32 {
+    let f: UnOp = |x| x + 1;
+    f(1)
+}
+"#;
+
+struct CheckInterpret;
+
+impl Callbacks for CheckInterpret {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_interpret);
+        Compilation::Stop
+    }
+}
+
+/// Returns the definition of the specified kind with the specified name (the one in the impl for
+/// trait methods).
+fn find(tcx: TyCtxt<'_>, kind: DefKind, name: &str) -> DefId {
+    tcx.hir_crate_items(())
+        .definitions()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| {
+            tcx.def_kind(def_id) == kind
+                && tcx.item_name(def_id).as_str() == name
+                && (kind != DefKind::AssocFn || tcx.impl_of_method(def_id).is_some())
+        })
+        .unwrap_or_else(|| panic!("no {kind:?} named `{name}`"))
+}
+
+fn fn_ptr_sig<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> PolyFnSig<'tcx> {
+    tcx.type_of(find(tcx, DefKind::TyAlias, name)).instantiate_identity().fn_sig(tcx)
+}
+
+/// Returns the `FnOnce` shim of the only closure, as closures are called through function pointers.
+fn closure_shim(tcx: TyCtxt<'_>) -> Instance<'_> {
+    let def_id = tcx
+        .hir()
+        .body_owners()
+        .map(|def_id| def_id.to_def_id())
+        .find(|&def_id| tcx.def_kind(def_id) == DefKind::Closure)
+        .expect("no closure");
+    let closure_ty = tcx.type_of(def_id).instantiate_identity();
+    let ty::Closure(_, args) = *closure_ty.kind() else {
+        panic!("unexpected closure type `{closure_ty}`");
+    };
+    Instance::fn_once_adapter_instance(tcx, def_id, args)
+}
+
+fn check_interpret(tcx: TyCtxt<'_>) {
+    let options = TypeIdOptions::empty();
+
+    let bin_op = fn_ptr_sig(tcx, "BinOp");
+    let un_op = fn_ptr_sig(tcx, "UnOp");
+    let method = fn_ptr_sig(tcx, "Method");
+
+    let add = Instance::mono(tcx, find(tcx, DefKind::Fn, "add"));
+    let neg = Instance::mono(tcx, find(tcx, DefKind::Fn, "neg"));
+    let inherent = Instance::mono(tcx, find(tcx, DefKind::AssocFn, "inherent"));
+    let trait_method = Instance::mono(tcx, find(tcx, DefKind::AssocFn, "method"));
+    let closure_shim = closure_shim(tcx);
+
+    let bin_op_typeid = typeid_for_fn_ptr(tcx, bin_op, options);
+    assert_eq!(typeid_for_callee(tcx, add, options).as_ref(), Some(&bin_op_typeid));
+    assert_ne!(typeid_for_callee(tcx, neg, options).as_ref(), Some(&bin_op_typeid));
+
+    for (fn_ptr_sig, callee, valid) in [
+        (bin_op, add, true),
+        (bin_op, neg, false),
+        (un_op, add, false),
+        (un_op, closure_shim, true),
+        (bin_op, closure_shim, false),
+        (method, inherent, true),
+        (method, trait_method, true),
+        (un_op, trait_method, false),
+    ] {
+        assert_eq!(
+            is_valid_fn_ptr_call(tcx, fn_ptr_sig, callee, options),
+            valid,
+            "call to `{callee}` through `{fn_ptr_sig}`"
+        );
+    }
+
+    // The functions are defined with the type metadata identifiers of pointers to them.
+    for instance in [add, neg, inherent, trait_method] {
+        assert!(typeid_inconsistency(tcx, instance, options).is_none(), "`{instance}`");
+    }
+}
+
+fn main() {
+    let path = "cfi_interpret_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut CheckInterpret).run().unwrap();
+}
@@ -0,0 +1,82 @@
+//@ run-pass
+// Test that drivers can replace the CFI type metadata identifiers computed by the compiler by
+// overriding the `override_cfi_typeid` hook.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::{Config, Queries};
+use rustc_middle::query::TyCtxtAt;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_middle::util::Providers;
+use rustc_session::Session;
+use rustc_symbol_mangling::typeid::{typeid_for_instance, TypeIdOptions};
+
+struct OverrideTypeIds;
+
+impl Callbacks for OverrideTypeIds {
+    fn config(&mut self, config: &mut Config) {
+        config.override_queries = Some(override_queries);
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_typeids);
+        Compilation::Stop
+    }
+}
+
+fn override_queries(_session: &Session, providers: &mut Providers) {
+    providers.hooks.override_cfi_typeid = override_cfi_typeid;
+}
+
+// Salts the type metadata identifiers of the functions named `salted`, and leaves the others as is.
+fn override_cfi_typeid<'tcx>(
+    tcx: TyCtxtAt<'tcx>,
+    instance: Option<Instance<'tcx>>,
+    _options: u32,
+    typeid: &str,
+) -> Option<String> {
+    let instance = instance?;
+    (tcx.item_name(instance.def_id()).as_str() == "salted").then(|| format!("{typeid}.salted"))
+}
+
+fn check_typeids(tcx: TyCtxt<'_>) {
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::Fn {
+            continue;
+        }
+        let instance = Instance::mono(tcx, def_id.to_def_id());
+        let typeid = typeid_for_instance(tcx, instance, TypeIdOptions::empty());
+        match tcx.item_name(def_id.to_def_id()).as_str() {
+            "salted" => assert_eq!(typeid, "_ZTSFvu3i32E.salted"),
+            _ => assert_eq!(typeid, "_ZTSFvu3i32E"),
+        }
+        checked += 1;
+    }
+    assert_eq!(checked, 2);
+}
+
+fn main() {
+    let path = "cfi_override_typeid_input.rs";
+    std::fs::write(path, "pub fn salted(_: i32) {}\npub fn unsalted(_: i32) {}\n").unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut OverrideTypeIds).run().unwrap();
+}
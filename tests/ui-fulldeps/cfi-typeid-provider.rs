@@ -0,0 +1,129 @@
+//@ run-pass
+// Test that `ItaniumTypeIdProvider` provides the type metadata identifiers, KCFI type metadata
+// identifiers, and vtable identifiers the compiler itself computes (i.e., that backends using it
+// interoperate with the LLVM backend).
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_symbol_mangling::api::{ItaniumTypeIdProvider, TypeIdOptions, TypeIdProvider};
+use rustc_symbol_mangling::typeid::kcfi::{
+    kcfi_typeid_for_fnabi, kcfi_typeid_for_fnsig, kcfi_typeid_for_instance,
+};
+use rustc_symbol_mangling::typeid::{typeid_for_fnabi, typeid_for_fnsig, typeid_for_instance};
+use rustc_symbol_mangling::typeid_for_trait_ref;
+
+const INPUT: &str = r#"
+pub struct S(u32);
+
+pub trait Trait {
+    fn method(&self, x: *const u8) -> S;
+}
+
+impl Trait for S {
+    fn method(&self, _: *const u8) -> S { S(self.0) }
+}
+
+pub fn f(_: &mut S, _: u64, _: fn(i8) -> bool) -> usize { 0 }
+pub extern "C" fn g(_: *mut S, _: u16) {}
+"#;
+
+const OPTIONS: [TypeIdOptions; 4] = [
+    TypeIdOptions::empty(),
+    TypeIdOptions::NORMALIZE_INTEGERS,
+    TypeIdOptions::GENERALIZE_POINTERS,
+    TypeIdOptions::USE_CONCRETE_SELF,
+];
+
+struct CheckProvider;
+
+impl Callbacks for CheckProvider {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_provider);
+        Compilation::Stop
+    }
+}
+
+fn check_provider(tcx: TyCtxt<'_>) {
+    let provider = ItaniumTypeIdProvider;
+    let param_env = ty::ParamEnv::reveal_all();
+
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        let def_id = def_id.to_def_id();
+        match tcx.def_kind(def_id) {
+            DefKind::Fn => {}
+            // The method of the impl, not the declaration of the trait
+            DefKind::AssocFn if tcx.impl_of_method(def_id).is_some() => {}
+            DefKind::Trait => {
+                let trait_ref =
+                    ty::Binder::dummy(ty::ExistentialTraitRef { def_id, args: ty::List::empty() });
+                assert_eq!(
+                    provider.typeid_for_vtable(tcx, trait_ref),
+                    typeid_for_trait_ref(tcx, trait_ref)
+                );
+                checked += 1;
+                continue;
+            }
+            _ => continue,
+        }
+
+        let instance = Instance::mono(tcx, def_id);
+        let fn_sig = tcx.fn_sig(def_id).instantiate_identity();
+        let fn_abi = tcx.fn_abi_of_instance(param_env.and((instance, ty::List::empty()))).unwrap();
+        for options in OPTIONS {
+            assert_eq!(
+                provider.typeid_for_instance(tcx, instance, options),
+                typeid_for_instance(tcx, instance, options)
+            );
+            assert_eq!(
+                provider.typeid_for_fnsig(tcx, fn_sig, options),
+                typeid_for_fnsig(tcx, fn_sig, options)
+            );
+            assert_eq!(
+                provider.typeid_for_fnabi(tcx, fn_abi, options),
+                typeid_for_fnabi(tcx, fn_abi, options)
+            );
+            assert_eq!(
+                provider.kcfi_typeid_for_instance(tcx, instance, options),
+                kcfi_typeid_for_instance(tcx, instance, options)
+            );
+            assert_eq!(
+                provider.kcfi_typeid_for_fnsig(tcx, fn_sig, options),
+                kcfi_typeid_for_fnsig(tcx, fn_sig, options)
+            );
+            assert_eq!(
+                provider.kcfi_typeid_for_fnabi(tcx, fn_abi, options),
+                kcfi_typeid_for_fnabi(tcx, fn_abi, options)
+            );
+        }
+        checked += 1;
+    }
+    // `Trait`, `<S as Trait>::method`, `f`, and `g`
+    assert_eq!(checked, 4);
+}
+
+fn main() {
+    let path = "cfi_typeid_provider_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    RunCompiler::new(&args, &mut CheckProvider).run().unwrap();
+}
@@ -0,0 +1,116 @@
+//@ run-pass
+// Test that the type metadata identifiers of functions with the "efiapi", "win64", and "sysv64"
+// calling conventions for x86_64-unknown-uefi are the ones Clang computes for their C counterparts
+// (i.e., the calling conventions aren't encoded, and repr(C) types are encoded as C types).
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ needs-llvm-components: x86
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_symbol_mangling::typeid::c_prototype::{typeid_for_c_prototype, CDataModel};
+use rustc_symbol_mangling::typeid::{typeid_for_instance, TypeIdOptions};
+
+const INPUT: &str = r#"
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[repr(C)]
+pub struct Guid {
+    pub data1: u32,
+    pub data2: u16,
+    pub data3: u16,
+    pub data4: [u8; 8],
+}
+
+pub extern "efiapi" fn f1(_: *const Guid, _: *mut *mut u8) -> u64 { 0 }
+pub extern "win64" fn f2(_: Guid, _: u32) -> i32 { 0 }
+pub extern "sysv64" fn f3(_: *mut u16, _: bool) {}
+pub extern "efiapi" fn f4(_: extern "efiapi" fn(*mut Guid) -> u64) {}
+pub extern "C" fn f5(_: extern "win64" fn(Guid), _: i8) {}
+"#;
+
+// The C counterparts of the functions, and whether their type metadata identifiers are expected to
+// be identical with `TypeIdOptions::GENERALIZE_POINTERS`.
+const PROTOTYPES: &[(&str, &str, bool)] = &[
+    ("f1", "uint64_t f1(const struct Guid *, uint8_t **)", true),
+    ("f2", "int32_t f2(struct Guid, uint32_t)", true),
+    ("f3", "void f3(uint16_t *, _Bool)", true),
+    // Known difference: Rust generalizes function pointers into `*const ()` (i.e., `PKv`), and
+    // Clang into `void *` (i.e., `Pv`) (see `cfi-clang-conformance.rs`).
+    ("f4", "void f4(uint64_t (*)(struct Guid *))", false),
+    ("f5", "void f5(void (*)(struct Guid), int8_t)", false),
+];
+
+struct CheckUefiTypeIds;
+
+impl Callbacks for CheckUefiTypeIds {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_typeids);
+        Compilation::Stop
+    }
+}
+
+fn check_typeids(tcx: TyCtxt<'_>) {
+    assert_eq!(tcx.sess.target.llvm_target, "x86_64-unknown-windows");
+    let data_model = CDataModel::for_target(&tcx.sess.target);
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::Fn {
+            continue;
+        }
+        let name = tcx.item_name(def_id.to_def_id());
+        let &(_, prototype, identical_generalized) =
+            PROTOTYPES.iter().find(|(fn_name, ..)| *fn_name == name.as_str()).unwrap();
+        let instance = Instance::mono(tcx, def_id.to_def_id());
+        for (options, identical) in [
+            (TypeIdOptions::NORMALIZE_INTEGERS, true),
+            (
+                TypeIdOptions::NORMALIZE_INTEGERS | TypeIdOptions::GENERALIZE_POINTERS,
+                identical_generalized,
+            ),
+        ] {
+            let typeid = typeid_for_instance(tcx, instance, options);
+            let expected = typeid_for_c_prototype(prototype, data_model, options).unwrap();
+            if identical {
+                assert_eq!(typeid, expected, "`{prototype}` with {options:?}");
+            } else {
+                assert_ne!(typeid, expected, "`{prototype}` with {options:?}");
+            }
+        }
+        checked += 1;
+    }
+    assert_eq!(checked, PROTOTYPES.len());
+}
+
+fn main() {
+    let path = "cfi_uefi_abis_input.rs";
+    std::fs::write(path, INPUT).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--target=x86_64-unknown-uefi".to_string(),
+        path.to_string(),
+    ];
+    RunCompiler::new(&args, &mut CheckUefiTypeIds).run().unwrap();
+}
@@ -0,0 +1,83 @@
+//@ run-pass
+// Test that drivers can compute the symbol names and type metadata identifiers of instances with
+// the `rustc_symbol_mangling::api` facade, and that they are the ones the compiler uses.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_symbol_mangling;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::interface::Compiler;
+use rustc_interface::Queries;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_symbol_mangling::api::{
+    kcfi_typeid_for_instance, symbol_name_for_instance, typeid_for_instance, typeid_options,
+    KcfiTypeId, TypeIdOptions,
+};
+
+struct CheckApi;
+
+impl Callbacks for CheckApi {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(check_api);
+        Compilation::Stop
+    }
+}
+
+fn check_api(tcx: TyCtxt<'_>) {
+    // Functions aren't instrumented with any option without `-Zsanitizer=cfi` or `kcfi`.
+    let (options, kcfi_options) = typeid_options(tcx.sess);
+    assert!(options.is_empty() && kcfi_options.is_empty());
+
+    let mut checked = 0;
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        if tcx.def_kind(def_id) != DefKind::Fn {
+            continue;
+        }
+        let instance = Instance::mono(tcx, def_id.to_def_id());
+        let symbol = symbol_name_for_instance(tcx, instance);
+        assert_eq!(symbol, tcx.symbol_name(instance).name);
+        match tcx.item_name(def_id.to_def_id()).as_str() {
+            "mangled" => assert!(symbol.starts_with("_RNv"), "unexpected symbol `{symbol}`"),
+            _ => assert_eq!(symbol, "unmangled"),
+        }
+
+        assert_eq!(typeid_for_instance(tcx, instance, options), "_ZTSFvu3i32E");
+        assert_eq!(
+            typeid_for_instance(tcx, instance, TypeIdOptions::NORMALIZE_INTEGERS),
+            "_ZTSFvu3i32E.normalized"
+        );
+        assert!(matches!(
+            kcfi_typeid_for_instance(tcx, instance, kcfi_options),
+            KcfiTypeId::U32(_)
+        ));
+        checked += 1;
+    }
+    assert_eq!(checked, 2);
+}
+
+fn main() {
+    let path = "symbol_mangling_api_input.rs";
+    std::fs::write(path, "pub fn mangled(_: i32) {}\n#[no_mangle]\npub fn unmangled(_: i32) {}\n")
+        .unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "-Csymbol-mangling-version=v0".to_string(),
+        path.to_string(),
+    ];
+    RunCompiler::new(&args, &mut CheckApi).run().unwrap();
+}
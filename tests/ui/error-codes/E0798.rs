@@ -0,0 +1,16 @@
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+//@ normalize-stderr-test: "u[0-9]+NtCs[0-9a-zA-Z]+_" -> "u[LEN]NtCs[HASH]_"
+
+#![feature(cfi_encoding)]
+
+#[cfi_encoding = ""] //~ ERROR E0798
+pub struct Type1(i32);
+
+pub fn foo(_: Type1) {}
+
+fn main() {
+    let f: fn(Type1) = foo;
+    f(Type1(0));
+}
@@ -0,0 +1,22 @@
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+use std::marker::ConstParamTy;
+
+#[derive(PartialEq, Eq, ConstParamTy)]
+pub struct Foo {
+    x: u8,
+}
+
+pub struct Bar<const F: Foo>; //~ ERROR E0800
+
+pub fn baz(_: &Bar<{ Foo { x: 1 } }>) {}
+
+fn main() {
+    let f: fn(&Bar<{ Foo { x: 1 } }>) = baz;
+    f(&Bar);
+}
@@ -0,0 +1,4 @@
+#![crate_type = "lib"]
+
+#[repr(C)]
+pub struct Xyz(pub i32);
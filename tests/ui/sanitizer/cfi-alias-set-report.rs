@@ -0,0 +1,17 @@
+// Verifies that `-Zcfi-alias-set-report` prints the sets of functions that share a type metadata
+// identifier, largest first.
+//
+//@ build-pass
+//@ compile-flags: -Zcfi-alias-set-report
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub fn bar(x: i32) -> i32 {
+    x + 1
+}
+
+pub fn baz() {}
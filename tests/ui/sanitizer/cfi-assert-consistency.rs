@@ -0,0 +1,47 @@
+// Verifies that `-Zcfi-assert-consistency` accepts direct calls to functions, methods, trait
+// methods, and generic functions, whose type metadata identifiers match their function pointer
+// types'.
+//
+//@ build-pass
+//@ compile-flags: -Zcfi-assert-consistency
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+pub trait Trait {
+    fn method(&self, x: i32) -> i32;
+
+    fn provided(&self) -> i32 {
+        self.method(1)
+    }
+}
+
+pub struct Type;
+
+impl Type {
+    pub fn inherent(&self, _: PhantomData<u8>, x: &mut [u8; 4]) -> usize {
+        x.len()
+    }
+}
+
+impl Trait for Type {
+    fn method(&self, x: i32) -> i32 {
+        x
+    }
+}
+
+pub fn generic<T: Trait>(t: &T) -> i32 {
+    t.provided()
+}
+
+pub extern "C" fn c_fn(x: *const u8, y: Option<&u32>) -> bool {
+    !x.is_null() && y.is_some()
+}
+
+pub fn foo() -> i32 {
+    let mut x = [0; 4];
+    let y = Type.inherent(PhantomData, &mut x) as i32;
+    let z = c_fn(x.as_ptr(), None) as i32;
+    generic(&Type) + Type.method(y) + z
+}
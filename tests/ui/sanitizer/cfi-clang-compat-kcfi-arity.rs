@@ -0,0 +1,10 @@
+// Verifies that `-Zcfi-clang-compat` rejects `-Zsanitizer-kcfi-arity` before Clang 21.
+//
+//@ min-llvm-version: 21.0.0
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-none -Cpanic=abort -Zsanitizer=kcfi
+//@ compile-flags: -Zcfi-clang-compat=20 -Zsanitizer-kcfi-arity
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
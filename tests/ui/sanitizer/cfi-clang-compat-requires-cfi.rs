@@ -0,0 +1,8 @@
+// Verifies that `-Zcfi-clang-compat` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zcfi-clang-compat=17
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
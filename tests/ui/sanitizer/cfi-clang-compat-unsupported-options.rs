@@ -0,0 +1,11 @@
+// Verifies that `-Zcfi-clang-compat` rejects the options the specified Clang version doesn't
+// support.
+//
+//@ needs-sanitizer-kcfi
+//@ compile-flags: -Cno-prepopulate-passes -Cpanic=abort -Zsanitizer=kcfi -Zcfi-clang-compat=16
+//@ compile-flags: -Zsanitizer-cfi-normalize-integers
+//@ compile-flags: -Zsanitizer-kcfi-typeid-width=64
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
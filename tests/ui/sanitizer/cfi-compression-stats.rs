@@ -0,0 +1,17 @@
+// Verifies that `-Zcfi-compression-stats` prints the statistics about the compression of the type
+// metadata identifiers computed (here, by `-Zcfi-alias-set-report`), largest first.
+//
+//@ build-pass
+//@ compile-flags: -Zcfi-alias-set-report -Zcfi-compression-stats
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub fn bar(x: i32) -> i32 {
+    x + 1
+}
+
+pub fn baz() {}
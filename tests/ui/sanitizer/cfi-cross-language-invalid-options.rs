@@ -0,0 +1,9 @@
+// Verifies that `-Zcfi-cross-language` conflicts with `-Zsanitizer-cfi-normalize-integers=no`, and
+// that it requires `-Clinker-plugin-lto` with `-Zsanitizer=cfi`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zcfi-cross-language -Zsanitizer-cfi-normalize-integers=no
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,30 @@
+// Verifies that functions with deeply nested types in their signatures are encoded without
+// overflowing the stack, as they are compiled without CFI.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Ccodegen-units=1 -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+//@ no-prefer-dynamic
+//@ only-x86_64-unknown-linux-gnu
+//@ build-pass
+
+type D0<T> = Option<Option<T>>;
+type D1<T> = D0<D0<T>>;
+type D2<T> = D1<D1<T>>;
+type D3<T> = D2<D2<T>>;
+type D4<T> = D3<D3<T>>;
+type D5<T> = D4<D4<T>>;
+type D6<T> = D5<D5<T>>;
+type D7<T> = D6<D6<T>>;
+type D8<T> = D7<D7<T>>;
+type D9<T> = D8<D8<T>>;
+type D10<T> = D9<D9<T>>;
+type D11<T> = D10<D10<T>>;
+type D12<T> = D11<D11<T>>;
+
+// 8192 nested `Option`s
+fn foo(_: &D12<u8>) {}
+
+fn main() {
+    let f: fn(&D12<u8>) = foo;
+    f(&None);
+}
@@ -0,0 +1,17 @@
+// Verifies that `-Zcfi-diff-typeids=<path>,<path>` prints the type metadata identifiers of two
+// functions and explains where they first differ.
+//
+//@ check-pass
+//@ compile-flags: -Zcfi-diff-typeids=foo,bar::baz
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub mod bar {
+    pub fn baz(x: u32) -> i32 {
+        x as i32
+    }
+}
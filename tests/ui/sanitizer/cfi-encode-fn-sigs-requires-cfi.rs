@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-cfi-encode-fn-sigs` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-encode-fn-sigs
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
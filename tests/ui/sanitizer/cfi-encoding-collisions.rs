@@ -0,0 +1,50 @@
+// Verifies that the `cfi_encoding_collisions` lint flags user-provided CFI encodings that collide
+// with the encoding of another type in the crate graph, and that it can be allowed.
+//
+//@ check-pass
+//@ aux-build:cfi-encoding-collisions-aux.rs
+
+#![feature(cfi_encoding, extern_types)]
+#![crate_type = "lib"]
+
+extern crate cfi_encoding_collisions_aux;
+
+#[repr(C)]
+pub struct Foo(pub i32);
+
+#[cfi_encoding = "3Foo"]
+//~^ WARN CFI encoding `3Foo` of `Bar` collides with the encoding of `Foo`
+pub struct Bar(pub i64);
+
+#[cfi_encoding = "u3i32"]
+//~^ WARN CFI encoding `u3i32` of `Baz` collides with the encoding of `i32`
+#[repr(transparent)]
+pub struct Baz(pub i32);
+
+extern "C" {
+    pub type Qux;
+    #[cfi_encoding = "3Qux"]
+    //~^ WARN CFI encoding `3Qux` of `Quux` collides with the encoding of `Qux`
+    pub type Quux;
+}
+
+#[cfi_encoding = "3Xyz"]
+//~^ WARN CFI encoding `3Xyz` of `Local` collides with the encoding of `Xyz`
+pub struct Local(pub i32);
+
+#[cfi_encoding = "3Abc"]
+//~^ WARN CFI encoding `3Abc` of `Abc1` collides with the encoding of `Abc2`
+pub struct Abc1(pub i32);
+
+#[cfi_encoding = "3Abc"]
+//~^ WARN CFI encoding `3Abc` of `Abc2` collides with the encoding of `Abc1`
+pub struct Abc2(pub i32);
+
+#[allow(cfi_encoding_collisions)]
+#[cfi_encoding = "u3u32"]
+#[repr(transparent)]
+pub struct Allowed(pub u32);
+
+#[cfi_encoding = "l"]
+#[repr(transparent)]
+pub struct CLong(pub i64);
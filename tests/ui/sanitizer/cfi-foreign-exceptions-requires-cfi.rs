@@ -0,0 +1,8 @@
+// Verifies that `-Zcfi-foreign-exceptions` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zcfi-foreign-exceptions
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,8 @@
+// Verifies that `-Zcfi-foreign-exceptions` requires `-Cpanic=unwind`.
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clinker-plugin-lto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Cpanic=abort -Zsanitizer=cfi -Zcfi-foreign-exceptions
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
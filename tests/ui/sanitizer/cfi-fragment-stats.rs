@@ -0,0 +1,17 @@
+// Verifies that `-Zcfi-fragment-stats` prints the statistics about the encodings of types computed
+// once and spliced into the type metadata identifiers computed (here, by
+// `-Zcfi-alias-set-report`), which depend on how often these are computed.
+//
+//@ build-pass
+//@ compile-flags: -Zcfi-alias-set-report -Zcfi-fragment-stats
+//@ normalize-stdout-test: "\"[0-9]+\"" -> "\"N\""
+
+#![crate_type = "lib"]
+
+pub fn foo(x: &i32) -> &i32 {
+    x
+}
+
+pub fn bar(x: &i32) -> &i32 {
+    x
+}
@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-cfi-generalize-arrays` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-generalize-arrays
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
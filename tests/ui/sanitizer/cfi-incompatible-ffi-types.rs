@@ -0,0 +1,54 @@
+// Verifies that the `cfi_incompatible_ffi_types` lint flags the parameter and return types of
+// functions with the "C" calling convention that can never match a C type under cross-language
+// CFI.
+//
+//@ needs-sanitizer-cfi
+//@ check-pass
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi -Zsanitizer-cfi-normalize-integers
+
+#![crate_type = "lib"]
+#![allow(improper_ctypes, improper_ctypes_definitions)]
+
+pub struct Foo(pub i32);
+
+#[repr(C)]
+pub struct Bar(pub i32);
+
+pub extern "C" fn string(_s: String) {}
+//~^ WARN `extern` function uses type `String`
+
+pub extern "C" fn foo(x: Foo) -> i32 {
+    //~^ WARN `extern` function uses type `Foo`
+    x.0
+}
+
+pub extern "C" fn reference(x: &i32) -> i32 {
+    //~^ WARN `extern` function uses type `&i32`
+    *x
+}
+
+pub extern "C" fn trait_object(_x: *const dyn Fn()) {}
+//~^ WARN `extern` function uses type `dyn Fn()`
+
+pub extern "C" fn compatible(_x: *const Bar, _y: u64, _z: bool) -> i32 {
+    0
+}
+
+extern "C" {
+    pub fn declared(x: Vec<u8>);
+    //~^ WARN `extern` function uses type `Vec<u8>`
+}
+
+pub extern "C" fn string_slice(_s: &str) {}
+//~^ WARN `extern` function uses type `&str`
+
+pub extern "C" fn nullable_reference(_x: Option<&mut i32>) {}
+//~^ WARN `extern` function uses type `Option<&mut i32>`
+
+pub extern "C" fn nullable_non_null(_x: Option<std::ptr::NonNull<u8>>) {}
+//~^ WARN `extern` function uses type `Option<*const u8>`
+
+extern "C" {
+    pub fn declared_reference(x: &i32);
+    //~^ WARN `extern` function uses type `&i32`
+}
@@ -0,0 +1,32 @@
+// Verifies that invalid `cfi_encodings` entries, entries that don't name a type, and entries that
+// conflict with other entries or with the `cfi_encoding` attributes of their types are reported.
+//
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+
+#![feature(cfi_encoding)]
+#![cfi_encodings(
+    crate::Type1, //~ ERROR invalid `cfi_encodings` entry
+    crate::Type2 = "", //~ ERROR invalid `cfi_encodings` entry
+    crate::Type2 = "3Foo3Bar", //~ ERROR invalid `cfi_encodings` entry
+    crate::Type5 = "5type5", //~ ERROR `crate::Type5` in `cfi_encodings` doesn't name
+    crate::Type3 = "5type3", //~ ERROR `crate::Type3` is registered with a CFI encoding other
+    crate::Type4 = "5type4",
+    crate::Type4 = "5other", //~ ERROR conflicting CFI encodings for `crate::Type4`
+)]
+
+pub struct Type1(i32);
+
+pub struct Type2(i32);
+
+#[cfi_encoding = "3Foo"]
+pub struct Type3(i32);
+
+pub struct Type4(i32);
+
+pub fn foo(_: Type1, _: Type2, _: Type3, _: Type4) {}
+
+fn main() {
+    foo(Type1(0), Type2(0), Type3(0), Type4(0));
+}
@@ -0,0 +1,33 @@
+// Verifies that `same_as` paths of cfi_encoding attributes that don't name a type that isn't
+// generic, or that lead back to their type, can't be used.
+//
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+//@ normalize-stderr-test: "u[0-9]+NtCs[0-9a-zA-Z]+_" -> "u[LEN]NtCs[HASH]_"
+
+#![feature(cfi_encoding)]
+
+#[cfi_encoding(same_as = "crate::Missing")] //~ ERROR E0799
+pub struct Type1(i32);
+
+#[cfi_encoding(same_as = "crate::Generic")] //~ ERROR E0799
+pub struct Type2(i32);
+
+pub struct Generic<T>(T);
+
+#[cfi_encoding(same_as = "crate::Type4")] //~ ERROR E0799
+pub struct Type3(i32);
+
+#[cfi_encoding(same_as = "crate::Type3")] //~ ERROR E0799
+pub struct Type4(i32);
+
+#[cfi_encoding(same_as = "crate::Type1", name = "Foo")] //~ ERROR E0799
+pub struct Type5(i32);
+
+pub fn foo(_: Type1, _: Type2, _: Type3, _: Type4, _: Type5) {}
+
+fn main() {
+    let f: fn(Type1, Type2, Type3, Type4, Type5) = foo;
+    f(Type1(0), Type2(0), Type3(0), Type4(0), Type5(0));
+}
@@ -0,0 +1,39 @@
+// Verifies that invalid structured user-defined CFI encodings can't be used.
+//
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+//@ normalize-stderr-test: "u[0-9]+NtCs[0-9a-zA-Z]+_" -> "u[LEN]NtCs[HASH]_"
+
+#![feature(cfi_encoding)]
+
+#[cfi_encoding(name = "1Foo")] //~ ERROR E0799
+pub struct Type1(i32);
+
+#[cfi_encoding(namespace = "ns::", name = "Foo")] //~ ERROR E0799
+pub struct Type2(i32);
+
+#[cfi_encoding(name = "Foo", template_args = "all")] //~ ERROR E0799
+pub struct Type3(i32);
+
+#[cfi_encoding(namespace = "ns")] //~ ERROR E0799
+pub struct Type4(i32);
+
+#[cfi_encoding(name = "Foo", size = "4")] //~ ERROR E0799
+pub struct Type5(i32);
+
+#[cfi_encoding(name)] //~ ERROR E0799
+pub struct Type6(i32);
+
+#[cfi_encoding(transform = "int")] //~ ERROR E0799
+pub struct Type7(i32);
+
+#[cfi_encoding(name = "Foo", transform = "void")] //~ ERROR E0799
+pub struct Type8(i32);
+
+pub fn foo(_: Type1, _: Type2, _: Type3, _: Type4, _: Type5, _: Type6, _: Type7, _: Type8) {}
+
+fn main() {
+    let f: fn(Type1, Type2, Type3, Type4, Type5, Type6, Type7, Type8) = foo;
+    f(Type1(0), Type2(0), Type3(0), Type4(0), Type5(0), Type6(0), Type7(0), Type8(0));
+}
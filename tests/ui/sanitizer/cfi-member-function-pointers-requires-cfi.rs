@@ -0,0 +1,9 @@
+// Verifies that `-Zsanitizer-cfi-member-function-pointers` requires `-Zsanitizer=cfi` or
+// `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-member-function-pointers
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,9 @@
+// Verifies that `-Zsanitizer-cfi-normalize-ptr-sized-integers` requires `-Zsanitizer=cfi` or
+// `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-normalize-ptr-sized-integers
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
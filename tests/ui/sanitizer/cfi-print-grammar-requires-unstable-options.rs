@@ -0,0 +1,5 @@
+// Verifies that `--print cfi-grammar` requires `-Zunstable-options`.
+//
+//@ compile-flags: --print cfi-grammar
+
+#![crate_type = "lib"]
@@ -0,0 +1,10 @@
+// Verifies that `--print cfi-type-id=<path>` reports paths that don't resolve to a function in the
+// current crate, and generic functions.
+//
+//@ compile-flags: -Zunstable-options --print cfi-type-id=missing --print cfi-type-id=generic
+
+#![crate_type = "lib"]
+
+pub fn generic<T>(x: T) -> T {
+    x
+}
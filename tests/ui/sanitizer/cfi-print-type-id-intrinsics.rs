@@ -0,0 +1,16 @@
+// Verifies that `--print cfi-type-id=<path>` reports intrinsics without fallback bodies, which are
+// never codegened as functions.
+//
+//@ compile-flags: -Zunstable-options --print cfi-type-id=const_deallocate --print cfi-type-id=abort
+
+#![crate_type = "lib"]
+#![feature(intrinsics, rustc_attrs)]
+#![allow(dead_code)]
+
+#[rustc_intrinsic]
+#[rustc_intrinsic_must_be_overridden]
+unsafe fn const_deallocate(_ptr: *mut u8, _size: usize, _align: usize) {}
+
+extern "rust-intrinsic" {
+    fn abort() -> !;
+}
@@ -0,0 +1,7 @@
+// Verifies that `--print cfi-type-id=<path>` requires `-Zunstable-options`.
+//
+//@ compile-flags: --print cfi-type-id=foo
+
+#![crate_type = "lib"]
+
+pub fn foo() {}
@@ -0,0 +1,35 @@
+// Verifies that `--print cfi-type-id=<path>` also prints the WebAssembly function type (i.e., the
+// signature `call_indirect` instructions are checked against) of functions on WebAssembly targets.
+//
+//@ check-pass
+//@ needs-llvm-components: webassembly
+//@ compile-flags: --target wasm32-unknown-unknown -Zunstable-options
+//@ compile-flags: --print cfi-type-id=foo --print cfi-type-id=bar --print cfi-type-id=baz
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+impl Copy for i32 {}
+impl Copy for i64 {}
+impl Copy for f64 {}
+
+pub extern "C" fn foo(_x: i32, _y: i64) -> f64 {
+    loop {}
+}
+
+#[repr(C)]
+pub struct Pair {
+    a: i32,
+    b: i32,
+}
+
+pub extern "C" fn bar(pair: Pair) -> Pair {
+    pair
+}
+
+pub extern "C" fn baz(_x: &i32) {}
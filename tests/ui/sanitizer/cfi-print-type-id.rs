@@ -0,0 +1,15 @@
+// Verifies that `--print cfi-type-id=<path>` prints the type metadata identifier and KCFI type
+// metadata identifier of functions in the current crate.
+//
+//@ check-pass
+//@ compile-flags: -Zunstable-options --print cfi-type-id=foo --print cfi-type-id=crate::bar::baz
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub mod bar {
+    pub fn baz() {}
+}
@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-cfi-trait-object-projections=none` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-trait-object-projections=none
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
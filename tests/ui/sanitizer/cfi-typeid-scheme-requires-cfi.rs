@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-cfi-typeid-scheme` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`
+//
+//@ needs-sanitizer-cfi
+//@ compile-flags: -Clto -Cno-prepopulate-passes -Ctarget-feature=-crt-static -Zsanitizer-cfi-typeid-scheme=msvc
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,12 @@
+// Verifies that `-Zverify-typeid-stability` reports type metadata identifiers that differ from the
+// snapshot, and functions that are only in either the crate or the snapshot.
+//
+//@ compile-flags: -Zverify-typeid-stability={{src-base}}/sanitizer/cfi-typeid-stability-mismatch.txt
+
+#![crate_type = "lib"]
+
+pub fn changed(_: i64) {}
+//~^ ERROR type metadata identifier of `changed` differs from the snapshot
+
+pub fn added(_: i32) {}
+//~^ ERROR type metadata identifier of `added` is not in the snapshot
@@ -0,0 +1,40 @@
+// Verifies that the type metadata identifiers of a curated set of functions match the ones in the
+// committed snapshot, so changes to their encoding are explicit.
+//
+// Only types whose encoding doesn't depend on the crate disambiguator are used, so the snapshot is
+// the same for every build of this test.
+//
+//@ check-pass
+//@ compile-flags: -Zverify-typeid-stability={{src-base}}/sanitizer/cfi-typeid-stability.txt
+
+#![crate_type = "lib"]
+
+#[repr(C)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub fn unit() {}
+
+pub fn primitives(_: bool, _: char, _: i8, _: u64, _: f32, _: f64) -> i32 {
+    0
+}
+
+pub fn pointers(_: &i32, _: &mut i32, _: *const i32, _: *mut i32) {}
+
+pub fn sequences(_: &[u8], _: &str, _: (i32, bool), _: [u16; 4]) {}
+
+pub fn callback(_: fn(i32) -> i32, _: i32) {}
+
+pub fn diverges() -> ! {
+    loop {}
+}
+
+pub extern "C" fn repr_c(_: Point, _: *const Point) -> i64 {
+    0
+}
+
+extern "C" {
+    pub fn variadic(_: *const u8, ...) -> i32;
+}
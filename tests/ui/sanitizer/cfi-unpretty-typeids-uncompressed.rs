@@ -0,0 +1,15 @@
+// Verifies that `-Zcfi-uncompressed-typeids` prints the type metadata identifiers of
+// `-Zunpretty=cfi-typeids` with every repeated component encoded in full.
+//
+//@ check-pass
+//@ compile-flags: -Zunpretty=cfi-typeids -Zcfi-uncompressed-typeids
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> i32 {
+    x
+}
+
+pub fn bar(_: &[i32], _: &[i32]) {}
+
+pub fn baz(_: *const u8, _: *const u8, _: &mut *const u8) {}
@@ -0,0 +1,16 @@
+// Verifies that `-Zunpretty=cfi-typeids` prints the transformed signature and type metadata
+// identifier of every non-generic function, in source order.
+//
+//@ check-pass
+//@ compile-flags: -Zunpretty=cfi-typeids
+
+#![crate_type = "lib"]
+
+pub fn foo(x: i32) -> bool {
+    x != 0
+}
+
+// Generic functions have no type metadata identifier until they are instantiated
+pub fn bar<T>(_: T) {}
+
+pub fn baz(_: &[i32], _: &[i32]) {}
@@ -0,0 +1,26 @@
+// Verifies that const arguments of types that can't be encoded in type metadata identifiers are
+// reported instead of causing an ICE.
+//
+//@ needs-sanitizer-cfi
+//@ build-fail
+//@ compile-flags: -Clto -Ctarget-feature=-crt-static -Zsanitizer=cfi
+
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+use std::marker::ConstParamTy;
+
+#[derive(PartialEq, Eq, ConstParamTy)]
+pub struct Foo {
+    x: u8,
+}
+
+pub struct Bar<const F: Foo>;
+//~^ ERROR type `Foo` cannot be used as a const parameter with CFI enabled
+
+pub fn baz(_: &Bar<{ Foo { x: 1 } }>) {}
+
+fn main() {
+    let f: fn(&Bar<{ Foo { x: 1 } }>) = baz;
+    f(&Bar);
+}
@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-kcfi-arity` requires `-Zsanitizer=kcfi`.
+//
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-none -Cpanic=abort -Zsanitizer-kcfi-arity
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,9 @@
+// Verifies that `-Zsanitizer-kcfi-arity` is only supported on x86-64 targets.
+//
+//@ needs-llvm-components: aarch64
+//@ compile-flags: --target aarch64-unknown-none -Cpanic=abort -Zsanitizer=kcfi
+//@ compile-flags: -Zsanitizer-kcfi-arity
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
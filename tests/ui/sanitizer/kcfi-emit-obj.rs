@@ -0,0 +1,21 @@
+// Verifies that the KCFI operand bundles and type metadata pass the LLVM verifier and are lowered
+// when building all the way to an object file.
+//
+//@ build-pass
+//@ needs-sanitizer-kcfi
+//@ compile-flags: -Cpanic=abort -Zsanitizer=kcfi -Zverify-llvm-ir --emit=obj
+
+#![crate_type="lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+impl Copy for i32 {}
+
+pub fn foo(f: fn(i32) -> i32, arg: i32) -> i32 {
+    f(arg)
+}
@@ -0,0 +1,30 @@
+// Verifies that the `large_cfi_alias_sets` lint is emitted for sets of functions that share a type
+// metadata identifier with more functions than `-Zcfi-alias-set-threshold`.
+//
+//@ needs-sanitizer-kcfi
+//@ compile-flags: -Cpanic=abort -Cprefer-dynamic=off -Zsanitizer=kcfi -Zcfi-alias-set-threshold=2
+//@ build-pass
+
+#![crate_type = "lib"]
+#![warn(large_cfi_alias_sets)]
+
+pub fn a(x: i32) -> i32 {
+    //~^ WARN 3 functions share the CFI type metadata identifier `_ZTSFu3i32S_E`
+    x
+}
+
+pub fn b(x: i32) -> i32 {
+    x + 1
+}
+
+pub fn c(x: i32) -> i32 {
+    x + 2
+}
+
+pub fn d(x: u32) -> u32 {
+    x
+}
+
+pub fn e(x: u32) -> u32 {
+    x + 1
+}
@@ -0,0 +1,11 @@
+// Verifies that a KCFI seed warns when combined with the options for cross-language KCFI support,
+// as Clang has no KCFI seed.
+//
+//@ check-pass
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-none -Cpanic=abort -Zsanitizer=kcfi
+//@ compile-flags: -Zsanitizer-cfi-normalize-integers -Zsanitizer-kcfi-seed=0x1234abcd
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,8 @@
+// Verifies that `-Zsanitizer-kcfi-seed` requires `-Zsanitizer=kcfi`.
+//
+//@ needs-sanitizer-kcfi
+//@ compile-flags: -Cno-prepopulate-passes -Cpanic=abort -Zsanitizer-kcfi-seed=0x1234abcd
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,10 @@
+// Verifies that `-Zsanitizer-kcfi-typeid-width=64` is rejected by the LLVM backend, as LLVM only
+// supports 32-bit KCFI type metadata identifiers.
+//
+//@ needs-sanitizer-kcfi
+//@ compile-flags: -Cno-prepopulate-passes -Cpanic=abort -Zsanitizer=kcfi
+//@ compile-flags: -Zsanitizer-kcfi-typeid-width=64
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,11 @@
+//@ revisions: no-hashed too-few too-many bits-128
+//@ [no-hashed] compile-flags: -Zsymbol-mangling-hash-bits=32
+//@ [too-few] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [too-few] compile-flags: -Zsymbol-mangling-hash-bits=7
+//@ [too-many] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [too-many] compile-flags: -Zsymbol-mangling-hash-bits=129
+//@ [bits-128] build-pass
+//@ [bits-128] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [bits-128] compile-flags: -Zsymbol-mangling-hash-bits=128
+
+fn main() {}
@@ -0,0 +1,9 @@
+//@ revisions: no-hashed bad private
+//@ [no-hashed] compile-flags: -Zsymbol-mangling-hash-policy=private
+//@ [bad] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [bad] compile-flags: -Zsymbol-mangling-hash-policy=public
+//@ [private] build-pass
+//@ [private] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [private] compile-flags: -Zsymbol-mangling-hash-policy=private
+
+fn main() {}
@@ -0,0 +1,8 @@
+//@ revisions: default legacy hashed v0
+//@ compile-flags: -Zsymbol-mangling-legacy-aliases
+//@ [legacy] compile-flags: -Zunstable-options -Csymbol-mangling-version=legacy
+//@ [hashed] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [v0] build-pass
+//@ [v0] compile-flags: -Csymbol-mangling-version=v0
+
+fn main() {}
@@ -0,0 +1,3 @@
+//@ compile-flags: -Zsymbol-length-cap=31
+
+fn main() {}
@@ -0,0 +1,5 @@
+//@ revisions: empty dot
+//@ [empty] compile-flags: -Zsymbol-mangling-suffix=
+//@ [dot] compile-flags: -Zsymbol-mangling-suffix=a.b
+
+fn main() {}
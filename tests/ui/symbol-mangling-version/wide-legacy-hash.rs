@@ -0,0 +1,8 @@
+//@ revisions: v0 hashed legacy
+//@ compile-flags: -Zsymbol-mangling-wide-legacy-hash
+//@ [v0] compile-flags: -Csymbol-mangling-version=v0
+//@ [hashed] compile-flags: -Zunstable-options -Csymbol-mangling-version=hashed
+//@ [legacy] build-pass
+//@ [legacy] compile-flags: -Zunstable-options -Csymbol-mangling-version=legacy
+
+fn main() {}
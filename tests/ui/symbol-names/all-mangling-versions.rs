@@ -0,0 +1,19 @@
+//@ build-fail
+//@ dont-check-compiler-stderr
+// Checks that `#[rustc_symbol_name(all)]` reports the symbol name under every mangling version,
+// regardless of the one the crate is compiled with.
+
+#![feature(rustc_attrs)]
+
+#[rustc_symbol_name(all)]
+//~^ ERROR symbol-names
+//~| NOTE legacy: symbol-name(_ZN21all_mangling_versions4main
+//~| NOTE legacy: demangling(all_mangling_versions::main::h
+//~| NOTE legacy: demangling-alt(all_mangling_versions::main)
+//~| NOTE v0: symbol-name(_RNv
+//~| NOTE v0: demangling(all_mangling_versions[
+//~| NOTE v0: demangling-alt(all_mangling_versions::main)
+//~| NOTE hashed: symbol-name(_RNxC21all_mangling_versions
+//~| NOTE hashed: demangling(all_mangling_versions::
+//~| NOTE hashed: demangling-alt(all_mangling_versions::
+fn main() {}
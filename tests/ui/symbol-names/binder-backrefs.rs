@@ -0,0 +1,28 @@
+// Checks that types and paths which refer to a binder are referred to by backrefs when they're
+// repeated under the same binder.
+
+//@ build-fail
+//@ compile-flags: -C symbol-mangling-version=v0 --crate-name=c
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+
+#![feature(rustc_attrs)]
+
+pub trait Tr<T> {
+    type Out;
+}
+
+pub struct W<T: ?Sized>(T);
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<for<'a> fn(&'a [u8], &'a [u8]) -> &'a [u8]>>)
+impl W<for<'a> fn(&'a [u8], &'a [u8]) -> &'a [u8]> {}
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<dyn for<'a> c::Tr<&'a u8, Out = &'a u8>>>)
+impl W<dyn for<'a> Tr<&'a u8, Out = &'a u8>> {}
+
+fn main() {}
@@ -0,0 +1,48 @@
+//@ build-fail
+//@ compile-flags: -C symbol-mangling-version=v0 --crate-name=c
+
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+
+#![feature(adt_const_params, rustc_attrs)]
+#![allow(incomplete_features)]
+
+use std::marker::ConstParamTy;
+
+pub struct RefStr<const RS: &'static &'static str>;
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::RefStr<{&"abc"}>>)
+impl RefStr<{&"abc"}> {}
+
+pub struct StrArray<const SA: [&'static str; 2]>;
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::StrArray<{["abc", "abc"]}>>)
+impl StrArray<{["abc", "abc"]}> {}
+
+pub struct StrSlice<const SS: &'static [&'static str]>;
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::StrSlice<{&["a", "b"]}>>)
+impl StrSlice<{&["a", "b"]}> {}
+
+#[derive(PartialEq, Eq, ConstParamTy)]
+pub enum Name {
+    Anonymous,
+    Named(&'static str),
+}
+pub struct Name_<const N: Name>;
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::Name_<{c::Name::Named("abc")}>>)
+impl Name_<{Name::Named("abc")}> {}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Checks that `dyn*` trait objects are mangled distinctly from `dyn` ones.
+
+//@ build-fail
+//@ compile-flags: -C symbol-mangling-version=v0 --crate-name=c
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+
+#![feature(dyn_star, rustc_attrs)]
+#![allow(incomplete_features)]
+
+pub trait Trait {}
+
+pub struct W<T: ?Sized>(T);
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<dyn c::Trait>>)
+impl W<dyn Trait> {}
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<(dyn c::Trait, _)>>)
+impl W<dyn* Trait> {}
+
+fn main() {}
@@ -0,0 +1,40 @@
+//@ build-fail
+//@ compile-flags: -Z unstable-options -C symbol-mangling-version=hashed
+//@ compile-flags: -Z symbol-mangling-hash-policy=private --crate-name=c
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+//@ normalize-stderr-test: "[0-9]*H[0-9a-zA-Z]{8,}" -> "HASH"
+
+// Checks that only the symbols of the items of the public API are kept unhashed with
+// `-Z symbol-mangling-hash-policy=private`, besides those marked `#[rustc_unhashed_symbol]`.
+
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+
+pub mod public {
+    #[rustc_symbol_name]
+    //~^ ERROR symbol-name(_RNvNtCsCRATE_HASH_1c6public3foo)
+    //~| ERROR demangling(c[HASH]::public::foo)
+    //~| ERROR demangling-alt(c::public::foo)
+    pub fn foo() {}
+
+    #[rustc_symbol_name]
+    //~^ ERROR symbol-name(_RNxC1cHASH)
+    //~| ERROR demangling(c::HASH)
+    //~| ERROR demangling-alt(c::HASH)
+    fn bar() {}
+}
+
+mod private {
+    #[rustc_symbol_name]
+    //~^ ERROR symbol-name(_RNxC1cHASH)
+    //~| ERROR demangling(c::HASH)
+    //~| ERROR demangling-alt(c::HASH)
+    pub fn baz() {}
+}
+
+#[rustc_unhashed_symbol]
+#[rustc_symbol_name]
+//~^ ERROR symbol-name(_RNvCsCRATE_HASH_1c3qux)
+//~| ERROR demangling(c[HASH]::qux)
+//~| ERROR demangling-alt(c::qux)
+fn qux() {}
@@ -0,0 +1,16 @@
+//@ build-fail
+//@ compile-flags: -Zunstable-options -Csymbol-mangling-version=legacy
+//@ compile-flags: -Zsymbol-mangling-wide-legacy-hash --crate-name=c
+//@ normalize-stderr-test: "33h[0-9a-f]{32}E" -> "33hHASHE"
+//@ normalize-stderr-test: "::h[0-9a-f]{32}" -> "::hHASH"
+
+// Checks that `-Zsymbol-mangling-wide-legacy-hash` gives legacy symbols a 128-bit hash, in a form
+// that demanglers still recognize as a hash.
+
+#![feature(rustc_attrs)]
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name(_ZN1c4main33hHASHE)
+//~| ERROR demangling(c::main::hHASH)
+//~| ERROR demangling-alt(c::main)
+fn main() {}
@@ -0,0 +1,32 @@
+//@ build-fail
+//@ revisions: unmangled mangled
+//@ [mangled] compile-flags: -Zmangle-internal-symbols
+//@ normalize-stderr-test: "__rustc\[[0-9a-f]+\]" -> "__rustc[HASH]"
+
+// Checks that `-Zmangle-internal-symbols` mangles the symbols internal to the standard library with
+// the disambiguator of the crate defining the `Sized` lang item (this one), and that the weak lang
+// items are left alone.
+
+#![feature(lang_items, no_core, rustc_attrs)]
+#![crate_type = "lib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[rustc_std_internal_symbol]
+#[rustc_symbol_name]
+//[unmangled]~^ ERROR symbol-name(__rust_foo)
+//[unmangled]~| ERROR demangling(__rust_foo)
+//[unmangled]~| ERROR demangling-alt(__rust_foo)
+//[mangled]~^^^^ ERROR symbol-name(_RNvCsCRATE_HASH_7___rustc10___rust_foo)
+//[mangled]~| ERROR demangling(__rustc[HASH]::__rust_foo)
+//[mangled]~| ERROR demangling-alt(__rustc::__rust_foo)
+pub fn __rust_foo() {}
+
+#[lang = "eh_personality"]
+#[rustc_symbol_name]
+//~^ ERROR symbol-name(rust_eh_personality)
+//~| ERROR demangling(rust_eh_personality)
+//~| ERROR demangling-alt(rust_eh_personality)
+fn eh_personality() {}
@@ -0,0 +1,23 @@
+//@ build-fail
+//@ compile-flags: -C symbol-mangling-version=v0 --crate-name=c
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+
+#![feature(core_pattern_type, core_pattern_types, pattern_types, rustc_attrs)]
+
+use std::pat::pattern_type;
+
+pub struct W<T>(T);
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<(u32, [(); 1], [(); 5], [(); true])>>)
+impl W<pattern_type!(u32 is 1..=5)> {}
+
+#[rustc_symbol_name]
+//~^ ERROR symbol-name
+//~| ERROR demangling
+//~| ERROR demangling-alt(<c::W<(i8, [(); -1], [(); _], [(); false])>>)
+impl W<pattern_type!(i8 is -1..)> {}
+
+fn main() {}
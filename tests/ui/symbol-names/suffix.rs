@@ -0,0 +1,22 @@
+//@ build-fail
+//@ revisions: legacy v0
+//@ compile-flags: -Zsymbol-mangling-suffix=plugin_1 --crate-name=c
+//@ [legacy] compile-flags: -Zunstable-options -Csymbol-mangling-version=legacy
+//@ [v0] compile-flags: -Csymbol-mangling-version=v0
+//@ normalize-stderr-test: "c\[[0-9a-f]+\]" -> "c[HASH]"
+//@ normalize-stderr-test: "17h[0-9a-f]{16}E" -> "17hHASHE"
+//@ normalize-stderr-test: "::h[0-9a-f]{16}" -> "::hHASH"
+
+// Checks that `-Zsymbol-mangling-suffix` appends its suffix to mangled symbols in a form that
+// demanglers keep.
+
+#![feature(rustc_attrs)]
+
+#[rustc_symbol_name]
+//[legacy]~^ ERROR symbol-name(_ZN1c4main17hHASHE.plugin_1)
+//[legacy]~| ERROR demangling(c::main::hHASH.plugin_1)
+//[legacy]~| ERROR demangling-alt(c::main.plugin_1)
+//[v0]~^^^^ ERROR symbol-name(_RNvCsCRATE_HASH_1c4main.plugin_1)
+//[v0]~| ERROR demangling(c[HASH]::main.plugin_1)
+//[v0]~| ERROR demangling-alt(c::main.plugin_1)
+fn main() {}
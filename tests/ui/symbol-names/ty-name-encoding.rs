@@ -0,0 +1,58 @@
+//@ build-fail
+//@ compile-flags: --crate-name=a
+//@ normalize-stderr-test: "Cs[0-9a-zA-Z]+_" -> "CsCRATE_HASH_"
+
+// Checks the encoding of item names in type metadata identifiers (i.e., their namespace tags, crate
+// disambiguator and name, and path disambiguators and names).
+
+#![feature(extern_types, rustc_attrs)]
+#![allow(dead_code)]
+
+#[rustc_ty_name_encoding]
+//~^ ERROR ty-name-encoding(NtCsCRATE_HASH_1a6Struct)
+pub struct Struct;
+
+pub mod module {
+    #[rustc_ty_name_encoding]
+    //~^ ERROR ty-name-encoding(NtNtCsCRATE_HASH_1a6module4Enum)
+    pub enum Enum {}
+}
+
+#[rustc_ty_name_encoding]
+//~^ ERROR ty-name-encoding(NvCsCRATE_HASH_1a8function)
+pub fn function() {
+    {
+        #[rustc_ty_name_encoding]
+        //~^ ERROR ty-name-encoding(NtNvCsCRATE_HASH_1a8function5Local)
+        struct Local;
+    }
+    {
+        #[rustc_ty_name_encoding]
+        //~^ ERROR ty-name-encoding(NtNvCsCRATE_HASH_1a8functions_5Local)
+        struct Local;
+    }
+}
+
+pub trait Trait {
+    #[rustc_ty_name_encoding]
+    //~^ ERROR ty-name-encoding(NtNtCsCRATE_HASH_1a5Trait5Assoc)
+    type Assoc;
+}
+
+impl Trait for Struct {
+    #[rustc_ty_name_encoding]
+    //~^ ERROR ty-name-encoding(NtNICsCRATE_HASH_1a8{{impl}}5Assoc)
+    type Assoc = ();
+}
+
+extern "C" {
+    #[rustc_ty_name_encoding]
+    //~^ ERROR ty-name-encoding(NtNFCsCRATE_HASH_1a10{{extern}}6Opaque)
+    pub type Opaque;
+}
+
+#[rustc_ty_name_encoding]
+//~^ ERROR ty-name-encoding(NtCsCRATE_HASH_1a8__Private)
+pub struct _Private;
+
+fn main() {}